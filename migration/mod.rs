@@ -1,3 +0,0 @@
-mod m20251204_222043_name1;
-mod m20251204_222257_create_trades_table;
-mod m20251204_222434_create_wallets_table;
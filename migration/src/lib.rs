@@ -3,6 +3,19 @@ pub use sea_orm_migration::prelude::*;
 mod m20251204_222043_name1;
 mod m20251204_222257_create_trades_table;
 mod m20251204_222434_create_wallets_table;
+mod m20251210_000001_add_platform_fee_to_trades;
+mod m20251215_000001_create_limit_orders_table;
+mod m20251218_000001_create_exit_orders_table;
+mod m20251222_000001_create_dca_schedules_table;
+mod m20251226_000001_create_copy_trading_tables;
+mod m20251229_000001_create_price_alerts_table;
+mod m20260105_000001_create_sniper_tables;
+mod m20260112_000001_add_simulation_to_trades;
+mod m20260115_000001_add_priority_fees;
+mod m20260119_000001_add_balance_notifications_to_wallets;
+mod m20260122_000001_create_tokens_table;
+mod m20260126_000001_widen_trade_and_wallet_enums;
+mod m20260129_000001_create_trade_events_table;
 
 pub struct Migrator;
 
@@ -11,6 +24,19 @@ impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
         vec![Box::new(m20251204_222043_name1::Migration),
         Box::new(m20251204_222257_create_trades_table::Migration),
-        Box::new(m20251204_222434_create_wallets_table::Migration)]
+        Box::new(m20251204_222434_create_wallets_table::Migration),
+        Box::new(m20251210_000001_add_platform_fee_to_trades::Migration),
+        Box::new(m20251215_000001_create_limit_orders_table::Migration),
+        Box::new(m20251218_000001_create_exit_orders_table::Migration),
+        Box::new(m20251222_000001_create_dca_schedules_table::Migration),
+        Box::new(m20251226_000001_create_copy_trading_tables::Migration),
+        Box::new(m20251229_000001_create_price_alerts_table::Migration),
+        Box::new(m20260105_000001_create_sniper_tables::Migration),
+        Box::new(m20260112_000001_add_simulation_to_trades::Migration),
+        Box::new(m20260115_000001_add_priority_fees::Migration),
+        Box::new(m20260119_000001_add_balance_notifications_to_wallets::Migration),
+        Box::new(m20260122_000001_create_tokens_table::Migration),
+        Box::new(m20260126_000001_widen_trade_and_wallet_enums::Migration),
+        Box::new(m20260129_000001_create_trade_events_table::Migration)]
     }
 }
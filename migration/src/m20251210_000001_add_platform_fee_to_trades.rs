@@ -0,0 +1,62 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Trades::Table)
+                    .add_column(ColumnDef::new(Trades::PlatformFeeBps).integer().null())
+                    .add_column(ColumnDef::new(Trades::PlatformFeeAmount).decimal_len(30, 9).null())
+                    .add_column(ColumnDef::new(Trades::PlatformFeeMint).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_trades_platform_fee_mint")
+                    .table(Trades::Table)
+                    .col(Trades::PlatformFeeMint)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_trades_platform_fee_mint")
+                    .table(Trades::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Trades::Table)
+                    .drop_column(Trades::PlatformFeeBps)
+                    .drop_column(Trades::PlatformFeeAmount)
+                    .drop_column(Trades::PlatformFeeMint)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Trades {
+    Table,
+    PlatformFeeBps,
+    PlatformFeeAmount,
+    PlatformFeeMint,
+}
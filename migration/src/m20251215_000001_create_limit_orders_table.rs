@@ -0,0 +1,125 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(LimitOrders::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LimitOrders::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("gen_random_uuid()")),
+                    )
+                    .col(ColumnDef::new(LimitOrders::UserId).big_integer().not_null())
+                    .col(ColumnDef::new(LimitOrders::Side).string_len(8).not_null())
+                    .col(ColumnDef::new(LimitOrders::TokenMint).string().not_null())
+                    .col(ColumnDef::new(LimitOrders::TokenSymbol).string().not_null())
+                    .col(ColumnDef::new(LimitOrders::Amount).decimal_len(30, 9).not_null())
+                    .col(ColumnDef::new(LimitOrders::TargetPrice).decimal_len(30, 12).not_null())
+                    .col(ColumnDef::new(LimitOrders::MaxSlippageBps).integer().not_null())
+                    .col(ColumnDef::new(LimitOrders::Status).string_len(16).not_null())
+                    .col(ColumnDef::new(LimitOrders::TradeId).uuid().null())
+                    .col(ColumnDef::new(LimitOrders::ErrorMessage).text().null())
+                    .col(ColumnDef::new(LimitOrders::ExpiresAt).timestamp_with_time_zone().null())
+                    .col(ColumnDef::new(LimitOrders::TriggeredAt).timestamp_with_time_zone().null())
+                    .col(
+                        ColumnDef::new(LimitOrders::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(LimitOrders::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_limit_orders_user_id")
+                            .from(LimitOrders::Table, LimitOrders::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_limit_orders_trade_id")
+                            .from(LimitOrders::Table, LimitOrders::TradeId)
+                            .to(Trades::Table, Trades::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_limit_orders_user_id")
+                    .table(LimitOrders::Table)
+                    .col(LimitOrders::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_limit_orders_status")
+                    .table(LimitOrders::Table)
+                    .col(LimitOrders::Status)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LimitOrders::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum LimitOrders {
+    Table,
+    Id,
+    UserId,
+    Side,
+    TokenMint,
+    TokenSymbol,
+    Amount,
+    TargetPrice,
+    MaxSlippageBps,
+    Status,
+    TradeId,
+    ErrorMessage,
+    ExpiresAt,
+    TriggeredAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Trades {
+    Table,
+    Id,
+}
@@ -0,0 +1,141 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ExitOrders::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ExitOrders::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("gen_random_uuid()")),
+                    )
+                    .col(ColumnDef::new(ExitOrders::UserId).big_integer().not_null())
+                    .col(ColumnDef::new(ExitOrders::TradeId).uuid().not_null())
+                    .col(ColumnDef::new(ExitOrders::Kind).string_len(16).not_null())
+                    .col(ColumnDef::new(ExitOrders::TokenMint).string().not_null())
+                    .col(ColumnDef::new(ExitOrders::TokenSymbol).string().not_null())
+                    .col(ColumnDef::new(ExitOrders::QuoteMint).string().not_null())
+                    .col(ColumnDef::new(ExitOrders::Amount).decimal_len(30, 9).not_null())
+                    .col(ColumnDef::new(ExitOrders::EntryPrice).decimal_len(30, 12).not_null())
+                    .col(ColumnDef::new(ExitOrders::TriggerPrice).decimal_len(30, 12).not_null())
+                    .col(ColumnDef::new(ExitOrders::TrailPct).decimal_len(10, 4).null())
+                    .col(ColumnDef::new(ExitOrders::HighWaterPrice).decimal_len(30, 12).null())
+                    .col(ColumnDef::new(ExitOrders::MaxSlippageBps).integer().not_null())
+                    .col(ColumnDef::new(ExitOrders::Status).string_len(16).not_null())
+                    .col(ColumnDef::new(ExitOrders::SellTradeId).uuid().null())
+                    .col(ColumnDef::new(ExitOrders::ErrorMessage).text().null())
+                    .col(ColumnDef::new(ExitOrders::TriggeredAt).timestamp_with_time_zone().null())
+                    .col(
+                        ColumnDef::new(ExitOrders::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(ExitOrders::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_exit_orders_user_id")
+                            .from(ExitOrders::Table, ExitOrders::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_exit_orders_trade_id")
+                            .from(ExitOrders::Table, ExitOrders::TradeId)
+                            .to(Trades::Table, Trades::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_exit_orders_sell_trade_id")
+                            .from(ExitOrders::Table, ExitOrders::SellTradeId)
+                            .to(Trades::Table, Trades::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_exit_orders_trade_id")
+                    .table(ExitOrders::Table)
+                    .col(ExitOrders::TradeId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_exit_orders_status")
+                    .table(ExitOrders::Table)
+                    .col(ExitOrders::Status)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ExitOrders::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum ExitOrders {
+    Table,
+    Id,
+    UserId,
+    TradeId,
+    Kind,
+    TokenMint,
+    TokenSymbol,
+    QuoteMint,
+    Amount,
+    EntryPrice,
+    TriggerPrice,
+    TrailPct,
+    HighWaterPrice,
+    MaxSlippageBps,
+    Status,
+    SellTradeId,
+    ErrorMessage,
+    TriggeredAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Trades {
+    Table,
+    Id,
+}
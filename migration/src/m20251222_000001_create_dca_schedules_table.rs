@@ -0,0 +1,168 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DcaSchedules::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DcaSchedules::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("gen_random_uuid()")),
+                    )
+                    .col(ColumnDef::new(DcaSchedules::UserId).big_integer().not_null())
+                    .col(ColumnDef::new(DcaSchedules::TokenMint).string().not_null())
+                    .col(ColumnDef::new(DcaSchedules::TokenSymbol).string().not_null())
+                    .col(ColumnDef::new(DcaSchedules::AmountSol).decimal_len(30, 9).not_null())
+                    .col(ColumnDef::new(DcaSchedules::IntervalSecs).big_integer().not_null())
+                    .col(ColumnDef::new(DcaSchedules::TotalCycles).integer().not_null())
+                    .col(ColumnDef::new(DcaSchedules::CyclesDone).integer().not_null().default(0))
+                    .col(ColumnDef::new(DcaSchedules::CyclesSkipped).integer().not_null().default(0))
+                    .col(ColumnDef::new(DcaSchedules::MaxSlippageBps).integer().not_null())
+                    .col(ColumnDef::new(DcaSchedules::Status).string_len(16).not_null())
+                    .col(ColumnDef::new(DcaSchedules::NextRunAt).timestamp_with_time_zone().not_null())
+                    .col(ColumnDef::new(DcaSchedules::LastRunAt).timestamp_with_time_zone().null())
+                    .col(ColumnDef::new(DcaSchedules::LastError).text().null())
+                    .col(
+                        ColumnDef::new(DcaSchedules::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(DcaSchedules::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_dca_schedules_user_id")
+                            .from(DcaSchedules::Table, DcaSchedules::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_dca_schedules_user_id")
+                    .table(DcaSchedules::Table)
+                    .col(DcaSchedules::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_dca_schedules_status_next_run_at")
+                    .table(DcaSchedules::Table)
+                    .col(DcaSchedules::Status)
+                    .col(DcaSchedules::NextRunAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Trades::Table)
+                    .add_column(ColumnDef::new(Trades::DcaScheduleId).uuid().null())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_trades_dca_schedule_id")
+                            .from_tbl(Trades::Table)
+                            .from_col(Trades::DcaScheduleId)
+                            .to_tbl(DcaSchedules::Table)
+                            .to_col(DcaSchedules::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_trades_dca_schedule_id")
+                    .table(Trades::Table)
+                    .col(Trades::DcaScheduleId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_trades_dca_schedule_id")
+                    .table(Trades::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Trades::Table)
+                    .drop_foreign_key(Alias::new("fk_trades_dca_schedule_id"))
+                    .drop_column(Trades::DcaScheduleId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(DcaSchedules::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum DcaSchedules {
+    Table,
+    Id,
+    UserId,
+    TokenMint,
+    TokenSymbol,
+    AmountSol,
+    IntervalSecs,
+    TotalCycles,
+    CyclesDone,
+    CyclesSkipped,
+    MaxSlippageBps,
+    Status,
+    NextRunAt,
+    LastRunAt,
+    LastError,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Trades {
+    Table,
+    DcaScheduleId,
+}
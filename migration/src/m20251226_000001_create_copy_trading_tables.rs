@@ -0,0 +1,229 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CopyFollows::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CopyFollows::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("gen_random_uuid()")),
+                    )
+                    .col(ColumnDef::new(CopyFollows::UserId).big_integer().not_null())
+                    .col(ColumnDef::new(CopyFollows::LeaderWallet).string().not_null())
+                    .col(ColumnDef::new(CopyFollows::Mode).string_len(16).not_null())
+                    .col(ColumnDef::new(CopyFollows::Amount).decimal_len(30, 9).not_null())
+                    .col(ColumnDef::new(CopyFollows::MaxAmountSol).decimal_len(30, 9).not_null())
+                    .col(
+                        ColumnDef::new(CopyFollows::AllowedMints)
+                            .json_binary()
+                            .not_null()
+                            .default(Expr::cust("'[]'::jsonb")),
+                    )
+                    .col(
+                        ColumnDef::new(CopyFollows::DeniedMints)
+                            .json_binary()
+                            .not_null()
+                            .default(Expr::cust("'[]'::jsonb")),
+                    )
+                    .col(ColumnDef::new(CopyFollows::DelaySecs).integer().not_null().default(0))
+                    .col(ColumnDef::new(CopyFollows::MaxSlippageBps).integer().not_null())
+                    .col(ColumnDef::new(CopyFollows::Status).string_len(16).not_null())
+                    .col(ColumnDef::new(CopyFollows::LastSignature).string().null())
+                    .col(
+                        ColumnDef::new(CopyFollows::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(CopyFollows::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_copy_follows_user_id")
+                            .from(CopyFollows::Table, CopyFollows::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_copy_follows_user_id")
+                    .table(CopyFollows::Table)
+                    .col(CopyFollows::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_copy_follows_status")
+                    .table(CopyFollows::Table)
+                    .col(CopyFollows::Status)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(CopyTrades::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CopyTrades::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("gen_random_uuid()")),
+                    )
+                    .col(ColumnDef::new(CopyTrades::FollowId).uuid().not_null())
+                    .col(ColumnDef::new(CopyTrades::UserId).big_integer().not_null())
+                    .col(ColumnDef::new(CopyTrades::LeaderSignature).string().not_null())
+                    .col(ColumnDef::new(CopyTrades::InputMint).string().not_null())
+                    .col(ColumnDef::new(CopyTrades::OutputMint).string().not_null())
+                    .col(ColumnDef::new(CopyTrades::LeaderInputAmount).decimal_len(40, 0).not_null())
+                    .col(ColumnDef::new(CopyTrades::LeaderOutputAmount).decimal_len(40, 0).not_null())
+                    .col(ColumnDef::new(CopyTrades::LeaderInputBalance).decimal_len(40, 0).not_null())
+                    .col(ColumnDef::new(CopyTrades::Status).string_len(16).not_null())
+                    .col(ColumnDef::new(CopyTrades::TradeId).uuid().null())
+                    .col(ColumnDef::new(CopyTrades::ErrorMessage).text().null())
+                    .col(ColumnDef::new(CopyTrades::ExecuteAfter).timestamp_with_time_zone().not_null())
+                    .col(
+                        ColumnDef::new(CopyTrades::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(CopyTrades::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_copy_trades_follow_id")
+                            .from(CopyTrades::Table, CopyTrades::FollowId)
+                            .to(CopyFollows::Table, CopyFollows::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_copy_trades_trade_id")
+                            .from(CopyTrades::Table, CopyTrades::TradeId)
+                            .to(Trades::Table, Trades::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Одна транзакция лидера повторяется для подписки не больше одного раза
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_copy_trades_follow_id_leader_signature")
+                    .table(CopyTrades::Table)
+                    .col(CopyTrades::FollowId)
+                    .col(CopyTrades::LeaderSignature)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_copy_trades_status_execute_after")
+                    .table(CopyTrades::Table)
+                    .col(CopyTrades::Status)
+                    .col(CopyTrades::ExecuteAfter)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CopyTrades::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(CopyFollows::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum CopyFollows {
+    Table,
+    Id,
+    UserId,
+    LeaderWallet,
+    Mode,
+    Amount,
+    MaxAmountSol,
+    AllowedMints,
+    DeniedMints,
+    DelaySecs,
+    MaxSlippageBps,
+    Status,
+    LastSignature,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum CopyTrades {
+    Table,
+    Id,
+    FollowId,
+    UserId,
+    LeaderSignature,
+    InputMint,
+    OutputMint,
+    LeaderInputAmount,
+    LeaderOutputAmount,
+    LeaderInputBalance,
+    Status,
+    TradeId,
+    ErrorMessage,
+    ExecuteAfter,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Trades {
+    Table,
+    Id,
+}
@@ -0,0 +1,95 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PriceAlerts::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PriceAlerts::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("gen_random_uuid()")),
+                    )
+                    .col(ColumnDef::new(PriceAlerts::UserId).big_integer().not_null())
+                    .col(ColumnDef::new(PriceAlerts::TokenMint).string().not_null())
+                    .col(ColumnDef::new(PriceAlerts::TokenSymbol).string().not_null())
+                    .col(ColumnDef::new(PriceAlerts::Kind).string_len(16).not_null())
+                    .col(ColumnDef::new(PriceAlerts::Threshold).decimal_len(30, 12).not_null())
+                    .col(ColumnDef::new(PriceAlerts::WindowSecs).big_integer().null())
+                    .col(ColumnDef::new(PriceAlerts::Armed).boolean().not_null().default(true))
+                    .col(ColumnDef::new(PriceAlerts::TriggerCount).integer().not_null().default(0))
+                    .col(ColumnDef::new(PriceAlerts::LastTriggeredAt).timestamp_with_time_zone().null())
+                    .col(
+                        ColumnDef::new(PriceAlerts::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(PriceAlerts::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_price_alerts_user_id")
+                            .from(PriceAlerts::Table, PriceAlerts::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_price_alerts_user_id")
+                    .table(PriceAlerts::Table)
+                    .col(PriceAlerts::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PriceAlerts::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum PriceAlerts {
+    Table,
+    Id,
+    UserId,
+    TokenMint,
+    TokenSymbol,
+    Kind,
+    Threshold,
+    WindowSecs,
+    Armed,
+    TriggerCount,
+    LastTriggeredAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
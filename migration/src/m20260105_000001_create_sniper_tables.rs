@@ -0,0 +1,178 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SniperSubscriptions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SniperSubscriptions::UserId)
+                            .big_integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SniperSubscriptions::AmountSol).decimal_len(30, 9).not_null())
+                    .col(ColumnDef::new(SniperSubscriptions::MaxSlippageBps).integer().not_null())
+                    .col(ColumnDef::new(SniperSubscriptions::Enabled).boolean().not_null().default(true))
+                    .col(
+                        ColumnDef::new(SniperSubscriptions::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(SniperSubscriptions::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_sniper_subscriptions_user_id")
+                            .from(SniperSubscriptions::Table, SniperSubscriptions::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(SniperDecisions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SniperDecisions::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("gen_random_uuid()")),
+                    )
+                    .col(ColumnDef::new(SniperDecisions::UserId).big_integer().null())
+                    .col(ColumnDef::new(SniperDecisions::TokenMint).string().not_null())
+                    .col(ColumnDef::new(SniperDecisions::TokenSymbol).string().not_null())
+                    .col(ColumnDef::new(SniperDecisions::PoolAddress).string().null())
+                    .col(ColumnDef::new(SniperDecisions::PoolCreatedAt).timestamp_with_time_zone().null())
+                    .col(ColumnDef::new(SniperDecisions::Status).string_len(16).not_null())
+                    .col(
+                        ColumnDef::new(SniperDecisions::Reasons)
+                            .json_binary()
+                            .not_null()
+                            .default(Expr::cust("'[]'::jsonb")),
+                    )
+                    .col(ColumnDef::new(SniperDecisions::Report).json_binary().null())
+                    .col(ColumnDef::new(SniperDecisions::TradeId).uuid().null())
+                    .col(
+                        ColumnDef::new(SniperDecisions::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(SniperDecisions::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_sniper_decisions_user_id")
+                            .from(SniperDecisions::Table, SniperDecisions::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_sniper_decisions_trade_id")
+                            .from(SniperDecisions::Table, SniperDecisions::TradeId)
+                            .to(Trades::Table, Trades::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sniper_decisions_token_mint")
+                    .table(SniperDecisions::Table)
+                    .col(SniperDecisions::TokenMint)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sniper_decisions_status_created_at")
+                    .table(SniperDecisions::Table)
+                    .col(SniperDecisions::Status)
+                    .col(SniperDecisions::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SniperDecisions::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(SniperSubscriptions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum SniperSubscriptions {
+    Table,
+    UserId,
+    AmountSol,
+    MaxSlippageBps,
+    Enabled,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum SniperDecisions {
+    Table,
+    Id,
+    UserId,
+    TokenMint,
+    TokenSymbol,
+    PoolAddress,
+    PoolCreatedAt,
+    Status,
+    Reasons,
+    Report,
+    TradeId,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Trades {
+    Table,
+    Id,
+}
@@ -0,0 +1,95 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(FeePreferences::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(FeePreferences::UserId)
+                            .big_integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(FeePreferences::Tier).string_len(16).not_null())
+                    .col(ColumnDef::new(FeePreferences::MaxLamports).big_integer().null())
+                    .col(
+                        ColumnDef::new(FeePreferences::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(FeePreferences::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_fee_preferences_user_id")
+                            .from(FeePreferences::Table, FeePreferences::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Trades::Table)
+                    .add_column(ColumnDef::new(Trades::PriorityFeeLamports).big_integer().null())
+                    .add_column(ColumnDef::new(Trades::PriorityFeeTier).string_len(16).null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Trades::Table)
+                    .drop_column(Trades::PriorityFeeLamports)
+                    .drop_column(Trades::PriorityFeeTier)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(FeePreferences::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum FeePreferences {
+    Table,
+    UserId,
+    Tier,
+    MaxLamports,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Trades {
+    Table,
+    PriorityFeeLamports,
+    PriorityFeeTier,
+}
@@ -0,0 +1,59 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Tokens::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Tokens::Mint)
+                            .string_len(44)
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Tokens::Symbol).string().null())
+                    .col(ColumnDef::new(Tokens::Name).string().null())
+                    .col(ColumnDef::new(Tokens::Decimals).small_integer().not_null())
+                    .col(ColumnDef::new(Tokens::Token2022).boolean().not_null().default(false))
+                    .col(
+                        ColumnDef::new(Tokens::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Tokens::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Tokens::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Tokens {
+    Table,
+    Mint,
+    Symbol,
+    Name,
+    Decimals,
+    #[iden = "token_2022"]
+    Token2022,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,91 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+// Столбец-перечисление и его допустимые значения
+struct EnumColumn {
+    table: &'static str,
+    column: &'static str,
+    values: &'static [&'static str],
+}
+
+const ENUM_COLUMNS: &[EnumColumn] = &[
+    EnumColumn { table: "trades", column: "trade_type", values: &["BUY", "SELL", "SWAP"] },
+    EnumColumn {
+        table: "trades",
+        column: "status",
+        values: &["PENDING", "EXECUTING", "COMPLETED", "FAILED", "CANCELLED"],
+    },
+    EnumColumn { table: "wallets", column: "wallet_type", values: &["HOT", "LEDGER", "PHANTOM", "SOLFLARE"] },
+];
+
+impl EnumColumn {
+    fn constraint(&self) -> String {
+        format!("chk_{}_{}", self.table, self.column)
+    }
+
+    fn value_list(&self) -> String {
+        self.values.iter().map(|value| format!("'{}'", value)).collect::<Vec<_>>().join(", ")
+    }
+
+    // Однобуквенный код превращается в первое значение на эту букву, остальное — в верхний регистр.
+    // Значения, которые так не распознать, не пройдут CHECK и остановят миграцию.
+    fn convert_sql(&self) -> String {
+        let mut cases = String::new();
+        for value in self.values {
+            let code = &value[..1];
+            if !cases.contains(&format!("WHEN '{}'", code)) {
+                cases.push_str(&format!("WHEN '{}' THEN '{}' ", code, value));
+            }
+        }
+        format!(
+            "UPDATE {table} SET {column} = CASE UPPER({column}) {cases}ELSE UPPER({column}) END",
+            table = self.table,
+            column = self.column,
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        for column in ENUM_COLUMNS {
+            db.execute_unprepared(&format!(
+                "ALTER TABLE {} ALTER COLUMN {} TYPE varchar(16)",
+                column.table, column.column,
+            ))
+            .await?;
+            db.execute_unprepared(&column.convert_sql()).await?;
+            db.execute_unprepared(&format!(
+                "ALTER TABLE {} ADD CONSTRAINT {} CHECK ({} IN ({}))",
+                column.table, column.constraint(), column.column, column.value_list(),
+            ))
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        for column in ENUM_COLUMNS {
+            db.execute_unprepared(&format!(
+                "ALTER TABLE {} DROP CONSTRAINT IF EXISTS {}",
+                column.table, column.constraint(),
+            ))
+            .await?;
+            db.execute_unprepared(&format!(
+                "ALTER TABLE {table} ALTER COLUMN {column} TYPE varchar(1) USING LEFT({column}, 1)",
+                table = column.table,
+                column = column.column,
+            ))
+            .await?;
+        }
+
+        Ok(())
+    }
+}
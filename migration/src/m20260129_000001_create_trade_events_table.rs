@@ -0,0 +1,85 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TradeEvents::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TradeEvents::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .default(Expr::cust("gen_random_uuid()")),
+                    )
+                    .col(ColumnDef::new(TradeEvents::TradeId).uuid().not_null())
+                    .col(ColumnDef::new(TradeEvents::Kind).string_len(16).not_null())
+                    .col(ColumnDef::new(TradeEvents::Status).string_len(16).not_null())
+                    .col(ColumnDef::new(TradeEvents::Slot).big_integer().null())
+                    .col(ColumnDef::new(TradeEvents::Details).json_binary().null())
+                    .col(ColumnDef::new(TradeEvents::Error).text().null())
+                    .col(
+                        ColumnDef::new(TradeEvents::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .check(Expr::cust(
+                        "kind IN ('QUOTE_RECEIVED', 'SIGNED', 'SENT', 'CONFIRMED', 'ERROR', 'CANCELLED')",
+                    ))
+                    .check(Expr::cust("status IN ('PENDING', 'EXECUTING', 'COMPLETED', 'FAILED', 'CANCELLED')"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_trade_events_trade_id")
+                            .from(TradeEvents::Table, TradeEvents::TradeId)
+                            .to(Trades::Table, Trades::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_trade_events_trade_id_created_at")
+                    .table(TradeEvents::Table)
+                    .col(TradeEvents::TradeId)
+                    .col(TradeEvents::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TradeEvents::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum TradeEvents {
+    Table,
+    Id,
+    TradeId,
+    Kind,
+    Status,
+    Slot,
+    Details,
+    Error,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum Trades {
+    Table,
+    Id,
+}
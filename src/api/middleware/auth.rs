@@ -1,15 +1,37 @@
 use axum::{
-    extract::Request,
+    extract::{Request, State},
     middleware::Next,
     response::Response,
-    http::StatusCode,
+    http::{StatusCode, header::AUTHORIZATION},
 };
-use std::collections::HashMap;
+use secrecy::{ExposeSecret, SecretString};
 
+/// Совпадает ли заголовок `Authorization` с `Bearer <token>`.
+/// Сравнение идет по всей длине, чтобы время ответа не выдавало префикс токена.
+pub fn is_authorized(header: Option<&str>, token: &str) -> bool {
+    let Some(provided) = header.and_then(|header| header.strip_prefix("Bearer ")) else {
+        return false;
+    };
+    if token.is_empty() || provided.len() != token.len() {
+        return false;
+    }
+    provided.bytes().zip(token.bytes()).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// Пускает к админским маршрутам только с токеном `api.admin_token`;
+/// без настроенного токена админский API закрыт целиком
 pub async fn auth_middleware(
+    State(token): State<Option<SecretString>>,
     request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    // TODO: Implement authentication
+    let Some(token) = token else {
+        return Err(StatusCode::UNAUTHORIZED);
+    };
+    let header = request.headers().get(AUTHORIZATION).and_then(|value| value.to_str().ok());
+    if !is_authorized(header, token.expose_secret()) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
     Ok(next.run(request).await)
 }
@@ -1,11 +1,22 @@
 use axum::{
     Extension,
     Json,
+    extract::{Path, Query},
     response::IntoResponse,
     http::StatusCode,
 };
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
 use serde_json::json;
-use crate::database::connection::DatabaseConnectionPool;
+use solana_sdk::pubkey::Pubkey;
+use uuid::Uuid;
+use crate::config::settings::{LimitOrderSettings, TradingLimits, default_limit_slippage_bps};
+use crate::database::{
+    connection::DatabaseConnectionPool, limit_orders::{self, NewLimitOrder}, sniper, trade_events, trades, users, wallets,
+};
+use crate::entities::{limit_orders::{LimitOrderStatus, OrderSide}, sniper_decisions::SniperDecisionStatus};
+use crate::solana::constants::{find_known_token, token_symbol};
 
 pub async fn get_status() -> impl IntoResponse {
     (StatusCode::OK, Json(json!({"status": "running"})))
@@ -24,3 +35,199 @@ pub async fn list_trades(
     // TODO: Implement trades listing
     (StatusCode::OK, Json(json!({"trades": []})))
 }
+
+/// Сделка и ее история от первого события к последнему
+pub async fn trade_timeline(
+    Extension(db): Extension<DatabaseConnectionPool>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    let trade = match trades::find(db.get_connection(), id).await {
+        Ok(Some(trade)) => trade,
+        Ok(None) => return (StatusCode::NOT_FOUND, Json(json!({"error": "trade not found"}))),
+        Err(e) => {
+            tracing::error!("Failed to load trade {}: {}", id, e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "failed to load trade"})));
+        }
+    };
+
+    match trade_events::timeline(db.get_connection(), id).await {
+        Ok(events) => (StatusCode::OK, Json(json!({"trade": trade, "events": events}))),
+        Err(e) => {
+            tracing::error!("Failed to load timeline of trade {}: {}", id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "failed to load trade timeline"})))
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FeeReportQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+pub async fn fee_revenue(
+    Extension(db): Extension<DatabaseConnectionPool>,
+    Query(query): Query<FeeReportQuery>,
+) -> impl IntoResponse {
+    match trades::fee_revenue(db.get_connection(), query.from, query.to).await {
+        Ok(rows) => {
+            let fees: Vec<_> = rows
+                .into_iter()
+                .map(|row| {
+                    let symbol = find_known_token(&row.mint).map(|token| token.symbol);
+                    json!({
+                        "mint": row.mint,
+                        "symbol": symbol,
+                        "total_amount": row.total_amount.to_string(),
+                        "trades": row.trades,
+                    })
+                })
+                .collect();
+
+            (StatusCode::OK, Json(json!({
+                "from": query.from,
+                "to": query.to,
+                "fees": fees,
+            })))
+        }
+        Err(e) => {
+            tracing::error!("Failed to build fee revenue report: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "failed to build fee report"})))
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LimitOrdersQuery {
+    pub user_id: Option<i64>,
+    pub status: Option<LimitOrderStatus>,
+}
+
+pub async fn list_limit_orders(
+    Extension(db): Extension<DatabaseConnectionPool>,
+    Query(query): Query<LimitOrdersQuery>,
+) -> impl IntoResponse {
+    match limit_orders::list(db.get_connection(), query.user_id, query.status).await {
+        Ok(orders) => (StatusCode::OK, Json(json!({"limit_orders": orders}))),
+        Err(e) => {
+            tracing::error!("Failed to list limit orders: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "failed to list limit orders"})))
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateLimitOrderRequest {
+    pub user_id: i64,
+    pub side: OrderSide,
+    pub token_mint: String,
+    pub amount: BigDecimal,
+    pub target_price: BigDecimal,
+    #[serde(default = "default_limit_slippage_bps")]
+    pub max_slippage_bps: u64,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+pub async fn create_limit_order(
+    Extension(db): Extension<DatabaseConnectionPool>,
+    Extension(limits): Extension<TradingLimits>,
+    Extension(settings): Extension<LimitOrderSettings>,
+    Json(request): Json<CreateLimitOrderRequest>,
+) -> impl IntoResponse {
+    let Ok(mint) = request.token_mint.parse::<Pubkey>() else {
+        return (StatusCode::BAD_REQUEST, Json(json!({"error": "invalid token_mint"})));
+    };
+    if request.amount <= BigDecimal::zero() || request.target_price <= BigDecimal::zero() {
+        return (StatusCode::BAD_REQUEST, Json(json!({"error": "amount and target_price must be positive"})));
+    }
+    let max_slippage_bps = match i32::try_from(request.max_slippage_bps) {
+        Ok(bps) if request.max_slippage_bps <= limits.max_slippage_bps => bps,
+        _ => {
+            return (StatusCode::UNPROCESSABLE_ENTITY, Json(json!({
+                "error": format!("max_slippage_bps must not exceed {}", limits.max_slippage_bps),
+            })));
+        }
+    };
+    // Как и в Telegram, ордер без срока истекает через срок по умолчанию
+    let expires_at = request
+        .expires_at
+        .unwrap_or_else(|| Utc::now() + Duration::hours(settings.default_expiry_hours as i64));
+    if expires_at <= Utc::now() {
+        return (StatusCode::BAD_REQUEST, Json(json!({"error": "expires_at must be in the future"})));
+    }
+
+    // Движок исполняет ордер с кошелька пользователя по умолчанию: без него ордер не создается
+    let connection = db.get_connection();
+    match users::find(connection, request.user_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return (StatusCode::NOT_FOUND, Json(json!({"error": "user not found"}))),
+        Err(e) => {
+            tracing::error!("Failed to load user {}: {}", request.user_id, e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "failed to load user"})));
+        }
+    }
+    match wallets::find_default(connection, request.user_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return (StatusCode::UNPROCESSABLE_ENTITY, Json(json!({"error": "user has no default wallet"}))),
+        Err(e) => {
+            tracing::error!("Failed to load default wallet of user {}: {}", request.user_id, e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "failed to load wallet"})));
+        }
+    }
+
+    let result = limit_orders::create(connection, NewLimitOrder {
+        user_id: request.user_id,
+        side: request.side,
+        token_mint: mint.to_string(),
+        token_symbol: token_symbol(&mint),
+        amount: request.amount,
+        target_price: request.target_price,
+        max_slippage_bps,
+        expires_at: Some(expires_at),
+    }).await;
+
+    match result {
+        Ok(order) => (StatusCode::CREATED, Json(json!({"limit_order": order}))),
+        Err(e) => {
+            tracing::error!("Failed to create limit order: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "failed to create limit order"})))
+        }
+    }
+}
+
+pub async fn cancel_limit_order(
+    Extension(db): Extension<DatabaseConnectionPool>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match limit_orders::cancel(db.get_connection(), id, None).await {
+        Ok(true) => (StatusCode::OK, Json(json!({"id": id, "status": LimitOrderStatus::Cancelled}))),
+        Ok(false) => (StatusCode::CONFLICT, Json(json!({"error": "limit order is not open"}))),
+        Err(e) => {
+            tracing::error!("Failed to cancel limit order {}: {}", id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "failed to cancel limit order"})))
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SniperDecisionsQuery {
+    pub user_id: Option<i64>,
+    pub status: Option<SniperDecisionStatus>,
+    #[serde(default = "default_decisions_limit")]
+    pub limit: u64,
+}
+
+fn default_decisions_limit() -> u64 { 100 }
+
+pub async fn list_sniper_decisions(
+    Extension(db): Extension<DatabaseConnectionPool>,
+    Query(query): Query<SniperDecisionsQuery>,
+) -> impl IntoResponse {
+    match sniper::recent_decisions(db.get_connection(), query.user_id, query.status, query.limit.min(1000)).await {
+        Ok(decisions) => (StatusCode::OK, Json(json!({"decisions": decisions}))),
+        Err(e) => {
+            tracing::error!("Failed to list sniper decisions: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "failed to list sniper decisions"})))
+        }
+    }
+}
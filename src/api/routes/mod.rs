@@ -4,4 +4,7 @@ pub mod admin;
 
 pub use health::health_check;
 pub use metrics::get_metrics;
-pub use admin::{get_status, list_users, list_trades};
+pub use admin::{
+    get_status, list_users, list_trades, trade_timeline, fee_revenue, list_limit_orders, create_limit_order,
+    cancel_limit_order, list_sniper_decisions,
+};
@@ -2,6 +2,7 @@ use axum::{
     Router,
     routing::{get, post},
     Extension,
+    middleware,
 };
 use tower_http::{
     trace::TraceLayer,
@@ -12,14 +13,13 @@ use tower_http::{
     sensitive_headers::SetSensitiveHeadersLayer,
 };
 use std::time::Duration;
-use std::sync::Arc;
 
 use crate::{
-    config::settings::{ApiSettings},
+    config::settings::{ApiSettings, LimitOrderSettings, TradingLimits},
     database::connection::DatabaseConnectionPool,
     monitoring::metrics::MetricsRegistry,
     security::secrets_manager::SecretsManager,
-    api::routes,
+    api::{middleware::auth::auth_middleware, routes},
 };
 
 pub struct ApiServer {
@@ -27,6 +27,8 @@ pub struct ApiServer {
     database: DatabaseConnectionPool,
     metrics: MetricsRegistry,
     secrets: SecretsManager,
+    trading_limits: TradingLimits,
+    limit_orders: LimitOrderSettings,
 }
 
 impl ApiServer {
@@ -35,12 +37,16 @@ impl ApiServer {
         database: DatabaseConnectionPool,
         metrics: MetricsRegistry,
         secrets: SecretsManager,
+        trading_limits: TradingLimits,
+        limit_orders: LimitOrderSettings,
     ) -> Self {
         Self {
             settings,
             database,
             metrics,
             secrets,
+            trading_limits,
+            limit_orders,
         }
     }
 
@@ -65,16 +71,34 @@ impl ApiServer {
     }
 
     async fn create_router(&self) -> Result<Router, anyhow::Error> {
+        // Админские роуты доступны только с токеном
+        let admin_routes = Router::new()
+            .route("/admin/status", get(routes::admin::get_status))
+            .route("/admin/users", get(routes::admin::list_users))
+            .route("/admin/trades", get(routes::admin::list_trades))
+            .route("/admin/trades/{id}/timeline", get(routes::admin::trade_timeline))
+            .route("/admin/fees", get(routes::admin::fee_revenue))
+            .route(
+                "/admin/limit-orders",
+                get(routes::admin::list_limit_orders).post(routes::admin::create_limit_order),
+            )
+            .route("/admin/limit-orders/{id}/cancel", post(routes::admin::cancel_limit_order))
+            .route("/admin/sniper/decisions", get(routes::admin::list_sniper_decisions))
+            .route_layer(middleware::from_fn_with_state(
+                self.settings.admin_token.clone(),
+                auth_middleware,
+            ));
+
         // Создаем основные роуты
         let api_routes = Router::new()
             .route("/health", get(routes::health::health_check))
             .route("/metrics", get(routes::metrics::get_metrics))
-            .route("/admin/status", get(routes::admin::get_status))
-            .route("/admin/users", get(routes::admin::list_users))
-            .route("/admin/trades", get(routes::admin::list_trades))
+            .merge(admin_routes)
             .layer(Extension(self.database.clone()))
             .layer(Extension(self.metrics.clone()))
-            .layer(Extension(self.secrets.clone()));
+            .layer(Extension(self.secrets.clone()))
+            .layer(Extension(self.trading_limits.clone()))
+            .layer(Extension(self.limit_orders.clone()));
 
         // Создаем основной роутер с middleware
         let app = Router::new()
@@ -111,7 +135,10 @@ impl ApiServer {
             )
             .layer(CompressionLayer::new())
             .layer(RequestBodyLimitLayer::new(10 * 1024 * 1024)) // 10MB limit
-            .layer(TimeoutLayer::new(Duration::from_secs(30)))
+            .layer(TimeoutLayer::with_status_code(
+                axum::http::StatusCode::REQUEST_TIMEOUT,
+                Duration::from_secs(30),
+            ))
             .layer(SetSensitiveHeadersLayer::new(std::iter::once(
                 axum::http::header::AUTHORIZATION,
             )));
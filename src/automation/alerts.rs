@@ -0,0 +1,251 @@
+use std::collections::{HashMap, VecDeque};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bigdecimal::ToPrimitive;
+use chrono::{DateTime, Utc};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    database::{DatabaseConnectionPool, alerts},
+    entities::price_alerts::{AlertKind, Model as PriceAlert},
+    jupiter::{PriceService, TokenPrice},
+    telegram::notifier::Notifier,
+    utils::short_id,
+};
+
+// Окно, для которого Jupiter сам отдает изменение цены
+const JUPITER_CHANGE_WINDOW_SECS: i64 = 86_400;
+
+/// История цен за последние сутки для расчета изменения в произвольном окне
+#[derive(Default)]
+pub struct PriceHistory {
+    samples: HashMap<Pubkey, VecDeque<(DateTime<Utc>, f64)>>,
+}
+
+impl PriceHistory {
+    pub fn record(&mut self, mint: Pubkey, at: DateTime<Utc>, price: f64) {
+        let samples = self.samples.entry(mint).or_default();
+        samples.push_back((at, price));
+
+        let oldest = at - chrono::Duration::seconds(JUPITER_CHANGE_WINDOW_SECS);
+        // Последняя запись до начала суток остается базой для суточного окна
+        while samples.get(1).is_some_and(|(sampled_at, _)| *sampled_at <= oldest) {
+            samples.pop_front();
+        }
+    }
+
+    /// Изменение цены в процентах за `window`: от последней цены, записанной не позже
+    /// начала окна, до последней записанной. `None`, пока история короче окна.
+    pub fn change_pct(&self, mint: &Pubkey, now: DateTime<Utc>, window: chrono::Duration) -> Option<f64> {
+        let samples = self.samples.get(mint)?;
+        let start = now - window;
+
+        let (_, base) = samples.iter().rev().find(|(at, _)| *at <= start)?;
+        let (_, last) = samples.back()?;
+
+        (*base > 0.0).then(|| (last - base) / base * 100.0)
+    }
+}
+
+/// Рыночные данные токена на момент проверки
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Observation {
+    pub price: f64,
+    pub change_pct: Option<f64>,
+    pub volume_24h: Option<f64>,
+}
+
+/// Выполняется ли условие уведомления. `None`, если для проверки не хватает данных.
+pub fn condition_met(kind: AlertKind, threshold: f64, observation: &Observation) -> Option<bool> {
+    match kind {
+        AlertKind::Above => Some(observation.price >= threshold),
+        AlertKind::Below => Some(observation.price <= threshold),
+        AlertKind::Rise => observation.change_pct.map(|change| change >= threshold),
+        AlertKind::Drop => observation.change_pct.map(|change| change <= -threshold),
+        AlertKind::Move => observation.change_pct.map(|change| change.abs() >= threshold),
+        AlertKind::Volume => observation.volume_24h.map(|volume| volume >= threshold),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertAction {
+    Fire,
+    Rearm,
+    Wait,
+}
+
+/// Уведомление срабатывает один раз при входе в условие и не чаще, чем раз в `cooldown`.
+/// Снова оно включается, только когда условие перестанет выполняться.
+pub fn decide(
+    armed: bool,
+    met: bool,
+    last_triggered_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    cooldown: chrono::Duration,
+) -> AlertAction {
+    match (armed, met) {
+        (true, true) if last_triggered_at.is_none_or(|at| now - at >= cooldown) => AlertAction::Fire,
+        (false, false) => AlertAction::Rearm,
+        _ => AlertAction::Wait,
+    }
+}
+
+pub fn describe_condition(alert: &PriceAlert) -> String {
+    let threshold = alert.threshold.normalized();
+    let window = alert.window_secs.map(format_window).unwrap_or_default();
+
+    match alert.kind {
+        AlertKind::Above => format!("цена ≥ ${}", threshold),
+        AlertKind::Below => format!("цена ≤ ${}", threshold),
+        AlertKind::Rise => format!("рост на {}% за {}", threshold, window),
+        AlertKind::Drop => format!("падение на {}% за {}", threshold, window),
+        AlertKind::Move => format!("изменение на ±{}% за {}", threshold, window),
+        AlertKind::Volume => format!("объем за 24ч ≥ ${}", threshold),
+    }
+}
+
+fn format_window(secs: i64) -> String {
+    match secs {
+        s if s % 3_600 == 0 => format!("{}ч", s / 3_600),
+        s => format!("{}мин", s / 60),
+    }
+}
+
+/// Фоновая проверка ценовых уведомлений: раз в интервал запрашивает рыночные данные
+/// по всем токенам с уведомлениями и отправляет сработавшие в Telegram
+pub struct AlertEngine {
+    database: Arc<DatabaseConnectionPool>,
+    price_service: Arc<PriceService>,
+    notifier: Notifier,
+    poll_interval: Duration,
+    cooldown: chrono::Duration,
+    history: PriceHistory,
+}
+
+impl AlertEngine {
+    pub fn new(
+        database: Arc<DatabaseConnectionPool>,
+        price_service: Arc<PriceService>,
+        notifier: Notifier,
+        poll_interval: Duration,
+        cooldown: Duration,
+    ) -> Self {
+        Self {
+            database,
+            price_service,
+            notifier,
+            poll_interval,
+            cooldown: chrono::Duration::from_std(cooldown).unwrap_or(chrono::Duration::zero()),
+            history: PriceHistory::default(),
+        }
+    }
+
+    pub async fn run(mut self) {
+        tracing::info!("Price alert engine started (poll interval {:?})", self.poll_interval);
+
+        let mut interval = tokio::time::interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.tick().await {
+                tracing::warn!("Price alert check failed: {:#}", e);
+            }
+        }
+    }
+
+    async fn tick(&mut self) -> anyhow::Result<()> {
+        let db = self.database.get_connection();
+        let now = Utc::now();
+
+        let alerts = alerts::all(db).await?;
+        if alerts.is_empty() {
+            return Ok(());
+        }
+
+        let mut mints = Vec::new();
+        for alert in &alerts {
+            match Pubkey::from_str(&alert.token_mint) {
+                Ok(mint) if !mints.contains(&mint) => mints.push(mint),
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Price alert {} has invalid mint: {}", alert.id, e),
+            }
+        }
+
+        let market = self.price_service.get_market_data(&mints).await?;
+        for (mint, price) in &market {
+            self.history.record(*mint, now, price.price);
+        }
+
+        for alert in alerts {
+            let Some((mint, price)) = Pubkey::from_str(&alert.token_mint)
+                .ok()
+                .and_then(|mint| market.get(&mint).map(|price| (mint, price)))
+            else {
+                continue;
+            };
+            let observation = self.observe(&alert, mint, price, now);
+            let Some(met) = alert
+                .threshold
+                .to_f64()
+                .and_then(|threshold| condition_met(alert.kind, threshold, &observation))
+            else {
+                continue;
+            };
+
+            match decide(alert.armed, met, alert.last_triggered_at, now, self.cooldown) {
+                AlertAction::Fire => self.fire(&alert, &observation, now).await,
+                AlertAction::Rearm => {
+                    if let Err(e) = alerts::rearm(db, alert.id).await {
+                        tracing::warn!("Failed to rearm price alert {}: {}", alert.id, e);
+                    }
+                }
+                AlertAction::Wait => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn observe(&self, alert: &PriceAlert, mint: Pubkey, price: &TokenPrice, now: DateTime<Utc>) -> Observation {
+        let change_pct = alert.window_secs.and_then(|window| {
+            let from_history = self.history.change_pct(&mint, now, chrono::Duration::seconds(window));
+            match window {
+                JUPITER_CHANGE_WINDOW_SECS => price.price_change_24h.or(from_history),
+                _ => from_history,
+            }
+        });
+
+        Observation {
+            price: price.price,
+            change_pct,
+            volume_24h: price.volume_24h,
+        }
+    }
+
+    async fn fire(&self, alert: &PriceAlert, observation: &Observation, now: DateTime<Utc>) {
+        match alerts::mark_fired(self.database.get_connection(), alert.id, now).await {
+            Ok(true) => {}
+            Ok(false) => return,
+            Err(e) => {
+                tracing::warn!("Failed to mark price alert {} fired: {}", alert.id, e);
+                return;
+            }
+        }
+
+        let mut text = format!(
+            "🔔 {}: {}\nЦена: ${}",
+            alert.token_symbol, describe_condition(alert), observation.price,
+        );
+        if let Some(change) = observation.change_pct {
+            text.push_str(&format!(" ({:+.2}%)", change));
+        }
+        if let Some(volume) = observation.volume_24h.filter(|_| alert.kind == AlertKind::Volume) {
+            text.push_str(&format!("\nОбъем за 24ч: ${:.0}", volume));
+        }
+        text.push_str(&format!("\n/alert delete {} — удалить", short_id(&alert.id)));
+
+        self.notifier.notify(alert.user_id, text).await;
+    }
+}
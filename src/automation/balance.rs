@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::{
+    database::{DatabaseConnectionPool, wallets},
+    solana::{
+        amount::TokenAmount,
+        pubsub::{PubsubEvent, PubsubManager, PubsubTopic, Subscription},
+        rpc::SolanaRpc,
+    },
+    telegram::notifier::Notifier,
+};
+
+/// Стоит ли сообщать об изменении баланса: мелкие колебания вроде комиссий пропускаются
+pub fn should_notify(previous: u64, current: u64, min_change_lamports: u64) -> bool {
+    previous != current && previous.abs_diff(current) >= min_change_lamports
+}
+
+pub fn balance_change_text(wallet_name: &str, previous: u64, current: u64) -> String {
+    let (sign, icon) = if current >= previous { ("+", "📈") } else { ("-", "📉") };
+    format!(
+        "{} Баланс кошелька {}: {}{:.4} SOL\nТеперь: {:.4} SOL",
+        icon,
+        wallet_name,
+        sign,
+        TokenAmount::sol(previous.abs_diff(current)),
+        TokenAmount::sol(current),
+    )
+}
+
+struct WatchedWallet {
+    user_id: i64,
+    name: String,
+    pubkey: Pubkey,
+    lamports: u64,
+    _subscription: Subscription,
+}
+
+/// Живые уведомления о балансе SOL: подписывается на аккаунты кошельков, владельцы
+/// которых включили `/notify on`, и раз в интервал сверяет подписки со списком кошельков
+pub struct BalanceWatcher {
+    database: Arc<DatabaseConnectionPool>,
+    rpc: Arc<dyn SolanaRpc>,
+    pubsub: Arc<PubsubManager>,
+    notifier: Notifier,
+    refresh_interval: Duration,
+    min_change_lamports: u64,
+    watched: HashMap<Uuid, WatchedWallet>,
+}
+
+impl BalanceWatcher {
+    pub fn new(
+        database: Arc<DatabaseConnectionPool>,
+        rpc: Arc<dyn SolanaRpc>,
+        pubsub: Arc<PubsubManager>,
+        notifier: Notifier,
+        refresh_interval: Duration,
+        min_change_lamports: u64,
+    ) -> Self {
+        Self {
+            database,
+            rpc,
+            pubsub,
+            notifier,
+            refresh_interval,
+            min_change_lamports,
+            watched: HashMap::new(),
+        }
+    }
+
+    pub async fn run(mut self) {
+        tracing::info!("Balance watcher started (refresh interval {:?})", self.refresh_interval);
+
+        let (sender, mut events) = self.pubsub.channel();
+        let mut interval = tokio::time::interval(self.refresh_interval);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    if let Err(e) = self.refresh(&sender).await {
+                        tracing::warn!("Balance watcher refresh failed: {:#}", e);
+                    }
+                }
+                Some(event) = events.recv() => self.handle(event).await,
+            }
+        }
+    }
+
+    /// Подписывается на новые кошельки и отписывается от тех, где уведомления выключены
+    async fn refresh(&mut self, sender: &mpsc::Sender<PubsubEvent>) -> anyhow::Result<()> {
+        let enabled = wallets::with_balance_notifications(self.database.get_connection()).await?;
+
+        self.watched.retain(|id, _| enabled.iter().any(|wallet| wallet.id == *id));
+
+        for wallet in enabled {
+            if self.watched.contains_key(&wallet.id) {
+                continue;
+            }
+            let pubkey = match Pubkey::from_str(&wallet.public_key) {
+                Ok(pubkey) => pubkey,
+                Err(e) => {
+                    tracing::warn!("Wallet {} has invalid public key: {}", wallet.id, e);
+                    continue;
+                }
+            };
+            // Отправной баланс берется из сети: сохраненный в БД мог устареть
+            let lamports = self.rpc.get_balance(&pubkey).await?;
+            let subscription = self.pubsub.subscribe(PubsubTopic::Account(pubkey), sender.clone())?;
+
+            self.watched.insert(wallet.id, WatchedWallet {
+                user_id: wallet.user_id,
+                name: wallet.name,
+                pubkey,
+                lamports,
+                _subscription: subscription,
+            });
+        }
+
+        Ok(())
+    }
+
+    async fn handle(&mut self, event: PubsubEvent) {
+        match event {
+            PubsubEvent::Account { pubkey, lamports, .. } => {
+                let ids: Vec<Uuid> = self.watched.iter()
+                    .filter(|(_, wallet)| wallet.pubkey == pubkey)
+                    .map(|(id, _)| *id)
+                    .collect();
+                for id in ids {
+                    self.update(id, lamports).await;
+                }
+            }
+            // Изменения за время разрыва не пришли: сверяем балансы напрямую
+            PubsubEvent::Reconnected => {
+                let wallets: Vec<(Uuid, Pubkey)> = self.watched.iter().map(|(id, wallet)| (*id, wallet.pubkey)).collect();
+                for (id, pubkey) in wallets {
+                    match self.rpc.get_balance(&pubkey).await {
+                        Ok(lamports) => self.update(id, lamports).await,
+                        Err(e) => tracing::warn!("Failed to get balance of {}: {}", pubkey, e),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    async fn update(&mut self, id: Uuid, lamports: u64) {
+        let Some(wallet) = self.watched.get_mut(&id) else {
+            return;
+        };
+        if !should_notify(wallet.lamports, lamports, self.min_change_lamports) {
+            return;
+        }
+
+        let text = balance_change_text(&wallet.name, wallet.lamports, lamports);
+        wallet.lamports = lamports;
+        let user_id = wallet.user_id;
+
+        let balance_sol = TokenAmount::sol(lamports).to_big_decimal();
+        if let Err(e) = wallets::update_balance(self.database.get_connection(), id, balance_sol).await {
+            tracing::warn!("Failed to save balance of wallet {}: {}", id, e);
+        }
+        self.notifier.notify(user_id, text).await;
+    }
+}
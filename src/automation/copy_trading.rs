@@ -0,0 +1,393 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bigdecimal::{BigDecimal, ToPrimitive};
+use chrono::Utc;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+
+use super::{TradeOutcome, UNCONFIRMED_TIMEOUT, ensure_sol_balance, settle_trade};
+use crate::{
+    database::{DatabaseConnectionPool, copy_trading::{self, NewCopyTrade}, trade_events::NewTradeEvent},
+    entities::{
+        copy_follows::{CopyFollowStatus, CopyMode, Model as CopyFollow},
+        copy_trades::{CopyTradeStatus, Model as CopyTrade},
+        trades::{TradeStatus, TradeType},
+    },
+    jupiter::{QuoteResponseV6, SwapMode},
+    security::wallet_keys,
+    solana::{
+        amount::TokenAmount,
+        constants::{SOL_DECIMALS, SOL_MINT, short_address, token_symbol},
+        executor::{ExecutionError, SwapExecutor, SwapOrder},
+        swap_decoder::{DecodedSwap, decode_swap},
+    },
+    telegram::notifier::Notifier,
+    utils::short_id,
+};
+
+/// Проверяет свап лидера по спискам токенов подписки.
+/// Возвращает причину пропуска, если свап повторять не нужно.
+pub fn filter_reason(follow: &CopyFollow, swap: &DecodedSwap) -> Option<String> {
+    let sol_mint = Pubkey::from_str(SOL_MINT).ok()?;
+    let mints: Vec<String> = [swap.input_mint, swap.output_mint]
+        .into_iter()
+        .filter(|mint| *mint != sol_mint)
+        .map(|mint| mint.to_string())
+        .collect();
+
+    if let Some(mint) = mints.iter().find(|mint| follow.denied_mints.0.contains(mint)) {
+        return Some(format!("токен {} в списке запрещенных", mint));
+    }
+    if !follow.allowed_mints.0.is_empty()
+        && let Some(mint) = mints.iter().find(|mint| !follow.allowed_mints.0.contains(mint))
+    {
+        return Some(format!("токена {} нет в списке разрешенных", mint));
+    }
+
+    None
+}
+
+/// Сумма покупки за SOL в лампортах: фиксированная или доля от суммы лидера,
+/// но не больше лимита подписки
+pub fn buy_amount_lamports(mode: CopyMode, amount: &BigDecimal, max_amount_sol: &BigDecimal, leader_lamports: u64) -> Option<u64> {
+    let max = TokenAmount::from_big_decimal(max_amount_sol, SOL_DECIMALS).ok()?.raw();
+    let lamports = match mode {
+        CopyMode::Fixed => TokenAmount::from_big_decimal(amount, SOL_DECIMALS).ok()?.raw(),
+        CopyMode::Proportional => (BigDecimal::from(leader_lamports) * amount).to_u64()?,
+    };
+
+    Some(lamports.min(max))
+}
+
+/// Продажа повторяет долю позиции, которую продал лидер: если он продал половину
+/// своего баланса, подписчик тоже продает половину своего
+pub fn sell_amount(own_balance: u64, leader_input: u64, leader_balance_before: u64) -> u64 {
+    if leader_balance_before == 0 || leader_input >= leader_balance_before {
+        return own_balance;
+    }
+
+    (own_balance as u128 * leader_input as u128 / leader_balance_before as u128) as u64
+}
+
+/// Фоновый копитрейдинг: опрашивает транзакции отслеживаемых кошельков,
+/// записывает их свапы для каждой подписки и после задержки повторяет их через общий путь свапа
+pub struct CopyTradingEngine {
+    database: Arc<DatabaseConnectionPool>,
+    executor: Arc<SwapExecutor>,
+    notifier: Notifier,
+    poll_interval: Duration,
+    signatures_per_poll: usize,
+    max_lag: chrono::Duration,
+}
+
+impl CopyTradingEngine {
+    pub fn new(
+        database: Arc<DatabaseConnectionPool>,
+        executor: Arc<SwapExecutor>,
+        notifier: Notifier,
+        poll_interval: Duration,
+        signatures_per_poll: usize,
+        max_lag: Duration,
+    ) -> Self {
+        Self {
+            database,
+            executor,
+            notifier,
+            poll_interval,
+            signatures_per_poll,
+            max_lag: chrono::Duration::from_std(max_lag).unwrap_or(chrono::Duration::MAX),
+        }
+    }
+
+    pub async fn run(self) {
+        tracing::info!("Copy trading engine started (poll interval {:?})", self.poll_interval);
+
+        let mut interval = tokio::time::interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.reconcile().await {
+                tracing::warn!("Copy trade reconciliation failed: {:#}", e);
+            }
+            if let Err(e) = self.watch().await {
+                tracing::warn!("Copy trading watch failed: {:#}", e);
+            }
+            if let Err(e) = self.execute_due().await {
+                tracing::warn!("Copy trade execution failed: {:#}", e);
+            }
+        }
+    }
+
+    /// Находит новые свапы отслеживаемых кошельков и записывает их для подписок
+    async fn watch(&self) -> anyhow::Result<()> {
+        let db = self.database.get_connection();
+        // Транзакцию кошелька, на который подписано несколько пользователей, разбираем один раз
+        let mut decoded: HashMap<String, Option<DecodedSwap>> = HashMap::new();
+
+        for follow in copy_trading::active_follows(db).await? {
+            if let Err(e) = self.watch_follow(&follow, &mut decoded).await {
+                tracing::warn!("Failed to watch wallet {} for follow {}: {:#}", follow.leader_wallet, follow.id, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn watch_follow(
+        &self,
+        follow: &CopyFollow,
+        decoded: &mut HashMap<String, Option<DecodedSwap>>,
+    ) -> anyhow::Result<()> {
+        let db = self.database.get_connection();
+        let rpc = self.executor.trader().rpc();
+        let leader = Pubkey::from_str(&follow.leader_wallet)?;
+
+        let until = follow.last_signature.as_deref().map(Signature::from_str).transpose()?;
+        let signatures = rpc.get_signatures_for_address(&leader, until, self.signatures_per_poll).await?;
+
+        let Some(newest) = signatures.first().map(|status| status.signature.clone()) else {
+            return Ok(());
+        };
+        // Новая подписка начинает с текущего момента, история не повторяется
+        if follow.last_signature.is_none() {
+            copy_trading::advance_cursor(db, follow.id, &newest).await?;
+            return Ok(());
+        }
+        if signatures.len() == self.signatures_per_poll {
+            tracing::warn!("Wallet {} made more than {} transactions between polls", follow.leader_wallet, self.signatures_per_poll);
+        }
+
+        for status in signatures.iter().rev().filter(|status| status.err.is_none()) {
+            let swap = match decoded.get(&status.signature) {
+                Some(swap) => swap.clone(),
+                None => {
+                    let swap = self.fetch_swap(&leader, &status.signature).await?;
+                    decoded.insert(status.signature.clone(), swap.clone());
+                    swap
+                }
+            };
+            let Some(swap) = swap else {
+                continue;
+            };
+
+            let skip_reason = filter_reason(follow, &swap);
+            let inserted = copy_trading::insert_copy_trade(db, NewCopyTrade {
+                follow_id: follow.id,
+                user_id: follow.user_id,
+                leader_signature: status.signature.clone(),
+                input_mint: swap.input_mint.to_string(),
+                output_mint: swap.output_mint.to_string(),
+                leader_input_amount: BigDecimal::from(swap.input_amount),
+                leader_output_amount: BigDecimal::from(swap.output_amount),
+                leader_input_balance: BigDecimal::from(swap.input_balance_before),
+                status: if skip_reason.is_some() { CopyTradeStatus::Skipped } else { CopyTradeStatus::Pending },
+                error_message: skip_reason,
+                execute_after: Utc::now() + chrono::Duration::seconds(follow.delay_secs as i64),
+            }).await?;
+
+            if inserted {
+                tracing::info!(
+                    "Follow {}: {} swapped {} -> {} via {} ({})",
+                    follow.id, follow.leader_wallet, swap.input_mint, swap.output_mint, swap.program, status.signature,
+                );
+            }
+        }
+
+        copy_trading::advance_cursor(db, follow.id, &newest).await?;
+        Ok(())
+    }
+
+    async fn fetch_swap(&self, leader: &Pubkey, signature: &str) -> anyhow::Result<Option<DecodedSwap>> {
+        let tx = self.executor.trader().rpc().get_transaction(&Signature::from_str(signature)?).await?;
+
+        Ok(decode_swap(leader, &tx))
+    }
+
+    async fn execute_due(&self) -> anyhow::Result<()> {
+        let db = self.database.get_connection();
+        let now = Utc::now();
+
+        for copy in copy_trading::due_copy_trades(db, now).await? {
+            if !copy_trading::claim_copy_trade(db, copy.id).await? {
+                continue;
+            }
+
+            // После простоя бота повтор по устаревшей цене может навредить
+            if now - copy.execute_after > self.max_lag {
+                copy_trading::finish_copy_trade(db, copy.id, CopyTradeStatus::Skipped, Some("повтор устарел".to_string())).await?;
+                continue;
+            }
+
+            let Some(follow) = copy_trading::find_follow(db, copy.follow_id).await? else {
+                continue;
+            };
+            if follow.status != CopyFollowStatus::Active {
+                copy_trading::finish_copy_trade(db, copy.id, CopyTradeStatus::Skipped, Some("подписка приостановлена".to_string())).await?;
+                continue;
+            }
+
+            self.copy(&follow, &copy).await;
+        }
+
+        Ok(())
+    }
+
+    async fn copy(&self, follow: &CopyFollow, copy: &CopyTrade) {
+        let db = self.database.get_connection();
+
+        let quote = match self.quote(follow, copy).await {
+            Ok(Some(quote)) => quote,
+            Ok(None) => {
+                // Лидер продает токен, которого у подписчика нет: повторять нечего
+                if let Err(e) = copy_trading::finish_copy_trade(db, copy.id, CopyTradeStatus::Skipped, Some("нет токена на балансе".to_string())).await {
+                    tracing::error!("Failed to skip copy trade {}: {}", copy.id, e);
+                }
+                return;
+            }
+            Err(e) => {
+                self.fail(follow, copy, e.to_string()).await;
+                return;
+            }
+        };
+
+        match self.execute(follow, copy, &quote).await {
+            Ok(()) => {}
+            // Повтор остается в исполнении и связанным со сделкой: итог определит сверка
+            Err(e) if e.downcast_ref::<ExecutionError>().is_some_and(ExecutionError::is_unconfirmed) => {
+                tracing::warn!("Copy trade {} is waiting for confirmation: {}", copy.id, e);
+                self.notifier.notify(follow.user_id, format!(
+                    "⏳ Свап по подписке {} отправлен, но еще не подтвержден: сообщим об итоге",
+                    short_id(&follow.id),
+                )).await;
+            }
+            Err(e) => self.fail(follow, copy, e.to_string()).await,
+        }
+    }
+
+    /// Котировка повтора: покупки за SOL по правилам подписки, остальное — той же долей позиции
+    async fn quote(&self, follow: &CopyFollow, copy: &CopyTrade) -> anyhow::Result<Option<QuoteResponseV6>> {
+        let trader = self.executor.trader();
+        let input_mint = Pubkey::from_str(&copy.input_mint)?;
+        let output_mint = Pubkey::from_str(&copy.output_mint)?;
+        let leader_input = copy.leader_input_amount.to_u64().unwrap_or(u64::MAX);
+        let slippage_bps = follow.max_slippage_bps as u64;
+
+        let amount = if copy.input_mint == SOL_MINT {
+            buy_amount_lamports(follow.mode, &follow.amount, &follow.max_amount_sol, leader_input)
+                .ok_or_else(|| anyhow::anyhow!("некорректная сумма подписки"))?
+        } else {
+            let owner = wallet_keys::default_wallet_address(self.executor.repositories().wallets.as_ref(), follow.user_id).await?;
+            let balance = trader.token_balance(&owner, &input_mint).await?;
+            let leader_balance = copy.leader_input_balance.to_u64().unwrap_or(u64::MAX);
+            sell_amount(balance, leader_input, leader_balance)
+        };
+        if amount == 0 {
+            return Ok(None);
+        }
+
+        let quote = trader.quote(input_mint, output_mint, amount, SwapMode::ExactIn, slippage_bps).await?;
+        trader.check_limits(&quote)?;
+        if copy.input_mint == SOL_MINT {
+            ensure_sol_balance(&self.executor, follow.user_id, &quote).await?;
+        }
+
+        Ok(Some(quote))
+    }
+
+    async fn execute(&self, follow: &CopyFollow, copy: &CopyTrade, quote: &QuoteResponseV6) -> anyhow::Result<()> {
+        let db = self.database.get_connection();
+        let trader = self.executor.trader();
+
+        let recorded = self.executor.record(SwapOrder {
+            user_id: follow.user_id,
+            trade_type: match (copy.input_mint == SOL_MINT, copy.output_mint == SOL_MINT) {
+                (true, _) => TradeType::Buy,
+                (_, true) => TradeType::Sell,
+                _ => TradeType::Swap,
+            },
+            quote,
+            input: trader.token_meta(&quote.input_mint).await?,
+            output: trader.token_meta(&quote.output_mint).await?,
+            jupiter_quote_id: None,
+            exit: false,
+        }).await?;
+
+        let trade_id = recorded.trade.id;
+        if let Err(e) = copy_trading::link_copy_trade(db, copy.id, trade_id).await {
+            let event = NewTradeEvent::cancelled("не отправлена: повтор не удалось связать со сделкой");
+            if let Err(e) = self.executor.repositories().trades.transition(&recorded.trade, TradeStatus::Cancelled, event).await {
+                tracing::error!("Failed to cancel trade {}: {}", trade_id, e);
+            }
+            return Err(e.into());
+        }
+
+        let executed = self.executor.send(recorded).await?;
+
+        if let Err(e) = copy_trading::finish_copy_trade(db, copy.id, CopyTradeStatus::Copied, None).await {
+            tracing::error!("Failed to mark copy trade {} copied: {}", copy.id, e);
+        }
+        self.notifier.notify(follow.user_id, format!(
+            "👥 Повторен свап {}: {} → {}\nhttps://solscan.io/tx/{}",
+            short_address(&Pubkey::from_str(&follow.leader_wallet)?),
+            token_symbol(&quote.input_mint),
+            token_symbol(&quote.output_mint),
+            executed.signature,
+        )).await;
+
+        Ok(())
+    }
+
+    async fn fail(&self, follow: &CopyFollow, copy: &CopyTrade, error: String) {
+        tracing::warn!("Copy trade {} failed: {}", copy.id, error);
+
+        if let Err(e) = copy_trading::finish_copy_trade(
+            self.database.get_connection(),
+            copy.id,
+            CopyTradeStatus::Failed,
+            Some(error.clone()),
+        ).await {
+            tracing::error!("Failed to mark copy trade {} failed: {}", copy.id, e);
+        }
+
+        self.notifier.notify(follow.user_id, format!(
+            "❌ Не удалось повторить свап по подписке {}: {}",
+            short_id(&follow.id), error,
+        )).await;
+    }
+
+    /// Сверка повторов, оставшихся в исполнении после сбоя или перезапуска
+    async fn reconcile(&self) -> anyhow::Result<()> {
+        let db = self.database.get_connection();
+
+        for copy in copy_trading::copy_trades_with_status(db, CopyTradeStatus::Executing).await? {
+            // Без сделки транзакция не подписывалась; повтор по старой цене не выполняем
+            let Some(trade_id) = copy.trade_id else {
+                if Utc::now() - copy.updated_at > UNCONFIRMED_TIMEOUT {
+                    copy_trading::finish_copy_trade(db, copy.id, CopyTradeStatus::Failed, Some("исполнение прервано".to_string())).await?;
+                }
+                continue;
+            };
+
+            match settle_trade(&self.executor, trade_id).await? {
+                TradeOutcome::Landed => {
+                    copy_trading::finish_copy_trade(db, copy.id, CopyTradeStatus::Copied, None).await?;
+                    self.notifier.notify(copy.user_id, format!(
+                        "👥 Повторен свап по подписке {}", short_id(&copy.follow_id),
+                    )).await;
+                }
+                TradeOutcome::Failed(error) => {
+                    copy_trading::finish_copy_trade(db, copy.id, CopyTradeStatus::Failed, Some(error.clone())).await?;
+                    self.notifier.notify(copy.user_id, format!(
+                        "❌ Не удалось повторить свап по подписке {}: {}",
+                        short_id(&copy.follow_id), error,
+                    )).await;
+                }
+                TradeOutcome::Pending => {}
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,205 @@
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::Utc;
+use solana_sdk::pubkey::Pubkey;
+
+use super::{TradeOutcome, ensure_sol_balance, settle_trade};
+use crate::{
+    database::{DatabaseConnectionPool, dca, trade_events::NewTradeEvent},
+    entities::{dca_schedules::Model as DcaSchedule, trades::{TradeStatus, TradeType}},
+    jupiter::{QuoteResponseV6, SwapMode},
+    solana::{
+        amount::TokenAmount,
+        constants::{SOL_DECIMALS, SOL_MINT},
+        executor::{ExecutionError, SwapExecutor, SwapOrder},
+    },
+    telegram::notifier::Notifier,
+    utils::short_id,
+};
+
+/// Планировщик регулярных покупок: на каждом опросе выполняет циклы расписаний,
+/// у которых подошло время. Цикл занимается атомарно до покупки, поэтому
+/// после перезапуска он не повторится, а неудачный цикл пропускается с уведомлением.
+pub struct DcaScheduler {
+    database: Arc<DatabaseConnectionPool>,
+    executor: Arc<SwapExecutor>,
+    notifier: Notifier,
+    poll_interval: Duration,
+}
+
+impl DcaScheduler {
+    pub fn new(
+        database: Arc<DatabaseConnectionPool>,
+        executor: Arc<SwapExecutor>,
+        notifier: Notifier,
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            database,
+            executor,
+            notifier,
+            poll_interval,
+        }
+    }
+
+    pub async fn run(self) {
+        tracing::info!("DCA scheduler started (poll interval {:?})", self.poll_interval);
+
+        let mut interval = tokio::time::interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.reconcile().await {
+                tracing::warn!("DCA reconciliation failed: {:#}", e);
+            }
+            if let Err(e) = self.tick().await {
+                tracing::warn!("DCA check failed: {:#}", e);
+            }
+        }
+    }
+
+    async fn tick(&self) -> anyhow::Result<()> {
+        let now = Utc::now();
+
+        for schedule in dca::due(self.database.get_connection(), now).await? {
+            self.run_cycle(schedule).await;
+        }
+
+        Ok(())
+    }
+
+    async fn run_cycle(&self, schedule: DcaSchedule) {
+        let db = self.database.get_connection();
+
+        match dca::claim_cycle(db, &schedule, Utc::now()).await {
+            Ok(true) => {}
+            Ok(false) => return,
+            Err(e) => {
+                tracing::warn!("Failed to claim DCA schedule {}: {}", schedule.id, e);
+                return;
+            }
+        }
+        let cycle = schedule.cycles_done + 1;
+        let last = cycle >= schedule.total_cycles;
+        tracing::info!("DCA schedule {} cycle {}/{}", schedule.id, cycle, schedule.total_cycles);
+
+        match self.buy(&schedule).await {
+            Ok(signature) => {
+                self.notifier.notify(schedule.user_id, format!(
+                    "🔁 DCA {}: куплен {} на {} SOL (цикл {}/{})\nhttps://solscan.io/tx/{}",
+                    short_id(&schedule.id), schedule.token_symbol, schedule.amount_sol.normalized(),
+                    cycle, schedule.total_cycles, signature,
+                )).await;
+            }
+            // Покупка еще может попасть в блок: цикл остается связанным со сделкой,
+            // а засчитает его или пропустит сверка
+            Err(e) if e.downcast_ref::<ExecutionError>().is_some_and(ExecutionError::is_unconfirmed) => {
+                tracing::warn!("DCA schedule {} cycle {} is waiting for confirmation: {}", schedule.id, cycle, e);
+                self.notifier.notify(schedule.user_id, format!(
+                    "⏳ DCA {}: покупка {} (цикл {}/{}) отправлена, но еще не подтверждена: сообщим об итоге",
+                    short_id(&schedule.id), schedule.token_symbol, cycle, schedule.total_cycles,
+                )).await;
+            }
+            Err(e) => {
+                let error = e.to_string();
+                tracing::warn!("DCA schedule {} cycle {} skipped: {:#}", schedule.id, cycle, e);
+                if let Err(e) = dca::record_skip(db, schedule.id, &error).await {
+                    tracing::error!("Failed to record skipped DCA cycle {}: {}", schedule.id, e);
+                }
+                self.notifier.notify(schedule.user_id, format!(
+                    "⚠️ DCA {}: цикл {}/{} по {} пропущен: {}",
+                    short_id(&schedule.id), cycle, schedule.total_cycles, schedule.token_symbol, error,
+                )).await;
+            }
+        }
+
+        if last {
+            self.notifier.notify(schedule.user_id, format!(
+                "✅ DCA {} по {} завершен",
+                short_id(&schedule.id), schedule.token_symbol,
+            )).await;
+        }
+    }
+
+    /// Покупка одного цикла. Баланс и лимиты проверяются до подписи транзакции.
+    async fn buy(&self, schedule: &DcaSchedule) -> anyhow::Result<String> {
+        let trader = self.executor.trader();
+
+        let quote = self.quote(schedule).await?;
+        trader.check_limits(&quote)?;
+
+        ensure_sol_balance(&self.executor, schedule.user_id, &quote).await?;
+
+        let recorded = self.executor.record(SwapOrder {
+            user_id: schedule.user_id,
+            trade_type: TradeType::Buy,
+            quote: &quote,
+            input: trader.token_meta(&quote.input_mint).await?,
+            output: trader.token_meta(&quote.output_mint).await?,
+            jupiter_quote_id: None,
+            exit: false,
+        }).await?;
+
+        let trade_id = recorded.trade.id;
+        if let Err(e) = self.executor.repositories().trades.link_dca_schedule(trade_id, schedule.id).await {
+            let event = NewTradeEvent::cancelled("не отправлена: сделку не удалось связать с DCA");
+            if let Err(e) = self.executor.repositories().trades.transition(&recorded.trade, TradeStatus::Cancelled, event).await {
+                tracing::error!("Failed to cancel trade {}: {}", trade_id, e);
+            }
+            return Err(e.into());
+        }
+
+        let executed = self.executor.send(recorded).await?;
+        Ok(executed.signature.to_string())
+    }
+
+    async fn quote(&self, schedule: &DcaSchedule) -> anyhow::Result<QuoteResponseV6> {
+        let sol_mint = Pubkey::from_str(SOL_MINT)?;
+        let token_mint = Pubkey::from_str(&schedule.token_mint)?;
+        let lamports = TokenAmount::from_big_decimal(&schedule.amount_sol, SOL_DECIMALS)
+            .context("Invalid DCA amount")?
+            .raw();
+
+        self.executor
+            .trader()
+            .quote(sol_mint, token_mint, lamports, SwapMode::ExactIn, schedule.max_slippage_bps as u64)
+            .await
+    }
+
+    /// Сверка покупок DCA, оставшихся неподтвержденными после сбоя или перезапуска
+    async fn reconcile(&self) -> anyhow::Result<()> {
+        let db = self.database.get_connection();
+
+        for trade in self.executor.repositories().trades.unsettled_dca().await? {
+            let outcome = settle_trade(&self.executor, trade.id).await?;
+            let Some(schedule_id) = trade.dca_schedule_id else {
+                continue;
+            };
+            let Some(schedule) = dca::find(db, schedule_id).await? else {
+                continue;
+            };
+
+            match outcome {
+                TradeOutcome::Landed => {
+                    self.notifier.notify(schedule.user_id, format!(
+                        "🔁 DCA {}: куплен {} на {} SOL",
+                        short_id(&schedule.id), schedule.token_symbol, schedule.amount_sol.normalized(),
+                    )).await;
+                }
+                TradeOutcome::Failed(error) => {
+                    dca::record_skip(db, schedule.id, &error).await?;
+                    self.notifier.notify(schedule.user_id, format!(
+                        "⚠️ DCA {}: цикл по {} пропущен: {}",
+                        short_id(&schedule.id), schedule.token_symbol, error,
+                    )).await;
+                }
+                TradeOutcome::Pending => {}
+            }
+        }
+
+        Ok(())
+    }
+}
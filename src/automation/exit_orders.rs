@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive};
+use chrono::Utc;
+use solana_sdk::pubkey::Pubkey;
+
+use super::{TradeOutcome, UNCONFIRMED_TIMEOUT, settle_trade};
+use crate::{
+    database::{DatabaseConnectionPool, exit_orders, trade_events::NewTradeEvent},
+    entities::{
+        exit_orders::{ExitKind, ExitOrderStatus, Model as ExitOrder},
+        trades::{TradeStatus, TradeType},
+    },
+    jupiter::{PriceService, QuoteResponseV6, SwapMode},
+    security::wallet_keys,
+    solana::{
+        amount::TokenAmount,
+        executor::{ExecutionError, SwapExecutor, SwapOrder},
+    },
+    telegram::notifier::Notifier,
+    utils::short_id,
+};
+
+/// Результат проверки ордера на выход по текущей цене
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExitCheck {
+    pub triggered: bool,
+    pub trigger_price: f64,
+    // Новый максимум для трейлинг-стопа, если цена его обновила
+    pub new_high: Option<f64>,
+}
+
+/// Стоп-лосс срабатывает при падении до уровня, тейк-профит — при росте.
+/// Трейлинг-стоп держится на `trail_pct` ниже максимума и подтягивается вслед за ценой.
+pub fn evaluate(
+    kind: ExitKind,
+    trigger_price: f64,
+    trail_pct: Option<f64>,
+    high_water: Option<f64>,
+    price: f64,
+) -> ExitCheck {
+    match (kind, trail_pct) {
+        (ExitKind::TrailingStop, Some(trail_pct)) => {
+            let high = high_water.unwrap_or(price);
+            let (high, new_high) = if price > high { (price, Some(price)) } else { (high, None) };
+            let trigger_price = high * (1.0 - trail_pct / 100.0);
+
+            ExitCheck { triggered: price <= trigger_price, trigger_price, new_high }
+        }
+        (ExitKind::TakeProfit, _) => ExitCheck {
+            triggered: price >= trigger_price,
+            trigger_price,
+            new_high: None,
+        },
+        (ExitKind::StopLoss | ExitKind::TrailingStop, _) => ExitCheck {
+            triggered: price <= trigger_price,
+            trigger_price,
+            new_high: None,
+        },
+    }
+}
+
+pub fn kind_label(kind: ExitKind) -> &'static str {
+    match kind {
+        ExitKind::StopLoss => "Стоп-лосс",
+        ExitKind::TakeProfit => "Тейк-профит",
+        ExitKind::TrailingStop => "Трейлинг-стоп",
+    }
+}
+
+/// Фоновый обработчик стоп-лоссов и тейк-профитов: сравнивает текущую цену токена
+/// с ценой входа и продает удерживаемый баланс через общий путь свапа
+pub struct ExitOrderEngine {
+    database: Arc<DatabaseConnectionPool>,
+    executor: Arc<SwapExecutor>,
+    price_service: Arc<PriceService>,
+    notifier: Notifier,
+    poll_interval: Duration,
+}
+
+impl ExitOrderEngine {
+    pub fn new(
+        database: Arc<DatabaseConnectionPool>,
+        executor: Arc<SwapExecutor>,
+        price_service: Arc<PriceService>,
+        notifier: Notifier,
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            database,
+            executor,
+            price_service,
+            notifier,
+            poll_interval,
+        }
+    }
+
+    pub async fn run(self) {
+        tracing::info!("Exit order engine started (poll interval {:?})", self.poll_interval);
+
+        let mut interval = tokio::time::interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.reconcile().await {
+                tracing::warn!("Exit order reconciliation failed: {:#}", e);
+            }
+            if let Err(e) = self.tick().await {
+                tracing::warn!("Exit order check failed: {:#}", e);
+            }
+        }
+    }
+
+    async fn tick(&self) -> anyhow::Result<()> {
+        let db = self.database.get_connection();
+
+        let orders = exit_orders::list(db, None, Some(ExitOrderStatus::Open)).await?;
+        if orders.is_empty() {
+            return Ok(());
+        }
+
+        let mut mints = Vec::new();
+        for order in &orders {
+            for mint in [&order.token_mint, &order.quote_mint] {
+                if let Ok(mint) = Pubkey::from_str(mint)
+                    && !mints.contains(&mint)
+                {
+                    mints.push(mint);
+                }
+            }
+        }
+        let prices: HashMap<Pubkey, f64> = self.price_service.get_prices(&mints).await?;
+
+        for order in orders {
+            let Some(price) = relative_price(&prices, &order.token_mint, &order.quote_mint) else {
+                continue;
+            };
+            let (Some(trigger_price), trail_pct, high_water) = (
+                order.trigger_price.to_f64(),
+                order.trail_pct.as_ref().and_then(|pct| pct.to_f64()),
+                order.high_water_price.as_ref().and_then(|high| high.to_f64()),
+            ) else {
+                continue;
+            };
+
+            let check = evaluate(order.kind, trigger_price, trail_pct, high_water, price);
+
+            if let Some(high) = check.new_high
+                && let (Some(high), Some(trigger)) = (BigDecimal::from_f64(high), BigDecimal::from_f64(check.trigger_price))
+                && let Err(e) = exit_orders::update_trailing(db, order.id, high.with_scale(12), trigger.with_scale(12)).await
+            {
+                tracing::warn!("Failed to move trailing stop {}: {}", order.id, e);
+            }
+
+            if check.triggered {
+                self.trigger(order, price).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn trigger(&self, order: ExitOrder, price: f64) {
+        let db = self.database.get_connection();
+
+        match exit_orders::claim(db, order.id).await {
+            Ok(true) => {}
+            Ok(false) => return,
+            Err(e) => {
+                tracing::warn!("Failed to claim exit order {}: {}", order.id, e);
+                return;
+            }
+        }
+        tracing::info!("{} {} triggered at price {}", kind_label(order.kind), order.id, price);
+
+        let quote = match self.quote(&order).await {
+            Ok(Some(quote)) => quote,
+            Ok(None) => {
+                self.fail(&order, "токенов позиции нет на балансе".to_string()).await;
+                return;
+            }
+            Err(e) => {
+                tracing::warn!("Quote for exit order {} failed: {:#}", order.id, e);
+                if let Err(e) = exit_orders::release(db, order.id).await {
+                    tracing::error!("Failed to release exit order {}: {}", order.id, e);
+                }
+                return;
+            }
+        };
+
+        // Выход защищает позицию: лимиты размера, влияния на цену и активности его не блокируют
+        if let Err(e) = self.executor.trader().check_exit_limits(&quote) {
+            self.fail(&order, e.to_string()).await;
+            return;
+        }
+
+        match self.execute(&order, &quote).await {
+            Ok(()) => {}
+            // Продажа еще может попасть в блок: ордер остается сработавшим, а остальные выходы
+            // по позиции снимет сверка, когда увидит сделку в сети
+            Err(e) if e.downcast_ref::<ExecutionError>().is_some_and(ExecutionError::is_unconfirmed) => {
+                tracing::warn!("Exit order {} is waiting for confirmation: {}", order.id, e);
+                self.notifier.notify(order.user_id, format!(
+                    "⏳ {} {} по {} отправлен, но еще не подтвержден: сообщим об итоге",
+                    kind_label(order.kind), short_id(&order.id), order.token_symbol,
+                )).await;
+            }
+            Err(e) => self.fail(&order, e.to_string()).await,
+        }
+    }
+
+    /// Котировка продажи позиции, но не больше текущего баланса токена.
+    /// `None`, если продавать нечего.
+    async fn quote(&self, order: &ExitOrder) -> anyhow::Result<Option<QuoteResponseV6>> {
+        let trader = self.executor.trader();
+        let token_mint = Pubkey::from_str(&order.token_mint)?;
+        let quote_mint = Pubkey::from_str(&order.quote_mint)?;
+
+        let decimals = trader.mint_decimals(&token_mint).await?;
+        let position = TokenAmount::from_big_decimal(&order.amount, decimals)
+            .context("Invalid position amount")?
+            .raw();
+        let owner = wallet_keys::default_wallet_address(self.executor.repositories().wallets.as_ref(), order.user_id).await?;
+        let balance = trader.token_balance(&owner, &token_mint).await?;
+
+        let amount = position.min(balance);
+        if amount == 0 {
+            return Ok(None);
+        }
+
+        let slippage_bps = order.max_slippage_bps as u64;
+        let quote = trader.quote(token_mint, quote_mint, amount, SwapMode::ExactIn, slippage_bps).await?;
+        Ok(Some(quote))
+    }
+
+    async fn execute(&self, order: &ExitOrder, quote: &QuoteResponseV6) -> anyhow::Result<()> {
+        let db = self.database.get_connection();
+        let trader = self.executor.trader();
+
+        let recorded = self.executor.record(SwapOrder {
+            user_id: order.user_id,
+            trade_type: TradeType::Sell,
+            quote,
+            input: trader.token_meta(&quote.input_mint).await?,
+            output: trader.token_meta(&quote.output_mint).await?,
+            jupiter_quote_id: None,
+            exit: true,
+        }).await?;
+
+        let trade_id = recorded.trade.id;
+        if let Err(e) = exit_orders::link_trade(db, order.id, trade_id).await {
+            let event = NewTradeEvent::cancelled("не отправлена: ордер не удалось связать со сделкой");
+            if let Err(e) = self.executor.repositories().trades.transition(&recorded.trade, TradeStatus::Cancelled, event).await {
+                tracing::error!("Failed to cancel trade {}: {}", trade_id, e);
+            }
+            return Err(e.into());
+        }
+
+        let executed = self.executor.send(recorded).await?;
+
+        self.filled(order).await;
+        self.notifier.notify(order.user_id, format!(
+            "✅ {} {} по {} исполнен\nhttps://solscan.io/tx/{}",
+            kind_label(order.kind), short_id(&order.id), order.token_symbol, executed.signature,
+        )).await;
+
+        Ok(())
+    }
+
+    /// Отмечает ордер исполненным и снимает остальные выходы по той же позиции
+    async fn filled(&self, order: &ExitOrder) {
+        let db = self.database.get_connection();
+
+        if let Err(e) = exit_orders::finish(db, order.id, ExitOrderStatus::Filled, None).await {
+            tracing::error!("Failed to mark exit order {} filled: {}", order.id, e);
+        }
+
+        match exit_orders::cancel_siblings(db, order.trade_id, order.id).await {
+            Ok(cancelled) => {
+                for sibling in cancelled {
+                    self.notifier.notify(sibling.user_id, format!(
+                        "{} {} по {} снят: позиция закрыта",
+                        kind_label(sibling.kind), short_id(&sibling.id), sibling.token_symbol,
+                    )).await;
+                }
+            }
+            Err(e) => tracing::error!("Failed to cancel exit orders of trade {}: {}", order.trade_id, e),
+        }
+    }
+
+    async fn fail(&self, order: &ExitOrder, error: String) {
+        tracing::warn!("Exit order {} failed: {}", order.id, error);
+
+        if let Err(e) = exit_orders::finish(
+            self.database.get_connection(),
+            order.id,
+            ExitOrderStatus::Failed,
+            Some(error.clone()),
+        ).await {
+            tracing::error!("Failed to mark exit order {} failed: {}", order.id, e);
+        }
+
+        self.notifier.notify(order.user_id, format!(
+            "❌ {} {} по {} не исполнен: {}",
+            kind_label(order.kind), short_id(&order.id), order.token_symbol, error,
+        )).await;
+    }
+
+    /// Сверка ордеров, оставшихся в исполнении после сбоя или перезапуска
+    async fn reconcile(&self) -> anyhow::Result<()> {
+        let db = self.database.get_connection();
+
+        for order in exit_orders::list(db, None, Some(ExitOrderStatus::Triggered)).await? {
+            let Some(trade_id) = order.sell_trade_id else {
+                if order.triggered_at.is_some_and(|at| Utc::now() - at > UNCONFIRMED_TIMEOUT) {
+                    exit_orders::release(db, order.id).await?;
+                }
+                continue;
+            };
+
+            match settle_trade(&self.executor, trade_id).await? {
+                TradeOutcome::Landed => {
+                    self.filled(&order).await;
+                    self.notifier.notify(order.user_id, format!(
+                        "✅ {} {} по {} исполнен",
+                        kind_label(order.kind), short_id(&order.id), order.token_symbol,
+                    )).await;
+                }
+                TradeOutcome::Failed(error) => self.fail(&order, error).await,
+                TradeOutcome::Pending => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Цена токена в единицах `quote_mint` по USD-ценам обоих токенов
+fn relative_price(prices: &HashMap<Pubkey, f64>, token_mint: &str, quote_mint: &str) -> Option<f64> {
+    let token = prices.get(&Pubkey::from_str(token_mint).ok()?)?;
+    let quote = prices.get(&Pubkey::from_str(quote_mint).ok()?)?;
+
+    (*quote > 0.0).then(|| token / quote)
+}
@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use bigdecimal::ToPrimitive;
+use chrono::Utc;
+use solana_sdk::pubkey::Pubkey;
+
+use super::{TradeOutcome, UNCONFIRMED_TIMEOUT, settle_trade};
+use crate::{
+    database::{DatabaseConnectionPool, limit_orders, trade_events::NewTradeEvent},
+    entities::{
+        limit_orders::{LimitOrderStatus, Model as LimitOrder, OrderSide},
+        trades::{TradeStatus, TradeType},
+    },
+    jupiter::{PriceService, QuoteResponseV6, SwapMode},
+    solana::{
+        amount::TokenAmount,
+        constants::{SOL_DECIMALS, SOL_MINT},
+        executor::{ExecutionError, SwapExecutor, SwapOrder},
+    },
+    telegram::notifier::Notifier,
+    utils::short_id,
+};
+
+/// Покупка срабатывает, когда цена опустилась до цели, продажа — когда поднялась
+pub fn is_triggered(side: OrderSide, price: f64, target_price: f64) -> bool {
+    match side {
+        OrderSide::Buy => price <= target_price,
+        OrderSide::Sell => price >= target_price,
+    }
+}
+
+/// Фоновый движок лимитных ордеров: следит за ценами и исполняет сработавшие ордера
+/// через общий путь свапа. Ордер захватывается атомарно и связывается со сделкой
+/// до отправки, поэтому после перезапуска он не исполнится повторно.
+pub struct LimitOrderEngine {
+    database: Arc<DatabaseConnectionPool>,
+    executor: Arc<SwapExecutor>,
+    price_service: Arc<PriceService>,
+    notifier: Notifier,
+    poll_interval: Duration,
+}
+
+impl LimitOrderEngine {
+    pub fn new(
+        database: Arc<DatabaseConnectionPool>,
+        executor: Arc<SwapExecutor>,
+        price_service: Arc<PriceService>,
+        notifier: Notifier,
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            database,
+            executor,
+            price_service,
+            notifier,
+            poll_interval,
+        }
+    }
+
+    pub async fn run(self) {
+        tracing::info!("Limit order engine started (poll interval {:?})", self.poll_interval);
+
+        let mut interval = tokio::time::interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.reconcile().await {
+                tracing::warn!("Limit order reconciliation failed: {:#}", e);
+            }
+            if let Err(e) = self.tick().await {
+                tracing::warn!("Limit order check failed: {:#}", e);
+            }
+        }
+    }
+
+    async fn tick(&self) -> anyhow::Result<()> {
+        let db = self.database.get_connection();
+
+        for order in limit_orders::expire_due(db, Utc::now()).await? {
+            self.notifier.notify(order.user_id, format!(
+                "⌛ Лимитный ордер {} на {} истек",
+                short_id(&order.id), order.token_symbol,
+            )).await;
+        }
+
+        let orders = limit_orders::list(db, None, Some(LimitOrderStatus::Open)).await?;
+        if orders.is_empty() {
+            return Ok(());
+        }
+
+        let mut mints = Vec::new();
+        for order in &orders {
+            match Pubkey::from_str(&order.token_mint) {
+                Ok(mint) if !mints.contains(&mint) => mints.push(mint),
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Limit order {} has invalid mint: {}", order.id, e),
+            }
+        }
+        let prices: HashMap<Pubkey, f64> = self.price_service.get_prices(&mints).await?;
+
+        for order in orders {
+            let Some(price) = Pubkey::from_str(&order.token_mint).ok().and_then(|mint| prices.get(&mint)) else {
+                continue;
+            };
+            let Some(target_price) = order.target_price.to_f64() else {
+                continue;
+            };
+
+            if is_triggered(order.side, *price, target_price) {
+                self.trigger(order, *price).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn trigger(&self, order: LimitOrder, price: f64) {
+        let db = self.database.get_connection();
+
+        match limit_orders::claim(db, order.id).await {
+            Ok(true) => {}
+            Ok(false) => return,
+            Err(e) => {
+                tracing::warn!("Failed to claim limit order {}: {}", order.id, e);
+                return;
+            }
+        }
+        tracing::info!("Limit order {} triggered at price {}", order.id, price);
+
+        // Котировка может не получиться из-за временного сбоя: ордер остается открытым
+        let quote = match self.quote(&order).await {
+            Ok(quote) => quote,
+            Err(e) => {
+                tracing::warn!("Quote for limit order {} failed: {:#}", order.id, e);
+                if let Err(e) = limit_orders::release(db, order.id).await {
+                    tracing::error!("Failed to release limit order {}: {}", order.id, e);
+                }
+                return;
+            }
+        };
+
+        // Лимиты проверяются по текущим настройкам на момент срабатывания
+        if let Err(e) = self.executor.trader().check_limits(&quote) {
+            self.fail(&order, e.to_string()).await;
+            return;
+        }
+
+        match self.execute(&order, &quote).await {
+            Ok(()) => {}
+            // Ордер остается сработавшим и связанным со сделкой: итог определит сверка
+            Err(e) if e.downcast_ref::<ExecutionError>().is_some_and(ExecutionError::is_unconfirmed) => {
+                tracing::warn!("Limit order {} is waiting for confirmation: {}", order.id, e);
+                self.notifier.notify(order.user_id, format!(
+                    "⏳ Лимитный ордер {} на {} отправлен, но еще не подтвержден: сообщим об итоге",
+                    short_id(&order.id), order.token_symbol,
+                )).await;
+            }
+            Err(e) => self.fail(&order, e.to_string()).await,
+        }
+    }
+
+    async fn quote(&self, order: &LimitOrder) -> anyhow::Result<QuoteResponseV6> {
+        let trader = self.executor.trader();
+        let sol_mint = Pubkey::from_str(SOL_MINT)?;
+        let token_mint = Pubkey::from_str(&order.token_mint)?;
+        let slippage_bps = order.max_slippage_bps as u64;
+
+        match order.side {
+            OrderSide::Buy => {
+                let lamports = TokenAmount::from_big_decimal(&order.amount, SOL_DECIMALS).context("Invalid order amount")?;
+                trader.quote(sol_mint, token_mint, lamports.raw(), SwapMode::ExactIn, slippage_bps).await
+            }
+            OrderSide::Sell => {
+                let decimals = trader.mint_decimals(&token_mint).await?;
+                let amount = TokenAmount::from_big_decimal(&order.amount, decimals).context("Invalid order amount")?;
+                trader.quote(token_mint, sol_mint, amount.raw(), SwapMode::ExactIn, slippage_bps).await
+            }
+        }
+    }
+
+    async fn execute(&self, order: &LimitOrder, quote: &QuoteResponseV6) -> anyhow::Result<()> {
+        let db = self.database.get_connection();
+        let trader = self.executor.trader();
+
+        let recorded = self.executor.record(SwapOrder {
+            user_id: order.user_id,
+            trade_type: match order.side {
+                OrderSide::Buy => TradeType::Buy,
+                OrderSide::Sell => TradeType::Sell,
+            },
+            quote,
+            input: trader.token_meta(&quote.input_mint).await?,
+            output: trader.token_meta(&quote.output_mint).await?,
+            jupiter_quote_id: None,
+            exit: false,
+        }).await?;
+
+        // Связь со сделкой сохраняется до отправки: без нее после сбоя ордер нельзя сверить
+        let trade_id = recorded.trade.id;
+        if let Err(e) = limit_orders::link_trade(db, order.id, trade_id).await {
+            let event = NewTradeEvent::cancelled("не отправлена: ордер не удалось связать со сделкой");
+            if let Err(e) = self.executor.repositories().trades.transition(&recorded.trade, TradeStatus::Cancelled, event).await {
+                tracing::error!("Failed to cancel trade {}: {}", trade_id, e);
+            }
+            return Err(e.into());
+        }
+
+        let executed = self.executor.send(recorded).await?;
+
+        // Сделка уже отправлена: ошибка записи статуса исправится при сверке
+        if let Err(e) = limit_orders::finish(db, order.id, LimitOrderStatus::Filled, None).await {
+            tracing::error!("Failed to mark limit order {} filled: {}", order.id, e);
+        }
+        self.notifier.notify(order.user_id, format!(
+            "✅ Лимитный ордер {} на {} исполнен\nhttps://solscan.io/tx/{}",
+            short_id(&order.id), order.token_symbol, executed.signature,
+        )).await;
+
+        Ok(())
+    }
+
+    async fn fail(&self, order: &LimitOrder, error: String) {
+        tracing::warn!("Limit order {} failed: {}", order.id, error);
+
+        if let Err(e) = limit_orders::finish(
+            self.database.get_connection(),
+            order.id,
+            LimitOrderStatus::Failed,
+            Some(error.clone()),
+        ).await {
+            tracing::error!("Failed to mark limit order {} failed: {}", order.id, e);
+        }
+
+        self.notifier.notify(order.user_id, format!(
+            "❌ Лимитный ордер {} на {} не исполнен: {}",
+            short_id(&order.id), order.token_symbol, error,
+        )).await;
+    }
+
+    /// Сверка ордеров, оставшихся в исполнении после сбоя или перезапуска
+    async fn reconcile(&self) -> anyhow::Result<()> {
+        let db = self.database.get_connection();
+
+        for order in limit_orders::list(db, None, Some(LimitOrderStatus::Triggered)).await? {
+            // Без сделки транзакция не подписывалась и не отправлялась
+            let Some(trade_id) = order.trade_id else {
+                if order.triggered_at.is_some_and(|at| Utc::now() - at > UNCONFIRMED_TIMEOUT) {
+                    limit_orders::release(db, order.id).await?;
+                }
+                continue;
+            };
+
+            match settle_trade(&self.executor, trade_id).await? {
+                TradeOutcome::Landed => {
+                    limit_orders::finish(db, order.id, LimitOrderStatus::Filled, None).await?;
+                    self.notifier.notify(order.user_id, format!(
+                        "✅ Лимитный ордер {} на {} исполнен",
+                        short_id(&order.id), order.token_symbol,
+                    )).await;
+                }
+                TradeOutcome::Failed(error) => {
+                    limit_orders::finish(db, order.id, LimitOrderStatus::Failed, Some(error.clone())).await?;
+                    self.notifier.notify(order.user_id, format!(
+                        "❌ Лимитный ордер {} на {} не исполнен: {}",
+                        short_id(&order.id), order.token_symbol, error,
+                    )).await;
+                }
+                TradeOutcome::Pending => {}
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use std::str::FromStr;
+
+use chrono::Utc;
+use solana_sdk::signature::Signature;
+use uuid::Uuid;
+
+use crate::{
+    database::trade_events::NewTradeEvent,
+    entities::{trade_events::TradeEventKind, trades::TradeStatus},
+    jupiter::QuoteResponseV6,
+    security::wallet_keys,
+    solana::{
+        amount::TokenAmount,
+        executor::SwapExecutor,
+        sender::{LandingError, LandingStatus, confirm_landing},
+        trader::max_input_amount,
+    },
+};
+
+pub mod alerts;
+pub mod balance;
+pub mod copy_trading;
+pub mod dca;
+pub mod exit_orders;
+pub mod limit_orders;
+pub mod sniper;
+
+pub use alerts::AlertEngine;
+pub use balance::BalanceWatcher;
+pub use copy_trading::CopyTradingEngine;
+pub use dca::DcaScheduler;
+pub use exit_orders::ExitOrderEngine;
+pub use limit_orders::LimitOrderEngine;
+pub use sniper::SniperEngine;
+
+// Через сколько неподтвержденная транзакция точно не попадет в блок (blockhash живет ~90 секунд)
+pub(crate) const UNCONFIRMED_TIMEOUT: chrono::Duration = chrono::Duration::minutes(5);
+
+// SOL, который остается на кошельке для оплаты комиссий сети
+pub(crate) const FEE_RESERVE_LAMPORTS: u64 = 10_000_000;
+
+/// Проверяет, что на кошельке пользователя хватит SOL на покупку по котировке с запасом на комиссии
+pub(crate) async fn ensure_sol_balance(
+    executor: &SwapExecutor,
+    user_id: i64,
+    quote: &QuoteResponseV6,
+) -> anyhow::Result<()> {
+    let owner = wallet_keys::default_wallet_address(executor.repositories().wallets.as_ref(), user_id).await?;
+    let balance = executor.trader().rpc().get_balance(&owner).await?;
+    let required = max_input_amount(quote) + FEE_RESERVE_LAMPORTS;
+
+    if balance < required {
+        anyhow::bail!(
+            "недостаточно SOL: на балансе {}, нужно {}",
+            TokenAmount::sol(balance),
+            TokenAmount::sol(required),
+        );
+    }
+
+    Ok(())
+}
+
+/// Итог сделки, оставшейся незавершенной после сбоя или перезапуска
+pub enum TradeOutcome {
+    Landed,
+    Failed(String),
+    Pending,
+}
+
+/// Определяет итог сделки по ее статусу в БД или по статусу подписи в сети
+/// и записывает его в `trades`
+pub async fn settle_trade(executor: &SwapExecutor, trade_id: Uuid) -> anyhow::Result<TradeOutcome> {
+    let trades = &executor.repositories().trades;
+    let Some(trade) = trades.find(trade_id).await? else {
+        return Ok(TradeOutcome::Pending);
+    };
+
+    match trade.status {
+        TradeStatus::Completed => Ok(TradeOutcome::Landed),
+        TradeStatus::Failed | TradeStatus::Cancelled => Ok(TradeOutcome::Failed(
+            trade.error_message.unwrap_or_else(|| "сделка не выполнена".to_string()),
+        )),
+        // Переход в исполнение записывается до отправки: такая сделка не попала в сеть
+        TradeStatus::Pending if Utc::now() - trade.created_at > UNCONFIRMED_TIMEOUT => {
+            let error = "сделка не отправлена";
+            let cancelled = trades.transition(&trade, TradeStatus::Cancelled, NewTradeEvent::cancelled(error)).await?;
+            Ok(match cancelled {
+                Some(_) => TradeOutcome::Failed(error.to_string()),
+                // Отправка началась параллельно
+                None => TradeOutcome::Pending,
+            })
+        }
+        TradeStatus::Pending => Ok(TradeOutcome::Pending),
+        TradeStatus::Executing => {
+            let signature = Signature::from_str(&trade.transaction_signature)?;
+            let last_valid_block_height = match trades.last_valid_block_height(trade.id).await? {
+                Some(height) => height,
+                // Сделка отправлена до истории событий: ее blockhash заведомо истек за это время
+                None if Utc::now() - trade.created_at > UNCONFIRMED_TIMEOUT => 0,
+                None => return Ok(TradeOutcome::Pending),
+            };
+
+            // Итог в сети окончательный: параллельный переход (`None`) приходит к тому же
+            let rpc = executor.trader().rpc();
+            match confirm_landing(rpc.as_ref(), &signature, last_valid_block_height).await? {
+                (LandingStatus::Landed, slot) => {
+                    let event = NewTradeEvent { slot, ..NewTradeEvent::new(TradeEventKind::Confirmed) };
+                    trades.transition(&trade, TradeStatus::Completed, event).await?;
+                    Ok(TradeOutcome::Landed)
+                }
+                (LandingStatus::Failed(e), slot) => {
+                    let event = NewTradeEvent { slot, ..NewTradeEvent::error(e.to_string()) };
+                    trades.transition(&trade, TradeStatus::Failed, event).await?;
+                    Ok(TradeOutcome::Failed(e.to_string()))
+                }
+                (LandingStatus::Expired, _) => {
+                    let error = LandingError::Expired.to_string();
+                    trades.transition(&trade, TradeStatus::Failed, NewTradeEvent::error(&error)).await?;
+                    Ok(TradeOutcome::Failed(error))
+                }
+                (LandingStatus::Pending, _) => Ok(TradeOutcome::Pending),
+            }
+        }
+    }
+}
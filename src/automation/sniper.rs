@@ -0,0 +1,406 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use solana_sdk::pubkey::Pubkey;
+
+use super::{TradeOutcome, UNCONFIRMED_TIMEOUT, ensure_sol_balance, settle_trade};
+use crate::{
+    config::settings::SniperSettings,
+    database::{DatabaseConnectionPool, sniper::{self, NewSniperDecision}, trade_events::NewTradeEvent},
+    entities::{
+        sniper_decisions::SniperDecisionStatus,
+        sniper_subscriptions::Model as SniperSubscription,
+        trades::{TradeStatus, TradeType},
+    },
+    jupiter::{QuoteResponseV6, RecentToken, SwapMode},
+    solana::{
+        amount::TokenAmount,
+        constants::{SOL_DECIMALS, SOL_MINT},
+        executor::{ExecutionError, SwapExecutor, SwapOrder},
+        launch::{LaunchReport, MintState, PoolState, build_report},
+    },
+    telegram::notifier::Notifier,
+};
+
+/// Новый токен, ожидающий проверок
+#[derive(Debug, Clone)]
+pub struct Launch {
+    pub mint: Pubkey,
+    pub symbol: String,
+    pub pool: Option<Pubkey>,
+    pub pool_created_at: DateTime<Utc>,
+}
+
+impl Launch {
+    /// Токен из списка Jupiter, если его первый пул создан не раньше `max_age` назад
+    pub fn from_recent(token: &RecentToken, now: DateTime<Utc>, max_age: chrono::Duration) -> Option<Self> {
+        let pool = token.first_pool.as_ref()?;
+        if now - pool.created_at > max_age {
+            return None;
+        }
+
+        Some(Self {
+            mint: token.id,
+            symbol: token.symbol.clone(),
+            pool: Some(pool.id),
+            pool_created_at: pool.created_at,
+        })
+    }
+}
+
+/// Причины отказа от покупки по правилам снайпера; пустой список — проверки пройдены
+pub fn rejection_reasons(report: &LaunchReport, rules: &SniperSettings) -> Vec<String> {
+    let mut reasons = Vec::new();
+
+    if rules.require_mint_authority_revoked && let Some(authority) = &report.mint_authority {
+        reasons.push(format!("mint authority не отозван ({})", authority));
+    }
+    if rules.require_freeze_authority_revoked && let Some(authority) = &report.freeze_authority {
+        reasons.push(format!("freeze authority не отозван ({})", authority));
+    }
+    if let Some(burned) = report.lp_burned_pct
+        && burned < rules.min_lp_burned_pct
+    {
+        reasons.push(format!("сожжено {:.1}% LP, нужно не меньше {}%", burned, rules.min_lp_burned_pct));
+    }
+    if report.top_holders_pct > rules.max_top_holders_pct {
+        reasons.push(format!(
+            "у {} крупнейших держателей {:.1}% supply, допустимо {}%",
+            rules.top_holders, report.top_holders_pct, rules.max_top_holders_pct,
+        ));
+    }
+
+    reasons
+}
+
+/// Снайпер: находит токены, у которых только что появился первый пул, проверяет их
+/// и покупает за SOL для пользователей, включивших автопокупки. Каждое решение пишется в `sniper_decisions`.
+pub struct SniperEngine {
+    database: Arc<DatabaseConnectionPool>,
+    executor: Arc<SwapExecutor>,
+    notifier: Notifier,
+    settings: SniperSettings,
+    // Уже замеченные токены со временем создания пула
+    seen: HashMap<Pubkey, DateTime<Utc>>,
+    pending: Vec<Launch>,
+}
+
+impl SniperEngine {
+    pub fn new(
+        database: Arc<DatabaseConnectionPool>,
+        executor: Arc<SwapExecutor>,
+        notifier: Notifier,
+        settings: SniperSettings,
+    ) -> Self {
+        Self {
+            database,
+            executor,
+            notifier,
+            settings,
+            seen: HashMap::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    fn max_pool_age(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.settings.max_pool_age_secs as i64)
+    }
+
+    pub async fn run(mut self) {
+        if !self.settings.enabled {
+            tracing::info!("Sniper is disabled");
+            return;
+        }
+
+        let poll_interval = Duration::from_secs(self.settings.poll_interval_secs);
+        tracing::info!("Sniper started (poll interval {:?})", poll_interval);
+
+        let mut interval = tokio::time::interval(poll_interval);
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.reconcile().await {
+                tracing::warn!("Sniper reconciliation failed: {:#}", e);
+            }
+            if let Err(e) = self.detect().await {
+                tracing::warn!("Sniper detection failed: {:#}", e);
+            }
+            self.inspect_pending().await;
+        }
+    }
+
+    /// Добавляет в очередь проверок новые токены из списка Jupiter
+    async fn detect(&mut self) -> anyhow::Result<()> {
+        let now = Utc::now();
+        let max_age = self.max_pool_age();
+        self.seen.retain(|_, created_at| now - *created_at <= max_age * 2);
+
+        let tokens = self.executor.trader().jupiter().get_recent_tokens().await?;
+        for token in tokens {
+            let Some(launch) = Launch::from_recent(&token, now, max_age) else {
+                continue;
+            };
+            if self.seen.insert(launch.mint, launch.pool_created_at).is_some() {
+                continue;
+            }
+            if sniper::launch_logged(self.database.get_connection(), &launch.mint.to_string()).await? {
+                continue;
+            }
+            if self.pending.len() >= self.settings.max_pending {
+                tracing::warn!("Sniper queue is full, launch {} ignored", launch.mint);
+                continue;
+            }
+
+            tracing::info!("Sniper detected {} ({}), pool {:?}", launch.symbol, launch.mint, launch.pool);
+            self.pending.push(launch);
+        }
+
+        Ok(())
+    }
+
+    /// Проверяет токены из очереди. Непрошедшие проверки остаются в очереди до конца окна запуска:
+    /// авторитеты отзывают, а LP сжигают уже после создания пула.
+    async fn inspect_pending(&mut self) {
+        let now = Utc::now();
+        let max_age = self.max_pool_age();
+
+        for launch in std::mem::take(&mut self.pending) {
+            let expired = now - launch.pool_created_at > max_age;
+
+            let (report, reasons) = match self.inspect(&launch).await {
+                Ok(report) => {
+                    let reasons = rejection_reasons(&report, &self.settings);
+                    (Some(report), reasons)
+                }
+                Err(e) => (None, vec![format!("проверка не удалась: {}", e)]),
+            };
+
+            if reasons.is_empty() {
+                self.log_launch(&launch, SniperDecisionStatus::Passed, reasons, report.as_ref()).await;
+                self.buy_for_subscribers(&launch, report.as_ref()).await;
+            } else if expired {
+                tracing::info!("Sniper rejected {}: {}", launch.mint, reasons.join("; "));
+                self.log_launch(&launch, SniperDecisionStatus::Rejected, reasons, report.as_ref()).await;
+            } else {
+                self.pending.push(launch);
+            }
+        }
+    }
+
+    /// Читает mint, пул и держателей токена
+    async fn inspect(&self, launch: &Launch) -> anyhow::Result<LaunchReport> {
+        let rpc = self.executor.trader().rpc();
+
+        let mut keys = vec![launch.mint];
+        keys.extend(launch.pool);
+        let accounts = rpc.get_multiple_accounts(&keys).await?;
+
+        let mint_account = accounts
+            .first()
+            .and_then(Option::as_ref)
+            .ok_or_else(|| anyhow::anyhow!("mint не найден"))?;
+        let mint = MintState::decode(&mint_account.owner, &mint_account.data)
+            .ok_or_else(|| anyhow::anyhow!("аккаунт не является mint токена"))?;
+
+        let pool = accounts
+            .get(1)
+            .and_then(Option::as_ref)
+            .and_then(|account| PoolState::decode(&account.owner, &account.data))
+            .filter(|pool| pool.mint_a == launch.mint || pool.mint_b == launch.mint);
+
+        let lp_supply = match pool.as_ref().and_then(|pool| pool.lp_mint) {
+            Some(lp_mint) => Some(rpc.get_token_supply(&lp_mint).await?),
+            None => None,
+        };
+
+        let largest = rpc.get_token_largest_accounts(&launch.mint).await?;
+
+        Ok(build_report(&mint, pool.as_ref(), lp_supply, &largest, self.settings.top_holders))
+    }
+
+    async fn log_launch(
+        &self,
+        launch: &Launch,
+        status: SniperDecisionStatus,
+        reasons: Vec<String>,
+        report: Option<&LaunchReport>,
+    ) {
+        let result = sniper::insert_decision(self.database.get_connection(), NewSniperDecision {
+            user_id: None,
+            token_mint: launch.mint.to_string(),
+            token_symbol: launch.symbol.clone(),
+            pool_address: launch.pool.map(|pool| pool.to_string()),
+            pool_created_at: Some(launch.pool_created_at),
+            status,
+            reasons,
+            report: report.and_then(|report| serde_json::to_value(report).ok()),
+        }).await;
+
+        if let Err(e) = result {
+            tracing::error!("Failed to log sniper decision for {}: {}", launch.mint, e);
+        }
+    }
+
+    async fn buy_for_subscribers(&self, launch: &Launch, report: Option<&LaunchReport>) {
+        let subscriptions = match sniper::enabled_subscriptions(self.database.get_connection()).await {
+            Ok(subscriptions) => subscriptions,
+            Err(e) => {
+                tracing::error!("Failed to load sniper subscriptions: {}", e);
+                return;
+            }
+        };
+
+        for subscription in subscriptions {
+            self.buy(&subscription, launch, report).await;
+        }
+    }
+
+    async fn buy(&self, subscription: &SniperSubscription, launch: &Launch, report: Option<&LaunchReport>) {
+        let db = self.database.get_connection();
+        let user_id = subscription.user_id;
+
+        let decision = match sniper::insert_decision(db, NewSniperDecision {
+            user_id: Some(user_id),
+            token_mint: launch.mint.to_string(),
+            token_symbol: launch.symbol.clone(),
+            pool_address: launch.pool.map(|pool| pool.to_string()),
+            pool_created_at: Some(launch.pool_created_at),
+            status: SniperDecisionStatus::Executing,
+            reasons: Vec::new(),
+            report: report.and_then(|report| serde_json::to_value(report).ok()),
+        }).await {
+            Ok(decision) => decision,
+            Err(e) => {
+                tracing::error!("Failed to log sniper buy for user {}: {}", user_id, e);
+                return;
+            }
+        };
+
+        // Лимиты и баланс проверяются до подписи; такие отказы не стоят уведомления
+        let quote = match self.quote(subscription, launch).await {
+            Ok(quote) => quote,
+            Err(e) => {
+                self.finish(decision.id, SniperDecisionStatus::Skipped, e.to_string()).await;
+                return;
+            }
+        };
+
+        match self.execute(subscription, launch, decision.id, &quote).await {
+            Ok(signature) => {
+                if let Err(e) = sniper::finish_decision(db, decision.id, SniperDecisionStatus::Bought, Vec::new()).await {
+                    tracing::error!("Failed to mark sniper decision {} bought: {}", decision.id, e);
+                }
+                self.notifier.notify(user_id, format!(
+                    "🎯 Снайпер купил {} на {} SOL\nhttps://solscan.io/tx/{}",
+                    launch.symbol, subscription.amount_sol.normalized(), signature,
+                )).await;
+            }
+            // Покупка еще может попасть в блок: решение остается в исполнении до сверки
+            Err(e) if e.downcast_ref::<ExecutionError>().is_some_and(ExecutionError::is_unconfirmed) => {
+                tracing::warn!("Sniper decision {} is waiting for confirmation: {}", decision.id, e);
+                self.notifier.notify(user_id, format!(
+                    "⏳ Покупка {} снайпером отправлена, но еще не подтверждена: сообщим об итоге",
+                    launch.symbol,
+                )).await;
+            }
+            Err(e) => {
+                self.finish(decision.id, SniperDecisionStatus::Failed, e.to_string()).await;
+                self.notifier.notify(user_id, format!("❌ Снайпер не смог купить {}: {}", launch.symbol, e)).await;
+            }
+        }
+    }
+
+    async fn quote(&self, subscription: &SniperSubscription, launch: &Launch) -> anyhow::Result<QuoteResponseV6> {
+        let trader = self.executor.trader();
+        let max = TokenAmount::sol_setting(self.settings.max_amount_sol).raw();
+        let lamports = TokenAmount::from_big_decimal(&subscription.amount_sol, SOL_DECIMALS)
+            .map_err(|e| anyhow::anyhow!("некорректная сумма покупки: {}", e))?
+            .raw()
+            .min(max);
+        let slippage_bps = (subscription.max_slippage_bps as u64).min(trader.limits().max_slippage_bps);
+
+        let quote = trader.quote(Pubkey::from_str(SOL_MINT)?, launch.mint, lamports, SwapMode::ExactIn, slippage_bps).await?;
+        trader.check_limits(&quote)?;
+        ensure_sol_balance(&self.executor, subscription.user_id, &quote).await?;
+
+        Ok(quote)
+    }
+
+    async fn execute(
+        &self,
+        subscription: &SniperSubscription,
+        launch: &Launch,
+        decision_id: uuid::Uuid,
+        quote: &QuoteResponseV6,
+    ) -> anyhow::Result<String> {
+        let db = self.database.get_connection();
+        let trader = self.executor.trader();
+
+        let recorded = self.executor.record(SwapOrder {
+            user_id: subscription.user_id,
+            trade_type: TradeType::Buy,
+            quote,
+            input: trader.token_meta(&quote.input_mint).await?,
+            output: trader.token_meta(&launch.mint).await?,
+            jupiter_quote_id: None,
+            exit: false,
+        }).await?;
+
+        let trade_id = recorded.trade.id;
+        if let Err(e) = sniper::link_decision_trade(db, decision_id, trade_id).await {
+            let event = NewTradeEvent::cancelled("не отправлена: покупку не удалось связать со сделкой");
+            if let Err(e) = self.executor.repositories().trades.transition(&recorded.trade, TradeStatus::Cancelled, event).await {
+                tracing::error!("Failed to cancel trade {}: {}", trade_id, e);
+            }
+            return Err(e.into());
+        }
+
+        let executed = self.executor.send(recorded).await?;
+        Ok(executed.signature.to_string())
+    }
+
+    async fn finish(&self, id: uuid::Uuid, status: SniperDecisionStatus, reason: String) {
+        tracing::info!("Sniper decision {} {:?}: {}", id, status, reason);
+
+        if let Err(e) = sniper::finish_decision(self.database.get_connection(), id, status, vec![reason]).await {
+            tracing::error!("Failed to finish sniper decision {}: {}", id, e);
+        }
+    }
+
+    /// Сверка покупок, оставшихся в исполнении после сбоя или перезапуска
+    async fn reconcile(&self) -> anyhow::Result<()> {
+        let db = self.database.get_connection();
+
+        for decision in sniper::decisions_with_status(db, SniperDecisionStatus::Executing).await? {
+            let Some(trade_id) = decision.trade_id else {
+                if Utc::now() - decision.updated_at > UNCONFIRMED_TIMEOUT {
+                    sniper::finish_decision(db, decision.id, SniperDecisionStatus::Failed, vec!["исполнение прервано".to_string()]).await?;
+                }
+                continue;
+            };
+
+            match settle_trade(&self.executor, trade_id).await? {
+                TradeOutcome::Landed => {
+                    sniper::finish_decision(db, decision.id, SniperDecisionStatus::Bought, Vec::new()).await?;
+                    if let Some(user_id) = decision.user_id {
+                        self.notifier.notify(user_id, format!("🎯 Снайпер купил {}", decision.token_symbol)).await;
+                    }
+                }
+                TradeOutcome::Failed(error) => {
+                    sniper::finish_decision(db, decision.id, SniperDecisionStatus::Failed, vec![error.clone()]).await?;
+                    if let Some(user_id) = decision.user_id {
+                        self.notifier.notify(user_id, format!(
+                            "❌ Снайпер не смог купить {}: {}", decision.token_symbol, error,
+                        )).await;
+                    }
+                }
+                TradeOutcome::Pending => {}
+            }
+        }
+
+        Ok(())
+    }
+}
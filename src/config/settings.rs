@@ -3,6 +3,9 @@ use serde::Deserialize;
 use secrecy::{SecretString};
 use std::env;
 use dotenvy::dotenv;
+use rust_decimal::Decimal;
+
+use crate::entities::fee_preferences::PriorityTier;
 
 
 
@@ -52,21 +55,54 @@ pub fn default_rate_limit_per_hour() -> u32 { 300 }
 pub struct SolanaSettings {
     #[serde(default = "default_solana_rpc_url")]
     pub rpc_url: String,
+    // Пул узлов `url|вес`; если пуст, используется `rpc_url`
+    #[serde(default = "default_rpc_endpoints")]
+    pub rpc_endpoints: Vec<String>,
     #[serde(default = "default_solana_ws_url")]
     pub ws_url: String,
     #[serde(default = "default_commitment")]
     pub commitment: String,
     #[serde(default = "default_timeout_ms")]
     pub timeout_ms: u64,
+    // Сколько раз повторить чтение на другом узле
     #[serde(default = "default_retry_count")]
     pub retry_count: u32,
+    // На сколько узлов одновременно отправлять транзакцию
+    #[serde(default = "default_send_fanout")]
+    pub send_fanout: u32,
+    // Отставание в слотах, после которого узел исключается из пула
+    #[serde(default = "default_max_slot_lag")]
+    pub max_slot_lag: u64,
+    #[serde(default = "default_health_check_interval_secs")]
+    pub health_check_interval_secs: u64,
+    // Интервал повторной отправки транзакции до подтверждения
+    #[serde(default = "default_rebroadcast_interval_ms")]
+    pub rebroadcast_interval_ms: u64,
+    // Подписки через ws_url: подтверждения транзакций и изменения балансов приходят push-уведомлениями
+    #[serde(default = "default_pubsub_enabled")]
+    pub pubsub_enabled: bool,
+    #[serde(default = "default_pubsub_channel_capacity")]
+    pub pubsub_channel_capacity: usize,
+    #[serde(default = "default_pubsub_max_subscriptions")]
+    pub pubsub_max_subscriptions: usize,
+    #[serde(default = "default_pubsub_reconnect_max_secs")]
+    pub pubsub_reconnect_max_secs: u64,
 }
 
 pub fn default_solana_rpc_url() -> String { "https://api.mainnet-beta.solana.com".to_string() }
+pub fn default_rpc_endpoints() -> Vec<String> { vec![] }
 pub fn default_solana_ws_url() -> String { "wss://api.mainnet-beta.solana.com".to_string() }
 pub fn default_commitment() -> String { "confirmed".to_string() }
 pub fn default_timeout_ms() -> u64 { 30000 }
 pub fn default_retry_count() -> u32 { 3 }
+pub fn default_send_fanout() -> u32 { 3 }
+pub fn default_max_slot_lag() -> u64 { 50 }
+pub fn default_health_check_interval_secs() -> u64 { 10 }
+pub fn default_rebroadcast_interval_ms() -> u64 { 2000 }
+pub fn default_pubsub_enabled() -> bool { true }
+pub fn default_pubsub_channel_capacity() -> usize { 256 }
+pub fn default_pubsub_max_subscriptions() -> usize { 1000 }
+pub fn default_pubsub_reconnect_max_secs() -> u64 { 30 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct JupiterSettings {
@@ -118,9 +154,9 @@ pub fn default_encrypted_master_key_path() -> String { "./secrets/master_key.bin
 #[derive(Debug, Deserialize, Clone)]
 pub struct TradingLimits {
     #[serde(default = "default_max_trade_amount_sol")]
-    pub max_trade_amount_sol: f64,
+    pub max_trade_amount_sol: Decimal,
     #[serde(default = "default_min_trade_amount_sol")]
-    pub min_trade_amount_sol: f64,
+    pub min_trade_amount_sol: Decimal,
     #[serde(default = "default_max_slippage_bps")]
     pub max_slippage_bps: u64,
     #[serde(default = "default_max_trades_per_hour")]
@@ -128,15 +164,243 @@ pub struct TradingLimits {
     #[serde(default = "default_max_trades_per_day")]
     pub max_trades_per_day: u32,
     #[serde(default = "default_daily_trade_limit_sol")]
-    pub daily_trade_limit_sol: f64,
+    pub daily_trade_limit_sol: Decimal,
+    #[serde(default = "default_price_impact_warn_pct")]
+    pub price_impact_warn_pct: f64,
+    #[serde(default = "default_price_impact_block_pct")]
+    pub price_impact_block_pct: f64,
 }
 
-pub fn default_max_trade_amount_sol() -> f64 { 10.0 }
-pub fn default_min_trade_amount_sol() -> f64 { 0.01 }
+pub fn default_max_trade_amount_sol() -> Decimal { Decimal::TEN }
+pub fn default_min_trade_amount_sol() -> Decimal { Decimal::new(1, 2) }
 pub fn default_max_slippage_bps() -> u64 { 200 }
 pub fn default_max_trades_per_hour() -> u32 { 10 }
 pub fn default_max_trades_per_day() -> u32 { 50 }
-pub fn default_daily_trade_limit_sol() -> f64 { 100.0 }
+pub fn default_daily_trade_limit_sol() -> Decimal { Decimal::ONE_HUNDRED }
+pub fn default_price_impact_warn_pct() -> f64 { 1.0 }
+pub fn default_price_impact_block_pct() -> f64 { 5.0 }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PlatformFeeSettings {
+    #[serde(default = "default_platform_fee_bps")]
+    pub fee_bps: u16,
+    #[serde(default)]
+    pub referral_account: Option<String>,
+}
+
+pub fn default_platform_fee_bps() -> u16 { 0 }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct QuoteSettings {
+    #[serde(default = "default_quote_ttl_secs")]
+    pub ttl_secs: u64,
+    #[serde(default = "default_requote_tolerance_bps")]
+    pub requote_tolerance_bps: u64,
+}
+
+pub fn default_quote_ttl_secs() -> u64 { 20 }
+pub fn default_requote_tolerance_bps() -> u64 { 50 }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct LimitOrderSettings {
+    #[serde(default = "default_limit_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    #[serde(default = "default_limit_expiry_hours")]
+    pub default_expiry_hours: u64,
+    #[serde(default = "default_limit_slippage_bps")]
+    pub default_slippage_bps: u64,
+}
+
+pub fn default_limit_poll_interval_secs() -> u64 { 15 }
+pub fn default_limit_expiry_hours() -> u64 { 168 }
+pub fn default_limit_slippage_bps() -> u64 { 100 }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ExitOrderSettings {
+    #[serde(default = "default_exit_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    // Выход важнее цены исполнения, поэтому допуск выше, чем у покупок
+    #[serde(default = "default_exit_slippage_bps")]
+    pub default_slippage_bps: u64,
+}
+
+pub fn default_exit_poll_interval_secs() -> u64 { 10 }
+pub fn default_exit_slippage_bps() -> u64 { 150 }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct DcaSettings {
+    #[serde(default = "default_dca_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    #[serde(default = "default_dca_min_interval_secs")]
+    pub min_interval_secs: u64,
+    #[serde(default = "default_dca_max_cycles")]
+    pub max_cycles: u32,
+    #[serde(default = "default_dca_slippage_bps")]
+    pub default_slippage_bps: u64,
+}
+
+pub fn default_dca_poll_interval_secs() -> u64 { 30 }
+pub fn default_dca_min_interval_secs() -> u64 { 300 }
+pub fn default_dca_max_cycles() -> u32 { 1000 }
+pub fn default_dca_slippage_bps() -> u64 { 100 }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CopyTradingSettings {
+    #[serde(default = "default_copy_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    // Сколько последних транзакций кошелька запрашивать за один опрос
+    #[serde(default = "default_copy_signatures_per_poll")]
+    pub signatures_per_poll: usize,
+    #[serde(default = "default_copy_max_delay_secs")]
+    pub max_delay_secs: u64,
+    // Повторы, опоздавшие больше чем на это время, пропускаются
+    #[serde(default = "default_copy_max_lag_secs")]
+    pub max_lag_secs: u64,
+    #[serde(default = "default_copy_max_amount_sol")]
+    pub default_max_amount_sol: Decimal,
+    #[serde(default = "default_copy_slippage_bps")]
+    pub default_slippage_bps: u64,
+}
+
+pub fn default_copy_poll_interval_secs() -> u64 { 10 }
+pub fn default_copy_signatures_per_poll() -> usize { 25 }
+pub fn default_copy_max_delay_secs() -> u64 { 3600 }
+pub fn default_copy_max_lag_secs() -> u64 { 300 }
+pub fn default_copy_max_amount_sol() -> Decimal { Decimal::new(5, 1) }
+pub fn default_copy_slippage_bps() -> u64 { 150 }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct AlertSettings {
+    #[serde(default = "default_alert_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    // Минимальный интервал между повторными срабатываниями одного уведомления
+    #[serde(default = "default_alert_cooldown_secs")]
+    pub cooldown_secs: u64,
+    #[serde(default = "default_alert_max_per_user")]
+    pub max_alerts_per_user: u64,
+    // Окно для уведомлений об изменении цены, если оно не указано в команде
+    #[serde(default = "default_alert_window_secs")]
+    pub default_window_secs: i64,
+}
+
+pub fn default_alert_poll_interval_secs() -> u64 { 30 }
+pub fn default_alert_cooldown_secs() -> u64 { 900 }
+pub fn default_alert_max_per_user() -> u64 { 20 }
+pub fn default_alert_window_secs() -> i64 { 3600 }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct BalanceNotificationSettings {
+    // Как часто сверять подписки со списком кошельков, включивших уведомления
+    #[serde(default = "default_balance_refresh_interval_secs")]
+    pub refresh_interval_secs: u64,
+    // Изменения меньше порога (например, списание комиссии) не присылаются
+    #[serde(default = "default_balance_min_change_lamports")]
+    pub min_change_lamports: u64,
+}
+
+pub fn default_balance_refresh_interval_secs() -> u64 { 60 }
+pub fn default_balance_min_change_lamports() -> u64 { 1_000_000 }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SniperSettings {
+    // Снайпер выключен, пока его явно не включат
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_sniper_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    // Сколько после создания пула токен проверяется и покупается
+    #[serde(default = "default_sniper_max_pool_age_secs")]
+    pub max_pool_age_secs: u64,
+    #[serde(default = "default_sniper_max_pending")]
+    pub max_pending: usize,
+    #[serde(default = "default_sniper_amount_sol")]
+    pub default_amount_sol: Decimal,
+    #[serde(default = "default_sniper_max_amount_sol")]
+    pub max_amount_sol: Decimal,
+    #[serde(default = "default_sniper_slippage_bps")]
+    pub slippage_bps: u64,
+    #[serde(default = "default_sniper_require_revoked")]
+    pub require_mint_authority_revoked: bool,
+    #[serde(default = "default_sniper_require_revoked")]
+    pub require_freeze_authority_revoked: bool,
+    // Проверяется только у пулов с LP-токеном
+    #[serde(default = "default_sniper_min_lp_burned_pct")]
+    pub min_lp_burned_pct: f64,
+    #[serde(default = "default_sniper_max_top_holders_pct")]
+    pub max_top_holders_pct: f64,
+    #[serde(default = "default_sniper_top_holders")]
+    pub top_holders: usize,
+}
+
+pub fn default_sniper_poll_interval_secs() -> u64 { 5 }
+pub fn default_sniper_max_pool_age_secs() -> u64 { 120 }
+pub fn default_sniper_max_pending() -> usize { 50 }
+pub fn default_sniper_amount_sol() -> Decimal { Decimal::new(1, 1) }
+pub fn default_sniper_max_amount_sol() -> Decimal { Decimal::ONE }
+pub fn default_sniper_slippage_bps() -> u64 { 500 }
+pub fn default_sniper_require_revoked() -> bool { true }
+pub fn default_sniper_min_lp_burned_pct() -> f64 { 90.0 }
+pub fn default_sniper_max_top_holders_pct() -> f64 { 30.0 }
+pub fn default_sniper_top_holders() -> usize { 10 }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct TokenSafetySettings {
+    #[serde(default = "default_token_safety_enabled")]
+    pub enabled: bool,
+    // Покупка блокируется, если оценка риска выше порога (0–100)
+    #[serde(default = "default_token_safety_max_risk_score")]
+    pub max_risk_score: u8,
+    #[serde(default)]
+    pub block_freeze_authority: bool,
+    #[serde(default = "default_token_safety_block_permanent_delegate")]
+    pub block_permanent_delegate: bool,
+    #[serde(default)]
+    pub block_transfer_hook: bool,
+    // Нет маршрута для обратной продажи в SOL
+    #[serde(default = "default_token_safety_block_unsellable")]
+    pub block_unsellable: bool,
+    #[serde(default = "default_token_safety_max_transfer_fee_bps")]
+    pub max_transfer_fee_bps: u16,
+    // Потери на обратной продаже сверх влияния на цену
+    #[serde(default = "default_token_safety_max_sell_tax_pct")]
+    pub max_sell_tax_pct: f64,
+    #[serde(default = "default_token_safety_top_holders")]
+    pub top_holders: usize,
+    #[serde(default = "default_token_safety_cache_ttl_secs")]
+    pub cache_ttl_secs: u64,
+}
+
+pub fn default_token_safety_enabled() -> bool { true }
+pub fn default_token_safety_max_risk_score() -> u8 { 70 }
+pub fn default_token_safety_block_permanent_delegate() -> bool { true }
+pub fn default_token_safety_block_unsellable() -> bool { true }
+pub fn default_token_safety_max_transfer_fee_bps() -> u16 { 500 }
+pub fn default_token_safety_max_sell_tax_pct() -> f64 { 15.0 }
+pub fn default_token_safety_top_holders() -> usize { 10 }
+pub fn default_token_safety_cache_ttl_secs() -> u64 { 60 }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PriorityFeeSettings {
+    // Уровень для пользователей, не выбравших свой через /fee
+    #[serde(default = "default_priority_fee_tier")]
+    pub default_tier: PriorityTier,
+    #[serde(default = "default_priority_fee_default_max_lamports")]
+    pub default_max_lamports: u64,
+    // Общий потолок: пользовательский потолок не может быть выше
+    #[serde(default = "default_priority_fee_max_lamports")]
+    pub max_lamports: u64,
+    // Цена единицы вычислений переводится в лампорты по этой оценке размера свапа
+    #[serde(default = "default_priority_fee_compute_unit_estimate")]
+    pub compute_unit_estimate: u32,
+    #[serde(default = "default_priority_fee_cache_ttl_secs")]
+    pub cache_ttl_secs: u64,
+}
+
+pub fn default_priority_fee_tier() -> PriorityTier { PriorityTier::Medium }
+pub fn default_priority_fee_default_max_lamports() -> u64 { 1_000_000 }
+pub fn default_priority_fee_max_lamports() -> u64 { 10_000_000 }
+pub fn default_priority_fee_compute_unit_estimate() -> u32 { 300_000 }
+pub fn default_priority_fee_cache_ttl_secs() -> u64 { 5 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct RateLimitSettings {
@@ -162,6 +426,9 @@ pub struct ApiSettings {
     pub metrics_port: u16,
     #[serde(default = "default_health_check_port")]
     pub health_check_port: u16,
+    // Токен админского API; пока он не задан, админские маршруты закрыты
+    #[serde(default)]
+    pub admin_token: Option<SecretString>,
 }
 
 pub fn default_host() -> String { "0.0.0.0".to_string() }
@@ -208,6 +475,28 @@ pub struct Settings {
     pub security: SecuritySettings,
     #[serde(default = "default_trading_limits")]
     pub trading_limits: TradingLimits,
+    #[serde(default = "default_platform_fee")]
+    pub platform_fee: PlatformFeeSettings,
+    #[serde(default = "default_quote")]
+    pub quote: QuoteSettings,
+    #[serde(default = "default_limit_orders")]
+    pub limit_orders: LimitOrderSettings,
+    #[serde(default = "default_exit_orders")]
+    pub exit_orders: ExitOrderSettings,
+    #[serde(default = "default_dca")]
+    pub dca: DcaSettings,
+    #[serde(default = "default_copy_trading")]
+    pub copy_trading: CopyTradingSettings,
+    #[serde(default = "default_alerts")]
+    pub alerts: AlertSettings,
+    #[serde(default = "default_balance_notifications")]
+    pub balance_notifications: BalanceNotificationSettings,
+    #[serde(default = "default_sniper")]
+    pub sniper: SniperSettings,
+    #[serde(default = "default_token_safety")]
+    pub token_safety: TokenSafetySettings,
+    #[serde(default = "default_priority_fee")]
+    pub priority_fee: PriorityFeeSettings,
     #[serde(default = "default_rate_limit")]
     pub rate_limit: RateLimitSettings,
     #[serde(default = "default_api")]
@@ -251,10 +540,19 @@ pub fn default_telegram() -> TelegramSettings {
 pub fn default_solana() -> SolanaSettings {
     SolanaSettings {
         rpc_url: default_solana_rpc_url(),
+        rpc_endpoints: default_rpc_endpoints(),
         ws_url: default_solana_ws_url(),
         commitment: default_commitment(),
         timeout_ms: default_timeout_ms(),
         retry_count: default_retry_count(),
+        send_fanout: default_send_fanout(),
+        max_slot_lag: default_max_slot_lag(),
+        health_check_interval_secs: default_health_check_interval_secs(),
+        rebroadcast_interval_ms: default_rebroadcast_interval_ms(),
+        pubsub_enabled: default_pubsub_enabled(),
+        pubsub_channel_capacity: default_pubsub_channel_capacity(),
+        pubsub_max_subscriptions: default_pubsub_max_subscriptions(),
+        pubsub_reconnect_max_secs: default_pubsub_reconnect_max_secs(),
     }
 }
 
@@ -287,6 +585,115 @@ pub fn default_trading_limits() -> TradingLimits {
         max_trades_per_hour: default_max_trades_per_hour(),
         max_trades_per_day: default_max_trades_per_day(),
         daily_trade_limit_sol: default_daily_trade_limit_sol(),
+        price_impact_warn_pct: default_price_impact_warn_pct(),
+        price_impact_block_pct: default_price_impact_block_pct(),
+    }
+}
+
+pub fn default_platform_fee() -> PlatformFeeSettings {
+    PlatformFeeSettings {
+        fee_bps: default_platform_fee_bps(),
+        referral_account: None,
+    }
+}
+
+pub fn default_quote() -> QuoteSettings {
+    QuoteSettings {
+        ttl_secs: default_quote_ttl_secs(),
+        requote_tolerance_bps: default_requote_tolerance_bps(),
+    }
+}
+
+pub fn default_limit_orders() -> LimitOrderSettings {
+    LimitOrderSettings {
+        poll_interval_secs: default_limit_poll_interval_secs(),
+        default_expiry_hours: default_limit_expiry_hours(),
+        default_slippage_bps: default_limit_slippage_bps(),
+    }
+}
+
+pub fn default_exit_orders() -> ExitOrderSettings {
+    ExitOrderSettings {
+        poll_interval_secs: default_exit_poll_interval_secs(),
+        default_slippage_bps: default_exit_slippage_bps(),
+    }
+}
+
+pub fn default_dca() -> DcaSettings {
+    DcaSettings {
+        poll_interval_secs: default_dca_poll_interval_secs(),
+        min_interval_secs: default_dca_min_interval_secs(),
+        max_cycles: default_dca_max_cycles(),
+        default_slippage_bps: default_dca_slippage_bps(),
+    }
+}
+
+pub fn default_copy_trading() -> CopyTradingSettings {
+    CopyTradingSettings {
+        poll_interval_secs: default_copy_poll_interval_secs(),
+        signatures_per_poll: default_copy_signatures_per_poll(),
+        max_delay_secs: default_copy_max_delay_secs(),
+        max_lag_secs: default_copy_max_lag_secs(),
+        default_max_amount_sol: default_copy_max_amount_sol(),
+        default_slippage_bps: default_copy_slippage_bps(),
+    }
+}
+
+pub fn default_alerts() -> AlertSettings {
+    AlertSettings {
+        poll_interval_secs: default_alert_poll_interval_secs(),
+        cooldown_secs: default_alert_cooldown_secs(),
+        max_alerts_per_user: default_alert_max_per_user(),
+        default_window_secs: default_alert_window_secs(),
+    }
+}
+
+pub fn default_balance_notifications() -> BalanceNotificationSettings {
+    BalanceNotificationSettings {
+        refresh_interval_secs: default_balance_refresh_interval_secs(),
+        min_change_lamports: default_balance_min_change_lamports(),
+    }
+}
+
+pub fn default_sniper() -> SniperSettings {
+    SniperSettings {
+        enabled: false,
+        poll_interval_secs: default_sniper_poll_interval_secs(),
+        max_pool_age_secs: default_sniper_max_pool_age_secs(),
+        max_pending: default_sniper_max_pending(),
+        default_amount_sol: default_sniper_amount_sol(),
+        max_amount_sol: default_sniper_max_amount_sol(),
+        slippage_bps: default_sniper_slippage_bps(),
+        require_mint_authority_revoked: default_sniper_require_revoked(),
+        require_freeze_authority_revoked: default_sniper_require_revoked(),
+        min_lp_burned_pct: default_sniper_min_lp_burned_pct(),
+        max_top_holders_pct: default_sniper_max_top_holders_pct(),
+        top_holders: default_sniper_top_holders(),
+    }
+}
+
+pub fn default_token_safety() -> TokenSafetySettings {
+    TokenSafetySettings {
+        enabled: default_token_safety_enabled(),
+        max_risk_score: default_token_safety_max_risk_score(),
+        block_freeze_authority: false,
+        block_permanent_delegate: default_token_safety_block_permanent_delegate(),
+        block_transfer_hook: false,
+        block_unsellable: default_token_safety_block_unsellable(),
+        max_transfer_fee_bps: default_token_safety_max_transfer_fee_bps(),
+        max_sell_tax_pct: default_token_safety_max_sell_tax_pct(),
+        top_holders: default_token_safety_top_holders(),
+        cache_ttl_secs: default_token_safety_cache_ttl_secs(),
+    }
+}
+
+pub fn default_priority_fee() -> PriorityFeeSettings {
+    PriorityFeeSettings {
+        default_tier: default_priority_fee_tier(),
+        default_max_lamports: default_priority_fee_default_max_lamports(),
+        max_lamports: default_priority_fee_max_lamports(),
+        compute_unit_estimate: default_priority_fee_compute_unit_estimate(),
+        cache_ttl_secs: default_priority_fee_cache_ttl_secs(),
     }
 }
 
@@ -304,6 +711,7 @@ pub fn default_api() -> ApiSettings {
         port: default_port(),
         metrics_port: default_metrics_port(),
         health_check_port: default_health_check_port(),
+        admin_token: None,
     }
 }
 
@@ -0,0 +1,96 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, Set, sea_query::Expr,
+};
+use uuid::Uuid;
+
+use crate::entities::price_alerts::{self, AlertKind};
+
+/// Данные нового ценового уведомления
+pub struct NewPriceAlert {
+    pub user_id: i64,
+    pub token_mint: String,
+    pub token_symbol: String,
+    pub kind: AlertKind,
+    pub threshold: BigDecimal,
+    pub window_secs: Option<i64>,
+}
+
+pub async fn create(db: &DatabaseConnection, alert: NewPriceAlert) -> Result<price_alerts::Model, DbErr> {
+    let now = Utc::now();
+    price_alerts::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(alert.user_id),
+        token_mint: Set(alert.token_mint),
+        token_symbol: Set(alert.token_symbol),
+        kind: Set(alert.kind),
+        threshold: Set(alert.threshold),
+        window_secs: Set(alert.window_secs),
+        armed: Set(true),
+        trigger_count: Set(0),
+        last_triggered_at: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(db)
+    .await
+}
+
+pub async fn list_for_user(db: &DatabaseConnection, user_id: i64) -> Result<Vec<price_alerts::Model>, DbErr> {
+    price_alerts::Entity::find()
+        .filter(price_alerts::Column::UserId.eq(user_id))
+        .order_by_asc(price_alerts::Column::CreatedAt)
+        .all(db)
+        .await
+}
+
+pub async fn count_for_user(db: &DatabaseConnection, user_id: i64) -> Result<u64, DbErr> {
+    price_alerts::Entity::find()
+        .filter(price_alerts::Column::UserId.eq(user_id))
+        .count(db)
+        .await
+}
+
+pub async fn all(db: &DatabaseConnection) -> Result<Vec<price_alerts::Model>, DbErr> {
+    price_alerts::Entity::find().all(db).await
+}
+
+pub async fn delete(db: &DatabaseConnection, id: Uuid, user_id: i64) -> Result<bool, DbErr> {
+    let result = price_alerts::Entity::delete_many()
+        .filter(price_alerts::Column::Id.eq(id))
+        .filter(price_alerts::Column::UserId.eq(user_id))
+        .exec(db)
+        .await?;
+
+    Ok(result.rows_affected == 1)
+}
+
+/// Отмечает срабатывание; повторно уведомление сработает только после `rearm`.
+/// Возвращает `false`, если уведомление уже сработало в другом обработчике.
+pub async fn mark_fired(db: &DatabaseConnection, id: Uuid, now: DateTime<Utc>) -> Result<bool, DbErr> {
+    let result = price_alerts::Entity::update_many()
+        .col_expr(price_alerts::Column::Armed, Expr::value(false))
+        .col_expr(price_alerts::Column::TriggerCount, Expr::col(price_alerts::Column::TriggerCount).add(1))
+        .col_expr(price_alerts::Column::LastTriggeredAt, Expr::value(now))
+        .col_expr(price_alerts::Column::UpdatedAt, Expr::value(now))
+        .filter(price_alerts::Column::Id.eq(id))
+        .filter(price_alerts::Column::Armed.eq(true))
+        .exec(db)
+        .await?;
+
+    Ok(result.rows_affected == 1)
+}
+
+/// Снова включает уведомление, когда условие перестало выполняться
+pub async fn rearm(db: &DatabaseConnection, id: Uuid) -> Result<(), DbErr> {
+    price_alerts::Entity::update_many()
+        .col_expr(price_alerts::Column::Armed, Expr::value(true))
+        .col_expr(price_alerts::Column::UpdatedAt, Expr::value(Utc::now()))
+        .filter(price_alerts::Column::Id.eq(id))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
@@ -1,10 +1,27 @@
-use sea_orm::{Database as SeaDatabase, DatabaseConnection, DbErr, ConnectOptions, TransactionTrait};
+use sea_orm::{
+    ConnectionTrait, Database as SeaDatabase, DatabaseConnection, DatabaseTransaction, DbBackend, DbErr,
+    ConnectOptions, IsolationLevel, RuntimeErr, Statement, TransactionTrait,
+};
 use tracing::info;
+use std::collections::HashSet;
+use std::future::Future;
+use std::pin::Pin;
 use std::time::Duration;
 use secrecy::ExposeSecret;
+use migration::{Migrator, MigratorTrait};
 
 use crate::config::settings::DatabaseSettings;
 
+// Ключ advisory-блокировки миграций: реплики, стартующие одновременно, применяют их по очереди
+const MIGRATION_LOCK_KEY: i64 = 0x736f_6c62_6f74;
+
+/// Миграция из крейта `migration` и признак того, что она применена
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationState {
+    pub name: String,
+    pub applied: bool,
+}
+
 #[derive(Clone)]
 pub struct DatabaseConnectionPool {
     connection: DatabaseConnection,
@@ -37,35 +54,159 @@ impl DatabaseConnectionPool {
         &self.connection
     }
 
+    /// Транзакция, удерживающая блокировку миграций до commit или rollback
+    async fn migration_transaction(&self) -> Result<DatabaseTransaction, DbErr> {
+        let transaction = self.connection.begin().await?;
+        if transaction.get_database_backend() == DbBackend::Postgres {
+            transaction
+                .execute(Statement::from_sql_and_values(
+                    DbBackend::Postgres,
+                    "SELECT pg_advisory_xact_lock($1)",
+                    [MIGRATION_LOCK_KEY.into()],
+                ))
+                .await?;
+        }
+        Ok(transaction)
+    }
+
+    /// Применяет все новые миграции. Если их уже применила другая реплика, ничего не делает.
     pub async fn run_migrations(&self) -> Result<(), DbErr> {
-        // Для SeaORM миграции нужно использовать sea-orm-cli
-        // Если миграции не нужны, можно оставить пустую реализацию
-        info!("Skipping migrations (not implemented)");
+        let transaction = self.migration_transaction().await?;
+        let pending = Migrator::get_pending_migrations(&transaction).await?;
+        if !pending.is_empty() {
+            Migrator::up(&transaction, None).await?;
+        }
+        transaction.commit().await?;
+
+        info!("Applied {} migration(s)", pending.len());
         Ok(())
     }
 
+    /// Откатывает последние `steps` примененных миграций
+    pub async fn rollback_migrations(&self, steps: u32) -> Result<(), DbErr> {
+        let transaction = self.migration_transaction().await?;
+        Migrator::down(&transaction, Some(steps)).await?;
+        transaction.commit().await
+    }
+
+    /// Все миграции по порядку с отметкой о применении
+    pub async fn migration_status(&self) -> Result<Vec<MigrationState>, DbErr> {
+        let applied: HashSet<String> = Migrator::get_applied_migrations(&self.connection)
+            .await?
+            .iter()
+            .map(|migration| migration.name().to_string())
+            .collect();
+
+        Ok(Migrator::migrations()
+            .iter()
+            .map(|migration| MigrationState {
+                name: migration.name().to_string(),
+                applied: applied.contains(migration.name()),
+            })
+            .collect())
+    }
+
     pub async fn health_check(&self) -> Result<(), DbErr> {
         self.connection.ping().await?;
         Ok(())
     }
 
+    /// Выполняет `callback` в транзакции: commit, если он вернул `Ok`, иначе rollback.
+    /// Внутри callback можно открыть вложенную транзакцию через [`in_transaction`].
     pub async fn transaction<F, T, E>(&self, callback: F) -> Result<T, E>
     where
-        F: FnOnce(&DatabaseConnection) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<T, E>> + Send>> + Send,
+        F: for<'c> FnOnce(&'c DatabaseTransaction) -> BoxFuture<'c, Result<T, E>> + Send,
         T: Send,
         E: From<DbErr> + Send,
     {
-        let transaction = self.connection.begin().await?;
+        in_transaction(&self.connection, callback).await
+    }
+
+    /// Транзакция SERIALIZABLE, которая повторяется при конфликте сериализации (SQLSTATE 40001),
+    /// всего не больше `max_attempts` раз. Поэтому `callback` может вызываться несколько раз.
+    pub async fn serializable_transaction<F, T, E>(&self, max_attempts: u32, callback: F) -> Result<T, E>
+    where
+        F: for<'c> Fn(&'c DatabaseTransaction) -> BoxFuture<'c, Result<T, E>> + Send + Sync,
+        T: Send,
+        E: RetryableError + Send,
+    {
+        let mut attempt = 1;
+        loop {
+            let transaction = self.connection
+                .begin_with_config(Some(IsolationLevel::Serializable), None)
+                .await?;
+            let result = callback(&transaction).await;
 
-        match callback(&self.connection).await {
-            Ok(result) => {
-                transaction.commit().await?;
-                Ok(result)
+            match finish(transaction, result).await {
+                Err(e) if e.is_serialization_failure() && attempt < max_attempts => {
+                    tracing::warn!("Serialization failure, retrying transaction (attempt {})", attempt);
+                    tokio::time::sleep(SERIALIZATION_RETRY_DELAY * 2u32.pow(attempt - 1)).await;
+                    attempt += 1;
+                }
+                result => return result,
             }
-            Err(err) => {
-                transaction.rollback().await?;
-                Err(err)
+        }
+    }
+}
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+// Пауза перед первым повтором транзакции, дальше удваивается
+const SERIALIZATION_RETRY_DELAY: Duration = Duration::from_millis(10);
+
+/// Выполняет `callback` в новой транзакции `db` или, если `db` уже транзакция,
+/// в точке сохранения: ошибка откатывает только изменения этого callback.
+pub async fn in_transaction<C, F, T, E>(db: &C, callback: F) -> Result<T, E>
+where
+    C: TransactionTrait,
+    F: for<'c> FnOnce(&'c DatabaseTransaction) -> BoxFuture<'c, Result<T, E>> + Send,
+    T: Send,
+    E: From<DbErr> + Send,
+{
+    let transaction = db.begin().await?;
+    let result = callback(&transaction).await;
+    finish(transaction, result).await
+}
+
+async fn finish<T, E: From<DbErr>>(transaction: DatabaseTransaction, result: Result<T, E>) -> Result<T, E> {
+    match result {
+        Ok(value) => {
+            transaction.commit().await?;
+            Ok(value)
+        }
+        Err(e) => {
+            // Исходная ошибка важнее ошибки отката
+            if let Err(rollback_error) = transaction.rollback().await {
+                tracing::warn!("Failed to roll back transaction: {}", rollback_error);
             }
+            Err(e)
         }
     }
 }
+
+/// Ошибка транзакции, по которой видно, можно ли ее повторить
+pub trait RetryableError: From<DbErr> {
+    fn is_serialization_failure(&self) -> bool;
+}
+
+impl RetryableError for DbErr {
+    fn is_serialization_failure(&self) -> bool {
+        let (DbErr::Query(error) | DbErr::Exec(error) | DbErr::Conn(error)) = self else {
+            return false;
+        };
+        matches!(
+            error,
+            RuntimeErr::SqlxError(sqlx::Error::Database(database_error))
+                if database_error.code().as_deref() == Some(SERIALIZATION_FAILURE)
+        )
+    }
+}
+
+impl RetryableError for anyhow::Error {
+    fn is_serialization_failure(&self) -> bool {
+        self.downcast_ref::<DbErr>().is_some_and(DbErr::is_serialization_failure)
+    }
+}
+
+// SQLSTATE `serialization_failure`
+const SERIALIZATION_FAILURE: &str = "40001";
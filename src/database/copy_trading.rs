@@ -0,0 +1,233 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    Set, sea_query::{Expr, OnConflict},
+};
+use uuid::Uuid;
+
+use crate::entities::{
+    copy_follows::{self, CopyFollowStatus, CopyMode, MintList},
+    copy_trades::{self, CopyTradeStatus},
+};
+
+/// Данные новой подписки на кошелек
+pub struct NewCopyFollow {
+    pub user_id: i64,
+    pub leader_wallet: String,
+    pub mode: CopyMode,
+    pub amount: BigDecimal,
+    pub max_amount_sol: BigDecimal,
+    pub delay_secs: i32,
+    pub max_slippage_bps: i32,
+}
+
+pub async fn create_follow(db: &DatabaseConnection, follow: NewCopyFollow) -> Result<copy_follows::Model, DbErr> {
+    let now = Utc::now();
+    copy_follows::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(follow.user_id),
+        leader_wallet: Set(follow.leader_wallet),
+        mode: Set(follow.mode),
+        amount: Set(follow.amount),
+        max_amount_sol: Set(follow.max_amount_sol),
+        allowed_mints: Set(MintList::default()),
+        denied_mints: Set(MintList::default()),
+        delay_secs: Set(follow.delay_secs),
+        max_slippage_bps: Set(follow.max_slippage_bps),
+        status: Set(CopyFollowStatus::Active),
+        last_signature: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(db)
+    .await
+}
+
+/// Подписки пользователя, кроме остановленных
+pub async fn follows_for_user(db: &DatabaseConnection, user_id: i64) -> Result<Vec<copy_follows::Model>, DbErr> {
+    copy_follows::Entity::find()
+        .filter(copy_follows::Column::UserId.eq(user_id))
+        .filter(copy_follows::Column::Status.ne(CopyFollowStatus::Stopped))
+        .order_by_desc(copy_follows::Column::CreatedAt)
+        .all(db)
+        .await
+}
+
+pub async fn active_follows(db: &DatabaseConnection) -> Result<Vec<copy_follows::Model>, DbErr> {
+    copy_follows::Entity::find()
+        .filter(copy_follows::Column::Status.eq(CopyFollowStatus::Active))
+        .all(db)
+        .await
+}
+
+pub async fn find_follow(db: &DatabaseConnection, id: Uuid) -> Result<Option<copy_follows::Model>, DbErr> {
+    copy_follows::Entity::find_by_id(id).one(db).await
+}
+
+/// Переводит подписку пользователя из `from` в `to`
+pub async fn transition_follow(
+    db: &DatabaseConnection,
+    id: Uuid,
+    user_id: i64,
+    from: &[CopyFollowStatus],
+    to: CopyFollowStatus,
+) -> Result<bool, DbErr> {
+    let result = copy_follows::Entity::update_many()
+        .col_expr(copy_follows::Column::Status, Expr::value(to))
+        .col_expr(copy_follows::Column::UpdatedAt, Expr::value(Utc::now()))
+        .filter(copy_follows::Column::Id.eq(id))
+        .filter(copy_follows::Column::UserId.eq(user_id))
+        .filter(copy_follows::Column::Status.is_in(from.iter().copied()))
+        .exec(db)
+        .await?;
+
+    Ok(result.rows_affected == 1)
+}
+
+/// Заменяет списки разрешенных и запрещенных токенов; `None` оставляет список без изменений
+pub async fn set_mint_filters(
+    db: &DatabaseConnection,
+    id: Uuid,
+    allowed: Option<Vec<String>>,
+    denied: Option<Vec<String>>,
+) -> Result<(), DbErr> {
+    copy_follows::ActiveModel {
+        id: Set(id),
+        allowed_mints: allowed.map_or(Default::default(), |mints| Set(MintList(mints))),
+        denied_mints: denied.map_or(Default::default(), |mints| Set(MintList(mints))),
+        updated_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .update(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Запоминает последнюю просмотренную транзакцию лидера
+pub async fn advance_cursor(db: &DatabaseConnection, id: Uuid, signature: &str) -> Result<(), DbErr> {
+    copy_follows::Entity::update_many()
+        .col_expr(copy_follows::Column::LastSignature, Expr::value(signature))
+        .col_expr(copy_follows::Column::UpdatedAt, Expr::value(Utc::now()))
+        .filter(copy_follows::Column::Id.eq(id))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+/// Свап лидера, который нужно повторить или пропустить
+pub struct NewCopyTrade {
+    pub follow_id: Uuid,
+    pub user_id: i64,
+    pub leader_signature: String,
+    pub input_mint: String,
+    pub output_mint: String,
+    pub leader_input_amount: BigDecimal,
+    pub leader_output_amount: BigDecimal,
+    pub leader_input_balance: BigDecimal,
+    pub status: CopyTradeStatus,
+    pub error_message: Option<String>,
+    pub execute_after: DateTime<Utc>,
+}
+
+/// Записывает свап лидера для подписки. Возвращает `false`, если он уже записан.
+pub async fn insert_copy_trade(db: &DatabaseConnection, copy: NewCopyTrade) -> Result<bool, DbErr> {
+    let now = Utc::now();
+    let model = copy_trades::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        follow_id: Set(copy.follow_id),
+        user_id: Set(copy.user_id),
+        leader_signature: Set(copy.leader_signature),
+        input_mint: Set(copy.input_mint),
+        output_mint: Set(copy.output_mint),
+        leader_input_amount: Set(copy.leader_input_amount),
+        leader_output_amount: Set(copy.leader_output_amount),
+        leader_input_balance: Set(copy.leader_input_balance),
+        status: Set(copy.status),
+        trade_id: Set(None),
+        error_message: Set(copy.error_message),
+        execute_after: Set(copy.execute_after),
+        created_at: Set(now),
+        updated_at: Set(now),
+    };
+
+    let inserted = copy_trades::Entity::insert(model)
+        .on_conflict(
+            OnConflict::columns([copy_trades::Column::FollowId, copy_trades::Column::LeaderSignature])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+
+    Ok(inserted == 1)
+}
+
+pub async fn copy_trades_with_status(
+    db: &DatabaseConnection,
+    status: CopyTradeStatus,
+) -> Result<Vec<copy_trades::Model>, DbErr> {
+    copy_trades::Entity::find()
+        .filter(copy_trades::Column::Status.eq(status))
+        .order_by_asc(copy_trades::Column::ExecuteAfter)
+        .all(db)
+        .await
+}
+
+/// Повторы, у которых закончилась задержка
+pub async fn due_copy_trades(db: &DatabaseConnection, now: DateTime<Utc>) -> Result<Vec<copy_trades::Model>, DbErr> {
+    copy_trades::Entity::find()
+        .filter(copy_trades::Column::Status.eq(CopyTradeStatus::Pending))
+        .filter(copy_trades::Column::ExecuteAfter.lte(now))
+        .order_by_asc(copy_trades::Column::ExecuteAfter)
+        .all(db)
+        .await
+}
+
+/// Атомарно переводит ожидающий повтор в исполнение
+pub async fn claim_copy_trade(db: &DatabaseConnection, id: Uuid) -> Result<bool, DbErr> {
+    let result = copy_trades::Entity::update_many()
+        .col_expr(copy_trades::Column::Status, Expr::value(CopyTradeStatus::Executing))
+        .col_expr(copy_trades::Column::UpdatedAt, Expr::value(Utc::now()))
+        .filter(copy_trades::Column::Id.eq(id))
+        .filter(copy_trades::Column::Status.eq(CopyTradeStatus::Pending))
+        .exec(db)
+        .await?;
+
+    Ok(result.rows_affected == 1)
+}
+
+/// Связывает повтор со сделкой до ее отправки
+pub async fn link_copy_trade(db: &DatabaseConnection, id: Uuid, trade_id: Uuid) -> Result<(), DbErr> {
+    copy_trades::ActiveModel {
+        id: Set(id),
+        trade_id: Set(Some(trade_id)),
+        updated_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .update(db)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn finish_copy_trade(
+    db: &DatabaseConnection,
+    id: Uuid,
+    status: CopyTradeStatus,
+    error: Option<String>,
+) -> Result<(), DbErr> {
+    copy_trades::ActiveModel {
+        id: Set(id),
+        status: Set(status),
+        error_message: Set(error),
+        updated_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .update(db)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    Set, sea_query::Expr,
+};
+use uuid::Uuid;
+
+use crate::entities::dca_schedules::{self, DcaStatus};
+
+/// Данные нового расписания регулярных покупок
+pub struct NewDcaSchedule {
+    pub user_id: i64,
+    pub token_mint: String,
+    pub token_symbol: String,
+    pub amount_sol: BigDecimal,
+    pub interval_secs: i64,
+    pub total_cycles: i32,
+    pub max_slippage_bps: i32,
+}
+
+/// Первый цикл выполняется сразу, следующие — через `interval_secs`
+pub async fn create(db: &DatabaseConnection, schedule: NewDcaSchedule) -> Result<dca_schedules::Model, DbErr> {
+    let now = Utc::now();
+    dca_schedules::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(schedule.user_id),
+        token_mint: Set(schedule.token_mint),
+        token_symbol: Set(schedule.token_symbol),
+        amount_sol: Set(schedule.amount_sol),
+        interval_secs: Set(schedule.interval_secs),
+        total_cycles: Set(schedule.total_cycles),
+        cycles_done: Set(0),
+        cycles_skipped: Set(0),
+        max_slippage_bps: Set(schedule.max_slippage_bps),
+        status: Set(DcaStatus::Active),
+        next_run_at: Set(now),
+        last_run_at: Set(None),
+        last_error: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(db)
+    .await
+}
+
+pub async fn find(db: &DatabaseConnection, id: Uuid) -> Result<Option<dca_schedules::Model>, DbErr> {
+    dca_schedules::Entity::find_by_id(id).one(db).await
+}
+
+/// Расписания пользователя, кроме завершенных и отмененных
+pub async fn list_for_user(db: &DatabaseConnection, user_id: i64) -> Result<Vec<dca_schedules::Model>, DbErr> {
+    dca_schedules::Entity::find()
+        .filter(dca_schedules::Column::UserId.eq(user_id))
+        .filter(dca_schedules::Column::Status.is_in([DcaStatus::Active, DcaStatus::Paused]))
+        .order_by_desc(dca_schedules::Column::CreatedAt)
+        .all(db)
+        .await
+}
+
+/// Активные расписания, для которых подошло время очередного цикла
+pub async fn due(db: &DatabaseConnection, now: DateTime<Utc>) -> Result<Vec<dca_schedules::Model>, DbErr> {
+    dca_schedules::Entity::find()
+        .filter(dca_schedules::Column::Status.eq(DcaStatus::Active))
+        .filter(dca_schedules::Column::NextRunAt.lte(now))
+        .order_by_asc(dca_schedules::Column::NextRunAt)
+        .all(db)
+        .await
+}
+
+/// Переводит расписание пользователя из `from` в `to`.
+/// Возвращает `false`, если расписание уже в другом статусе.
+pub async fn transition(
+    db: &DatabaseConnection,
+    id: Uuid,
+    user_id: i64,
+    from: &[DcaStatus],
+    to: DcaStatus,
+) -> Result<bool, DbErr> {
+    let now = Utc::now();
+    let mut query = dca_schedules::Entity::update_many()
+        .col_expr(dca_schedules::Column::Status, Expr::value(to))
+        .col_expr(dca_schedules::Column::UpdatedAt, Expr::value(now));
+
+    // После паузы следующий цикл выполняется сразу, без догоняющих покупок
+    if to == DcaStatus::Active {
+        query = query.col_expr(dca_schedules::Column::NextRunAt, Expr::value(now));
+    }
+
+    let result = query
+        .filter(dca_schedules::Column::Id.eq(id))
+        .filter(dca_schedules::Column::UserId.eq(user_id))
+        .filter(dca_schedules::Column::Status.is_in(from.iter().copied()))
+        .exec(db)
+        .await?;
+
+    Ok(result.rows_affected == 1)
+}
+
+/// Атомарно занимает очередной цикл: увеличивает счетчик и переносит следующий запуск.
+/// Возвращает `false`, если цикл уже занят другим обработчиком или расписание изменилось.
+pub async fn claim_cycle(
+    db: &DatabaseConnection,
+    schedule: &dca_schedules::Model,
+    now: DateTime<Utc>,
+) -> Result<bool, DbErr> {
+    let cycle = schedule.cycles_done + 1;
+    let status = if cycle >= schedule.total_cycles {
+        DcaStatus::Completed
+    } else {
+        DcaStatus::Active
+    };
+
+    let result = dca_schedules::Entity::update_many()
+        .col_expr(dca_schedules::Column::CyclesDone, Expr::value(cycle))
+        .col_expr(dca_schedules::Column::Status, Expr::value(status))
+        .col_expr(dca_schedules::Column::NextRunAt, Expr::value(now + Duration::seconds(schedule.interval_secs)))
+        .col_expr(dca_schedules::Column::LastRunAt, Expr::value(now))
+        .col_expr(dca_schedules::Column::UpdatedAt, Expr::value(now))
+        .filter(dca_schedules::Column::Id.eq(schedule.id))
+        .filter(dca_schedules::Column::Status.eq(DcaStatus::Active))
+        .filter(dca_schedules::Column::CyclesDone.eq(schedule.cycles_done))
+        .exec(db)
+        .await?;
+
+    Ok(result.rows_affected == 1)
+}
+
+/// Отмечает пропущенный цикл и причину пропуска
+pub async fn record_skip(db: &DatabaseConnection, id: Uuid, error: &str) -> Result<(), DbErr> {
+    dca_schedules::Entity::update_many()
+        .col_expr(dca_schedules::Column::CyclesSkipped, Expr::col(dca_schedules::Column::CyclesSkipped).add(1))
+        .col_expr(dca_schedules::Column::LastError, Expr::value(error))
+        .col_expr(dca_schedules::Column::UpdatedAt, Expr::value(Utc::now()))
+        .filter(dca_schedules::Column::Id.eq(id))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
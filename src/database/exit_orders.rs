@@ -0,0 +1,187 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    Set, sea_query::{Expr, Query},
+};
+use uuid::Uuid;
+
+use crate::entities::exit_orders::{self, ExitKind, ExitOrderStatus};
+
+/// Данные нового ордера на выход из позиции
+pub struct NewExitOrder {
+    pub user_id: i64,
+    pub trade_id: Uuid,
+    pub kind: ExitKind,
+    pub token_mint: String,
+    pub token_symbol: String,
+    pub quote_mint: String,
+    pub amount: BigDecimal,
+    pub entry_price: BigDecimal,
+    pub trigger_price: BigDecimal,
+    pub trail_pct: Option<BigDecimal>,
+    pub max_slippage_bps: i32,
+}
+
+pub async fn create(db: &DatabaseConnection, order: NewExitOrder) -> Result<exit_orders::Model, DbErr> {
+    let now = Utc::now();
+    // Трейлинг-стоп отсчитывается от максимума, начиная с цены входа
+    let high_water_price = order.trail_pct.as_ref().map(|_| order.entry_price.clone());
+
+    exit_orders::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(order.user_id),
+        trade_id: Set(order.trade_id),
+        kind: Set(order.kind),
+        token_mint: Set(order.token_mint),
+        token_symbol: Set(order.token_symbol),
+        quote_mint: Set(order.quote_mint),
+        amount: Set(order.amount),
+        entry_price: Set(order.entry_price),
+        trigger_price: Set(order.trigger_price),
+        trail_pct: Set(order.trail_pct),
+        high_water_price: Set(high_water_price),
+        max_slippage_bps: Set(order.max_slippage_bps),
+        status: Set(ExitOrderStatus::Open),
+        sell_trade_id: Set(None),
+        error_message: Set(None),
+        triggered_at: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(db)
+    .await
+}
+
+pub async fn list(
+    db: &DatabaseConnection,
+    user_id: Option<i64>,
+    status: Option<ExitOrderStatus>,
+) -> Result<Vec<exit_orders::Model>, DbErr> {
+    let mut query = exit_orders::Entity::find();
+
+    if let Some(user_id) = user_id {
+        query = query.filter(exit_orders::Column::UserId.eq(user_id));
+    }
+    if let Some(status) = status {
+        query = query.filter(exit_orders::Column::Status.eq(status));
+    }
+
+    query.order_by_desc(exit_orders::Column::CreatedAt).all(db).await
+}
+
+pub async fn cancel(db: &DatabaseConnection, id: Uuid, user_id: i64) -> Result<bool, DbErr> {
+    let result = exit_orders::Entity::update_many()
+        .col_expr(exit_orders::Column::Status, Expr::value(ExitOrderStatus::Cancelled))
+        .col_expr(exit_orders::Column::UpdatedAt, Expr::value(Utc::now()))
+        .filter(exit_orders::Column::Id.eq(id))
+        .filter(exit_orders::Column::UserId.eq(user_id))
+        .filter(exit_orders::Column::Status.eq(ExitOrderStatus::Open))
+        .exec(db)
+        .await?;
+
+    Ok(result.rows_affected == 1)
+}
+
+/// Новый максимум цены и подтянутый за ним уровень трейлинг-стопа
+pub async fn update_trailing(
+    db: &DatabaseConnection,
+    id: Uuid,
+    high_water_price: BigDecimal,
+    trigger_price: BigDecimal,
+) -> Result<(), DbErr> {
+    exit_orders::Entity::update_many()
+        .col_expr(exit_orders::Column::HighWaterPrice, Expr::value(high_water_price))
+        .col_expr(exit_orders::Column::TriggerPrice, Expr::value(trigger_price))
+        .col_expr(exit_orders::Column::UpdatedAt, Expr::value(Utc::now()))
+        .filter(exit_orders::Column::Id.eq(id))
+        .filter(exit_orders::Column::Status.eq(ExitOrderStatus::Open))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+/// Атомарно переводит открытый ордер в исполнение
+pub async fn claim(db: &DatabaseConnection, id: Uuid) -> Result<bool, DbErr> {
+    let now = Utc::now();
+    let result = exit_orders::Entity::update_many()
+        .col_expr(exit_orders::Column::Status, Expr::value(ExitOrderStatus::Triggered))
+        .col_expr(exit_orders::Column::TriggeredAt, Expr::value(now))
+        .col_expr(exit_orders::Column::UpdatedAt, Expr::value(now))
+        .filter(exit_orders::Column::Id.eq(id))
+        .filter(exit_orders::Column::Status.eq(ExitOrderStatus::Open))
+        // Пока позиция продается по другому выходу, итог которого еще не известен,
+        // остальные выходы не срабатывают: иначе позицию продали бы дважды
+        .filter(exit_orders::Column::TradeId.not_in_subquery(
+            Query::select()
+                .column(exit_orders::Column::TradeId)
+                .from(exit_orders::Entity)
+                .and_where(exit_orders::Column::Status.eq(ExitOrderStatus::Triggered))
+                .to_owned(),
+        ))
+        .exec(db)
+        .await?;
+
+    Ok(result.rows_affected == 1)
+}
+
+/// Возвращает захваченный ордер в открытые, если продажа по нему не создавалась
+pub async fn release(db: &DatabaseConnection, id: Uuid) -> Result<(), DbErr> {
+    exit_orders::Entity::update_many()
+        .col_expr(exit_orders::Column::Status, Expr::value(ExitOrderStatus::Open))
+        .col_expr(exit_orders::Column::TriggeredAt, Expr::value(Option::<DateTime<Utc>>::None))
+        .col_expr(exit_orders::Column::UpdatedAt, Expr::value(Utc::now()))
+        .filter(exit_orders::Column::Id.eq(id))
+        .filter(exit_orders::Column::Status.eq(ExitOrderStatus::Triggered))
+        .filter(exit_orders::Column::SellTradeId.is_null())
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+/// Связывает ордер со сделкой продажи до ее отправки
+pub async fn link_trade(db: &DatabaseConnection, id: Uuid, trade_id: Uuid) -> Result<(), DbErr> {
+    exit_orders::ActiveModel {
+        id: Set(id),
+        sell_trade_id: Set(Some(trade_id)),
+        updated_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .update(db)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn finish(
+    db: &DatabaseConnection,
+    id: Uuid,
+    status: ExitOrderStatus,
+    error: Option<String>,
+) -> Result<(), DbErr> {
+    exit_orders::ActiveModel {
+        id: Set(id),
+        status: Set(status),
+        error_message: Set(error),
+        updated_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .update(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Позиция закрыта одним выходом — остальные ордера по той же сделке больше не нужны
+pub async fn cancel_siblings(db: &DatabaseConnection, trade_id: Uuid, filled_id: Uuid) -> Result<Vec<exit_orders::Model>, DbErr> {
+    exit_orders::Entity::update_many()
+        .col_expr(exit_orders::Column::Status, Expr::value(ExitOrderStatus::Cancelled))
+        .col_expr(exit_orders::Column::UpdatedAt, Expr::value(Utc::now()))
+        .filter(exit_orders::Column::TradeId.eq(trade_id))
+        .filter(exit_orders::Column::Id.ne(filled_id))
+        .filter(exit_orders::Column::Status.eq(ExitOrderStatus::Open))
+        .exec_with_returning(db)
+        .await
+}
@@ -0,0 +1,38 @@
+use chrono::Utc;
+use sea_orm::{DatabaseConnection, DbErr, EntityTrait, Set, sea_query::OnConflict};
+
+use crate::entities::fee_preferences::{self, PriorityTier};
+
+pub async fn find(db: &DatabaseConnection, user_id: i64) -> Result<Option<fee_preferences::Model>, DbErr> {
+    fee_preferences::Entity::find_by_id(user_id).one(db).await
+}
+
+/// Сохраняет уровень приоритетной комиссии пользователя и ее потолок
+pub async fn upsert(
+    db: &DatabaseConnection,
+    user_id: i64,
+    tier: PriorityTier,
+    max_lamports: Option<i64>,
+) -> Result<fee_preferences::Model, DbErr> {
+    let now = Utc::now();
+    let model = fee_preferences::ActiveModel {
+        user_id: Set(user_id),
+        tier: Set(tier),
+        max_lamports: Set(max_lamports),
+        created_at: Set(now),
+        updated_at: Set(now),
+    };
+
+    fee_preferences::Entity::insert(model)
+        .on_conflict(
+            OnConflict::column(fee_preferences::Column::UserId)
+                .update_columns([
+                    fee_preferences::Column::Tier,
+                    fee_preferences::Column::MaxLamports,
+                    fee_preferences::Column::UpdatedAt,
+                ])
+                .to_owned(),
+        )
+        .exec_with_returning(db)
+        .await
+}
@@ -0,0 +1,151 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    Set, sea_query::Expr,
+};
+use uuid::Uuid;
+
+use crate::entities::limit_orders::{self, LimitOrderStatus, OrderSide};
+
+/// Данные нового лимитного ордера
+pub struct NewLimitOrder {
+    pub user_id: i64,
+    pub side: OrderSide,
+    pub token_mint: String,
+    pub token_symbol: String,
+    pub amount: BigDecimal,
+    pub target_price: BigDecimal,
+    pub max_slippage_bps: i32,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+pub async fn create(db: &DatabaseConnection, order: NewLimitOrder) -> Result<limit_orders::Model, DbErr> {
+    let now = Utc::now();
+    limit_orders::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(order.user_id),
+        side: Set(order.side),
+        token_mint: Set(order.token_mint),
+        token_symbol: Set(order.token_symbol),
+        amount: Set(order.amount),
+        target_price: Set(order.target_price),
+        max_slippage_bps: Set(order.max_slippage_bps),
+        status: Set(LimitOrderStatus::Open),
+        trade_id: Set(None),
+        error_message: Set(None),
+        expires_at: Set(order.expires_at),
+        triggered_at: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(db)
+    .await
+}
+
+pub async fn list(
+    db: &DatabaseConnection,
+    user_id: Option<i64>,
+    status: Option<LimitOrderStatus>,
+) -> Result<Vec<limit_orders::Model>, DbErr> {
+    let mut query = limit_orders::Entity::find();
+
+    if let Some(user_id) = user_id {
+        query = query.filter(limit_orders::Column::UserId.eq(user_id));
+    }
+    if let Some(status) = status {
+        query = query.filter(limit_orders::Column::Status.eq(status));
+    }
+
+    query.order_by_desc(limit_orders::Column::CreatedAt).all(db).await
+}
+
+/// Отменяет открытый ордер; `user_id` ограничивает отмену ордерами пользователя
+pub async fn cancel(db: &DatabaseConnection, id: Uuid, user_id: Option<i64>) -> Result<bool, DbErr> {
+    let mut query = limit_orders::Entity::update_many()
+        .col_expr(limit_orders::Column::Status, Expr::value(LimitOrderStatus::Cancelled))
+        .col_expr(limit_orders::Column::UpdatedAt, Expr::value(Utc::now()))
+        .filter(limit_orders::Column::Id.eq(id))
+        .filter(limit_orders::Column::Status.eq(LimitOrderStatus::Open));
+
+    if let Some(user_id) = user_id {
+        query = query.filter(limit_orders::Column::UserId.eq(user_id));
+    }
+
+    Ok(query.exec(db).await?.rows_affected == 1)
+}
+
+/// Атомарно переводит открытый ордер в исполнение.
+/// Возвращает `false`, если ордер уже захвачен, отменен или истек.
+pub async fn claim(db: &DatabaseConnection, id: Uuid) -> Result<bool, DbErr> {
+    let now = Utc::now();
+    let result = limit_orders::Entity::update_many()
+        .col_expr(limit_orders::Column::Status, Expr::value(LimitOrderStatus::Triggered))
+        .col_expr(limit_orders::Column::TriggeredAt, Expr::value(now))
+        .col_expr(limit_orders::Column::UpdatedAt, Expr::value(now))
+        .filter(limit_orders::Column::Id.eq(id))
+        .filter(limit_orders::Column::Status.eq(LimitOrderStatus::Open))
+        .exec(db)
+        .await?;
+
+    Ok(result.rows_affected == 1)
+}
+
+/// Возвращает захваченный ордер в открытые, если сделка по нему не создавалась
+pub async fn release(db: &DatabaseConnection, id: Uuid) -> Result<(), DbErr> {
+    limit_orders::Entity::update_many()
+        .col_expr(limit_orders::Column::Status, Expr::value(LimitOrderStatus::Open))
+        .col_expr(limit_orders::Column::TriggeredAt, Expr::value(Option::<DateTime<Utc>>::None))
+        .col_expr(limit_orders::Column::UpdatedAt, Expr::value(Utc::now()))
+        .filter(limit_orders::Column::Id.eq(id))
+        .filter(limit_orders::Column::Status.eq(LimitOrderStatus::Triggered))
+        .filter(limit_orders::Column::TradeId.is_null())
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+/// Связывает ордер со сделкой до ее отправки
+pub async fn link_trade(db: &DatabaseConnection, id: Uuid, trade_id: Uuid) -> Result<(), DbErr> {
+    limit_orders::ActiveModel {
+        id: Set(id),
+        trade_id: Set(Some(trade_id)),
+        updated_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .update(db)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn finish(
+    db: &DatabaseConnection,
+    id: Uuid,
+    status: LimitOrderStatus,
+    error: Option<String>,
+) -> Result<(), DbErr> {
+    limit_orders::ActiveModel {
+        id: Set(id),
+        status: Set(status),
+        error_message: Set(error),
+        updated_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .update(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Закрывает открытые ордера с истекшим сроком и возвращает их
+pub async fn expire_due(db: &DatabaseConnection, now: DateTime<Utc>) -> Result<Vec<limit_orders::Model>, DbErr> {
+    limit_orders::Entity::update_many()
+        .col_expr(limit_orders::Column::Status, Expr::value(LimitOrderStatus::Expired))
+        .col_expr(limit_orders::Column::UpdatedAt, Expr::value(now))
+        .filter(limit_orders::Column::Status.eq(LimitOrderStatus::Open))
+        .filter(limit_orders::Column::ExpiresAt.lte(now))
+        .exec_with_returning(db)
+        .await
+}
@@ -1,2 +1,15 @@
+pub mod alerts;
 pub mod connection;
+pub mod copy_trading;
+pub mod dca;
+pub mod exit_orders;
+pub mod fee_preferences;
+pub mod limit_orders;
+pub mod repositories;
+pub mod sniper;
+pub mod tokens;
+pub mod trade_events;
+pub mod trades;
+pub mod users;
+pub mod wallets;
 pub use connection::DatabaseConnectionPool;
@@ -0,0 +1,442 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{DbErr, TryIntoModel};
+use uuid::Uuid;
+
+use crate::{
+    database::{
+        DatabaseConnectionPool,
+        fee_preferences,
+        trade_events::{self, NewTradeEvent},
+        trades::{self, NewTrade, TradeTotals, TransitionError, activity_from_totals},
+        users::{self, TelegramProfile},
+        wallets,
+    },
+    entities::{
+        fee_preferences::Model as FeePreference,
+        trade_events::{Model as TradeEvent, TradeEventKind},
+        trades::{Model as Trade, TradeStatus},
+        users::Model as User,
+        wallets::Model as Wallet,
+    },
+    solana::{
+        amount::TokenAmount,
+        constants::{SOL_DECIMALS, SOL_MINT},
+        simulation::SwapSimulation,
+        trader::TradeActivity,
+    },
+};
+
+#[async_trait]
+pub trait UserRepository: Send + Sync {
+    async fn find(&self, id: i64) -> Result<Option<User>, DbErr>;
+
+    /// Создает пользователя или обновляет профиль и время последней активности
+    async fn upsert(&self, profile: TelegramProfile) -> Result<User, DbErr>;
+
+    /// Выбранный пользователем уровень приоритетной комиссии; `None` — по умолчанию
+    async fn fee_preference(&self, user_id: i64) -> Result<Option<FeePreference>, DbErr>;
+}
+
+#[async_trait]
+pub trait WalletRepository: Send + Sync {
+    /// Активный кошелек пользователя по умолчанию
+    async fn default_wallet(&self, user_id: i64) -> Result<Option<Wallet>, DbErr>;
+}
+
+#[async_trait]
+pub trait TradeRepository: Send + Sync {
+    async fn find(&self, id: Uuid) -> Result<Option<Trade>, DbErr>;
+
+    /// Записывает подписанную сделку до отправки в статусе `Pending`
+    async fn create_pending(&self, trade: NewTrade<'_>) -> Result<Trade, DbErr>;
+
+    /// Переводит сделку в новый статус и записывает событие, если ее не меняли после чтения `trade`.
+    /// `None` — сделку успели изменить параллельно.
+    async fn transition(&self, trade: &Trade, status: TradeStatus, event: NewTradeEvent)
+        -> Result<Option<Trade>, TransitionError>;
+
+    /// Перечитывает сделку и переводит ее в новый статус.
+    /// `None` — сделки нет или ее изменили между чтением и переходом.
+    async fn transition_by_id(&self, id: Uuid, status: TradeStatus, event: NewTradeEvent)
+        -> Result<Option<Trade>, TransitionError>
+    {
+        match self.find(id).await? {
+            Some(trade) => self.transition(&trade, status, event).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Записывает событие без смены статуса сделки
+    async fn record_event(&self, trade_id: Uuid, status: TradeStatus, event: NewTradeEvent) -> Result<(), DbErr>;
+
+    /// Сохраняет результат симуляции транзакции сделки
+    async fn set_simulation(&self, id: Uuid, simulation: &SwapSimulation) -> Result<(), DbErr>;
+
+    /// Связывает сделку с расписанием DCA до ее отправки
+    async fn link_dca_schedule(&self, id: Uuid, schedule_id: Uuid) -> Result<(), DbErr>;
+
+    /// Покупки DCA, итог которых еще не записан
+    async fn unsettled_dca(&self) -> Result<Vec<Trade>, DbErr>;
+
+    /// История сделки от первого события к последнему
+    async fn timeline(&self, trade_id: Uuid) -> Result<Vec<TradeEvent>, DbErr>;
+
+    /// Высота блока, после которой истекает blockhash отправленной транзакции, из события `Sent`
+    async fn last_valid_block_height(&self, trade_id: Uuid) -> Result<Option<u64>, DbErr>;
+
+    /// Сделки пользователя, созданные в `[from, to)`, без неудачных и отмененных
+    async fn totals(&self, user_id: i64, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<TradeTotals, DbErr>;
+
+    /// Активность за последний час и сутки для лимитов торговли
+    async fn activity(&self, user_id: i64, now: DateTime<Utc>) -> Result<TradeActivity, DbErr> {
+        let last_hour = self.totals(user_id, now - Duration::hours(1), now).await?;
+        let last_day = self.totals(user_id, now - Duration::days(1), now).await?;
+        Ok(activity_from_totals(last_hour, last_day))
+    }
+}
+
+/// Репозитории, которые получают обработчики бота
+#[derive(Clone)]
+pub struct Repositories {
+    pub users: Arc<dyn UserRepository>,
+    pub wallets: Arc<dyn WalletRepository>,
+    pub trades: Arc<dyn TradeRepository>,
+}
+
+impl Repositories {
+    pub fn postgres(database: Arc<DatabaseConnectionPool>) -> Self {
+        Self {
+            users: Arc::new(PostgresUserRepository::new(database.clone())),
+            wallets: Arc::new(PostgresWalletRepository::new(database.clone())),
+            trades: Arc::new(PostgresTradeRepository::new(database)),
+        }
+    }
+}
+
+pub struct PostgresUserRepository {
+    database: Arc<DatabaseConnectionPool>,
+}
+
+impl PostgresUserRepository {
+    pub fn new(database: Arc<DatabaseConnectionPool>) -> Self {
+        Self { database }
+    }
+}
+
+#[async_trait]
+impl UserRepository for PostgresUserRepository {
+    async fn find(&self, id: i64) -> Result<Option<User>, DbErr> {
+        users::find(self.database.get_connection(), id).await
+    }
+
+    async fn upsert(&self, profile: TelegramProfile) -> Result<User, DbErr> {
+        users::upsert(self.database.get_connection(), profile).await
+    }
+
+    async fn fee_preference(&self, user_id: i64) -> Result<Option<FeePreference>, DbErr> {
+        fee_preferences::find(self.database.get_connection(), user_id).await
+    }
+}
+
+pub struct PostgresWalletRepository {
+    database: Arc<DatabaseConnectionPool>,
+}
+
+impl PostgresWalletRepository {
+    pub fn new(database: Arc<DatabaseConnectionPool>) -> Self {
+        Self { database }
+    }
+}
+
+#[async_trait]
+impl WalletRepository for PostgresWalletRepository {
+    async fn default_wallet(&self, user_id: i64) -> Result<Option<Wallet>, DbErr> {
+        wallets::find_default(self.database.get_connection(), user_id).await
+    }
+}
+
+pub struct PostgresTradeRepository {
+    database: Arc<DatabaseConnectionPool>,
+}
+
+impl PostgresTradeRepository {
+    pub fn new(database: Arc<DatabaseConnectionPool>) -> Self {
+        Self { database }
+    }
+}
+
+#[async_trait]
+impl TradeRepository for PostgresTradeRepository {
+    async fn find(&self, id: Uuid) -> Result<Option<Trade>, DbErr> {
+        trades::find(self.database.get_connection(), id).await
+    }
+
+    async fn create_pending(&self, trade: NewTrade<'_>) -> Result<Trade, DbErr> {
+        trades::insert_pending(self.database.get_connection(), trade).await
+    }
+
+    async fn transition(&self, trade: &Trade, status: TradeStatus, event: NewTradeEvent)
+        -> Result<Option<Trade>, TransitionError>
+    {
+        trades::transition_status(self.database.get_connection(), trade, status, event).await
+    }
+
+    async fn record_event(&self, trade_id: Uuid, status: TradeStatus, event: NewTradeEvent) -> Result<(), DbErr> {
+        trade_events::record(self.database.get_connection(), trade_id, status, event).await.map(drop)
+    }
+
+    async fn set_simulation(&self, id: Uuid, simulation: &SwapSimulation) -> Result<(), DbErr> {
+        trades::set_simulation(self.database.get_connection(), id, simulation).await
+    }
+
+    async fn link_dca_schedule(&self, id: Uuid, schedule_id: Uuid) -> Result<(), DbErr> {
+        trades::link_dca_schedule(self.database.get_connection(), id, schedule_id).await
+    }
+
+    async fn unsettled_dca(&self) -> Result<Vec<Trade>, DbErr> {
+        trades::unsettled_dca(self.database.get_connection()).await
+    }
+
+    async fn timeline(&self, trade_id: Uuid) -> Result<Vec<TradeEvent>, DbErr> {
+        trade_events::timeline(self.database.get_connection(), trade_id).await
+    }
+
+    async fn last_valid_block_height(&self, trade_id: Uuid) -> Result<Option<u64>, DbErr> {
+        trade_events::last_valid_block_height(self.database.get_connection(), trade_id).await
+    }
+
+    async fn totals(&self, user_id: i64, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<TradeTotals, DbErr> {
+        trades::totals(self.database.get_connection(), user_id, from, to).await
+    }
+}
+
+/// Пользователи в памяти для тестов обработчиков
+#[derive(Default)]
+pub struct FakeUserRepository {
+    users: Mutex<HashMap<i64, User>>,
+    fee_preferences: Mutex<HashMap<i64, FeePreference>>,
+}
+
+impl FakeUserRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_fee_preference(&self, preference: FeePreference) {
+        self.fee_preferences.lock().unwrap().insert(preference.user_id, preference);
+    }
+}
+
+#[async_trait]
+impl UserRepository for FakeUserRepository {
+    async fn find(&self, id: i64) -> Result<Option<User>, DbErr> {
+        Ok(self.users.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn upsert(&self, profile: TelegramProfile) -> Result<User, DbErr> {
+        let now = Utc::now();
+        let mut users = self.users.lock().unwrap();
+        let user = users.entry(profile.id).or_insert_with(|| User {
+            id: profile.id,
+            telegram_username: None,
+            first_name: String::new(),
+            last_name: None,
+            language_code: None,
+            is_premium: None,
+            is_admin: false,
+            is_active: true,
+            daily_trade_limit: Decimal::ONE_HUNDRED,
+            total_trades: 0,
+            total_volume_sol: Decimal::ZERO,
+            created_at: now,
+            updated_at: now,
+            last_active_at: now,
+        });
+        user.telegram_username = profile.username;
+        user.first_name = profile.first_name;
+        user.last_name = profile.last_name;
+        user.language_code = profile.language_code;
+        user.is_premium = Some(profile.is_premium);
+        user.updated_at = now;
+        user.last_active_at = now;
+
+        Ok(user.clone())
+    }
+
+    async fn fee_preference(&self, user_id: i64) -> Result<Option<FeePreference>, DbErr> {
+        Ok(self.fee_preferences.lock().unwrap().get(&user_id).cloned())
+    }
+}
+
+/// Кошельки в памяти для тестов обработчиков
+#[derive(Default)]
+pub struct FakeWalletRepository {
+    wallets: Mutex<Vec<Wallet>>,
+}
+
+impl FakeWalletRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&self, wallet: Wallet) {
+        self.wallets.lock().unwrap().push(wallet);
+    }
+}
+
+#[async_trait]
+impl WalletRepository for FakeWalletRepository {
+    async fn default_wallet(&self, user_id: i64) -> Result<Option<Wallet>, DbErr> {
+        Ok(self.wallets
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|wallet| wallet.user_id == user_id && wallet.is_default && wallet.is_active)
+            .cloned())
+    }
+}
+
+/// Сделки в памяти для тестов: те же правила, что у запросов к Postgres
+#[derive(Default)]
+pub struct FakeTradeRepository {
+    trades: Mutex<HashMap<Uuid, Trade>>,
+    events: Mutex<Vec<TradeEvent>>,
+}
+
+impl FakeTradeRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Сделка с произвольными полями, например созданная в прошлом
+    pub fn insert(&self, trade: Trade) {
+        self.trades.lock().unwrap().insert(trade.id, trade);
+    }
+
+    fn record(&self, trade_id: Uuid, status: TradeStatus, event: NewTradeEvent) -> Result<(), DbErr> {
+        let event = trade_events::new_event_model(trade_id, status, event).try_into_model()?;
+        self.events.lock().unwrap().push(event);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TradeRepository for FakeTradeRepository {
+    async fn find(&self, id: Uuid) -> Result<Option<Trade>, DbErr> {
+        Ok(self.trades.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn create_pending(&self, trade: NewTrade<'_>) -> Result<Trade, DbErr> {
+        let events = trades::pending_events(&trade);
+        let trade = trades::new_trade_model(trade, TradeStatus::Pending).try_into_model()?;
+        self.insert(trade.clone());
+        for event in events {
+            self.record(trade.id, TradeStatus::Pending, event)?;
+        }
+        Ok(trade)
+    }
+
+    async fn transition(&self, trade: &Trade, status: TradeStatus, event: NewTradeEvent)
+        -> Result<Option<Trade>, TransitionError>
+    {
+        if !trade.status.can_transition_to(&status) {
+            return Err(TransitionError::Illegal { from: trade.status.clone(), to: status });
+        }
+
+        let mut trades = self.trades.lock().unwrap();
+        let Some(stored) = trades
+            .get_mut(&trade.id)
+            .filter(|stored| stored.status == trade.status && stored.updated_at == trade.updated_at)
+        else {
+            return Ok(None);
+        };
+
+        // Время изменения должно отличаться от прочитанного, иначе повторный переход не заметит гонку
+        let now = Utc::now().max(stored.updated_at + Duration::microseconds(1));
+        if status == TradeStatus::Completed {
+            stored.completed_at = Some(now);
+        }
+        if event.error.is_some() {
+            stored.error_message = event.error.clone();
+        }
+        stored.status = status.clone();
+        stored.updated_at = now;
+
+        self.record(trade.id, status, event)?;
+        Ok(Some(stored.clone()))
+    }
+
+    async fn record_event(&self, trade_id: Uuid, status: TradeStatus, event: NewTradeEvent) -> Result<(), DbErr> {
+        self.record(trade_id, status, event)
+    }
+
+    async fn set_simulation(&self, id: Uuid, simulation: &SwapSimulation) -> Result<(), DbErr> {
+        if let Some(trade) = self.trades.lock().unwrap().get_mut(&id) {
+            trade.simulation = serde_json::to_value(simulation).ok();
+            trade.updated_at = Utc::now();
+        }
+        Ok(())
+    }
+
+    async fn link_dca_schedule(&self, id: Uuid, schedule_id: Uuid) -> Result<(), DbErr> {
+        match self.trades.lock().unwrap().get_mut(&id) {
+            Some(trade) => {
+                trade.dca_schedule_id = Some(schedule_id);
+                trade.updated_at = Utc::now();
+                Ok(())
+            }
+            None => Err(DbErr::RecordNotUpdated),
+        }
+    }
+
+    async fn unsettled_dca(&self) -> Result<Vec<Trade>, DbErr> {
+        Ok(self.trades
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|trade| trade.dca_schedule_id.is_some())
+            .filter(|trade| matches!(trade.status, TradeStatus::Pending | TradeStatus::Executing))
+            .cloned()
+            .collect())
+    }
+
+    async fn timeline(&self, trade_id: Uuid) -> Result<Vec<TradeEvent>, DbErr> {
+        Ok(self.events.lock().unwrap().iter().filter(|event| event.trade_id == trade_id).cloned().collect())
+    }
+
+    async fn last_valid_block_height(&self, trade_id: Uuid) -> Result<Option<u64>, DbErr> {
+        Ok(self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .find(|event| event.trade_id == trade_id && event.kind == TradeEventKind::Sent)
+            .and_then(|event| event.details.as_ref())
+            .and_then(|details| details["last_valid_block_height"].as_u64()))
+    }
+
+    async fn totals(&self, user_id: i64, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<TradeTotals, DbErr> {
+        let trades = self.trades.lock().unwrap();
+        let counted: Vec<&Trade> = trades
+            .values()
+            .filter(|trade| trade.user_id == user_id)
+            .filter(|trade| !matches!(trade.status, TradeStatus::Failed | TradeStatus::Cancelled))
+            .filter(|trade| trade.created_at >= from && trade.created_at < to)
+            .collect();
+
+        let sol_spent = counted
+            .iter()
+            .filter(|trade| trade.input_mint == SOL_MINT)
+            .filter_map(|trade| TokenAmount::from_big_decimal(&trade.input_amount, SOL_DECIMALS).ok())
+            .fold(TokenAmount::zero(SOL_DECIMALS), |total, amount| {
+                total.checked_add(amount).unwrap_or(TokenAmount::sol(u64::MAX))
+            });
+
+        Ok(TradeTotals { trades: counted.len() as u64, sol_spent })
+    }
+}
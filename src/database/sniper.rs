@@ -0,0 +1,177 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set, prelude::Json, sea_query::OnConflict,
+};
+use uuid::Uuid;
+
+use crate::entities::{
+    sniper_decisions::{self, Reasons, SniperDecisionStatus},
+    sniper_subscriptions,
+};
+
+/// Включает автопокупки для пользователя или меняет их параметры
+pub async fn upsert_subscription(
+    db: &DatabaseConnection,
+    user_id: i64,
+    amount_sol: BigDecimal,
+    max_slippage_bps: i32,
+) -> Result<sniper_subscriptions::Model, DbErr> {
+    let now = Utc::now();
+    let model = sniper_subscriptions::ActiveModel {
+        user_id: Set(user_id),
+        amount_sol: Set(amount_sol),
+        max_slippage_bps: Set(max_slippage_bps),
+        enabled: Set(true),
+        created_at: Set(now),
+        updated_at: Set(now),
+    };
+
+    sniper_subscriptions::Entity::insert(model)
+        .on_conflict(
+            OnConflict::column(sniper_subscriptions::Column::UserId)
+                .update_columns([
+                    sniper_subscriptions::Column::AmountSol,
+                    sniper_subscriptions::Column::MaxSlippageBps,
+                    sniper_subscriptions::Column::Enabled,
+                    sniper_subscriptions::Column::UpdatedAt,
+                ])
+                .to_owned(),
+        )
+        .exec_with_returning(db)
+        .await
+}
+
+pub async fn disable_subscription(db: &DatabaseConnection, user_id: i64) -> Result<bool, DbErr> {
+    let result = sniper_subscriptions::Entity::update_many()
+        .col_expr(sniper_subscriptions::Column::Enabled, false.into())
+        .col_expr(sniper_subscriptions::Column::UpdatedAt, Utc::now().into())
+        .filter(sniper_subscriptions::Column::UserId.eq(user_id))
+        .filter(sniper_subscriptions::Column::Enabled.eq(true))
+        .exec(db)
+        .await?;
+
+    Ok(result.rows_affected == 1)
+}
+
+pub async fn find_subscription(db: &DatabaseConnection, user_id: i64) -> Result<Option<sniper_subscriptions::Model>, DbErr> {
+    sniper_subscriptions::Entity::find_by_id(user_id).one(db).await
+}
+
+pub async fn enabled_subscriptions(db: &DatabaseConnection) -> Result<Vec<sniper_subscriptions::Model>, DbErr> {
+    sniper_subscriptions::Entity::find()
+        .filter(sniper_subscriptions::Column::Enabled.eq(true))
+        .all(db)
+        .await
+}
+
+/// Данные записи о решении снайпера
+pub struct NewSniperDecision {
+    pub user_id: Option<i64>,
+    pub token_mint: String,
+    pub token_symbol: String,
+    pub pool_address: Option<String>,
+    pub pool_created_at: Option<DateTime<Utc>>,
+    pub status: SniperDecisionStatus,
+    pub reasons: Vec<String>,
+    pub report: Option<Json>,
+}
+
+pub async fn insert_decision(db: &DatabaseConnection, decision: NewSniperDecision) -> Result<sniper_decisions::Model, DbErr> {
+    let now = Utc::now();
+    sniper_decisions::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(decision.user_id),
+        token_mint: Set(decision.token_mint),
+        token_symbol: Set(decision.token_symbol),
+        pool_address: Set(decision.pool_address),
+        pool_created_at: Set(decision.pool_created_at),
+        status: Set(decision.status),
+        reasons: Set(Reasons(decision.reasons)),
+        report: Set(decision.report),
+        trade_id: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(db)
+    .await
+}
+
+/// Есть ли итог проверок по токену: после перезапуска запуск не проверяется повторно
+pub async fn launch_logged(db: &DatabaseConnection, token_mint: &str) -> Result<bool, DbErr> {
+    let count = sniper_decisions::Entity::find()
+        .filter(sniper_decisions::Column::TokenMint.eq(token_mint))
+        .filter(sniper_decisions::Column::UserId.is_null())
+        .count(db)
+        .await?;
+
+    Ok(count > 0)
+}
+
+/// Связывает покупку со сделкой до ее отправки
+pub async fn link_decision_trade(db: &DatabaseConnection, id: Uuid, trade_id: Uuid) -> Result<(), DbErr> {
+    sniper_decisions::ActiveModel {
+        id: Set(id),
+        trade_id: Set(Some(trade_id)),
+        updated_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .update(db)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn finish_decision(
+    db: &DatabaseConnection,
+    id: Uuid,
+    status: SniperDecisionStatus,
+    reasons: Vec<String>,
+) -> Result<(), DbErr> {
+    sniper_decisions::ActiveModel {
+        id: Set(id),
+        status: Set(status),
+        reasons: Set(Reasons(reasons)),
+        updated_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .update(db)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn decisions_with_status(
+    db: &DatabaseConnection,
+    status: SniperDecisionStatus,
+) -> Result<Vec<sniper_decisions::Model>, DbErr> {
+    sniper_decisions::Entity::find()
+        .filter(sniper_decisions::Column::Status.eq(status))
+        .order_by_asc(sniper_decisions::Column::CreatedAt)
+        .all(db)
+        .await
+}
+
+/// Последние решения, новые первыми
+pub async fn recent_decisions(
+    db: &DatabaseConnection,
+    user_id: Option<i64>,
+    status: Option<SniperDecisionStatus>,
+    limit: u64,
+) -> Result<Vec<sniper_decisions::Model>, DbErr> {
+    let mut query = sniper_decisions::Entity::find();
+
+    if let Some(user_id) = user_id {
+        query = query.filter(sniper_decisions::Column::UserId.eq(user_id));
+    }
+    if let Some(status) = status {
+        query = query.filter(sniper_decisions::Column::Status.eq(status));
+    }
+
+    query
+        .order_by_desc(sniper_decisions::Column::CreatedAt)
+        .limit(limit)
+        .all(db)
+        .await
+}
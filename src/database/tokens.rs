@@ -0,0 +1,46 @@
+use chrono::Utc;
+use sea_orm::{DatabaseConnection, DbErr, EntityTrait, Set, sea_query::OnConflict};
+
+use crate::entities::tokens;
+
+/// Метаданные mint, прочитанные из сети
+pub struct NewToken {
+    pub mint: String,
+    pub symbol: Option<String>,
+    pub name: Option<String>,
+    pub decimals: i16,
+    pub token_2022: bool,
+}
+
+pub async fn find(db: &DatabaseConnection, mint: &str) -> Result<Option<tokens::Model>, DbErr> {
+    tokens::Entity::find_by_id(mint.to_string()).one(db).await
+}
+
+/// Сохраняет метаданные mint, перезаписывая прочитанные ранее
+pub async fn upsert(db: &DatabaseConnection, token: NewToken) -> Result<tokens::Model, DbErr> {
+    let now = Utc::now();
+    let model = tokens::ActiveModel {
+        mint: Set(token.mint),
+        symbol: Set(token.symbol),
+        name: Set(token.name),
+        decimals: Set(token.decimals),
+        token_2022: Set(token.token_2022),
+        created_at: Set(now),
+        updated_at: Set(now),
+    };
+
+    tokens::Entity::insert(model)
+        .on_conflict(
+            OnConflict::column(tokens::Column::Mint)
+                .update_columns([
+                    tokens::Column::Symbol,
+                    tokens::Column::Name,
+                    tokens::Column::Decimals,
+                    tokens::Column::Token2022,
+                    tokens::Column::UpdatedAt,
+                ])
+                .to_owned(),
+        )
+        .exec_with_returning(db)
+        .await
+}
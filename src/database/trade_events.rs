@@ -0,0 +1,93 @@
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::entities::{
+    trade_events::{self, TradeEventKind},
+    trades::TradeStatus,
+};
+
+/// Событие для записи в историю сделки
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewTradeEvent {
+    pub kind: TradeEventKind,
+    pub slot: Option<u64>,
+    pub details: Option<Value>,
+    pub error: Option<String>,
+}
+
+impl NewTradeEvent {
+    pub fn new(kind: TradeEventKind) -> Self {
+        Self { kind, slot: None, details: None, error: None }
+    }
+
+    /// Ошибка исполнения; ее текст попадает и в `trades.error_message`
+    pub fn error(message: impl Into<String>) -> Self {
+        Self { error: Some(message.into()), ..Self::new(TradeEventKind::Error) }
+    }
+
+    /// Сделка снята до отправки с указанной причиной
+    pub fn cancelled(reason: impl Into<String>) -> Self {
+        Self { error: Some(reason.into()), ..Self::new(TradeEventKind::Cancelled) }
+    }
+
+    pub fn with_slot(mut self, slot: u64) -> Self {
+        self.slot = Some(slot);
+        self
+    }
+
+    pub fn with_details(mut self, details: Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+}
+
+/// Запись события сделки, которая после него находится в статусе `status`
+pub(crate) fn new_event_model(trade_id: Uuid, status: TradeStatus, event: NewTradeEvent) -> trade_events::ActiveModel {
+    trade_events::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        trade_id: Set(trade_id),
+        kind: Set(event.kind),
+        status: Set(status),
+        slot: Set(event.slot.map(|slot| slot as i64)),
+        details: Set(event.details),
+        error: Set(event.error),
+        created_at: Set(Utc::now()),
+    }
+}
+
+/// Записывает событие без смены статуса сделки
+pub async fn record<C: ConnectionTrait>(
+    db: &C,
+    trade_id: Uuid,
+    status: TradeStatus,
+    event: NewTradeEvent,
+) -> Result<trade_events::Model, DbErr> {
+    new_event_model(trade_id, status, event).insert(db).await
+}
+
+/// Высота блока, после которой истекает blockhash отправленной транзакции, из события `Sent`
+pub async fn last_valid_block_height(db: &DatabaseConnection, trade_id: Uuid) -> Result<Option<u64>, DbErr> {
+    let sent = trade_events::Entity::find()
+        .filter(trade_events::Column::TradeId.eq(trade_id))
+        .filter(trade_events::Column::Kind.eq(TradeEventKind::Sent))
+        .order_by_desc(trade_events::Column::CreatedAt)
+        .one(db)
+        .await?;
+
+    Ok(sent
+        .and_then(|event| event.details)
+        .and_then(|details| details["last_valid_block_height"].as_u64()))
+}
+
+/// История сделки от первого события к последнему
+pub async fn timeline(db: &DatabaseConnection, trade_id: Uuid) -> Result<Vec<trade_events::Model>, DbErr> {
+    trade_events::Entity::find()
+        .filter(trade_events::Column::TradeId.eq(trade_id))
+        .order_by_asc(trade_events::Column::CreatedAt)
+        .all(db)
+        .await
+}
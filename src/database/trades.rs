@@ -0,0 +1,332 @@
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, FromQueryResult,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, sea_query::Expr,
+};
+use serde::Serialize;
+use serde_json::json;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    database::{
+        connection::in_transaction,
+        trade_events::{self, NewTradeEvent},
+    },
+    entities::{
+        fee_preferences::PriorityTier,
+        trade_events::TradeEventKind,
+        trades::{self, TradeStatus, TradeType},
+    },
+    jupiter::{QuoteResponseV6, SwapMode},
+    solana::{
+        amount::TokenAmount,
+        constants::{SOL_DECIMALS, SOL_MINT},
+        simulation::SwapSimulation,
+        trader::TradeActivity,
+    },
+};
+
+/// Данные для записи сделки, которая подписана и будет отправлена
+pub struct NewTrade<'a> {
+    pub user_id: i64,
+    pub trade_type: TradeType,
+    pub quote: &'a QuoteResponseV6,
+    pub input_symbol: String,
+    pub output_symbol: String,
+    pub input_decimals: u8,
+    pub output_decimals: u8,
+    pub transaction_signature: String,
+    pub jupiter_quote_id: Option<String>,
+    pub priority_fee_lamports: u64,
+    pub priority_tier: PriorityTier,
+}
+
+/// Запись новой сделки в заданном статусе
+pub(crate) fn new_trade_model(trade: NewTrade<'_>, status: TradeStatus) -> trades::ActiveModel {
+    let quote = trade.quote;
+    let input_amount = TokenAmount::new(quote.in_amount, trade.input_decimals).to_big_decimal();
+    let output_amount = TokenAmount::new(quote.out_amount, trade.output_decimals).to_big_decimal();
+    let price = if output_amount.is_zero() {
+        BigDecimal::zero()
+    } else {
+        (&input_amount / &output_amount).with_scale(9)
+    };
+
+    // Комиссия удерживается во входном токене для ExactOut и в выходном для ExactIn
+    let (fee_mint, fee_decimals) = match quote.swap_mode {
+        SwapMode::ExactIn => (quote.output_mint, trade.output_decimals),
+        SwapMode::ExactOut => (quote.input_mint, trade.input_decimals),
+    };
+    let platform_fee = quote.platform_fee.as_ref().filter(|fee| fee.amount > 0);
+
+    let now = Utc::now();
+    trades::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(trade.user_id),
+        trade_type: Set(trade.trade_type),
+        input_mint: Set(quote.input_mint.to_string()),
+        output_mint: Set(quote.output_mint.to_string()),
+        input_amount: Set(input_amount),
+        output_amount: Set(output_amount),
+        input_symbol: Set(trade.input_symbol),
+        output_symbol: Set(trade.output_symbol),
+        price: Set(price),
+        slippage_bps: Set(quote.slippage_bps as i32),
+        transaction_signature: Set(trade.transaction_signature),
+        status: Set(status),
+        error_message: Set(None),
+        jupiter_quote_id: Set(trade.jupiter_quote_id),
+        platform_fee_bps: Set(platform_fee.map(|fee| fee.fee_bps as i32)),
+        platform_fee_amount: Set(platform_fee.map(|fee| TokenAmount::new(fee.amount, fee_decimals).to_big_decimal())),
+        platform_fee_mint: Set(platform_fee.map(|_| fee_mint.to_string())),
+        dca_schedule_id: Set(None),
+        simulation: Set(None),
+        priority_fee_lamports: Set(Some(trade.priority_fee_lamports as i64)),
+        priority_fee_tier: Set(Some(trade.priority_tier)),
+        created_at: Set(now),
+        updated_at: Set(now),
+        completed_at: Set(None),
+    }
+}
+
+/// События новой сделки: котировка получена и транзакция подписана
+pub(crate) fn pending_events(trade: &NewTrade<'_>) -> Vec<NewTradeEvent> {
+    let quote = trade.quote;
+    vec![
+        NewTradeEvent::new(TradeEventKind::QuoteReceived).with_details(json!({
+            "in_amount": quote.in_amount.to_string(),
+            "out_amount": quote.out_amount.to_string(),
+            "slippage_bps": quote.slippage_bps,
+            "price_impact_pct": quote.price_impact_pct,
+            "context_slot": quote.context_slot,
+            "jupiter_quote_id": trade.jupiter_quote_id,
+        })),
+        NewTradeEvent::new(TradeEventKind::Signed).with_details(json!({
+            "signature": trade.transaction_signature,
+            "priority_fee_lamports": trade.priority_fee_lamports,
+            "priority_tier": trade.priority_tier,
+        })),
+    ]
+}
+
+/// Подписанная, но еще не отправленная сделка: статус `Pending` и первые события истории
+pub async fn insert_pending(db: &DatabaseConnection, trade: NewTrade<'_>) -> Result<trades::Model, DbErr> {
+    let events = pending_events(&trade);
+    let model = new_trade_model(trade, TradeStatus::Pending);
+
+    in_transaction(db, |txn| Box::pin(async move {
+        let trade = model.insert(txn).await?;
+        for event in events {
+            trade_events::record(txn, trade.id, TradeStatus::Pending, event).await?;
+        }
+        Ok(trade)
+    }))
+    .await
+}
+
+#[derive(Debug, Error)]
+pub enum TransitionError {
+    #[error("недопустимый переход сделки из {from:?} в {to:?}")]
+    Illegal { from: TradeStatus, to: TradeStatus },
+    #[error(transparent)]
+    Database(#[from] DbErr),
+}
+
+/// Переводит сделку в новый статус и записывает событие перехода.
+/// Переход проверяется по статусу прочитанной `trade` и применяется, только если сделку
+/// не меняли после чтения; `None` — ее успели изменить параллельно или ее нет.
+/// `completed_at` выставляется при переходе в `Completed`, текст ошибки события —
+/// в `error_message`.
+pub async fn transition_status(
+    db: &DatabaseConnection,
+    trade: &trades::Model,
+    status: TradeStatus,
+    event: NewTradeEvent,
+) -> Result<Option<trades::Model>, TransitionError> {
+    if !trade.status.can_transition_to(&status) {
+        return Err(TransitionError::Illegal { from: trade.status.clone(), to: status });
+    }
+
+    let now = Utc::now();
+    let mut query = trades::Entity::update_many()
+        .col_expr(trades::Column::Status, Expr::value(status.clone()))
+        .col_expr(trades::Column::UpdatedAt, Expr::value(now))
+        .filter(trades::Column::Id.eq(trade.id))
+        .filter(trades::Column::Status.eq(trade.status.clone()))
+        .filter(trades::Column::UpdatedAt.eq(trade.updated_at));
+    if let Some(error) = &event.error {
+        query = query.col_expr(trades::Column::ErrorMessage, Expr::value(error.clone()));
+    }
+    if status == TradeStatus::Completed {
+        query = query.col_expr(trades::Column::CompletedAt, Expr::value(now));
+    }
+
+    in_transaction(db, |txn| Box::pin(async move {
+        let Some(updated) = query.exec_with_returning(txn).await?.into_iter().next() else {
+            return Ok(None);
+        };
+        trade_events::record(txn, updated.id, status, event).await?;
+        Ok(Some(updated))
+    }))
+    .await
+}
+
+/// Перечитывает сделку и переводит ее в новый статус.
+/// `None` — сделки нет или ее изменили между чтением и переходом.
+pub async fn transition_by_id(
+    db: &DatabaseConnection,
+    id: Uuid,
+    status: TradeStatus,
+    event: NewTradeEvent,
+) -> Result<Option<trades::Model>, TransitionError> {
+    match find(db, id).await? {
+        Some(trade) => transition_status(db, &trade, status, event).await,
+        None => Ok(None),
+    }
+}
+
+pub async fn find(db: &DatabaseConnection, id: Uuid) -> Result<Option<trades::Model>, DbErr> {
+    trades::Entity::find_by_id(id).one(db).await
+}
+
+/// Последние сделки пользователя, новые первыми
+pub async fn recent_for_user(db: &DatabaseConnection, user_id: i64, limit: u64) -> Result<Vec<trades::Model>, DbErr> {
+    trades::Entity::find()
+        .filter(trades::Column::UserId.eq(user_id))
+        .order_by_desc(trades::Column::CreatedAt)
+        .limit(limit)
+        .all(db)
+        .await
+}
+
+/// Число сделок и потраченный SOL за период
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TradeTotals {
+    pub trades: u64,
+    pub sol_spent: TokenAmount,
+}
+
+/// Сделки пользователя, созданные в `[from, to)`, и потраченный в них SOL.
+/// Неудачные и отмененные сделки не учитываются.
+pub async fn totals(
+    db: &DatabaseConnection,
+    user_id: i64,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<TradeTotals, DbErr> {
+    let counted = || {
+        trades::Entity::find()
+            .filter(trades::Column::UserId.eq(user_id))
+            .filter(trades::Column::Status.is_not_in([TradeStatus::Failed, TradeStatus::Cancelled]))
+            .filter(trades::Column::CreatedAt.gte(from))
+            .filter(trades::Column::CreatedAt.lt(to))
+    };
+
+    let trades = counted().count(db).await?;
+    let sol_spent: Option<BigDecimal> = counted()
+        .filter(trades::Column::InputMint.eq(SOL_MINT))
+        .select_only()
+        .column_as(trades::Column::InputAmount.sum(), "sol_spent")
+        .into_tuple()
+        .one(db)
+        .await?
+        .flatten();
+
+    Ok(TradeTotals {
+        trades,
+        sol_spent: sol_spent
+            .and_then(|amount| TokenAmount::from_big_decimal(&amount, SOL_DECIMALS).ok())
+            .unwrap_or(TokenAmount::zero(SOL_DECIMALS)),
+    })
+}
+
+/// Активность для лимитов из итогов за последний час и сутки
+pub fn activity_from_totals(last_hour: TradeTotals, last_day: TradeTotals) -> TradeActivity {
+    TradeActivity {
+        trades_last_hour: last_hour.trades,
+        trades_last_day: last_day.trades,
+        sol_spent_last_day: last_day.sol_spent,
+    }
+}
+
+/// Сделки пользователя за последний час и сутки и потраченный за сутки SOL
+pub async fn activity(db: &DatabaseConnection, user_id: i64, now: DateTime<Utc>) -> Result<TradeActivity, DbErr> {
+    let last_hour = totals(db, user_id, now - Duration::hours(1), now).await?;
+    let last_day = totals(db, user_id, now - Duration::days(1), now).await?;
+    Ok(activity_from_totals(last_hour, last_day))
+}
+
+/// Связывает сделку с расписанием DCA до ее отправки
+pub async fn link_dca_schedule(db: &DatabaseConnection, id: Uuid, schedule_id: Uuid) -> Result<(), DbErr> {
+    trades::ActiveModel {
+        id: Set(id),
+        dca_schedule_id: Set(Some(schedule_id)),
+        updated_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .update(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Покупки DCA, итог которых еще не записан
+pub async fn unsettled_dca(db: &DatabaseConnection) -> Result<Vec<trades::Model>, DbErr> {
+    trades::Entity::find()
+        .filter(trades::Column::DcaScheduleId.is_not_null())
+        .filter(trades::Column::Status.is_in([TradeStatus::Pending, TradeStatus::Executing]))
+        .all(db)
+        .await
+}
+
+/// Сохраняет результат симуляции транзакции сделки
+pub async fn set_simulation(db: &DatabaseConnection, id: Uuid, simulation: &SwapSimulation) -> Result<(), DbErr> {
+    trades::ActiveModel {
+        id: Set(id),
+        simulation: Set(serde_json::to_value(simulation).ok()),
+        updated_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .update(db)
+    .await?;
+
+    Ok(())
+}
+
+#[derive(Debug, Serialize, FromQueryResult)]
+pub struct FeeRevenue {
+    pub mint: String,
+    pub total_amount: BigDecimal,
+    pub trades: i64,
+}
+
+/// Собранные комиссии платформы по mint за период (только завершенные сделки)
+pub async fn fee_revenue(
+    db: &DatabaseConnection,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Result<Vec<FeeRevenue>, DbErr> {
+    let mut query = trades::Entity::find()
+        .select_only()
+        .column_as(trades::Column::PlatformFeeMint, "mint")
+        .column_as(trades::Column::PlatformFeeAmount.sum(), "total_amount")
+        .column_as(trades::Column::Id.count(), "trades")
+        .filter(trades::Column::Status.eq(TradeStatus::Completed))
+        .filter(trades::Column::PlatformFeeMint.is_not_null())
+        .filter(trades::Column::PlatformFeeAmount.is_not_null());
+
+    if let Some(from) = from {
+        query = query.filter(trades::Column::CreatedAt.gte(from));
+    }
+    if let Some(to) = to {
+        query = query.filter(trades::Column::CreatedAt.lt(to));
+    }
+
+    query
+        .group_by(trades::Column::PlatformFeeMint)
+        .into_model::<FeeRevenue>()
+        .all(db)
+        .await
+}
@@ -0,0 +1,61 @@
+use chrono::Utc;
+use rust_decimal::Decimal;
+use sea_orm::{DatabaseConnection, DbErr, EntityTrait, Set, sea_query::OnConflict};
+
+use crate::entities::users;
+
+// Суточный лимит нового пользователя, как в значении по умолчанию столбца
+const DEFAULT_DAILY_TRADE_LIMIT: Decimal = Decimal::ONE_HUNDRED;
+
+/// Профиль пользователя Telegram
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TelegramProfile {
+    pub id: i64,
+    pub username: Option<String>,
+    pub first_name: String,
+    pub last_name: Option<String>,
+    pub language_code: Option<String>,
+    pub is_premium: bool,
+}
+
+pub async fn find(db: &DatabaseConnection, id: i64) -> Result<Option<users::Model>, DbErr> {
+    users::Entity::find_by_id(id).one(db).await
+}
+
+/// Создает пользователя или обновляет профиль и время последней активности
+pub async fn upsert(db: &DatabaseConnection, profile: TelegramProfile) -> Result<users::Model, DbErr> {
+    let now = Utc::now();
+    let model = users::ActiveModel {
+        id: Set(profile.id),
+        telegram_username: Set(profile.username),
+        first_name: Set(profile.first_name),
+        last_name: Set(profile.last_name),
+        language_code: Set(profile.language_code),
+        is_premium: Set(Some(profile.is_premium)),
+        is_admin: Set(false),
+        is_active: Set(true),
+        daily_trade_limit: Set(DEFAULT_DAILY_TRADE_LIMIT),
+        total_trades: Set(0),
+        total_volume_sol: Set(Decimal::ZERO),
+        created_at: Set(now),
+        updated_at: Set(now),
+        last_active_at: Set(now),
+    };
+
+    users::Entity::insert(model)
+        .on_conflict(
+            OnConflict::column(users::Column::Id)
+                .update_columns([
+                    users::Column::TelegramUsername,
+                    users::Column::FirstName,
+                    users::Column::LastName,
+                    users::Column::LanguageCode,
+                    users::Column::IsPremium,
+                    users::Column::UpdatedAt,
+                    users::Column::LastActiveAt,
+                ])
+                .to_owned(),
+        )
+        .exec_with_returning(db)
+        .await
+}
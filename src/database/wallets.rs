@@ -0,0 +1,55 @@
+use bigdecimal::BigDecimal;
+use chrono::Utc;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, sea_query::Expr,
+};
+use uuid::Uuid;
+
+use crate::entities::wallets;
+
+/// Активный кошелек пользователя по умолчанию
+pub async fn find_default(db: &DatabaseConnection, user_id: i64) -> Result<Option<wallets::Model>, DbErr> {
+    wallets::Entity::find()
+        .filter(wallets::Column::UserId.eq(user_id))
+        .filter(wallets::Column::IsDefault.eq(true))
+        .filter(wallets::Column::IsActive.eq(true))
+        .one(db)
+        .await
+}
+
+/// Включает или выключает уведомления о балансе кошелька по умолчанию.
+/// Возвращает `false`, если у пользователя нет активного кошелька по умолчанию.
+pub async fn set_balance_notifications(db: &DatabaseConnection, user_id: i64, enabled: bool) -> Result<bool, DbErr> {
+    let result = wallets::Entity::update_many()
+        .col_expr(wallets::Column::NotifyBalance, Expr::value(enabled))
+        .col_expr(wallets::Column::UpdatedAt, Expr::value(Utc::now()))
+        .filter(wallets::Column::UserId.eq(user_id))
+        .filter(wallets::Column::IsDefault.eq(true))
+        .filter(wallets::Column::IsActive.eq(true))
+        .exec(db)
+        .await?;
+
+    Ok(result.rows_affected > 0)
+}
+
+/// Активные кошельки, владельцы которых включили уведомления о балансе
+pub async fn with_balance_notifications(db: &DatabaseConnection) -> Result<Vec<wallets::Model>, DbErr> {
+    wallets::Entity::find()
+        .filter(wallets::Column::NotifyBalance.eq(true))
+        .filter(wallets::Column::IsActive.eq(true))
+        .all(db)
+        .await
+}
+
+pub async fn update_balance(db: &DatabaseConnection, id: Uuid, balance_sol: BigDecimal) -> Result<(), DbErr> {
+    let now = Utc::now();
+    wallets::Entity::update_many()
+        .col_expr(wallets::Column::BalanceSol, Expr::value(balance_sol))
+        .col_expr(wallets::Column::LastSyncedAt, Expr::value(now))
+        .col_expr(wallets::Column::UpdatedAt, Expr::value(now))
+        .filter(wallets::Column::Id.eq(id))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use sea_orm::{FromJsonQueryResult, entity::prelude::*};
+use serde::{Deserialize, Serialize};
+use bigdecimal::BigDecimal;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "copy_follows")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub user_id: i64,
+    // Отслеживаемый кошелек
+    pub leader_wallet: String,
+    pub mode: CopyMode,
+    // Для FIXED — сумма покупки в SOL, для PROPORTIONAL — доля от суммы лидера
+    pub amount: BigDecimal,
+    pub max_amount_sol: BigDecimal,
+    pub allowed_mints: MintList,
+    pub denied_mints: MintList,
+    pub delay_secs: i32,
+    pub max_slippage_bps: i32,
+    pub status: CopyFollowStatus,
+    // Последняя обработанная транзакция лидера
+    pub last_signature: Option<String>,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+pub struct MintList(pub Vec<String>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum CopyMode {
+    #[sea_orm(string_value = "FIXED")]
+    Fixed,
+    #[sea_orm(string_value = "PROPORTIONAL")]
+    Proportional,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum CopyFollowStatus {
+    #[sea_orm(string_value = "ACTIVE")]
+    Active,
+    #[sea_orm(string_value = "PAUSED")]
+    Paused,
+    #[sea_orm(string_value = "STOPPED")]
+    Stopped,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id"
+    )]
+    User,
+    #[sea_orm(has_many = "super::copy_trades::Entity")]
+    CopyTrades,
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl Related<super::copy_trades::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CopyTrades.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,73 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use bigdecimal::BigDecimal;
+
+/// Свап отслеживаемого кошелька и его повтор для подписчика
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "copy_trades")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub follow_id: Uuid,
+    pub user_id: i64,
+    pub leader_signature: String,
+    pub input_mint: String,
+    pub output_mint: String,
+    // Суммы лидера в минимальных единицах токена
+    pub leader_input_amount: BigDecimal,
+    pub leader_output_amount: BigDecimal,
+    pub leader_input_balance: BigDecimal,
+    pub status: CopyTradeStatus,
+    pub trade_id: Option<Uuid>,
+    pub error_message: Option<String>,
+    pub execute_after: DateTimeUtc,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum CopyTradeStatus {
+    // Ждет окончания задержки
+    #[sea_orm(string_value = "PENDING")]
+    Pending,
+    #[sea_orm(string_value = "EXECUTING")]
+    Executing,
+    #[sea_orm(string_value = "COPIED")]
+    Copied,
+    // Отфильтрован правилами подписки
+    #[sea_orm(string_value = "SKIPPED")]
+    Skipped,
+    #[sea_orm(string_value = "FAILED")]
+    Failed,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::copy_follows::Entity",
+        from = "Column::FollowId",
+        to = "super::copy_follows::Column::Id"
+    )]
+    Follow,
+    #[sea_orm(
+        belongs_to = "super::trades::Entity",
+        from = "Column::TradeId",
+        to = "super::trades::Column::Id"
+    )]
+    Trade,
+}
+
+impl Related<super::copy_follows::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Follow.def()
+    }
+}
+
+impl Related<super::trades::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Trade.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,87 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use bigdecimal::BigDecimal;
+
+/// Автоматический выход из позиции, открытой сделкой `trade_id`.
+/// Цены указаны в единицах `trades.price`: сколько `quote_mint` за 1 токен.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "exit_orders")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub user_id: i64,
+    pub trade_id: Uuid,
+    pub kind: ExitKind,
+    pub token_mint: String,
+    pub token_symbol: String,
+    pub quote_mint: String,
+    // Размер позиции в токенах; продается не больше, чем есть на балансе
+    pub amount: BigDecimal,
+    pub entry_price: BigDecimal,
+    pub trigger_price: BigDecimal,
+    pub trail_pct: Option<BigDecimal>,
+    pub high_water_price: Option<BigDecimal>,
+    pub max_slippage_bps: i32,
+    pub status: ExitOrderStatus,
+    pub sell_trade_id: Option<Uuid>,
+    pub error_message: Option<String>,
+    pub triggered_at: Option<DateTimeUtc>,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum ExitKind {
+    #[sea_orm(string_value = "STOP_LOSS")]
+    StopLoss,
+    #[sea_orm(string_value = "TAKE_PROFIT")]
+    TakeProfit,
+    #[sea_orm(string_value = "TRAILING_STOP")]
+    TrailingStop,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum ExitOrderStatus {
+    #[sea_orm(string_value = "OPEN")]
+    Open,
+    #[sea_orm(string_value = "TRIGGERED")]
+    Triggered,
+    #[sea_orm(string_value = "FILLED")]
+    Filled,
+    #[sea_orm(string_value = "FAILED")]
+    Failed,
+    #[sea_orm(string_value = "CANCELLED")]
+    Cancelled,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id"
+    )]
+    User,
+    #[sea_orm(
+        belongs_to = "super::trades::Entity",
+        from = "Column::TradeId",
+        to = "super::trades::Column::Id"
+    )]
+    Trade,
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl Related<super::trades::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Trade.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
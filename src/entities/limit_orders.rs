@@ -0,0 +1,83 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use bigdecimal::BigDecimal;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "limit_orders")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub user_id: i64,
+    pub side: OrderSide,
+    pub token_mint: String,
+    pub token_symbol: String,
+    // Для покупки — сумма в SOL, для продажи — количество токенов
+    pub amount: BigDecimal,
+    // Цена токена в USD
+    pub target_price: BigDecimal,
+    pub max_slippage_bps: i32,
+    pub status: LimitOrderStatus,
+    pub trade_id: Option<Uuid>,
+    pub error_message: Option<String>,
+    pub expires_at: Option<DateTimeUtc>,
+    pub triggered_at: Option<DateTimeUtc>,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(8))")]
+pub enum OrderSide {
+    #[sea_orm(string_value = "BUY")]
+    Buy,
+    #[sea_orm(string_value = "SELL")]
+    Sell,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum LimitOrderStatus {
+    #[sea_orm(string_value = "OPEN")]
+    Open,
+    // Ордер захвачен движком и исполняется
+    #[sea_orm(string_value = "TRIGGERED")]
+    Triggered,
+    #[sea_orm(string_value = "FILLED")]
+    Filled,
+    #[sea_orm(string_value = "FAILED")]
+    Failed,
+    #[sea_orm(string_value = "CANCELLED")]
+    Cancelled,
+    #[sea_orm(string_value = "EXPIRED")]
+    Expired,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id"
+    )]
+    User,
+    #[sea_orm(
+        belongs_to = "super::trades::Entity",
+        from = "Column::TradeId",
+        to = "super::trades::Column::Id"
+    )]
+    Trade,
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl Related<super::trades::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Trade.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -4,7 +4,29 @@ pub mod prelude;
 pub mod users;
 pub mod trades;
 pub mod wallets;
+pub mod limit_orders;
+pub mod exit_orders;
+pub mod dca_schedules;
+pub mod copy_follows;
+pub mod copy_trades;
+pub mod price_alerts;
+pub mod sniper_subscriptions;
+pub mod sniper_decisions;
+pub mod fee_preferences;
+pub mod tokens;
+pub mod trade_events;
 
 pub use users::Entity as Users;
 pub use trades::Entity as Trades;
 pub use wallets::Entity as Wallets;
+pub use limit_orders::Entity as LimitOrders;
+pub use exit_orders::Entity as ExitOrders;
+pub use dca_schedules::Entity as DcaSchedules;
+pub use copy_follows::Entity as CopyFollows;
+pub use copy_trades::Entity as CopyTrades;
+pub use price_alerts::Entity as PriceAlerts;
+pub use sniper_subscriptions::Entity as SniperSubscriptions;
+pub use sniper_decisions::Entity as SniperDecisions;
+pub use fee_preferences::Entity as FeePreferences;
+pub use tokens::Entity as Tokens;
+pub use trade_events::Entity as TradeEvents;
@@ -0,0 +1,14 @@
+pub use super::copy_follows::Entity as CopyFollows;
+pub use super::copy_trades::Entity as CopyTrades;
+pub use super::dca_schedules::Entity as DcaSchedules;
+pub use super::exit_orders::Entity as ExitOrders;
+pub use super::fee_preferences::Entity as FeePreferences;
+pub use super::limit_orders::Entity as LimitOrders;
+pub use super::price_alerts::Entity as PriceAlerts;
+pub use super::sniper_decisions::Entity as SniperDecisions;
+pub use super::sniper_subscriptions::Entity as SniperSubscriptions;
+pub use super::tokens::Entity as Tokens;
+pub use super::trade_events::Entity as TradeEvents;
+pub use super::trades::Entity as Trades;
+pub use super::users::Entity as Users;
+pub use super::wallets::Entity as Wallets;
@@ -0,0 +1,60 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use bigdecimal::BigDecimal;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "price_alerts")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub user_id: i64,
+    pub token_mint: String,
+    pub token_symbol: String,
+    pub kind: AlertKind,
+    // Цена в USD, процент изменения или объем за 24 часа в USD — в зависимости от вида
+    pub threshold: BigDecimal,
+    // Окно изменения цены для процентных уведомлений
+    pub window_secs: Option<i64>,
+    // Уведомление снова срабатывает только после выхода из условия
+    pub armed: bool,
+    pub trigger_count: i32,
+    pub last_triggered_at: Option<DateTimeUtc>,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum AlertKind {
+    #[sea_orm(string_value = "ABOVE")]
+    Above,
+    #[sea_orm(string_value = "BELOW")]
+    Below,
+    #[sea_orm(string_value = "RISE")]
+    Rise,
+    #[sea_orm(string_value = "DROP")]
+    Drop,
+    // Рост или падение
+    #[sea_orm(string_value = "MOVE")]
+    Move,
+    #[sea_orm(string_value = "VOLUME")]
+    Volume,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,76 @@
+use sea_orm::{FromJsonQueryResult, entity::prelude::*};
+use serde::{Deserialize, Serialize};
+
+/// Решение снайпера по новому токену. Строка без `user_id` — итог проверок запуска,
+/// строки с `user_id` — покупки для подписчиков.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "sniper_decisions")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub user_id: Option<i64>,
+    pub token_mint: String,
+    pub token_symbol: String,
+    pub pool_address: Option<String>,
+    pub pool_created_at: Option<DateTimeUtc>,
+    pub status: SniperDecisionStatus,
+    pub reasons: Reasons,
+    // Результаты проверок токена (`LaunchReport`)
+    pub report: Option<Json>,
+    pub trade_id: Option<Uuid>,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+pub struct Reasons(pub Vec<String>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum SniperDecisionStatus {
+    // Проверки запуска пройдены
+    #[sea_orm(string_value = "PASSED")]
+    Passed,
+    // Проверки не пройдены до истечения окна запуска
+    #[sea_orm(string_value = "REJECTED")]
+    Rejected,
+    #[sea_orm(string_value = "EXECUTING")]
+    Executing,
+    #[sea_orm(string_value = "BOUGHT")]
+    Bought,
+    // Покупка не начиналась: лимиты или баланс
+    #[sea_orm(string_value = "SKIPPED")]
+    Skipped,
+    #[sea_orm(string_value = "FAILED")]
+    Failed,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id"
+    )]
+    User,
+    #[sea_orm(
+        belongs_to = "super::trades::Entity",
+        from = "Column::TradeId",
+        to = "super::trades::Column::Id"
+    )]
+    Trade,
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl Related<super::trades::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Trade.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,22 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Кэш метаданных mint: знаки из mint-аккаунта, символ и название из метаданных токена
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "tokens")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub mint: String,
+    // `None` — у токена нет метаданных Metaplex и расширения Token-2022
+    pub symbol: Option<String>,
+    pub name: Option<String>,
+    pub decimals: i16,
+    pub token_2022: bool,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,7 +1,8 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use bigdecimal::BigDecimal;
-use std::str::FromStr;
+
+use super::fee_preferences::PriorityTier;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "trades")]
@@ -22,13 +23,23 @@ pub struct Model {
     pub status: TradeStatus,
     pub error_message: Option<String>,
     pub jupiter_quote_id: Option<String>,
+    pub platform_fee_bps: Option<i32>,
+    pub platform_fee_amount: Option<BigDecimal>,
+    pub platform_fee_mint: Option<String>,
+    // Расписание DCA, в рамках которого совершена покупка
+    pub dca_schedule_id: Option<Uuid>,
+    // Результат `simulateTransaction` перед отправкой: ошибка, логи, вычислительные единицы
+    pub simulation: Option<Json>,
+    // Приоритетная комиссия из подписанной транзакции и уровень, по которому она выбрана
+    pub priority_fee_lamports: Option<i64>,
+    pub priority_fee_tier: Option<PriorityTier>,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
     pub completed_at: Option<DateTimeUtc>,
 }
 
 #[derive(Debug, Clone, PartialEq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
-#[sea_orm(rs_type = "String", db_type = "String(Some(1))")]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
 pub enum TradeType {
     #[sea_orm(string_value = "BUY")]
     Buy,
@@ -39,7 +50,7 @@ pub enum TradeType {
 }
 
 #[derive(Debug, Clone, PartialEq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
-#[sea_orm(rs_type = "String", db_type = "String(Some(1))")]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
 pub enum TradeStatus {
     #[sea_orm(string_value = "PENDING")]
     Pending,
@@ -53,6 +64,24 @@ pub enum TradeStatus {
     Cancelled,
 }
 
+impl TradeStatus {
+    /// Допустимые переходы: Pending → Executing → Completed | Failed, Pending → Cancelled
+    pub fn can_transition_to(&self, next: &TradeStatus) -> bool {
+        matches!(
+            (self, next),
+            (TradeStatus::Pending, TradeStatus::Executing)
+                | (TradeStatus::Pending, TradeStatus::Cancelled)
+                | (TradeStatus::Executing, TradeStatus::Completed)
+                | (TradeStatus::Executing, TradeStatus::Failed)
+        )
+    }
+
+    /// Из этого статуса переходов нет
+    pub fn is_final(&self) -> bool {
+        matches!(self, TradeStatus::Completed | TradeStatus::Failed | TradeStatus::Cancelled)
+    }
+}
+
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(
@@ -61,6 +90,12 @@ pub enum Relation {
         to = "super::users::Column::Id"
     )]
     User,
+    #[sea_orm(
+        belongs_to = "super::dca_schedules::Entity",
+        from = "Column::DcaScheduleId",
+        to = "super::dca_schedules::Column::Id"
+    )]
+    DcaSchedule,
 }
 
 impl Related<super::users::Entity> for Entity {
@@ -69,4 +104,10 @@ impl Related<super::users::Entity> for Entity {
     }
 }
 
+impl Related<super::dca_schedules::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DcaSchedule.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -1,6 +1,5 @@
 use sea_orm::entity::prelude::*;
-use serde::{Deserialize, Serialize};
-use secrecy::{Secret, Serialize as SecretSerialize};
+use serde::Serialize;
 use bigdecimal::BigDecimal;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize)]
@@ -17,13 +16,15 @@ pub struct Model {
     pub is_default: bool,
     pub is_active: bool,
     pub balance_sol: BigDecimal,
+    // Уведомлять владельца об изменениях баланса SOL
+    pub notify_balance: bool,
     pub last_synced_at: DateTimeUtc,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
 }
 
 #[derive(Debug, Clone, PartialEq, EnumIter, DeriveActiveEnum, Serialize)]
-#[sea_orm(rs_type = "String", db_type = "String(Some(1))")]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
 pub enum WalletType {
     #[sea_orm(string_value = "HOT")]
     Hot,
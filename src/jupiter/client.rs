@@ -1,15 +1,17 @@
-use std::str::FromStr;
 use reqwest::Client;
-use serde::{Deserialize, Serialize};
-use solana_sdk::pubkey::Pubkey;
-use anyhow::{Result, Context};
-use secrecy::Secret;
+use anyhow::Result;
+use secrecy::{ExposeSecret, SecretString};
+
+use super::models::{
+    PriceParams, PriceResponse, QuoteParamsV6, QuoteResponseV6, SwapParamsV6, SwapRequestV6,
+    RecentToken, SwapResponseV6, TokenInfo,
+};
 
 #[derive(Debug, Clone)]
 pub struct JupiterClient {
     client: Client,
     base_url: String,
-    api_key: Option<Secret<String>>,
+    api_key: Option<SecretString>,
 }
 
 impl JupiterClient {
@@ -22,7 +24,7 @@ impl JupiterClient {
         Self {
             client,
             base_url: base_url.to_string(),
-            api_key: api_key.map(Secret::new),
+            api_key: api_key.map(|key| SecretString::new(key.into_boxed_str())),
         }
     }
 
@@ -41,6 +43,10 @@ impl JupiterClient {
                 ("maxAccounts", &params.max_accounts.to_string()),
             ]);
 
+        if let Some(fee_bps) = params.platform_fee_bps.filter(|bps| *bps > 0) {
+            request = request.query(&[("platformFeeBps", fee_bps.to_string())]);
+        }
+
         if let Some(key) = &self.api_key {
             request = request.header("Authorization", format!("Bearer {}", key.expose_secret()));
         }
@@ -61,10 +67,11 @@ impl JupiterClient {
 
         let swap_request = SwapRequestV6 {
             quote_response: params.quote_response.clone(),
-            user_public_key: params.user_public_key.to_string(),
+            user_public_key: params.user_public_key,
             wrap_and_unwrap_sol: params.wrap_and_unwrap_sol,
             dynamic_compute_unit_limit: true,
-            prioritization_fee_lamports: "auto".to_string(),
+            prioritization_fee_lamports: params.prioritization_fee.clone(),
+            fee_account: params.fee_account,
         };
 
         let mut request = self.client.post(&url)
@@ -119,6 +126,26 @@ impl JupiterClient {
         Ok(tokens)
     }
 
+    /// Токены, недавно получившие первый пул, новые первыми
+    pub async fn get_recent_tokens(&self) -> Result<Vec<RecentToken>> {
+        let url = format!("{}/tokens/v2/recent", self.base_url);
+
+        let mut request = self.client.get(&url);
+        if let Some(key) = &self.api_key {
+            request = request.header("Authorization", format!("Bearer {}", key.expose_secret()));
+        }
+
+        let response = request.send().await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow::anyhow!("Jupiter tokens error: {}", error_text));
+        }
+
+        let tokens: Vec<RecentToken> = response.json().await?;
+        Ok(tokens)
+    }
+
     pub async fn search_tokens(&self, query: &str, limit: Option<u32>) -> Result<Vec<TokenInfo>> {
         let mut tokens = self.get_tokens().await?;
 
@@ -169,112 +196,3 @@ impl JupiterClient {
         score
     }
 }
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct QuoteParamsV6 {
-    pub input_mint: Pubkey,
-    pub output_mint: Pubkey,
-    pub amount: u64, // в наименьших единицах (лампортах для SOL)
-    pub slippage_bps: u64,
-    pub only_direct_routes: bool,
-    pub as_legacy_transaction: bool,
-    pub swap_mode: String, // "ExactIn" или "ExactOut"
-    pub max_accounts: u32,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct QuoteResponseV6 {
-    pub input_mint: String,
-    pub in_amount: String,
-    pub output_mint: String,
-    pub out_amount: String,
-    pub other_amount_threshold: String,
-    pub swap_mode: String,
-    pub slippage_bps: u64,
-    pub platform_fee: Option<PlatformFee>,
-    pub price_impact_pct: Option<String>,
-    pub context_slot: Option<u64>,
-    pub time_taken: Option<f64>,
-    pub route_plan: Vec<RoutePlan>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SwapParamsV6 {
-    pub quote_response: QuoteResponseV6,
-    pub user_public_key: Pubkey,
-    pub wrap_and_unwrap_sol: bool,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SwapRequestV6 {
-    pub quote_response: QuoteResponseV6,
-    pub user_public_key: String,
-    pub wrap_and_unwrap_sol: bool,
-    pub dynamic_compute_unit_limit: bool,
-    pub prioritization_fee_lamports: String,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SwapResponseV6 {
-    pub swap_transaction: String, // base64 encoded transaction
-    pub last_valid_block_height: u64,
-    pub prioritization_fee_lamports: Option<u64>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PriceParams {
-    pub ids: String, // comma separated token addresses
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PriceResponse {
-    pub data: std::collections::HashMap<String, TokenPrice>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TokenPrice {
-    pub id: String,
-    #[serde(rename = "type")]
-    pub token_type: String,
-    pub price: f64,
-    pub price_change_24h: Option<f64>,
-    pub volume_24h: Option<f64>,
-    pub market_cap: Option<f64>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TokenInfo {
-    pub address: String,
-    pub chain_id: u64,
-    pub decimals: u8,
-    pub name: String,
-    pub symbol: String,
-    #[serde(rename = "logoURI")]
-    pub logo_uri: Option<String>,
-    pub tags: Vec<String>,
-    pub extensions: Option<serde_json::Value>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PlatformFee {
-    pub amount: String,
-    pub fee_bps: u64,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RoutePlan {
-    pub swap_info: SwapInfo,
-    pub percent: u8,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SwapInfo {
-    pub amm_key: String,
-    pub label: Option<String>,
-    pub input_mint: String,
-    pub output_mint: String,
-    pub in_amount: String,
-    pub out_amount: String,
-    pub fee_amount: String,
-    pub fee_mint: String,
-}
@@ -0,0 +1,28 @@
+use std::str::FromStr;
+
+use solana_sdk::pubkey::Pubkey;
+
+use super::models::SwapMode;
+
+// Программа Jupiter Referral, в которой хранятся токен-аккаунты для комиссий
+pub const REFERRAL_PROGRAM_ID: &str = "REFER4ZgmyYx9c6He5XfaTMiGfdLwRnkV4RPp9t9iF3";
+
+/// Токен-аккаунт реферального аккаунта для комиссии в заданном mint.
+/// PDA: ["referral_ata", referral_account, mint] в программе Jupiter Referral.
+pub fn referral_fee_account(referral_account: &Pubkey, mint: &Pubkey) -> Pubkey {
+    let program_id = Pubkey::from_str(REFERRAL_PROGRAM_ID).expect("valid referral program id");
+
+    Pubkey::find_program_address(
+        &[b"referral_ata", referral_account.as_ref(), mint.as_ref()],
+        &program_id,
+    ).0
+}
+
+/// Mint, в котором Jupiter удерживает комиссию платформы:
+/// для ExactIn — выходной токен, для ExactOut — входной.
+pub fn fee_mint(input_mint: &Pubkey, output_mint: &Pubkey, swap_mode: SwapMode) -> Pubkey {
+    match swap_mode {
+        SwapMode::ExactIn => *output_mint,
+        SwapMode::ExactOut => *input_mint,
+    }
+}
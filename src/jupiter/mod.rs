@@ -1,5 +1,8 @@
 mod client;
+pub mod fees;
 mod models;
+mod price_service;
 
 pub use client::JupiterClient;
 pub use models::*;
+pub use price_service::PriceService;
@@ -1,24 +1,275 @@
 use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::fmt;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum SwapMode {
+    #[default]
     #[serde(rename = "ExactIn")]
     ExactIn,
     #[serde(rename = "ExactOut")]
     ExactOut,
 }
 
-impl Default for SwapMode {
-    fn default() -> Self {
-        Self::ExactIn
+impl fmt::Display for SwapMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SwapMode::ExactIn => f.write_str("ExactIn"),
+            SwapMode::ExactOut => f.write_str("ExactOut"),
+        }
     }
 }
 
-impl ToString for SwapMode {
-    fn to_string(&self) -> String {
-        match self {
-            SwapMode::ExactIn => "ExactIn".to_string(),
-            SwapMode::ExactOut => "ExactOut".to_string(),
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuoteParamsV6 {
+    #[serde(with = "string_encoded")]
+    pub input_mint: Pubkey,
+    #[serde(with = "string_encoded")]
+    pub output_mint: Pubkey,
+    pub amount: u64, // в наименьших единицах (лампортах для SOL)
+    pub slippage_bps: u64,
+    pub only_direct_routes: bool,
+    pub as_legacy_transaction: bool,
+    pub swap_mode: SwapMode,
+    pub max_accounts: u32,
+    pub platform_fee_bps: Option<u16>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteResponseV6 {
+    #[serde(with = "string_encoded")]
+    pub input_mint: Pubkey,
+    #[serde(with = "string_encoded")]
+    pub in_amount: u64,
+    #[serde(with = "string_encoded")]
+    pub output_mint: Pubkey,
+    #[serde(with = "string_encoded")]
+    pub out_amount: u64,
+    // Для ExactIn — минимальный выход, для ExactOut — максимальный вход с учетом slippage
+    #[serde(with = "string_encoded")]
+    pub other_amount_threshold: u64,
+    pub swap_mode: SwapMode,
+    pub slippage_bps: u64,
+    pub platform_fee: Option<PlatformFee>,
+    #[serde(with = "string_encoded")]
+    pub price_impact_pct: f64,
+    pub route_plan: Vec<RoutePlan>,
+    pub context_slot: Option<u64>,
+    pub time_taken: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapParamsV6 {
+    pub quote_response: QuoteResponseV6,
+    #[serde(with = "string_encoded")]
+    pub user_public_key: Pubkey,
+    pub wrap_and_unwrap_sol: bool,
+    #[serde(default, with = "string_encoded::option")]
+    pub fee_account: Option<Pubkey>,
+    pub prioritization_fee: PrioritizationFee,
+}
+
+/// `prioritizationFeeLamports` запроса свапа: точная сумма или оценка Jupiter с потолком
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PrioritizationFee {
+    Lamports(u64),
+    #[serde(rename_all = "camelCase")]
+    Capped { priority_level_with_max_lamports: PriorityLevelWithMaxLamports },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriorityLevelWithMaxLamports {
+    // medium, high или veryHigh
+    pub priority_level: String,
+    pub max_lamports: u64,
+}
+
+impl PrioritizationFee {
+    pub fn capped(priority_level: &str, max_lamports: u64) -> Self {
+        Self::Capped {
+            priority_level_with_max_lamports: PriorityLevelWithMaxLamports {
+                priority_level: priority_level.to_string(),
+                max_lamports,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapRequestV6 {
+    pub quote_response: QuoteResponseV6,
+    #[serde(with = "string_encoded")]
+    pub user_public_key: Pubkey,
+    pub wrap_and_unwrap_sol: bool,
+    pub dynamic_compute_unit_limit: bool,
+    pub prioritization_fee_lamports: PrioritizationFee,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "string_encoded::option"
+    )]
+    pub fee_account: Option<Pubkey>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapResponseV6 {
+    pub swap_transaction: String, // base64 encoded transaction
+    pub last_valid_block_height: u64,
+    pub prioritization_fee_lamports: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceParams {
+    pub ids: String, // comma separated token addresses
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceResponse {
+    // Для токенов без цены Jupiter возвращает null
+    pub data: std::collections::HashMap<String, Option<TokenPrice>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenPrice {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub token_type: String,
+    #[serde(with = "string_encoded")]
+    pub price: f64,
+    #[serde(default, rename = "priceChange24h")]
+    pub price_change_24h: Option<f64>,
+    #[serde(default, rename = "volume24h")]
+    pub volume_24h: Option<f64>,
+    #[serde(default)]
+    pub market_cap: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenInfo {
+    pub address: String,
+    pub chain_id: u64,
+    pub decimals: u8,
+    pub name: String,
+    pub symbol: String,
+    #[serde(rename = "logoURI")]
+    pub logo_uri: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub extensions: Option<serde_json::Value>,
+}
+
+/// Недавно ставший торгуемым токен из `/tokens/v2/recent`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentToken {
+    #[serde(with = "string_encoded")]
+    pub id: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub token_program: Option<String>,
+    pub first_pool: Option<FirstPool>,
+}
+
+/// Первый пул токена, в котором он стал торгуемым
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FirstPool {
+    #[serde(with = "string_encoded")]
+    pub id: Pubkey,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlatformFee {
+    #[serde(with = "string_encoded")]
+    pub amount: u64,
+    pub fee_bps: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutePlan {
+    pub swap_info: SwapInfo,
+    pub percent: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapInfo {
+    #[serde(with = "string_encoded")]
+    pub amm_key: Pubkey,
+    pub label: Option<String>,
+    #[serde(with = "string_encoded")]
+    pub input_mint: Pubkey,
+    #[serde(with = "string_encoded")]
+    pub output_mint: Pubkey,
+    #[serde(with = "string_encoded")]
+    pub in_amount: u64,
+    #[serde(with = "string_encoded")]
+    pub out_amount: u64,
+    #[serde(with = "string_encoded")]
+    pub fee_amount: u64,
+    #[serde(with = "string_encoded")]
+    pub fee_mint: Pubkey,
+}
+
+/// Jupiter передает суммы, ключи и проценты строками, чтобы не терять точность в JSON.
+/// Сериализация через `Display`/`FromStr` сохраняет этот формат на проводе.
+pub mod string_encoded {
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+    use std::{fmt::Display, str::FromStr};
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Display,
+        S: Serializer,
+    {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        raw.parse().map_err(D::Error::custom)
+    }
+
+    pub mod option {
+        use serde::{Deserialize, Deserializer, Serializer, de::Error};
+        use std::{fmt::Display, str::FromStr};
+
+        pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            T: Display,
+            S: Serializer,
+        {
+            match value {
+                Some(value) => serializer.collect_str(value),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+        where
+            T: FromStr,
+            T::Err: Display,
+            D: Deserializer<'de>,
+        {
+            Option::<String>::deserialize(deserializer)?
+                .map(|raw| raw.parse().map_err(D::Error::custom))
+                .transpose()
         }
     }
 }
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use dashmap::DashMap;
+use solana_sdk::pubkey::Pubkey;
+
+use super::{client::JupiterClient, models::{PriceParams, TokenPrice}};
+
+/// Рыночные (mid) цены токенов в USD через Jupiter Price API с коротким кешем
+pub struct PriceService {
+    jupiter: JupiterClient,
+    ttl: Duration,
+    cache: DashMap<Pubkey, (f64, Instant)>,
+}
+
+impl PriceService {
+    pub fn new(jupiter: JupiterClient, ttl: Duration) -> Self {
+        Self {
+            jupiter,
+            ttl,
+            cache: DashMap::new(),
+        }
+    }
+
+    pub async fn get_price(&self, mint: &Pubkey) -> Result<Option<f64>> {
+        Ok(self.get_prices(std::slice::from_ref(mint)).await?.get(mint).copied())
+    }
+
+    /// Цены для набора mint; токены без цены в ответе отсутствуют
+    pub async fn get_prices(&self, mints: &[Pubkey]) -> Result<HashMap<Pubkey, f64>> {
+        let mut prices = HashMap::new();
+        let mut missing = Vec::new();
+
+        for mint in mints {
+            match self.cache.get(mint) {
+                Some(entry) if entry.1.elapsed() < self.ttl => {
+                    prices.insert(*mint, entry.0);
+                }
+                _ if !missing.contains(mint) => missing.push(*mint),
+                _ => {}
+            }
+        }
+
+        if missing.is_empty() {
+            return Ok(prices);
+        }
+
+        let ids = missing.iter().map(|mint| mint.to_string()).collect::<Vec<_>>().join(",");
+        let response = self.jupiter.get_price(&PriceParams { ids }).await?;
+        let now = Instant::now();
+
+        for mint in missing {
+            if let Some(Some(price)) = response.data.get(&mint.to_string()) {
+                self.cache.insert(mint, (price.price, now));
+                prices.insert(mint, price.price);
+            }
+        }
+
+        Ok(prices)
+    }
+
+    /// Цены вместе с изменением за 24 часа и объемом, без кеша.
+    /// Полученные цены обновляют кеш `get_prices`.
+    pub async fn get_market_data(&self, mints: &[Pubkey]) -> Result<HashMap<Pubkey, TokenPrice>> {
+        if mints.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let ids = mints.iter().map(|mint| mint.to_string()).collect::<Vec<_>>().join(",");
+        let response = self.jupiter.get_price(&PriceParams { ids }).await?;
+        let now = Instant::now();
+
+        let mut market = HashMap::new();
+        for mint in mints {
+            if let Some(Some(price)) = response.data.get(&mint.to_string()) {
+                self.cache.insert(*mint, (price.price, now));
+                market.insert(*mint, price.clone());
+            }
+        }
+
+        Ok(market)
+    }
+}
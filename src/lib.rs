@@ -0,0 +1,11 @@
+pub mod api;
+pub mod automation;
+pub mod config;
+pub mod database;
+pub mod entities;
+pub mod jupiter;
+pub mod monitoring;
+pub mod security;
+pub mod solana;
+pub mod telegram;
+pub mod utils;
@@ -1,20 +1,26 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use solana_commitment_config::CommitmentConfig;
 use tracing::{info, error};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-mod config;
-mod database;
-mod security;
-mod telegram;
-mod api;
-mod monitoring;
-mod utils;
-
-use crate::config::Settings;
-use crate::database::connection::DatabaseConnectionPool;
-use crate::security::secrets_manager::SecretsManager;
-use crate::telegram::bot::TelegramBot;
-use crate::api::server::ApiServer;
-use crate::monitoring::metrics::MetricsRegistry;
+use solana_trading_bot::config::Settings;
+use solana_trading_bot::database::connection::DatabaseConnectionPool;
+use solana_trading_bot::database::repositories::Repositories;
+use solana_trading_bot::security::secrets_manager::SecretsManager;
+use solana_trading_bot::telegram::bot::TelegramBot;
+use solana_trading_bot::telegram::quote_store::QuoteStore;
+use solana_trading_bot::api::server::ApiServer;
+use solana_trading_bot::monitoring::metrics::MetricsRegistry;
+use solana_trading_bot::jupiter::{JupiterClient, PriceService};
+use solana_trading_bot::solana::executor::SwapExecutor;
+use solana_trading_bot::solana::priority_fee::PriorityFeeEstimator;
+use solana_trading_bot::solana::pubsub::PubsubManager;
+use solana_trading_bot::solana::rpc_pool::RpcPool;
+use solana_trading_bot::solana::token_safety::TokenSafety;
+use solana_trading_bot::solana::trader::{PlatformFeeConfig, Trader};
+use solana_trading_bot::automation::{AlertEngine, BalanceWatcher, CopyTradingEngine, DcaScheduler, ExitOrderEngine, LimitOrderEngine, SniperEngine};
 
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
@@ -27,6 +33,13 @@ async fn main() -> Result<(), anyhow::Error> {
     let settings = Settings::new()?;
     info!("Configuration loaded successfully");
 
+    // `migrate up|down [N]|status` управляет схемой БД без запуска бота
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("migrate") {
+        let database = DatabaseConnectionPool::connect(&settings.database).await?;
+        return migrate(&database, &args[1..]).await;
+    }
+
     // Initialize secrets manager
     let secrets_manager = SecretsManager::new(&settings).await?;
     info!("Secrets manager initialized");
@@ -39,38 +52,157 @@ async fn main() -> Result<(), anyhow::Error> {
     let database = DatabaseConnectionPool::connect(&settings.database).await?;
     info!("Database connected successfully");
 
-    // Run migrations
+    // Run migrations (replicas wait for each other on an advisory lock)
     database.run_migrations().await?;
     info!("Database migrations completed");
 
+    // Initialize trader (Jupiter + Solana RPC)
+    let jupiter = JupiterClient::new(
+        &settings.jupiter.api_url,
+        secrets_manager.get_jupiter_api_key().await,
+    );
+    let commitment = settings.solana.commitment.parse::<CommitmentConfig>()?;
+    let rpc_pool = Arc::new(RpcPool::new(&settings.solana, commitment, Arc::new(metrics.clone()))?);
+    let rpc = Arc::new(rpc_pool.client());
+    tokio::spawn(rpc_pool.clone().run_health_checks());
+    let pubsub = settings.solana.pubsub_enabled
+        .then(|| PubsubManager::start(&settings.solana, commitment));
+    let price_service = Arc::new(PriceService::new(jupiter.clone(), Duration::from_secs(10)));
+    let priority_fees = Arc::new(PriorityFeeEstimator::new(rpc.clone(), settings.priority_fee.clone()));
+    let mut trader = Trader::new(jupiter, rpc, settings.trading_limits.clone())
+        .with_rebroadcast_interval(Duration::from_millis(settings.solana.rebroadcast_interval_ms))
+        .with_pubsub(pubsub.clone())
+        .with_token_cache(Arc::new(database.clone()));
+    if let Some(referral_account) = &settings.platform_fee.referral_account {
+        trader = trader.with_platform_fee(PlatformFeeConfig {
+            fee_bps: settings.platform_fee.fee_bps,
+            referral_account: referral_account.parse()?,
+        });
+        info!("Platform fee enabled: {} bps", settings.platform_fee.fee_bps);
+    }
+    let trader = Arc::new(trader);
+    let quotes = Arc::new(QuoteStore::new(
+        Duration::from_secs(settings.quote.ttl_secs),
+        settings.quote.requote_tolerance_bps,
+    ));
+    let token_safety = Arc::new(TokenSafety::new(trader.clone(), settings.token_safety.clone()));
+    let executor = Arc::new(SwapExecutor::new(
+        Repositories::postgres(Arc::new(database.clone())),
+        Arc::new(secrets_manager.clone()),
+        Arc::new(metrics.clone()),
+        trader.clone(),
+        priority_fees.clone(),
+    ).with_token_safety(token_safety.clone()));
+    info!("Trader initialized");
+
     // Initialize API server
     let api_server = ApiServer::new(
         settings.api.clone(),
         database.clone(),
         metrics.clone(),
         secrets_manager.clone(),
+        settings.trading_limits.clone(),
+        settings.limit_orders.clone(),
     );
 
     // Initialize Telegram bot
     let telegram_bot = TelegramBot::new(
-        settings.telegram.clone(),
+        Arc::new(settings.clone()),
         database.clone(),
         secrets_manager.clone(),
         metrics.clone(),
+        trader.clone(),
+        executor.clone(),
+        price_service.clone(),
+        quotes.clone(),
+        token_safety.clone(),
+        priority_fees.clone(),
     ).await?;
 
+    // Background limit order engine
+    let limit_engine = LimitOrderEngine::new(
+        Arc::new(database.clone()),
+        executor.clone(),
+        price_service.clone(),
+        telegram_bot.notifier(),
+        Duration::from_secs(settings.limit_orders.poll_interval_secs),
+    );
+    tokio::spawn(limit_engine.run());
+
+    // Background stop-loss / take-profit engine
+    let exit_engine = ExitOrderEngine::new(
+        Arc::new(database.clone()),
+        executor.clone(),
+        price_service.clone(),
+        telegram_bot.notifier(),
+        Duration::from_secs(settings.exit_orders.poll_interval_secs),
+    );
+    tokio::spawn(exit_engine.run());
+
+    // Background DCA scheduler
+    let dca_scheduler = DcaScheduler::new(
+        Arc::new(database.clone()),
+        executor.clone(),
+        telegram_bot.notifier(),
+        Duration::from_secs(settings.dca.poll_interval_secs),
+    );
+    tokio::spawn(dca_scheduler.run());
+
+    // Background copy trading engine
+    let copy_engine = CopyTradingEngine::new(
+        Arc::new(database.clone()),
+        executor.clone(),
+        telegram_bot.notifier(),
+        Duration::from_secs(settings.copy_trading.poll_interval_secs),
+        settings.copy_trading.signatures_per_poll,
+        Duration::from_secs(settings.copy_trading.max_lag_secs),
+    );
+    tokio::spawn(copy_engine.run());
+
+    // Background price alert engine
+    let alert_engine = AlertEngine::new(
+        Arc::new(database.clone()),
+        price_service.clone(),
+        telegram_bot.notifier(),
+        Duration::from_secs(settings.alerts.poll_interval_secs),
+        Duration::from_secs(settings.alerts.cooldown_secs),
+    );
+    tokio::spawn(alert_engine.run());
+
+    // Live wallet balance notifications (requires pubsub)
+    if let Some(pubsub) = pubsub {
+        let balance_watcher = BalanceWatcher::new(
+            Arc::new(database.clone()),
+            trader.rpc().clone(),
+            pubsub,
+            telegram_bot.notifier(),
+            Duration::from_secs(settings.balance_notifications.refresh_interval_secs),
+            settings.balance_notifications.min_change_lamports,
+        );
+        tokio::spawn(balance_watcher.run());
+    }
+
+    // Background sniper (opt-in)
+    let sniper = SniperEngine::new(
+        Arc::new(database.clone()),
+        executor.clone(),
+        telegram_bot.notifier(),
+        settings.sniper.clone(),
+    );
+    tokio::spawn(sniper.run());
+
     // Run services concurrently
     tokio::select! {
         result = api_server.start() => {
             if let Err(e) = result {
                 error!("API server failed: {}", e);
-                return Err(e.into());
+                return Err(e);
             }
         }
         result = telegram_bot.start() => {
             if let Err(e) = result {
                 error!("Telegram bot failed: {}", e);
-                return Err(e.into());
+                return Err(e);
             }
         }
         _ = tokio::signal::ctrl_c() => {
@@ -82,6 +214,28 @@ async fn main() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+async fn migrate(database: &DatabaseConnectionPool, args: &[String]) -> Result<(), anyhow::Error> {
+    const USAGE: &str = "Usage: migrate [up | down [N] | status]";
+
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        [] | ["up"] => database.run_migrations().await?,
+        ["down"] => database.rollback_migrations(1).await?,
+        ["down", steps] => {
+            let steps = steps.parse().map_err(|_| anyhow::anyhow!(USAGE))?;
+            database.rollback_migrations(steps).await?;
+        }
+        ["status"] => {
+            for migration in database.migration_status().await? {
+                let status = if migration.applied { "applied" } else { "pending" };
+                println!("{:<8} {}", status, migration.name);
+            }
+        }
+        _ => anyhow::bail!(USAGE),
+    }
+
+    Ok(())
+}
+
 fn init_tracing() {
     tracing_subscriber::registry()
         .with(tracing_subscriber::EnvFilter::from_default_env())
@@ -1,5 +1,5 @@
 use std::sync::Arc;
-use prometheus::{Registry, Counter, Histogram, Encoder, TextEncoder};
+use prometheus::{Registry, Counter, CounterVec, Histogram, HistogramVec, IntGaugeVec, Encoder, TextEncoder};
 use anyhow::Result;
 
 #[derive(Clone)]
@@ -12,6 +12,8 @@ pub struct MetricsRegistry {
     pub trades_failed: Counter,
     pub trade_amount: Histogram,
     pub trade_duration: Histogram,
+    pub priority_fee_lamports: HistogramVec,
+    pub priority_fee_trades: CounterVec,
 
     // API metrics
     pub api_requests_total: Counter,
@@ -23,8 +25,10 @@ pub struct MetricsRegistry {
     pub telegram_commands_total: Counter,
 
     // Solana metrics
-    pub solana_rpc_calls_total: Counter,
-    pub solana_rpc_duration: Histogram,
+    pub solana_rpc_calls_total: CounterVec,
+    pub solana_rpc_duration: HistogramVec,
+    pub solana_rpc_errors_total: CounterVec,
+    pub solana_rpc_endpoint_healthy: IntGaugeVec,
     pub solana_transactions_total: Counter,
 }
 
@@ -62,6 +66,23 @@ impl MetricsRegistry {
             ).buckets(vec![0.1, 0.5, 1.0, 2.0, 5.0, 10.0, 30.0])
         ).unwrap();
 
+        // Приоритетная комиссия отправленных сделок и их исход по уровням
+        let priority_fee_lamports = HistogramVec::new(
+            prometheus::HistogramOpts::new(
+                "priority_fee_lamports",
+                "Priority fee paid per sent trade in lamports"
+            ).buckets(vec![1_000.0, 10_000.0, 50_000.0, 100_000.0, 500_000.0, 1_000_000.0, 5_000_000.0, 10_000_000.0]),
+            &["tier"]
+        ).unwrap();
+
+        let priority_fee_trades = CounterVec::new(
+            prometheus::Opts::new(
+                "priority_fee_trades_total",
+                "Sent trades by priority fee tier and result"
+            ),
+            &["tier", "result"]
+        ).unwrap();
+
         // API metrics
         let api_requests_total = Counter::new(
             "api_requests_total",
@@ -91,17 +112,37 @@ impl MetricsRegistry {
             "Total number of Telegram commands processed"
         ).unwrap();
 
-        // Solana metrics
-        let solana_rpc_calls_total = Counter::new(
-            "solana_rpc_calls_total",
-            "Total number of Solana RPC calls"
+        // Solana metrics: вызовы RPC по узлам пула и методам
+        let solana_rpc_calls_total = CounterVec::new(
+            prometheus::Opts::new(
+                "solana_rpc_calls_total",
+                "Total number of Solana RPC calls"
+            ),
+            &["endpoint", "method"]
         ).unwrap();
 
-        let solana_rpc_duration = Histogram::with_opts(
+        let solana_rpc_duration = HistogramVec::new(
             prometheus::HistogramOpts::new(
                 "solana_rpc_duration_seconds",
                 "Duration of Solana RPC calls in seconds"
-            ).buckets(vec![0.01, 0.05, 0.1, 0.5, 1.0, 2.0, 5.0])
+            ).buckets(vec![0.01, 0.05, 0.1, 0.5, 1.0, 2.0, 5.0]),
+            &["endpoint", "method"]
+        ).unwrap();
+
+        let solana_rpc_errors_total = CounterVec::new(
+            prometheus::Opts::new(
+                "solana_rpc_errors_total",
+                "Total number of failed Solana RPC calls"
+            ),
+            &["endpoint", "method"]
+        ).unwrap();
+
+        let solana_rpc_endpoint_healthy = IntGaugeVec::new(
+            prometheus::Opts::new(
+                "solana_rpc_endpoint_healthy",
+                "Whether the RPC endpoint passed the last health check"
+            ),
+            &["endpoint"]
         ).unwrap();
 
         let solana_transactions_total = Counter::new(
@@ -115,6 +156,8 @@ impl MetricsRegistry {
         registry.register(Box::new(trades_failed.clone())).unwrap();
         registry.register(Box::new(trade_amount.clone())).unwrap();
         registry.register(Box::new(trade_duration.clone())).unwrap();
+        registry.register(Box::new(priority_fee_lamports.clone())).unwrap();
+        registry.register(Box::new(priority_fee_trades.clone())).unwrap();
 
         registry.register(Box::new(api_requests_total.clone())).unwrap();
         registry.register(Box::new(api_request_duration.clone())).unwrap();
@@ -125,6 +168,8 @@ impl MetricsRegistry {
 
         registry.register(Box::new(solana_rpc_calls_total.clone())).unwrap();
         registry.register(Box::new(solana_rpc_duration.clone())).unwrap();
+        registry.register(Box::new(solana_rpc_errors_total.clone())).unwrap();
+        registry.register(Box::new(solana_rpc_endpoint_healthy.clone())).unwrap();
         registry.register(Box::new(solana_transactions_total.clone())).unwrap();
 
         Self {
@@ -134,6 +179,8 @@ impl MetricsRegistry {
             trades_failed,
             trade_amount,
             trade_duration,
+            priority_fee_lamports,
+            priority_fee_trades,
             api_requests_total,
             api_request_duration,
             api_errors_total,
@@ -141,6 +188,8 @@ impl MetricsRegistry {
             telegram_commands_total,
             solana_rpc_calls_total,
             solana_rpc_duration,
+            solana_rpc_errors_total,
+            solana_rpc_endpoint_healthy,
             solana_transactions_total,
         }
     }
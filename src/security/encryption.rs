@@ -1,25 +1,48 @@
+use aes_gcm::{
+    Aes256Gcm, Nonce,
+    aead::{Aead, KeyInit},
+};
 use anyhow::Result;
-use secrecy::SecretString;
+use secrecy::{ExposeSecret, SecretString};
+use sha2::{Digest, Sha256};
+
+const NONCE_LEN: usize = 12;
 
 pub struct AesGcmEncryption {
-    key: Vec<u8>,
+    cipher: Aes256Gcm,
 }
 
 impl AesGcmEncryption {
-    pub fn new(_key: &SecretString) -> Result<Self> {
-        // TODO: Implement AES-GCM encryption
-        Ok(Self {
-            key: Vec::new(),
-        })
+    pub fn new(key: &SecretString) -> Result<Self> {
+        // Мастер-ключ произвольной длины приводим к 256 битам
+        let digest = Sha256::digest(key.expose_secret().as_bytes());
+        let cipher = Aes256Gcm::new_from_slice(&digest)
+            .map_err(|e| anyhow::anyhow!("Invalid encryption key: {}", e))?;
+
+        Ok(Self { cipher })
     }
 
-    pub fn encrypt(&self, _data: &[u8]) -> Result<Vec<u8>> {
-        // TODO: Implement encryption
-        Ok(Vec::new())
+    /// Формат результата: nonce (12 байт) || ciphertext
+    pub fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let nonce_bytes: [u8; NONCE_LEN] = rand::random();
+        let ciphertext = self.cipher
+            .encrypt(Nonce::from_slice(&nonce_bytes), data)
+            .map_err(|_| anyhow::anyhow!("Encryption failed"))?;
+
+        let mut result = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        result.extend_from_slice(&nonce_bytes);
+        result.extend_from_slice(&ciphertext);
+        Ok(result)
     }
 
-    pub fn decrypt(&self, _data: &[u8]) -> Result<Vec<u8>> {
-        // TODO: Implement decryption
-        Ok(Vec::new())
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        if data.len() <= NONCE_LEN {
+            return Err(anyhow::anyhow!("Encrypted data is too short"));
+        }
+
+        let (nonce_bytes, ciphertext) = data.split_at(NONCE_LEN);
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+            .map_err(|_| anyhow::anyhow!("Decryption failed"))
     }
 }
@@ -1,2 +1,3 @@
 pub mod secrets_manager;
 pub mod encryption;
+pub mod wallet_keys;
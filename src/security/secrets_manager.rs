@@ -1,6 +1,6 @@
 use crate::config::Settings;
 use secrecy::{ExposeSecret, SecretString};
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 use anyhow::{Result, Context};
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -50,6 +50,14 @@ impl SecretsManager {
         })
     }
 
+    /// Готовые секреты без загрузки из окружения, например в тестах
+    pub fn from_secrets(secrets: BotSecrets) -> Self {
+        Self {
+            secrets: Arc::new(RwLock::new(secrets)),
+            backend: SecretsBackend::Environment,
+        }
+    }
+
     async fn load_from_env(settings: &Settings) -> Result<BotSecrets> {
         dotenv().ok();
 
@@ -0,0 +1,42 @@
+use anyhow::{Context, Result};
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair};
+
+use crate::{
+    database::repositories::WalletRepository,
+    entities::wallets,
+    security::{encryption::AesGcmEncryption, secrets_manager::SecretsManager},
+};
+
+async fn find_default_wallet(wallets: &dyn WalletRepository, user_id: i64) -> Result<wallets::Model> {
+    wallets.default_wallet(user_id)
+        .await?
+        .context("Default wallet not found")
+}
+
+/// Загрузка и расшифровка ключа кошелька пользователя по умолчанию
+pub async fn load_default_keypair(
+    wallets: &dyn WalletRepository,
+    secrets: &SecretsManager,
+    user_id: i64,
+) -> Result<Keypair> {
+    let wallet = find_default_wallet(wallets, user_id).await?;
+    decrypt_keypair(secrets, &wallet.encrypted_private_key).await
+}
+
+/// Адрес кошелька по умолчанию без расшифровки ключа
+pub async fn default_wallet_address(wallets: &dyn WalletRepository, user_id: i64) -> Result<Pubkey> {
+    let wallet = find_default_wallet(wallets, user_id).await?;
+    wallet.public_key.parse().context("Invalid wallet public key")
+}
+
+/// Приватный ключ хранится в БД как base64(nonce || ciphertext)
+pub async fn decrypt_keypair(secrets: &SecretsManager, encrypted: &str) -> Result<Keypair> {
+    let master_key = secrets.get_master_encryption_key().await;
+    let encryption = AesGcmEncryption::new(&master_key)?;
+
+    let raw = BASE64.decode(encrypted).context("Invalid base64 in encrypted private key")?;
+    let decrypted = zeroize::Zeroizing::new(encryption.decrypt(&raw)?);
+
+    Keypair::try_from(decrypted.as_slice()).map_err(|_| anyhow::anyhow!("Invalid keypair bytes"))
+}
@@ -0,0 +1,253 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use bigdecimal::{BigDecimal, ToPrimitive};
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+use crate::solana::constants::SOL_DECIMALS;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AmountError {
+    #[error("укажите сумму")]
+    Empty,
+    #[error("некорректная сумма «{0}»")]
+    Invalid(String),
+    #[error("сумма должна быть больше нуля")]
+    NotPositive,
+    #[error("у токена только {0} знаков после запятой")]
+    TooPrecise(u8),
+    #[error("слишком большая сумма")]
+    Overflow,
+    #[error("доля должна быть больше 0% и не больше 100%")]
+    InvalidPercent,
+    #[error("max и доля в процентах здесь не поддерживаются")]
+    BalanceRequired,
+}
+
+/// Точная сумма токена в минимальных единицах (лампортах для SOL) вместе с числом знаков mint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TokenAmount {
+    raw: u64,
+    decimals: u8,
+}
+
+impl TokenAmount {
+    pub const fn new(raw: u64, decimals: u8) -> Self {
+        Self { raw, decimals }
+    }
+
+    pub const fn sol(lamports: u64) -> Self {
+        Self::new(lamports, SOL_DECIMALS)
+    }
+
+    pub const fn zero(decimals: u8) -> Self {
+        Self::new(0, decimals)
+    }
+
+    pub const fn raw(&self) -> u64 {
+        self.raw
+    }
+
+    pub const fn decimals(&self) -> u8 {
+        self.decimals
+    }
+
+    pub const fn is_zero(&self) -> bool {
+        self.raw == 0
+    }
+
+    /// Точная конвертация: больше знаков после запятой, чем у mint, — ошибка, а не округление
+    pub fn from_decimal(value: Decimal, decimals: u8) -> Result<Self, AmountError> {
+        let value = value.normalize();
+        if value.scale() > decimals as u32 {
+            return Err(AmountError::TooPrecise(decimals));
+        }
+        Self::scale_up(value, decimals)
+    }
+
+    /// Конвертация с отбрасыванием знаков, которых нет у mint (для лимитов из настроек)
+    pub fn from_decimal_trunc(value: Decimal, decimals: u8) -> Result<Self, AmountError> {
+        Self::scale_up(value.trunc_with_scale(decimals as u32).normalize(), decimals)
+    }
+
+    fn scale_up(value: Decimal, decimals: u8) -> Result<Self, AmountError> {
+        if value.is_sign_negative() && !value.is_zero() {
+            return Err(AmountError::NotPositive);
+        }
+        let raw = 10i128
+            .checked_pow(decimals as u32 - value.scale())
+            .and_then(|multiplier| value.mantissa().checked_mul(multiplier))
+            .and_then(|raw| u64::try_from(raw).ok())
+            .ok_or(AmountError::Overflow)?;
+        Ok(Self::new(raw, decimals))
+    }
+
+    /// Сумма SOL из настроек: отрицательная считается нулем, слишком большая — максимумом
+    pub fn sol_setting(value: Decimal) -> Self {
+        match Self::from_decimal_trunc(value, SOL_DECIMALS) {
+            Ok(amount) => amount,
+            Err(AmountError::Overflow) => Self::sol(u64::MAX),
+            Err(_) => Self::zero(SOL_DECIMALS),
+        }
+    }
+
+    pub fn to_decimal(&self) -> Decimal {
+        // rust_decimal хранит не больше 28 знаков; у реальных mint их намного меньше
+        let scale = self.decimals.min(Decimal::MAX_SCALE as u8);
+        let raw = self.raw / 10u64.checked_pow((self.decimals - scale) as u32).unwrap_or(u64::MAX);
+        Decimal::from_i128_with_scale(raw as i128, scale as u32)
+    }
+
+    /// Сумма из БД; знаки сверх точности mint отбрасываются
+    pub fn from_big_decimal(value: &BigDecimal, decimals: u8) -> Result<Self, AmountError> {
+        if value < &BigDecimal::from(0) {
+            return Err(AmountError::NotPositive);
+        }
+        let raw = value
+            .with_scale(decimals as i64)
+            .as_bigint_and_exponent()
+            .0
+            .to_u64()
+            .ok_or(AmountError::Overflow)?;
+        Ok(Self::new(raw, decimals))
+    }
+
+    pub fn to_big_decimal(&self) -> BigDecimal {
+        BigDecimal::new(self.raw.into(), self.decimals as i64)
+    }
+
+    /// Сумма, введенная пользователем: «1.5» или «1,5»
+    pub fn parse(input: &str, decimals: u8) -> Result<Self, AmountError> {
+        match input.parse::<AmountInput>()? {
+            AmountInput::Exact(value) => Self::from_decimal(value, decimals),
+            AmountInput::Max | AmountInput::Percent(_) => Err(AmountError::BalanceRequired),
+        }
+    }
+
+    /// Доля суммы в процентах с округлением вниз
+    pub fn percent(&self, percent: Decimal) -> Self {
+        let raw = (Decimal::from(self.raw) * percent / Decimal::ONE_HUNDRED)
+            .trunc()
+            .to_u64()
+            .unwrap_or(self.raw)
+            .min(self.raw);
+        Self::new(raw, self.decimals)
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        if self.decimals != other.decimals {
+            return None;
+        }
+        Some(Self::new(self.raw.checked_add(other.raw)?, self.decimals))
+    }
+
+    pub fn saturating_sub(self, other: Self) -> Self {
+        if self.decimals != other.decimals {
+            return self;
+        }
+        Self::new(self.raw.saturating_sub(other.raw), self.decimals)
+    }
+
+    /// Приближенное значение для цен и метрик; для сумм сделок использовать нельзя
+    pub fn to_f64(&self) -> f64 {
+        self.raw as f64 / 10f64.powi(self.decimals as i32)
+    }
+}
+
+/// Суммы сравнимы только при одинаковом числе знаков
+impl PartialOrd for TokenAmount {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (self.decimals == other.decimals).then(|| self.raw.cmp(&other.raw))
+    }
+}
+
+/// Все знаки mint без лишних нулей: `1.5`, `0.000000001`. С точностью (`{:.4}`) —
+/// ровно столько знаков, лишние отбрасываются.
+impl fmt::Display for TokenAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = format!("{:0>width$}", self.raw, width = self.decimals as usize + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - self.decimals as usize);
+
+        let fraction = match f.precision() {
+            Some(precision) if precision <= fraction.len() => fraction[..precision].to_string(),
+            Some(precision) => format!("{:0<precision$}", fraction),
+            None => fraction.trim_end_matches('0').to_string(),
+        };
+
+        if fraction.is_empty() {
+            write!(f, "{}", integer)
+        } else {
+            write!(f, "{}.{}", integer, fraction)
+        }
+    }
+}
+
+/// Сумма в команде пользователя: точное число, весь баланс или доля баланса
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmountInput {
+    Exact(Decimal),
+    Max,
+    Percent(Decimal),
+}
+
+impl AmountInput {
+    pub fn needs_balance(&self) -> bool {
+        !matches!(self, Self::Exact(_))
+    }
+
+    /// Сумма в знаках mint; для max и доли нужен доступный баланс
+    pub fn resolve(&self, decimals: u8, available: Option<TokenAmount>) -> Result<TokenAmount, AmountError> {
+        let amount = match self {
+            Self::Exact(value) => TokenAmount::from_decimal(*value, decimals)?,
+            Self::Max => available.ok_or(AmountError::BalanceRequired)?,
+            Self::Percent(percent) => available.ok_or(AmountError::BalanceRequired)?.percent(*percent),
+        };
+
+        if amount.is_zero() {
+            return Err(AmountError::NotPositive);
+        }
+        Ok(amount)
+    }
+}
+
+impl FromStr for AmountInput {
+    type Err = AmountError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(AmountError::Empty);
+        }
+        if matches!(input.to_lowercase().as_str(), "max" | "all" | "все" | "всё") {
+            return Ok(Self::Max);
+        }
+
+        let (number, is_percent) = match input.strip_suffix('%') {
+            Some(number) => (number.trim_end(), true),
+            None => (input, false),
+        };
+        // Только цифры и одна точка или запятая: без экспоненты, знака и разделителей разрядов
+        let number = number.replace(',', ".");
+        let valid = !number.is_empty()
+            && number.chars().all(|c| c.is_ascii_digit() || c == '.')
+            && number.matches('.').count() <= 1
+            && number.chars().any(|c| c.is_ascii_digit());
+        if !valid {
+            return Err(AmountError::Invalid(input.to_string()));
+        }
+        let value = Decimal::from_str_exact(&number).map_err(|_| AmountError::Invalid(input.to_string()))?;
+
+        if is_percent {
+            if value.is_zero() || value > Decimal::ONE_HUNDRED {
+                return Err(AmountError::InvalidPercent);
+            }
+            return Ok(Self::Percent(value));
+        }
+        if value.is_zero() {
+            return Err(AmountError::NotPositive);
+        }
+        Ok(Self::Exact(value))
+    }
+}
@@ -1,4 +1,6 @@
-// Основные токены на Solana
+use solana_sdk::pubkey::Pubkey;
+
+// Адреса токенов, которые пользователь может указать по символу
 pub const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
 pub const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
 pub const USDT_MINT: &str = "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB";
@@ -6,20 +8,53 @@ pub const BONK_MINT: &str = "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263";
 pub const RAY_MINT: &str = "4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkX6R";
 pub const ORCA_MINT: &str = "orcaEKTdK7LKz57vaAYr9QeNsVEPfiu6QeMU1kektZE";
 
-// Декimals для токенов
+// Лампортов в SOL; знаки остальных токенов читаются из mint через `token_metadata::TokenResolver`
 pub const SOL_DECIMALS: u8 = 9;
-pub const USDC_DECIMALS: u8 = 6;
-pub const USDT_DECIMALS: u8 = 6;
-pub const BONK_DECIMALS: u8 = 5;
-pub const RAY_DECIMALS: u8 = 6;
-pub const ORCA_DECIMALS: u8 = 6;
-
-// Конвертация суммы в лампорты/минимальные единицы
-pub fn to_lamports(amount: f64, decimals: u8) -> u64 {
-    (amount * 10_f64.powi(decimals as i32)) as u64
-}
 
-// Конвертация из лампортов/минимальных единиц
+// Приближенная конвертация из минимальных единиц для цен и метрик;
+// точные суммы — через `amount::TokenAmount`
 pub fn from_lamports(lamports: u64, decimals: u8) -> f64 {
     lamports as f64 / 10_f64.powi(decimals as i32)
 }
+
+// Псевдонимы известных токенов для команд пользователя
+#[derive(Debug, Clone, Copy)]
+pub struct KnownToken {
+    pub symbol: &'static str,
+    pub mint: &'static str,
+}
+
+pub const KNOWN_TOKENS: &[KnownToken] = &[
+    KnownToken { symbol: "SOL", mint: SOL_MINT },
+    KnownToken { symbol: "USDC", mint: USDC_MINT },
+    KnownToken { symbol: "USDT", mint: USDT_MINT },
+    KnownToken { symbol: "BONK", mint: BONK_MINT },
+    KnownToken { symbol: "RAY", mint: RAY_MINT },
+    KnownToken { symbol: "ORCA", mint: ORCA_MINT },
+];
+
+// Поиск известного токена по символу (без учета регистра) или адресу mint
+pub fn find_known_token(query: &str) -> Option<&'static KnownToken> {
+    KNOWN_TOKENS
+        .iter()
+        .find(|token| token.symbol.eq_ignore_ascii_case(query) || token.mint == query)
+}
+
+/// Символ и количество знаков токена для отображения сумм
+#[derive(Debug, Clone)]
+pub struct TokenMeta {
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+/// Сокращенный адрес для токенов без известного символа
+pub fn short_address(mint: &Pubkey) -> String {
+    let address = mint.to_string();
+    format!("{}…{}", &address[..4], &address[address.len() - 4..])
+}
+
+pub fn token_symbol(mint: &Pubkey) -> String {
+    find_known_token(&mint.to_string())
+        .map(|token| token.symbol.to_string())
+        .unwrap_or_else(|| short_address(mint))
+}
@@ -0,0 +1,312 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use chrono::Utc;
+use sea_orm::DbErr;
+use serde_json::json;
+use solana_sdk::signature::Signature;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    database::{
+        repositories::Repositories,
+        trade_events::NewTradeEvent,
+        trades::{NewTrade, TransitionError},
+    },
+    entities::{
+        fee_preferences::PriorityTier,
+        trade_events::TradeEventKind,
+        trades::{Model as Trade, TradeStatus, TradeType},
+    },
+    jupiter::QuoteResponseV6,
+    monitoring::metrics::MetricsRegistry,
+    security::{secrets_manager::SecretsManager, wallet_keys},
+    solana::{
+        constants::{SOL_DECIMALS, TokenMeta, from_lamports},
+        priority_fee::PriorityFeeEstimator,
+        sender::LandingError,
+        token_safety::TokenSafety,
+        trader::{PreparedSwap, TradeError, Trader, worst_case_sol_lamports},
+    },
+};
+
+#[derive(Debug, Error)]
+pub enum ExecutionError {
+    #[error("не найден кошелек по умолчанию")]
+    Wallet(#[source] anyhow::Error),
+    #[error(transparent)]
+    Trade(#[from] TradeError),
+    #[error("не удалось сохранить сделку, она не была отправлена")]
+    Database(#[from] DbErr),
+    #[error("не удалось проверить безопасность токена")]
+    SafetyCheck(#[source] anyhow::Error),
+    #[error("покупка заблокирована проверкой безопасности: {}", .0.join("; "))]
+    UnsafeToken(Vec<String>),
+    #[error("не удалось начать исполнение, сделка не была отправлена")]
+    Transition(#[from] TransitionError),
+    #[error("сделка изменилась до отправки, она не была отправлена")]
+    Conflict(Uuid),
+    #[error("{error}")]
+    Send { trade_id: Uuid, error: TradeError },
+}
+
+impl ExecutionError {
+    /// Транзакция отправлена, но ее итог неизвестен: сделка остается в исполнении
+    /// и еще может попасть в блок, итог покажет сверка
+    pub fn is_unconfirmed(&self) -> bool {
+        matches!(self, Self::Send { error: TradeError::Landing(LandingError::Unknown(_)), .. })
+    }
+}
+
+/// Сделка по котировке от имени пользователя
+pub struct SwapOrder<'a> {
+    pub user_id: i64,
+    pub trade_type: TradeType,
+    pub quote: &'a QuoteResponseV6,
+    pub input: TokenMeta,
+    pub output: TokenMeta,
+    pub jupiter_quote_id: Option<String>,
+    // Защитный выход из позиции: лимиты на количество сделок и суточный объем его не останавливают
+    pub exit: bool,
+}
+
+/// Подписанная сделка, записанная в БД до отправки
+pub struct RecordedSwap {
+    pub trade: Trade,
+    prepared: PreparedSwap,
+    priority_tier: PriorityTier,
+    sol_lamports: Option<u64>,
+    started_at: Instant,
+}
+
+pub struct ExecutedSwap {
+    pub trade_id: Uuid,
+    pub signature: Signature,
+}
+
+/// Общий путь исполнения свапа для Telegram и фоновых задач:
+/// загрузка кошелька, подпись, запись сделки, отправка и фиксация результата
+pub struct SwapExecutor {
+    repositories: Repositories,
+    secrets: Arc<SecretsManager>,
+    metrics: Arc<MetricsRegistry>,
+    trader: Arc<Trader>,
+    priority_fees: Arc<PriorityFeeEstimator>,
+    token_safety: Option<Arc<TokenSafety>>,
+}
+
+impl SwapExecutor {
+    pub fn new(
+        repositories: Repositories,
+        secrets: Arc<SecretsManager>,
+        metrics: Arc<MetricsRegistry>,
+        trader: Arc<Trader>,
+        priority_fees: Arc<PriorityFeeEstimator>,
+    ) -> Self {
+        Self {
+            repositories,
+            secrets,
+            metrics,
+            trader,
+            priority_fees,
+            token_safety: None,
+        }
+    }
+
+    /// Проверка безопасности токена перед каждой покупкой
+    pub fn with_token_safety(mut self, token_safety: Arc<TokenSafety>) -> Self {
+        self.token_safety = Some(token_safety);
+        self
+    }
+
+    pub fn trader(&self) -> &Arc<Trader> {
+        &self.trader
+    }
+
+    pub fn repositories(&self) -> &Repositories {
+        &self.repositories
+    }
+
+    /// Подписывает сделку и записывает ее в БД, не отправляя.
+    /// Вызывающий может связать сделку со своей записью до отправки.
+    pub async fn record(&self, order: SwapOrder<'_>) -> Result<RecordedSwap, ExecutionError> {
+        let keypair = wallet_keys::load_default_keypair(
+            self.repositories.wallets.as_ref(),
+            &self.secrets,
+            order.user_id,
+        )
+        .await
+        .map_err(ExecutionError::Wallet)?;
+
+        if !order.exit {
+            let activity = self.repositories.trades.activity(order.user_id, Utc::now()).await?;
+            self.trader.check_activity_limits(&activity, order.quote)?;
+        }
+
+        if order.trade_type == TradeType::Buy && let Some(token_safety) = &self.token_safety {
+            let report = token_safety.check(order.quote).await.map_err(ExecutionError::SafetyCheck)?;
+            if let Some(reasons) = report
+                .map(|report| report.block_reasons(token_safety.settings()))
+                .filter(|reasons| !reasons.is_empty())
+            {
+                return Err(ExecutionError::UnsafeToken(reasons));
+            }
+        }
+
+        let preference = self.repositories.users.fee_preference(order.user_id).await?;
+        let priority_tier = preference
+            .as_ref()
+            .map(|preference| preference.tier)
+            .unwrap_or(self.priority_fees.settings().default_tier);
+        let max_lamports = preference.and_then(|preference| preference.max_lamports).map(|lamports| lamports as u64);
+        let prioritization_fee = self.priority_fees.fee_for(order.quote, priority_tier, max_lamports).await;
+
+        // Защитный выход ограничен только потолком проскальзывания
+        if order.exit {
+            self.trader.check_exit_limits(order.quote)?;
+        } else {
+            self.trader.check_limits(order.quote)?;
+        }
+
+        self.metrics.trades_total.inc();
+        let started_at = Instant::now();
+
+        let prepared = self.trader
+            .prepare_swap(order.quote, &keypair, prioritization_fee)
+            .await
+            .inspect_err(|_| self.metrics.trades_failed.inc())?;
+
+        // Сделка записывается до отправки, чтобы не потерять ее при сбое
+        let trade = self.repositories.trades.create_pending(NewTrade {
+            user_id: order.user_id,
+            trade_type: order.trade_type,
+            quote: order.quote,
+            input_symbol: order.input.symbol,
+            output_symbol: order.output.symbol,
+            input_decimals: order.input.decimals,
+            output_decimals: order.output.decimals,
+            transaction_signature: prepared.signature.to_string(),
+            jupiter_quote_id: order.jupiter_quote_id,
+            priority_fee_lamports: prepared.priority_fee_lamports,
+            priority_tier,
+        })
+        .await?;
+
+        Ok(RecordedSwap {
+            trade,
+            prepared,
+            priority_tier,
+            sol_lamports: worst_case_sol_lamports(order.quote),
+            started_at,
+        })
+    }
+
+    /// Отправляет записанную сделку и проводит ее по статусам: исполнение перед отправкой,
+    /// затем завершенная или неудачная. Сделка, не дошедшая до отправки, снимается.
+    pub async fn send(&self, recorded: RecordedSwap) -> Result<ExecutedSwap, ExecutionError> {
+        let trade_id = recorded.trade.id;
+        let trades = &self.repositories.trades;
+
+        // Симуляция до отправки: сделка с ошибкой снимается без комиссии сети
+        let (executing, result) = match self.simulate(trade_id, &recorded.prepared).await {
+            Ok(()) => {
+                let executing = self.start_execution(trade_id, &recorded.prepared).await?;
+                (Some(executing), self.trader.send_swap(&recorded.prepared).await)
+            }
+            Err(error) => (None, Err(error)),
+        };
+
+        let tier = recorded.priority_tier.name();
+        match result {
+            Ok(signature) => {
+                self.metrics.priority_fee_lamports
+                    .with_label_values(&[tier])
+                    .observe(recorded.prepared.priority_fee_lamports as f64);
+                self.metrics.priority_fee_trades.with_label_values(&[tier, "landed"]).inc();
+                self.metrics.trade_duration.observe(recorded.started_at.elapsed().as_secs_f64());
+                self.metrics.trades_success.inc();
+                if let Some(lamports) = recorded.sol_lamports {
+                    self.metrics.trade_amount.observe(from_lamports(lamports, SOL_DECIMALS));
+                }
+                let event = NewTradeEvent::new(TradeEventKind::Confirmed)
+                    .with_details(json!({"signature": signature.to_string()}));
+                // Отправка возможна только после перехода в исполнение
+                if let Some(executing) = &executing
+                    && let Err(e) = trades.transition(executing, TradeStatus::Completed, event).await
+                {
+                    tracing::error!("Failed to mark trade {} completed: {}", trade_id, e);
+                }
+
+                Ok(ExecutedSwap { trade_id, signature })
+            }
+            Err(error) => {
+                // Провал симуляции не связан с комиссией: транзакция не отправлялась
+                match &error {
+                    TradeError::Simulation(_) => {}
+                    TradeError::Landing(landing) if landing.is_retryable() => {
+                        self.metrics.priority_fee_trades.with_label_values(&[tier, "expired"]).inc();
+                    }
+                    _ => self.metrics.priority_fee_trades.with_label_values(&[tier, "failed"]).inc(),
+                }
+                self.metrics.trades_failed.inc();
+                tracing::warn!("Swap {} failed: {:#}", trade_id, error);
+
+                let message = error.to_string();
+                let finished: Result<(), TransitionError> = match executing {
+                    // Транзакция с неизвестным статусом еще может попасть в блок:
+                    // сделка остается в исполнении до сверки по подписи, в историю пишется ошибка
+                    Some(_) if matches!(error, TradeError::Landing(LandingError::Unknown(_))) => {
+                        let event = NewTradeEvent::error(message);
+                        trades.record_event(trade_id, TradeStatus::Executing, event).await.map_err(Into::into)
+                    }
+                    Some(executing) => {
+                        let event = NewTradeEvent::error(message);
+                        trades.transition(&executing, TradeStatus::Failed, event).await.map(drop)
+                    }
+                    None => {
+                        let event = NewTradeEvent::cancelled(message);
+                        trades.transition_by_id(trade_id, TradeStatus::Cancelled, event).await.map(drop)
+                    }
+                };
+                if let Err(e) = finished {
+                    tracing::error!("Failed to record failure of trade {}: {}", trade_id, e);
+                }
+
+                Err(ExecutionError::Send { trade_id, error })
+            }
+        }
+    }
+
+    /// Переводит сделку в исполнение перед отправкой: снятую или измененную сделку отправлять нельзя
+    async fn start_execution(&self, trade_id: Uuid, prepared: &PreparedSwap) -> Result<Trade, ExecutionError> {
+        let event = NewTradeEvent::new(TradeEventKind::Sent)
+            .with_details(json!({"last_valid_block_height": prepared.last_valid_block_height}));
+        self.repositories.trades.transition_by_id(trade_id, TradeStatus::Executing, event)
+            .await?
+            .ok_or(ExecutionError::Conflict(trade_id))
+    }
+
+    /// Симулирует транзакцию и сохраняет результат в сделке
+    async fn simulate(&self, trade_id: Uuid, prepared: &PreparedSwap) -> Result<(), TradeError> {
+        let simulation = self.trader.simulate_swap(prepared).await?;
+        tracing::debug!(
+            "Simulated trade {}: {:?} CU, error {:?}",
+            trade_id, simulation.units_consumed, simulation.error,
+        );
+
+        if let Err(e) = self.repositories.trades.set_simulation(trade_id, &simulation).await {
+            tracing::error!("Failed to store simulation of trade {}: {}", trade_id, e);
+        }
+
+        match simulation.failure() {
+            Some(failure) => Err(failure.into()),
+            None => Ok(()),
+        }
+    }
+
+    pub async fn execute(&self, order: SwapOrder<'_>) -> Result<ExecutedSwap, ExecutionError> {
+        let recorded = self.record(order).await?;
+        self.send(recorded).await
+    }
+}
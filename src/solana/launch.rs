@@ -0,0 +1,211 @@
+use std::str::FromStr;
+
+use serde::Serialize;
+use solana_client::rpc_response::RpcTokenAccountBalance;
+use solana_sdk::pubkey::Pubkey;
+use spl_token::solana_program::program_pack::Pack;
+
+/// Программа Token-2022: базовая часть mint совпадает с SPL Token
+pub const TOKEN_2022_PROGRAM: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum PoolKind {
+    RaydiumAmm,
+    RaydiumCpmm,
+    OrcaWhirlpool,
+    MeteoraDlmm,
+}
+
+impl PoolKind {
+    pub fn from_program(program: &Pubkey) -> Option<Self> {
+        match program.to_string().as_str() {
+            "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8" => Some(Self::RaydiumAmm),
+            "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C" => Some(Self::RaydiumCpmm),
+            "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc" => Some(Self::OrcaWhirlpool),
+            "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo" => Some(Self::MeteoraDlmm),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::RaydiumAmm => "Raydium AMM",
+            Self::RaydiumCpmm => "Raydium CPMM",
+            Self::OrcaWhirlpool => "Orca Whirlpool",
+            Self::MeteoraDlmm => "Meteora DLMM",
+        }
+    }
+}
+
+/// Аккаунт пула, разобранный по раскладке его программы
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolState {
+    pub kind: PoolKind,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub vault_a: Pubkey,
+    pub vault_b: Pubkey,
+    // LP-токен есть только у пулов с постоянным продуктом; у концентрированной ликвидности — позиции-NFT
+    pub lp_mint: Option<Pubkey>,
+    // Сколько LP выпущено по учету пула; сожженные LP из supply mint уходят, а отсюда нет
+    pub lp_issued: Option<u64>,
+}
+
+fn pubkey_at(data: &[u8], offset: usize) -> Option<Pubkey> {
+    let bytes: [u8; 32] = data.get(offset..offset + 32)?.try_into().ok()?;
+    Some(Pubkey::new_from_array(bytes))
+}
+
+fn u64_at(data: &[u8], offset: usize) -> Option<u64> {
+    let bytes: [u8; 8] = data.get(offset..offset + 8)?.try_into().ok()?;
+    Some(u64::from_le_bytes(bytes))
+}
+
+impl PoolState {
+    /// Разбирает аккаунт пула известной программы; `None` для других программ и битых данных
+    pub fn decode(owner: &Pubkey, data: &[u8]) -> Option<Self> {
+        let kind = PoolKind::from_program(owner)?;
+
+        // Смещения полей в аккаунтах пулов (у Anchor-программ с 8-байтным дискриминатором)
+        let state = match kind {
+            PoolKind::RaydiumAmm => Self {
+                kind,
+                vault_a: pubkey_at(data, 336)?,
+                vault_b: pubkey_at(data, 368)?,
+                mint_a: pubkey_at(data, 400)?,
+                mint_b: pubkey_at(data, 432)?,
+                lp_mint: Some(pubkey_at(data, 464)?),
+                lp_issued: Some(u64_at(data, 720)?),
+            },
+            PoolKind::RaydiumCpmm => Self {
+                kind,
+                vault_a: pubkey_at(data, 72)?,
+                vault_b: pubkey_at(data, 104)?,
+                lp_mint: Some(pubkey_at(data, 136)?),
+                mint_a: pubkey_at(data, 168)?,
+                mint_b: pubkey_at(data, 200)?,
+                lp_issued: Some(u64_at(data, 333)?),
+            },
+            PoolKind::OrcaWhirlpool => Self {
+                kind,
+                mint_a: pubkey_at(data, 101)?,
+                vault_a: pubkey_at(data, 133)?,
+                mint_b: pubkey_at(data, 181)?,
+                vault_b: pubkey_at(data, 213)?,
+                lp_mint: None,
+                lp_issued: None,
+            },
+            PoolKind::MeteoraDlmm => Self {
+                kind,
+                mint_a: pubkey_at(data, 88)?,
+                mint_b: pubkey_at(data, 120)?,
+                vault_a: pubkey_at(data, 152)?,
+                vault_b: pubkey_at(data, 184)?,
+                lp_mint: None,
+                lp_issued: None,
+            },
+        };
+
+        Some(state)
+    }
+}
+
+/// Базовые поля mint SPL Token или Token-2022
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MintState {
+    pub mint_authority: Option<Pubkey>,
+    pub freeze_authority: Option<Pubkey>,
+    pub supply: u64,
+    pub decimals: u8,
+}
+
+impl MintState {
+    pub fn decode(owner: &Pubkey, data: &[u8]) -> Option<Self> {
+        if *owner != spl_token::id() && owner.to_string() != TOKEN_2022_PROGRAM {
+            return None;
+        }
+
+        let mint = spl_token::state::Mint::unpack_from_slice(data.get(..spl_token::state::Mint::LEN)?).ok()?;
+        if !mint.is_initialized {
+            return None;
+        }
+
+        Some(Self {
+            mint_authority: mint.mint_authority.into(),
+            freeze_authority: mint.freeze_authority.into(),
+            supply: mint.supply,
+            decimals: mint.decimals,
+        })
+    }
+}
+
+/// Результат проверок нового токена; сохраняется вместе с решением снайпера
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LaunchReport {
+    pub pool: Option<PoolKind>,
+    pub mint_authority: Option<String>,
+    pub freeze_authority: Option<String>,
+    pub supply: u64,
+    // `None`, если у пула нет LP-токена
+    pub lp_burned_pct: Option<f64>,
+    // Доля supply у крупнейших держателей без хранилищ пула
+    pub top_holders_pct: f64,
+}
+
+/// Доля сожженных LP: выпущено по учету пула против текущего supply LP mint
+pub fn lp_burned_pct(lp_issued: u64, lp_supply: u64) -> Option<f64> {
+    if lp_issued == 0 {
+        return None;
+    }
+
+    let burned = lp_issued.saturating_sub(lp_supply);
+    Some(burned as f64 / lp_issued as f64 * 100.0)
+}
+
+/// Ответ `getTokenLargestAccounts` в виде пар (аккаунт, количество)
+pub fn holder_balances(largest: &[RpcTokenAccountBalance]) -> Vec<(Pubkey, u64)> {
+    largest
+        .iter()
+        .filter_map(|holder| Some((Pubkey::from_str(&holder.address).ok()?, holder.amount.amount.parse().ok()?)))
+        .collect()
+}
+
+/// Доля supply у `count` крупнейших держателей. Хранилища пула держателями не считаются.
+pub fn top_holders_pct(supply: u64, largest: &[(Pubkey, u64)], excluded: &[Pubkey], count: usize) -> f64 {
+    if supply == 0 {
+        return 0.0;
+    }
+
+    let mut amounts: Vec<u64> = largest
+        .iter()
+        .filter(|(account, _)| !excluded.contains(account))
+        .map(|(_, amount)| *amount)
+        .collect();
+    amounts.sort_unstable_by(|a, b| b.cmp(a));
+
+    let held: u128 = amounts.iter().take(count).map(|amount| *amount as u128).sum();
+    held as f64 / supply as f64 * 100.0
+}
+
+pub fn build_report(
+    mint: &MintState,
+    pool: Option<&PoolState>,
+    lp_supply: Option<u64>,
+    largest: &[(Pubkey, u64)],
+    top_holders: usize,
+) -> LaunchReport {
+    let excluded: Vec<Pubkey> = pool.map(|pool| vec![pool.vault_a, pool.vault_b]).unwrap_or_default();
+    let lp_burned = match (pool.and_then(|pool| pool.lp_issued), lp_supply) {
+        (Some(issued), Some(supply)) => lp_burned_pct(issued, supply),
+        _ => None,
+    };
+
+    LaunchReport {
+        pool: pool.map(|pool| pool.kind),
+        mint_authority: mint.mint_authority.map(|key| key.to_string()),
+        freeze_authority: mint.freeze_authority.map(|key| key.to_string()),
+        supply: mint.supply,
+        lp_burned_pct: lp_burned,
+        top_holders_pct: top_holders_pct(mint.supply, largest, &excluded, top_holders),
+    }
+}
@@ -0,0 +1,14 @@
+pub mod amount;
+pub mod constants;
+pub mod executor;
+pub mod launch;
+pub mod priority_fee;
+pub mod pubsub;
+pub mod rpc;
+pub mod rpc_pool;
+pub mod sender;
+pub mod simulation;
+pub mod swap_decoder;
+pub mod token_metadata;
+pub mod token_safety;
+pub mod trader;
@@ -0,0 +1,220 @@
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use solana_client::rpc_response::RpcPrioritizationFee;
+use solana_sdk::{pubkey::Pubkey, transaction::VersionedTransaction};
+
+use crate::{
+    config::settings::PriorityFeeSettings,
+    entities::fee_preferences::PriorityTier,
+    jupiter::{PrioritizationFee, QuoteResponseV6},
+    solana::rpc::SolanaRpc,
+};
+
+pub const COMPUTE_BUDGET_PROGRAM: &str = "ComputeBudget111111111111111111111111111111";
+
+// Инструкции ComputeBudget: SetComputeUnitLimit(u32) и SetComputeUnitPrice(u64)
+const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+const SET_COMPUTE_UNIT_PRICE: u8 = 3;
+
+// Лимит по умолчанию, если транзакция его не задает
+const DEFAULT_COMPUTE_UNIT_LIMIT: u32 = 200_000;
+
+// `getRecentPrioritizationFees` принимает не больше 128 аккаунтов
+const MAX_FEE_ACCOUNTS: usize = 128;
+
+impl PriorityTier {
+    pub const ALL: [PriorityTier; 4] = [Self::Low, Self::Medium, Self::High, Self::Turbo];
+
+    /// Перцентиль недавних комиссий, который платит уровень
+    pub fn percentile(&self) -> f64 {
+        match self {
+            Self::Low => 25.0,
+            Self::Medium => 50.0,
+            Self::High => 75.0,
+            Self::Turbo => 95.0,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+            Self::Turbo => "turbo",
+        }
+    }
+
+    /// Ближайший уровень оценки самого Jupiter, если своей оценки нет
+    fn jupiter_level(&self) -> &'static str {
+        match self {
+            Self::Low | Self::Medium => "medium",
+            Self::High => "high",
+            Self::Turbo => "veryHigh",
+        }
+    }
+}
+
+impl FromStr for PriorityTier {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|tier| tier.name().eq_ignore_ascii_case(value))
+            .ok_or(())
+    }
+}
+
+/// Перцентиль по ближайшему рангу; `samples` должны быть отсортированы
+pub fn percentile(samples: &[u64], pct: f64) -> u64 {
+    if samples.is_empty() {
+        return 0;
+    }
+
+    let rank = (pct / 100.0 * samples.len() as f64).ceil() as usize;
+    samples[rank.clamp(1, samples.len()) - 1]
+}
+
+/// Цены единицы вычислений (микролампорты) по уровням
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeLevels {
+    pub low: u64,
+    pub medium: u64,
+    pub high: u64,
+    pub turbo: u64,
+}
+
+impl FeeLevels {
+    /// Уровни по ответу `getRecentPrioritizationFees`: слоты без комиссий тоже входят в выборку
+    pub fn from_samples(fees: &[RpcPrioritizationFee]) -> Self {
+        let mut samples: Vec<u64> = fees.iter().map(|fee| fee.prioritization_fee).collect();
+        samples.sort_unstable();
+
+        Self {
+            low: percentile(&samples, PriorityTier::Low.percentile()),
+            medium: percentile(&samples, PriorityTier::Medium.percentile()),
+            high: percentile(&samples, PriorityTier::High.percentile()),
+            turbo: percentile(&samples, PriorityTier::Turbo.percentile()),
+        }
+    }
+
+    pub fn price(&self, tier: PriorityTier) -> u64 {
+        match tier {
+            PriorityTier::Low => self.low,
+            PriorityTier::Medium => self.medium,
+            PriorityTier::High => self.high,
+            PriorityTier::Turbo => self.turbo,
+        }
+    }
+}
+
+/// Комиссия в лампортах за `compute_units` по цене в микролампортах, с округлением вверх
+pub fn fee_lamports(micro_lamports_per_cu: u64, compute_units: u32) -> u64 {
+    (micro_lamports_per_cu as u128 * compute_units as u128).div_ceil(1_000_000) as u64
+}
+
+/// Приоритетная комиссия подписанной транзакции по ее инструкциям ComputeBudget.
+/// Комиссия берется за запрошенный лимит, а не за фактически потраченные единицы.
+pub fn paid_priority_fee(transaction: &VersionedTransaction) -> u64 {
+    let Ok(program) = Pubkey::from_str(COMPUTE_BUDGET_PROGRAM) else {
+        return 0;
+    };
+    let keys = transaction.message.static_account_keys();
+
+    let mut limit = None;
+    let mut price = 0;
+    let mut instructions = 0u32;
+    for instruction in transaction.message.instructions() {
+        if keys.get(instruction.program_id_index as usize) != Some(&program) {
+            instructions += 1;
+            continue;
+        }
+        match instruction.data.split_first() {
+            Some((&SET_COMPUTE_UNIT_LIMIT, rest)) => {
+                limit = rest.get(..4).and_then(|bytes| Some(u32::from_le_bytes(bytes.try_into().ok()?)));
+            }
+            Some((&SET_COMPUTE_UNIT_PRICE, rest)) => {
+                price = rest
+                    .get(..8)
+                    .and_then(|bytes| Some(u64::from_le_bytes(bytes.try_into().ok()?)))
+                    .unwrap_or(0);
+            }
+            _ => {}
+        }
+    }
+
+    let limit = limit.unwrap_or(DEFAULT_COMPUTE_UNIT_LIMIT.saturating_mul(instructions));
+    fee_lamports(price, limit)
+}
+
+/// Потолок комиссии: пользовательский, но не выше общего из настроек
+pub fn fee_cap(user_max_lamports: Option<u64>, settings: &PriorityFeeSettings) -> u64 {
+    user_max_lamports
+        .unwrap_or(settings.default_max_lamports)
+        .min(settings.max_lamports)
+}
+
+/// Оценка приоритетной комиссии по недавним комиссиям за аккаунты свапа
+pub struct PriorityFeeEstimator {
+    rpc: Arc<dyn SolanaRpc>,
+    settings: PriorityFeeSettings,
+    cache: DashMap<Vec<Pubkey>, (FeeLevels, Instant)>,
+}
+
+impl PriorityFeeEstimator {
+    pub fn new(rpc: Arc<dyn SolanaRpc>, settings: PriorityFeeSettings) -> Self {
+        Self {
+            rpc,
+            settings,
+            cache: DashMap::new(),
+        }
+    }
+
+    pub fn settings(&self) -> &PriorityFeeSettings {
+        &self.settings
+    }
+
+    /// Уровни комиссий для аккаунтов; пустой список — по всей сети
+    pub async fn levels(&self, accounts: &[Pubkey]) -> anyhow::Result<FeeLevels> {
+        let mut key: Vec<Pubkey> = accounts.to_vec();
+        key.sort_unstable();
+        key.dedup();
+        key.truncate(MAX_FEE_ACCOUNTS);
+
+        let ttl = Duration::from_secs(self.settings.cache_ttl_secs);
+        if let Some(entry) = self.cache.get(&key).filter(|entry| entry.1.elapsed() < ttl) {
+            return Ok(entry.0);
+        }
+
+        let fees = self.rpc.get_recent_prioritization_fees(&key).await?;
+        let levels = FeeLevels::from_samples(&fees);
+        self.cache.insert(key, (levels, Instant::now()));
+
+        Ok(levels)
+    }
+
+    /// Комиссия для свапа по котировке. Пулы маршрута — записываемые аккаунты, за которые
+    /// идет конкуренция. Если оценка недоступна, комиссию оценивает Jupiter с тем же потолком.
+    pub async fn fee_for(
+        &self,
+        quote: &QuoteResponseV6,
+        tier: PriorityTier,
+        user_max_lamports: Option<u64>,
+    ) -> PrioritizationFee {
+        let cap = fee_cap(user_max_lamports, &self.settings);
+        let accounts: Vec<Pubkey> = quote.route_plan.iter().map(|step| step.swap_info.amm_key).collect();
+
+        match self.levels(&accounts).await {
+            Ok(levels) => {
+                PrioritizationFee::Lamports(fee_lamports(levels.price(tier), self.settings.compute_unit_estimate).min(cap))
+            }
+            Err(e) => {
+                tracing::warn!("Failed to estimate priority fee, falling back to Jupiter: {:#}", e);
+                PrioritizationFee::capped(tier.jupiter_level(), cap)
+            }
+        }
+    }
+}
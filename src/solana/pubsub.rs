@@ -0,0 +1,467 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+use futures::{FutureExt, StreamExt, future::BoxFuture, stream::FuturesUnordered};
+use solana_client::{
+    nonblocking::pubsub_client::{PubsubClient, PubsubClientError},
+    rpc_config::{RpcAccountInfoConfig, RpcSignatureSubscribeConfig, RpcTransactionLogsConfig, RpcTransactionLogsFilter},
+    rpc_response::{Response, RpcLogsResponse, RpcSignatureResult},
+};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::TransactionError};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::config::settings::SolanaSettings;
+
+/// На что подписываться: изменения аккаунта, подтверждение подписи или логи транзакций с адресом
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PubsubTopic {
+    Account(Pubkey),
+    Signature(Signature),
+    Logs(Pubkey),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PubsubEvent {
+    Account {
+        pubkey: Pubkey,
+        lamports: u64,
+        slot: u64,
+    },
+    /// Транзакция подтверждена; `error` — транзакция попала в блок с ошибкой
+    Signature {
+        signature: Signature,
+        slot: u64,
+        error: Option<TransactionError>,
+    },
+    Logs {
+        address: Pubkey,
+        signature: String,
+        failed: bool,
+        logs: Vec<String>,
+    },
+    /// Соединение восстановлено: события за время разрыва могли быть пропущены
+    Reconnected,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PubsubError {
+    #[error("достигнут лимит подписок ({0})")]
+    TooManySubscriptions(usize),
+    #[error("подписки недоступны: менеджер остановлен")]
+    Closed,
+}
+
+/// Уведомление о подписи; `None` для уведомления о получении транзакции узлом
+pub fn signature_event(signature: Signature, response: Response<RpcSignatureResult>) -> Option<PubsubEvent> {
+    match response.value {
+        RpcSignatureResult::ProcessedSignature(result) => Some(PubsubEvent::Signature {
+            signature,
+            slot: response.context.slot,
+            error: result.err.map(Into::into),
+        }),
+        RpcSignatureResult::ReceivedSignature(_) => None,
+    }
+}
+
+pub fn logs_event(address: Pubkey, response: Response<RpcLogsResponse>) -> PubsubEvent {
+    PubsubEvent::Logs {
+        address,
+        signature: response.value.signature,
+        failed: response.value.err.is_some(),
+        logs: response.value.logs,
+    }
+}
+
+/// Пауза перед `attempt`-й попыткой переподключения: 1, 2, 4... секунд, не больше `max`
+pub fn reconnect_delay(attempt: u32, max: Duration) -> Duration {
+    Duration::from_secs(1u64 << attempt.min(16)).min(max)
+}
+
+enum Command {
+    Subscribe { id: u64, topic: PubsubTopic, sender: mpsc::Sender<PubsubEvent> },
+    Unsubscribe { id: u64 },
+}
+
+/// Подписка активна, пока жив этот объект
+pub struct Subscription {
+    id: u64,
+    commands: mpsc::UnboundedSender<Command>,
+    count: Arc<AtomicUsize>,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.count.fetch_sub(1, Ordering::Relaxed);
+        let _ = self.commands.send(Command::Unsubscribe { id: self.id });
+    }
+}
+
+/// Подписки через WebSocket (`SolanaSettings.ws_url`) с переподключением.
+/// После разрыва все подписки восстанавливаются, а подписчики получают `Reconnected`.
+/// События доставляются в ограниченные каналы подписчиков: если подписчик не успевает,
+/// новые события для него отбрасываются, а не копятся в памяти.
+pub struct PubsubManager {
+    commands: mpsc::UnboundedSender<Command>,
+    next_id: AtomicU64,
+    count: Arc<AtomicUsize>,
+    max_subscriptions: usize,
+    channel_capacity: usize,
+}
+
+impl PubsubManager {
+    /// Создает менеджер и запускает фоновое соединение
+    pub fn start(settings: &SolanaSettings, commitment: CommitmentConfig) -> Arc<Self> {
+        let (commands, receiver) = mpsc::unbounded_channel();
+        let connection = Connection {
+            url: settings.ws_url.clone(),
+            commitment,
+            reconnect_max: Duration::from_secs(settings.pubsub_reconnect_max_secs),
+            channel_capacity: settings.pubsub_channel_capacity.max(1),
+            subscribers: HashMap::new(),
+        };
+        tokio::spawn(connection.run(receiver));
+
+        Arc::new(Self {
+            commands,
+            next_id: AtomicU64::new(1),
+            count: Arc::new(AtomicUsize::new(0)),
+            max_subscriptions: settings.pubsub_max_subscriptions,
+            channel_capacity: settings.pubsub_channel_capacity.max(1),
+        })
+    }
+
+    /// Канал подписчика с размером буфера из настроек
+    pub fn channel(&self) -> (mpsc::Sender<PubsubEvent>, mpsc::Receiver<PubsubEvent>) {
+        mpsc::channel(self.channel_capacity)
+    }
+
+    /// Подписывает `sender` на события темы; несколько подписчиков одной темы делят одну подписку узла
+    pub fn subscribe(&self, topic: PubsubTopic, sender: mpsc::Sender<PubsubEvent>) -> Result<Subscription, PubsubError> {
+        if self.count.fetch_add(1, Ordering::Relaxed) >= self.max_subscriptions {
+            self.count.fetch_sub(1, Ordering::Relaxed);
+            return Err(PubsubError::TooManySubscriptions(self.max_subscriptions));
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let subscription = Subscription { id, commands: self.commands.clone(), count: self.count.clone() };
+        self.commands
+            .send(Command::Subscribe { id, topic, sender })
+            .map_err(|_| PubsubError::Closed)?;
+
+        Ok(subscription)
+    }
+}
+
+enum TopicEnd {
+    // Подписка отменена, потому что у темы не осталось подписчиков
+    Cancelled,
+    // Подтверждение подписи пришло, узел сам закрыл подписку
+    Completed,
+    // Поток закрылся сам: соединение разорвано
+    Disconnected,
+    // Узел отклонил подписку на тему; соединение при этом живо
+    Failed,
+}
+
+type Subscribers = HashMap<PubsubTopic, Vec<(u64, mpsc::Sender<PubsubEvent>)>>;
+
+/// Потоки подписок одного соединения. У темы может быть старый поток, который еще
+/// отписывается, и новый на смену ему: поколение различает их при завершении.
+struct Streams<'a> {
+    client: &'a PubsubClient,
+    commitment: CommitmentConfig,
+    events: mpsc::Sender<(PubsubTopic, PubsubEvent)>,
+    cancels: HashMap<PubsubTopic, (u64, oneshot::Sender<()>)>,
+    active: FuturesUnordered<BoxFuture<'a, (PubsubTopic, u64, TopicEnd)>>,
+    next_generation: u64,
+    // Подряд отклоненные подписки по теме: от них растет пауза перед повтором
+    failures: HashMap<PubsubTopic, u32>,
+}
+
+impl<'a> Streams<'a> {
+    fn start(&mut self, topic: PubsubTopic, delay: Duration) {
+        let (cancel, cancelled) = oneshot::channel();
+        let generation = self.next_generation;
+        self.next_generation += 1;
+        self.cancels.insert(topic, (generation, cancel));
+        self.active.push(stream_topic(self.client, topic, generation, delay, self.commitment, self.events.clone(), cancelled));
+    }
+
+    /// Снимает завершившийся поток; поток на смену ему остается
+    fn finish(&mut self, topic: PubsubTopic, generation: u64) {
+        if self.cancels.get(&topic).is_some_and(|(current, _)| *current == generation) {
+            self.cancels.remove(&topic);
+        }
+    }
+}
+
+struct Connection {
+    url: String,
+    commitment: CommitmentConfig,
+    reconnect_max: Duration,
+    channel_capacity: usize,
+    subscribers: Subscribers,
+}
+
+impl Connection {
+    async fn run(mut self, mut commands: mpsc::UnboundedReceiver<Command>) {
+        let mut attempt = 0;
+        let mut connected_before = false;
+
+        loop {
+            let client = match PubsubClient::new(self.url.as_str()).await {
+                Ok(client) => client,
+                Err(e) => {
+                    let delay = reconnect_delay(attempt, self.reconnect_max);
+                    tracing::warn!("WebSocket connection failed, retrying in {:?}: {}", delay, e);
+                    attempt += 1;
+                    // Команды копятся, чтобы подписки восстановились после подключения
+                    let deadline = tokio::time::sleep(delay);
+                    tokio::pin!(deadline);
+                    loop {
+                        tokio::select! {
+                            _ = &mut deadline => break,
+                            command = commands.recv() => match command {
+                                Some(command) => {
+                                    self.apply(command);
+                                }
+                                None => return,
+                            },
+                        }
+                    }
+                    continue;
+                }
+            };
+            attempt = 0;
+
+            if connected_before {
+                tracing::info!("WebSocket reconnected, restoring {} subscriptions", self.subscribers.len());
+                self.broadcast(PubsubEvent::Reconnected);
+            }
+            connected_before = true;
+
+            if !self.serve(&client, &mut commands).await {
+                return;
+            }
+            tracing::warn!("WebSocket connection lost");
+        }
+    }
+
+    /// Обслуживает подписки на одном соединении. `false` — менеджер удален, работа окончена.
+    async fn serve(&mut self, client: &PubsubClient, commands: &mut mpsc::UnboundedReceiver<Command>) -> bool {
+        let (events, mut received) = mpsc::channel::<(PubsubTopic, PubsubEvent)>(self.channel_capacity);
+        let mut streams = Streams {
+            client,
+            commitment: self.commitment,
+            events,
+            cancels: HashMap::new(),
+            active: FuturesUnordered::new(),
+            next_generation: 0,
+            failures: HashMap::new(),
+        };
+
+        for topic in self.subscribers.keys().copied().collect::<Vec<_>>() {
+            streams.start(topic, Duration::ZERO);
+        }
+
+        loop {
+            tokio::select! {
+                command = commands.recv() => {
+                    let Some(command) = command else {
+                        return false;
+                    };
+                    if let Some(topic) = self.apply(command)
+                        && !streams.cancels.contains_key(&topic)
+                    {
+                        streams.start(topic, Duration::ZERO);
+                    }
+                    self.cancel_unused(&mut streams.cancels);
+                }
+                Some((topic, event)) = received.recv() => {
+                    streams.failures.remove(&topic);
+                    self.dispatch(topic, event);
+                    self.cancel_unused(&mut streams.cancels);
+                }
+                Some((topic, generation, end)) = streams.active.next(), if !streams.active.is_empty() => {
+                    streams.finish(topic, generation);
+                    match end {
+                        TopicEnd::Cancelled => {}
+                        TopicEnd::Completed => {
+                            streams.failures.remove(&topic);
+                            self.subscribers.remove(&topic);
+                        }
+                        TopicEnd::Disconnected => return true,
+                        // Повторяется только эта тема, со своей паузой; остальные подписки не трогаются
+                        TopicEnd::Failed => {
+                            if self.subscribers.contains_key(&topic) && !streams.cancels.contains_key(&topic) {
+                                let attempt = streams.failures.entry(topic).or_default();
+                                let delay = reconnect_delay(*attempt, self.reconnect_max);
+                                *attempt += 1;
+                                tracing::debug!("Retrying subscription to {:?} in {:?}", topic, delay);
+                                streams.start(topic, delay);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Применяет команду; для новой темы возвращает ее, чтобы подписаться у узла
+    fn apply(&mut self, command: Command) -> Option<PubsubTopic> {
+        match command {
+            Command::Subscribe { id, topic, sender } => {
+                let subscribers = self.subscribers.entry(topic).or_default();
+                subscribers.push((id, sender));
+                Some(topic)
+            }
+            Command::Unsubscribe { id } => {
+                for subscribers in self.subscribers.values_mut() {
+                    subscribers.retain(|(subscriber, _)| *subscriber != id);
+                }
+                self.subscribers.retain(|_, subscribers| !subscribers.is_empty());
+                None
+            }
+        }
+    }
+
+    fn dispatch(&mut self, topic: PubsubTopic, event: PubsubEvent) {
+        let Some(subscribers) = self.subscribers.get_mut(&topic) else {
+            return;
+        };
+        subscribers.retain(|(id, sender)| match sender.try_send(event.clone()) {
+            Ok(()) => true,
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                tracing::warn!("Subscriber {} of {:?} is lagging, event dropped", id, topic);
+                true
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => false,
+        });
+        if subscribers.is_empty() {
+            self.subscribers.remove(&topic);
+        }
+    }
+
+    fn broadcast(&mut self, event: PubsubEvent) {
+        for topic in self.subscribers.keys().copied().collect::<Vec<_>>() {
+            self.dispatch(topic, event.clone());
+        }
+    }
+
+    fn cancel_unused(&self, cancels: &mut HashMap<PubsubTopic, (u64, oneshot::Sender<()>)>) {
+        let unused: Vec<PubsubTopic> = cancels.keys().filter(|topic| !self.subscribers.contains_key(topic)).copied().collect();
+        for topic in unused {
+            if let Some((_, cancel)) = cancels.remove(&topic) {
+                let _ = cancel.send(());
+            }
+        }
+    }
+}
+
+/// Подписка узла на одну тему; события пересылаются в общий канал соединения
+fn stream_topic<'a>(
+    client: &'a PubsubClient,
+    topic: PubsubTopic,
+    generation: u64,
+    delay: Duration,
+    commitment: CommitmentConfig,
+    events: mpsc::Sender<(PubsubTopic, PubsubEvent)>,
+    mut cancelled: oneshot::Receiver<()>,
+) -> BoxFuture<'a, (PubsubTopic, u64, TopicEnd)> {
+    async move {
+        // Пауза перед повторной подпиской; отмена прерывает ее
+        if !delay.is_zero() {
+            tokio::select! {
+                _ = &mut cancelled => return (topic, generation, TopicEnd::Cancelled),
+                _ = tokio::time::sleep(delay) => {}
+            }
+        }
+
+        let end = match topic {
+            PubsubTopic::Account(pubkey) => {
+                let config = RpcAccountInfoConfig { commitment: Some(commitment), ..Default::default() };
+                match client.account_subscribe(&pubkey, Some(config)).await {
+                    Ok((stream, unsubscribe)) => {
+                        let stream = stream.map(|response| Some(PubsubEvent::Account {
+                            pubkey,
+                            lamports: response.value.lamports,
+                            slot: response.context.slot,
+                        }));
+                        forward(topic, stream.boxed(), unsubscribe, &events, &mut cancelled).await
+                    }
+                    Err(e) => subscribe_failed(topic, e),
+                }
+            }
+            PubsubTopic::Signature(signature) => {
+                let config = RpcSignatureSubscribeConfig {
+                    commitment: Some(commitment),
+                    enable_received_notification: Some(false),
+                };
+                match client.signature_subscribe(&signature, Some(config)).await {
+                    Ok((stream, unsubscribe)) => {
+                        let stream = stream.map(move |response| signature_event(signature, response));
+                        forward(topic, stream.boxed(), unsubscribe, &events, &mut cancelled).await
+                    }
+                    Err(e) => subscribe_failed(topic, e),
+                }
+            }
+            PubsubTopic::Logs(address) => {
+                let filter = RpcTransactionLogsFilter::Mentions(vec![address.to_string()]);
+                let config = RpcTransactionLogsConfig { commitment: Some(commitment) };
+                match client.logs_subscribe(filter, config).await {
+                    Ok((stream, unsubscribe)) => {
+                        let stream = stream.map(move |response| Some(logs_event(address, response)));
+                        forward(topic, stream.boxed(), unsubscribe, &events, &mut cancelled).await
+                    }
+                    Err(e) => subscribe_failed(topic, e),
+                }
+            }
+        };
+        (topic, generation, end)
+    }
+    .boxed()
+}
+
+/// Разрыв соединения переподключает все подписки, отказ узла по теме — только ее
+fn subscribe_failed(topic: PubsubTopic, error: PubsubClientError) -> TopicEnd {
+    tracing::warn!("Failed to subscribe to {:?}: {}", topic, error);
+    match error {
+        PubsubClientError::ConnectionError(_) | PubsubClientError::WsError(_) | PubsubClientError::ConnectionClosed(_) => {
+            TopicEnd::Disconnected
+        }
+        _ => TopicEnd::Failed,
+    }
+}
+
+async fn forward(
+    topic: PubsubTopic,
+    mut stream: futures::stream::BoxStream<'_, Option<PubsubEvent>>,
+    unsubscribe: Box<dyn FnOnce() -> BoxFuture<'static, ()> + Send>,
+    events: &mpsc::Sender<(PubsubTopic, PubsubEvent)>,
+    cancelled: &mut oneshot::Receiver<()>,
+) -> TopicEnd {
+    loop {
+        tokio::select! {
+            _ = &mut *cancelled => {
+                unsubscribe().await;
+                return TopicEnd::Cancelled;
+            }
+            item = stream.next() => match item {
+                Some(Some(event)) => {
+                    let completed = matches!(event, PubsubEvent::Signature { .. });
+                    if events.send((topic, event)).await.is_err() {
+                        return TopicEnd::Cancelled;
+                    }
+                    if completed {
+                        return TopicEnd::Completed;
+                    }
+                }
+                Some(None) => {}
+                None => return TopicEnd::Disconnected,
+            },
+        }
+    }
+}
@@ -0,0 +1,518 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use anyhow::Context;
+use async_trait::async_trait;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_client::GetConfirmedSignaturesForAddress2Config,
+    rpc_config::{RpcSendTransactionConfig, RpcSimulateTransactionConfig, RpcTransactionConfig},
+    rpc_request::TokenAccountsFilter,
+    rpc_response::{RpcConfirmedTransactionStatusWithSignature, RpcPrioritizationFee},
+};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{
+    account::Account,
+    hash::Hash,
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::{TransactionError, VersionedTransaction},
+};
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, TransactionConfirmationStatus, TransactionStatus,
+    UiTransactionEncoding,
+};
+
+use crate::solana::{launch::holder_balances, simulation::SwapSimulation};
+
+/// Токен-аккаунт владельца и его баланс в минимальных единицах
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenAccountBalance {
+    pub pubkey: Pubkey,
+    pub amount: u64,
+}
+
+/// Статусы подписей и слот, на котором их отдал узел
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureStatuses {
+    pub context_slot: u64,
+    pub statuses: Vec<Option<TransactionStatus>>,
+}
+
+/// Запросы к сети Solana, которые нужны боту. Рабочая реализация — `RpcClient`
+/// (в том числе поверх пула узлов), для тестов — `FakeRpc` со сценарием ответов.
+#[async_trait]
+pub trait SolanaRpc: Send + Sync {
+    fn commitment(&self) -> CommitmentConfig;
+
+    async fn get_balance(&self, owner: &Pubkey) -> anyhow::Result<u64>;
+
+    async fn get_token_accounts(&self, owner: &Pubkey, mint: &Pubkey) -> anyhow::Result<Vec<TokenAccountBalance>>;
+
+    /// Последний blockhash и высота блока, после которой он истекает
+    async fn get_latest_blockhash(&self) -> anyhow::Result<(Hash, u64)>;
+
+    async fn get_block_height(&self) -> anyhow::Result<u64>;
+
+    /// Высота блока и слот, на котором она измерена, из одного ответа узла (`getEpochInfo`)
+    async fn get_block_height_with_slot(&self) -> anyhow::Result<(u64, u64)>;
+
+    /// Симуляция подписанной транзакции без подмены blockhash
+    async fn simulate_transaction(&self, transaction: &VersionedTransaction) -> anyhow::Result<SwapSimulation>;
+
+    /// Отправка без preflight и без повторов узла: повторами управляет вызывающий
+    async fn send_transaction(&self, transaction: &VersionedTransaction) -> anyhow::Result<Signature>;
+
+    /// Статусы из кэша узла: только транзакции последних ~300 слотов
+    async fn get_signature_statuses(&self, signatures: &[Signature]) -> anyhow::Result<Vec<Option<TransactionStatus>>>;
+
+    /// Статусы с поиском по всей истории узла (`searchTransactionHistory`)
+    async fn get_signature_statuses_with_history(&self, signatures: &[Signature]) -> anyhow::Result<SignatureStatuses>;
+
+    /// Аккаунт или `None`, если его нет в сети
+    async fn get_account(&self, pubkey: &Pubkey) -> anyhow::Result<Option<Account>>;
+
+    async fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> anyhow::Result<Vec<Option<Account>>>;
+
+    /// Крупнейшие токен-аккаунты mint и их балансы
+    async fn get_token_largest_accounts(&self, mint: &Pubkey) -> anyhow::Result<Vec<(Pubkey, u64)>>;
+
+    async fn get_token_supply(&self, mint: &Pubkey) -> anyhow::Result<u64>;
+
+    async fn get_recent_prioritization_fees(&self, accounts: &[Pubkey]) -> anyhow::Result<Vec<RpcPrioritizationFee>>;
+
+    /// Подписи адреса от новых к старым, до `until` не включительно
+    async fn get_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        until: Option<Signature>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<RpcConfirmedTransactionStatusWithSignature>>;
+
+    async fn get_transaction(&self, signature: &Signature) -> anyhow::Result<EncodedConfirmedTransactionWithStatusMeta>;
+}
+
+#[async_trait]
+impl SolanaRpc for RpcClient {
+    fn commitment(&self) -> CommitmentConfig {
+        RpcClient::commitment(self)
+    }
+
+    async fn get_balance(&self, owner: &Pubkey) -> anyhow::Result<u64> {
+        Ok(RpcClient::get_balance(self, owner).await?)
+    }
+
+    async fn get_token_accounts(&self, owner: &Pubkey, mint: &Pubkey) -> anyhow::Result<Vec<TokenAccountBalance>> {
+        let accounts = self
+            .get_token_accounts_by_owner(owner, TokenAccountsFilter::Mint(*mint))
+            .await
+            .with_context(|| format!("Failed to fetch {} token accounts of {}", mint, owner))?;
+
+        accounts
+            .into_iter()
+            .map(|account| {
+                // Аккаунты приходят в jsonParsed: parsed.info.tokenAmount.amount
+                let data = serde_json::to_value(&account.account.data)?;
+                let amount = data["parsed"]["info"]["tokenAmount"]["amount"]
+                    .as_str()
+                    .and_then(|amount| amount.parse::<u64>().ok())
+                    .with_context(|| format!("Unexpected token account data for {}", account.pubkey))?;
+                Ok(TokenAccountBalance { pubkey: account.pubkey.parse()?, amount })
+            })
+            .collect()
+    }
+
+    async fn get_latest_blockhash(&self) -> anyhow::Result<(Hash, u64)> {
+        Ok(self.get_latest_blockhash_with_commitment(RpcClient::commitment(self)).await?)
+    }
+
+    async fn get_block_height(&self) -> anyhow::Result<u64> {
+        Ok(self.get_block_height_with_commitment(RpcClient::commitment(self)).await?)
+    }
+
+    async fn get_block_height_with_slot(&self) -> anyhow::Result<(u64, u64)> {
+        let epoch = self.get_epoch_info_with_commitment(RpcClient::commitment(self)).await?;
+        Ok((epoch.block_height, epoch.absolute_slot))
+    }
+
+    async fn simulate_transaction(&self, transaction: &VersionedTransaction) -> anyhow::Result<SwapSimulation> {
+        let response = self
+            .simulate_transaction_with_config(transaction, RpcSimulateTransactionConfig {
+                // Blockhash не подменяется: устаревшая транзакция должна провалить симуляцию
+                replace_recent_blockhash: false,
+                commitment: Some(RpcClient::commitment(self)),
+                ..Default::default()
+            })
+            .await
+            .context("Failed to simulate transaction")?;
+
+        Ok(response.value.into())
+    }
+
+    async fn send_transaction(&self, transaction: &VersionedTransaction) -> anyhow::Result<Signature> {
+        Ok(self
+            .send_transaction_with_config(transaction, RpcSendTransactionConfig {
+                skip_preflight: true,
+                max_retries: Some(0),
+                ..Default::default()
+            })
+            .await?)
+    }
+
+    async fn get_signature_statuses(&self, signatures: &[Signature]) -> anyhow::Result<Vec<Option<TransactionStatus>>> {
+        Ok(RpcClient::get_signature_statuses(self, signatures).await?.value)
+    }
+
+    async fn get_signature_statuses_with_history(&self, signatures: &[Signature]) -> anyhow::Result<SignatureStatuses> {
+        let response = RpcClient::get_signature_statuses_with_history(self, signatures).await?;
+        Ok(SignatureStatuses { context_slot: response.context.slot, statuses: response.value })
+    }
+
+    async fn get_account(&self, pubkey: &Pubkey) -> anyhow::Result<Option<Account>> {
+        Ok(self.get_account_with_commitment(pubkey, RpcClient::commitment(self)).await?.value)
+    }
+
+    async fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> anyhow::Result<Vec<Option<Account>>> {
+        Ok(RpcClient::get_multiple_accounts(self, pubkeys).await?)
+    }
+
+    async fn get_token_largest_accounts(&self, mint: &Pubkey) -> anyhow::Result<Vec<(Pubkey, u64)>> {
+        Ok(holder_balances(&RpcClient::get_token_largest_accounts(self, mint).await?))
+    }
+
+    async fn get_token_supply(&self, mint: &Pubkey) -> anyhow::Result<u64> {
+        Ok(RpcClient::get_token_supply(self, mint).await?.amount.parse()?)
+    }
+
+    async fn get_recent_prioritization_fees(&self, accounts: &[Pubkey]) -> anyhow::Result<Vec<RpcPrioritizationFee>> {
+        Ok(RpcClient::get_recent_prioritization_fees(self, accounts).await?)
+    }
+
+    async fn get_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        until: Option<Signature>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+        Ok(self
+            .get_signatures_for_address_with_config(address, GetConfirmedSignaturesForAddress2Config {
+                until,
+                limit: Some(limit),
+                commitment: Some(CommitmentConfig::confirmed()),
+                ..Default::default()
+            })
+            .await?)
+    }
+
+    async fn get_transaction(&self, signature: &Signature) -> anyhow::Result<EncodedConfirmedTransactionWithStatusMeta> {
+        Ok(self
+            .get_transaction_with_config(signature, RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Json),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            })
+            .await?)
+    }
+}
+
+/// Когда отправленная транзакция попадает в блок в `FakeRpc`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FakeLanding {
+    /// После скольких отправок транзакция появляется в сети
+    pub after_sends: usize,
+    pub error: Option<TransactionError>,
+}
+
+#[derive(Default)]
+struct FakeState {
+    balances: HashMap<Pubkey, u64>,
+    token_accounts: HashMap<(Pubkey, Pubkey), Vec<TokenAccountBalance>>,
+    accounts: HashMap<Pubkey, Account>,
+    largest_accounts: HashMap<Pubkey, Vec<(Pubkey, u64)>>,
+    supplies: HashMap<Pubkey, u64>,
+    prioritization_fees: Option<Vec<RpcPrioritizationFee>>,
+    blockhash: (Hash, u64),
+    block_height: u64,
+    block_height_step: u64,
+    simulations: VecDeque<SwapSimulation>,
+    landing: Option<FakeLanding>,
+    statuses: HashMap<Signature, TransactionStatus>,
+    // Статусы старше кэша узла: их находит только поиск по истории
+    historical_statuses: HashMap<Signature, TransactionStatus>,
+    // На сколько слотов узел статусов отстает от узла высоты блока
+    status_lag: Option<u64>,
+    signatures: HashMap<Pubkey, Vec<RpcConfirmedTransactionStatusWithSignature>>,
+    // Ответы `getTransaction` в JSON: тип ответа не реализует Clone
+    transactions: HashMap<Signature, serde_json::Value>,
+    sent: Vec<VersionedTransaction>,
+    calls: Vec<&'static str>,
+}
+
+impl FakeState {
+    fn status_context_slot(&self) -> u64 {
+        self.block_height.saturating_sub(self.status_lag.unwrap_or(0))
+    }
+
+    /// Видит ли отстающий узел статусов транзакцию из слота `status.slot`
+    fn status_visible(&self, status: &TransactionStatus) -> bool {
+        self.status_lag.is_none_or(|_| status.slot <= self.status_context_slot())
+    }
+}
+
+/// Сеть в памяти для тестов: ответы задаются заранее, отправленные транзакции
+/// и вызовы записываются. Незаданные данные — ошибка RPC, как у недоступного узла.
+pub struct FakeRpc {
+    commitment: CommitmentConfig,
+    state: Mutex<FakeState>,
+}
+
+impl Default for FakeRpc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FakeRpc {
+    pub fn new() -> Self {
+        Self {
+            commitment: CommitmentConfig::confirmed(),
+            state: Mutex::new(FakeState::default()),
+        }
+    }
+
+    fn state(&self, call: &'static str) -> std::sync::MutexGuard<'_, FakeState> {
+        let mut state = self.state.lock().unwrap();
+        state.calls.push(call);
+        state
+    }
+
+    pub fn set_balance(&self, owner: Pubkey, lamports: u64) {
+        self.state.lock().unwrap().balances.insert(owner, lamports);
+    }
+
+    pub fn set_token_accounts(&self, owner: Pubkey, mint: Pubkey, accounts: Vec<TokenAccountBalance>) {
+        self.state.lock().unwrap().token_accounts.insert((owner, mint), accounts);
+    }
+
+    pub fn set_account(&self, pubkey: Pubkey, account: Account) {
+        self.state.lock().unwrap().accounts.insert(pubkey, account);
+    }
+
+    pub fn set_largest_accounts(&self, mint: Pubkey, holders: Vec<(Pubkey, u64)>) {
+        self.state.lock().unwrap().largest_accounts.insert(mint, holders);
+    }
+
+    pub fn set_token_supply(&self, mint: Pubkey, supply: u64) {
+        self.state.lock().unwrap().supplies.insert(mint, supply);
+    }
+
+    pub fn set_prioritization_fees(&self, fees: Vec<RpcPrioritizationFee>) {
+        self.state.lock().unwrap().prioritization_fees = Some(fees);
+    }
+
+    pub fn set_blockhash(&self, blockhash: Hash, last_valid_block_height: u64) {
+        self.state.lock().unwrap().blockhash = (blockhash, last_valid_block_height);
+    }
+
+    /// Текущая высота блока и ее прирост на каждый запрос высоты
+    pub fn set_block_height(&self, height: u64, step: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.block_height = height;
+        state.block_height_step = step;
+    }
+
+    /// Результат следующей симуляции; без сценария симуляция проходит
+    pub fn push_simulation(&self, simulation: SwapSimulation) {
+        self.state.lock().unwrap().simulations.push_back(simulation);
+    }
+
+    pub fn set_landing(&self, landing: FakeLanding) {
+        self.state.lock().unwrap().landing = Some(landing);
+    }
+
+    pub fn set_signature_status(&self, signature: Signature, status: TransactionStatus) {
+        self.state.lock().unwrap().statuses.insert(signature, status);
+    }
+
+    /// Статус транзакции, выпавшей из кэша недавних статусов узла
+    pub fn set_historical_status(&self, signature: Signature, status: TransactionStatus) {
+        self.state.lock().unwrap().historical_statuses.insert(signature, status);
+    }
+
+    /// Узел статусов отстает на `slots`: транзакции из более поздних слотов он еще не видит.
+    /// Слоты в `FakeRpc` совпадают с высотой блока.
+    pub fn set_status_lag(&self, slots: u64) {
+        self.state.lock().unwrap().status_lag = Some(slots);
+    }
+
+    pub fn set_signatures(&self, address: Pubkey, signatures: Vec<RpcConfirmedTransactionStatusWithSignature>) {
+        self.state.lock().unwrap().signatures.insert(address, signatures);
+    }
+
+    pub fn set_transaction(&self, signature: Signature, transaction: serde_json::Value) {
+        self.state.lock().unwrap().transactions.insert(signature, transaction);
+    }
+
+    /// Все отправленные транзакции по порядку, включая повторные отправки
+    pub fn sent(&self) -> Vec<VersionedTransaction> {
+        self.state.lock().unwrap().sent.clone()
+    }
+
+    /// Имена вызванных методов по порядку
+    pub fn calls(&self) -> Vec<&'static str> {
+        self.state.lock().unwrap().calls.clone()
+    }
+
+    /// Подтвержденный статус транзакции в сети
+    pub fn confirmed_status(slot: u64, error: Option<TransactionError>) -> TransactionStatus {
+        TransactionStatus {
+            slot,
+            confirmations: Some(1),
+            status: error.clone().map_or(Ok(()), Err),
+            err: error,
+            confirmation_status: Some(TransactionConfirmationStatus::Confirmed),
+        }
+    }
+}
+
+fn missing<T>(what: impl std::fmt::Display) -> anyhow::Result<T> {
+    Err(anyhow::anyhow!("FakeRpc: no response scripted for {}", what))
+}
+
+#[async_trait]
+impl SolanaRpc for FakeRpc {
+    fn commitment(&self) -> CommitmentConfig {
+        self.commitment
+    }
+
+    async fn get_balance(&self, owner: &Pubkey) -> anyhow::Result<u64> {
+        Ok(self.state("getBalance").balances.get(owner).copied().unwrap_or(0))
+    }
+
+    async fn get_token_accounts(&self, owner: &Pubkey, mint: &Pubkey) -> anyhow::Result<Vec<TokenAccountBalance>> {
+        Ok(self.state("getTokenAccountsByOwner").token_accounts.get(&(*owner, *mint)).cloned().unwrap_or_default())
+    }
+
+    async fn get_latest_blockhash(&self) -> anyhow::Result<(Hash, u64)> {
+        Ok(self.state("getLatestBlockhash").blockhash)
+    }
+
+    async fn get_block_height(&self) -> anyhow::Result<u64> {
+        let mut state = self.state("getBlockHeight");
+        let height = state.block_height;
+        state.block_height += state.block_height_step;
+        Ok(height)
+    }
+
+    async fn get_block_height_with_slot(&self) -> anyhow::Result<(u64, u64)> {
+        let mut state = self.state("getEpochInfo");
+        let height = state.block_height;
+        state.block_height += state.block_height_step;
+        Ok((height, height))
+    }
+
+    async fn simulate_transaction(&self, _transaction: &VersionedTransaction) -> anyhow::Result<SwapSimulation> {
+        Ok(self.state("simulateTransaction").simulations.pop_front().unwrap_or(SwapSimulation {
+            error: None,
+            units_consumed: Some(150_000),
+            logs: vec![],
+        }))
+    }
+
+    async fn send_transaction(&self, transaction: &VersionedTransaction) -> anyhow::Result<Signature> {
+        let mut state = self.state("sendTransaction");
+        let signature = transaction.signatures[0];
+        state.sent.push(transaction.clone());
+
+        let sends = state.sent.iter().filter(|sent| sent.signatures[0] == signature).count();
+        if let Some(landing) = state.landing.clone()
+            && sends >= landing.after_sends
+        {
+            let slot = state.block_height;
+            state.statuses.entry(signature).or_insert_with(|| Self::confirmed_status(slot, landing.error));
+        }
+
+        Ok(signature)
+    }
+
+    async fn get_signature_statuses(&self, signatures: &[Signature]) -> anyhow::Result<Vec<Option<TransactionStatus>>> {
+        let state = self.state("getSignatureStatuses");
+        Ok(signatures
+            .iter()
+            .map(|signature| state.statuses.get(signature).filter(|status| state.status_visible(status)).cloned())
+            .collect())
+    }
+
+    async fn get_signature_statuses_with_history(&self, signatures: &[Signature]) -> anyhow::Result<SignatureStatuses> {
+        let state = self.state("getSignatureStatuses");
+        let statuses = signatures
+            .iter()
+            .map(|signature| {
+                state.statuses.get(signature)
+                    .or_else(|| state.historical_statuses.get(signature))
+                    .filter(|status| state.status_visible(status))
+                    .cloned()
+            })
+            .collect();
+        Ok(SignatureStatuses { context_slot: state.status_context_slot(), statuses })
+    }
+
+    async fn get_account(&self, pubkey: &Pubkey) -> anyhow::Result<Option<Account>> {
+        Ok(self.state("getAccountInfo").accounts.get(pubkey).cloned())
+    }
+
+    async fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> anyhow::Result<Vec<Option<Account>>> {
+        let state = self.state("getMultipleAccounts");
+        Ok(pubkeys.iter().map(|pubkey| state.accounts.get(pubkey).cloned()).collect())
+    }
+
+    async fn get_token_largest_accounts(&self, mint: &Pubkey) -> anyhow::Result<Vec<(Pubkey, u64)>> {
+        match self.state("getTokenLargestAccounts").largest_accounts.get(mint) {
+            Some(holders) => Ok(holders.clone()),
+            None => missing(format_args!("getTokenLargestAccounts {}", mint)),
+        }
+    }
+
+    async fn get_token_supply(&self, mint: &Pubkey) -> anyhow::Result<u64> {
+        match self.state("getTokenSupply").supplies.get(mint) {
+            Some(supply) => Ok(*supply),
+            None => missing(format_args!("getTokenSupply {}", mint)),
+        }
+    }
+
+    async fn get_recent_prioritization_fees(&self, _accounts: &[Pubkey]) -> anyhow::Result<Vec<RpcPrioritizationFee>> {
+        match &self.state("getRecentPrioritizationFees").prioritization_fees {
+            Some(fees) => Ok(fees.clone()),
+            None => missing("getRecentPrioritizationFees"),
+        }
+    }
+
+    async fn get_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        until: Option<Signature>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+        let state = self.state("getSignaturesForAddress");
+        let until = until.map(|signature| signature.to_string());
+        Ok(state
+            .signatures
+            .get(address)
+            .map(|signatures| {
+                signatures
+                    .iter()
+                    .take_while(|status| Some(&status.signature) != until.as_ref())
+                    .take(limit)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn get_transaction(&self, signature: &Signature) -> anyhow::Result<EncodedConfirmedTransactionWithStatusMeta> {
+        match self.state("getTransaction").transactions.get(signature) {
+            Some(transaction) => Ok(serde_json::from_value(transaction.clone())?),
+            None => missing(format_args!("getTransaction {}", signature)),
+        }
+    }
+}
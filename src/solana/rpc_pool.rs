@@ -0,0 +1,293 @@
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use futures::future::{join_all, select_ok};
+use serde_json::Value;
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+    nonblocking::rpc_client::RpcClient,
+    rpc_client::RpcClientConfig,
+    rpc_custom_error::{
+        JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE, JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED,
+        JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY,
+    },
+    rpc_request::{RpcError, RpcRequest},
+    rpc_sender::{RpcSender, RpcTransportStats},
+};
+use solana_commitment_config::CommitmentConfig;
+
+use crate::{config::settings::SolanaSettings, monitoring::metrics::MetricsRegistry};
+
+/// Адрес узла и его вес из записи вида `https://host|3`; вес по умолчанию — 1
+pub fn parse_endpoint(spec: &str) -> Option<(String, u32)> {
+    let spec = spec.trim();
+    let (url, weight) = match spec.rsplit_once('|') {
+        Some((url, weight)) => (url.trim(), weight.trim().parse().ok()?),
+        None => (spec, 1),
+    };
+    let parsed = url::Url::parse(url).ok()?;
+    if !matches!(parsed.scheme(), "http" | "https") || weight == 0 {
+        return None;
+    }
+    Some((url.to_string(), weight))
+}
+
+/// Имя узла для логов и метрик: только хост, без ключей API в пути и параметрах
+pub fn endpoint_name(url: &str) -> String {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Отстает ли узел от самого свежего узла пула больше допустимого
+pub fn is_lagging(slot: u64, max_slot: u64, max_slot_lag: u64) -> bool {
+    max_slot.saturating_sub(slot) > max_slot_lag
+}
+
+/// Индекс узла по весам; `roll` — случайное число в `0..сумма весов`
+pub fn pick_weighted(weights: &[u32], roll: u64) -> Option<usize> {
+    let mut remaining = roll;
+    for (index, &weight) in weights.iter().enumerate() {
+        if remaining < weight as u64 {
+            return Some(index);
+        }
+        remaining -= weight as u64;
+    }
+    None
+}
+
+/// Можно ли повторить запрос на другом узле: сбой сети или самого узла,
+/// а не ошибка в запросе, которую другой узел вернет так же
+pub fn is_retryable(error: &ClientError) -> bool {
+    match error.kind() {
+        ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_) | ClientErrorKind::Middleware(_) => true,
+        ClientErrorKind::SerdeJson(_) => true,
+        ClientErrorKind::RpcError(RpcError::RpcRequestError(_)) => true,
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => matches!(
+            *code,
+            JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY
+                | JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED
+                | JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE
+        ),
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct EndpointHealth {
+    healthy: bool,
+    latency: Option<Duration>,
+}
+
+struct RpcEndpoint {
+    name: String,
+    weight: u32,
+    client: RpcClient,
+    health: RwLock<EndpointHealth>,
+}
+
+impl RpcEndpoint {
+    fn health(&self) -> EndpointHealth {
+        *self.health.read().unwrap()
+    }
+}
+
+/// Пул RPC-узлов с весами: чтения повторяются на другом узле, отправка транзакций
+/// идет на несколько узлов сразу, отстающие узлы исключаются проверкой здоровья
+pub struct RpcPool {
+    endpoints: Vec<Arc<RpcEndpoint>>,
+    metrics: Arc<MetricsRegistry>,
+    commitment: CommitmentConfig,
+    max_attempts: usize,
+    send_fanout: usize,
+    max_slot_lag: u64,
+    health_check_interval: Duration,
+}
+
+impl RpcPool {
+    /// Узлы из `rpc_endpoints`, а если список пуст — единственный `rpc_url`
+    pub fn new(
+        settings: &SolanaSettings,
+        commitment: CommitmentConfig,
+        metrics: Arc<MetricsRegistry>,
+    ) -> anyhow::Result<Self> {
+        let specs = if settings.rpc_endpoints.is_empty() {
+            vec![settings.rpc_url.clone()]
+        } else {
+            settings.rpc_endpoints.clone()
+        };
+
+        let timeout = Duration::from_millis(settings.timeout_ms);
+        let endpoints = specs
+            .iter()
+            .map(|spec| {
+                let (url, weight) = parse_endpoint(spec)
+                    .ok_or_else(|| anyhow::anyhow!("Invalid RPC endpoint: {}", endpoint_name(spec)))?;
+                Ok(Arc::new(RpcEndpoint {
+                    name: endpoint_name(&url),
+                    weight,
+                    client: RpcClient::new_with_timeout_and_commitment(url, timeout, commitment),
+                    // До первой проверки все узлы считаются здоровыми
+                    health: RwLock::new(EndpointHealth { healthy: true, latency: None }),
+                }))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            endpoints,
+            metrics,
+            commitment,
+            max_attempts: settings.retry_count as usize + 1,
+            send_fanout: settings.send_fanout.max(1) as usize,
+            max_slot_lag: settings.max_slot_lag,
+            health_check_interval: Duration::from_secs(settings.health_check_interval_secs),
+        })
+    }
+
+    /// `RpcClient`, который ходит в сеть через пул
+    pub fn client(self: &Arc<Self>) -> RpcClient {
+        RpcClient::new_sender(PoolSender(self.clone()), RpcClientConfig::with_commitment(self.commitment))
+    }
+
+    /// Фоновая проверка отставания и задержки узлов
+    pub async fn run_health_checks(self: Arc<Self>) {
+        if self.endpoints.len() < 2 {
+            return;
+        }
+
+        let mut interval = tokio::time::interval(self.health_check_interval);
+        loop {
+            interval.tick().await;
+            self.check_health().await;
+        }
+    }
+
+    async fn check_health(&self) {
+        let params = serde_json::json!([self.commitment]);
+        let results = join_all(self.endpoints.iter().map(|endpoint| async {
+            let started_at = Instant::now();
+            let slot = self.call(endpoint, RpcRequest::GetSlot, params.clone()).await
+                .ok()
+                .and_then(|value| value.as_u64());
+            (slot, started_at.elapsed())
+        })).await;
+
+        let max_slot = results.iter().filter_map(|(slot, _)| *slot).max().unwrap_or(0);
+        for (endpoint, (slot, latency)) in self.endpoints.iter().zip(results) {
+            let healthy = slot.is_some_and(|slot| !is_lagging(slot, max_slot, self.max_slot_lag));
+            let was_healthy = endpoint.health().healthy;
+            if was_healthy && !healthy {
+                tracing::warn!(
+                    "RPC endpoint {} excluded: slot {:?}, pool slot {}",
+                    endpoint.name, slot, max_slot,
+                );
+            } else if !was_healthy && healthy {
+                tracing::info!("RPC endpoint {} is healthy again", endpoint.name);
+            }
+
+            *endpoint.health.write().unwrap() = EndpointHealth {
+                healthy,
+                latency: slot.map(|_| latency),
+            };
+            self.metrics.solana_rpc_endpoint_healthy
+                .with_label_values(&[endpoint.name.as_str()])
+                .set(healthy as i64);
+        }
+    }
+
+    /// Порядок обхода узлов: первый здоровый выбирается по весам, остальные —
+    /// по весу и задержке, нездоровые идут последними как запасной вариант
+    fn ordered(&self) -> Vec<Arc<RpcEndpoint>> {
+        let (mut healthy, mut unhealthy): (Vec<_>, Vec<_>) =
+            self.endpoints.iter().cloned().partition(|endpoint| endpoint.health().healthy);
+
+        let by_preference = |a: &Arc<RpcEndpoint>, b: &Arc<RpcEndpoint>| {
+            b.weight.cmp(&a.weight).then_with(|| a.health().latency.cmp(&b.health().latency))
+        };
+        healthy.sort_by(by_preference);
+        unhealthy.sort_by(by_preference);
+
+        let weights: Vec<u32> = healthy.iter().map(|endpoint| endpoint.weight).collect();
+        let total: u64 = weights.iter().map(|&weight| weight as u64).sum();
+        if total > 0
+            && let Some(first) = pick_weighted(&weights, rand::random_range(0..total))
+        {
+            let endpoint = healthy.remove(first);
+            healthy.insert(0, endpoint);
+        }
+
+        healthy.extend(unhealthy);
+        healthy
+    }
+
+    async fn call(&self, endpoint: &RpcEndpoint, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        let method = request.to_string();
+        let labels = [endpoint.name.as_str(), method.as_str()];
+        let started_at = Instant::now();
+
+        let result = endpoint.client.send::<Value>(request, params).await;
+
+        self.metrics.solana_rpc_calls_total.with_label_values(&labels).inc();
+        self.metrics.solana_rpc_duration.with_label_values(&labels).observe(started_at.elapsed().as_secs_f64());
+        if result.is_err() {
+            self.metrics.solana_rpc_errors_total.with_label_values(&labels).inc();
+        }
+        result
+    }
+
+    /// Чтение: при сбое узла запрос повторяется на следующем
+    async fn read(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        let mut last_error = None;
+        for endpoint in self.ordered().into_iter().take(self.max_attempts) {
+            match self.call(&endpoint, request, params.clone()).await {
+                Ok(value) => return Ok(value),
+                Err(e) if is_retryable(&e) => {
+                    tracing::debug!("RPC {} failed on {}, trying next endpoint: {}", request, endpoint.name, e);
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| ClientErrorKind::Custom("no RPC endpoints".to_string()).into()))
+    }
+
+    /// Отправка транзакции сразу на несколько узлов; успех на любом — успех.
+    /// Запросы продолжают выполняться и после первого ответа.
+    async fn broadcast(self: &Arc<Self>, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        let sends = self.ordered().into_iter().take(self.send_fanout).map(|endpoint| {
+            let pool = self.clone();
+            let params = params.clone();
+            let task = tokio::spawn(async move { pool.call(&endpoint, request, params).await });
+            Box::pin(async move {
+                task.await.unwrap_or_else(|e| Err(ClientErrorKind::Custom(e.to_string()).into()))
+            })
+        });
+
+        match select_ok(sends).await {
+            Ok((value, _)) => Ok(value),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+struct PoolSender(Arc<RpcPool>);
+
+#[async_trait]
+impl RpcSender for PoolSender {
+    async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        match request {
+            RpcRequest::SendTransaction => self.0.broadcast(request, params).await,
+            _ => self.0.read(request, params).await,
+        }
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        RpcTransportStats::default()
+    }
+
+    fn url(&self) -> String {
+        self.0.endpoints.iter().map(|endpoint| endpoint.name.as_str()).collect::<Vec<_>>().join(",")
+    }
+}
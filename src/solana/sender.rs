@@ -0,0 +1,242 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{
+    signature::Signature,
+    transaction::{TransactionError, VersionedTransaction},
+};
+use solana_transaction_status::TransactionStatus;
+use thiserror::Error;
+use tokio::sync::mpsc;
+
+use crate::solana::{
+    pubsub::{PubsubEvent, PubsubManager, PubsubTopic, Subscription},
+    rpc::SolanaRpc,
+    simulation::classify,
+};
+
+// Blockhash действует ~150 блоков (около минуты); дольше ждать подтверждения бессмысленно,
+// если RPC не отвечает и высоту блока узнать нельзя
+const MAX_LANDING_TIME: Duration = Duration::from_secs(120);
+
+// С подписками статус все равно опрашивается на каждом N-м цикле: на случай,
+// если соединение не поднялось или уведомление потерялось без разрыва
+const PUSH_FALLBACK_POLL_EVERY: u32 = 5;
+
+/// Почему отправленная транзакция не дала результата
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum LandingError {
+    /// Blockhash истек, а транзакции нет в сети: она уже не может попасть в блок
+    #[error("транзакция не попала в блок до истечения blockhash, сделку можно повторить")]
+    Expired,
+    /// Транзакция попала в блок и списала комиссию, но свап не выполнен
+    #[error("транзакция попала в блок, но завершилась ошибкой: {}", classify(.0, &[]))]
+    Failed(TransactionError),
+    /// Статус выяснить не удалось: транзакция еще может попасть в блок
+    #[error("не удалось подтвердить транзакцию {0}, проверьте ее статус перед повтором")]
+    Unknown(Signature),
+}
+
+impl LandingError {
+    /// Можно ли подписать новую транзакцию без риска исполнить сделку дважды
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Expired)
+    }
+}
+
+/// Состояние отправленной транзакции на очередной проверке
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LandingStatus {
+    Pending,
+    Landed,
+    Failed(TransactionError),
+    Expired,
+}
+
+/// Состояние транзакции по ответу `getSignatureStatuses` и текущей высоте блока.
+/// Транзакция, уже попавшая в блок, ждет подтверждения даже после истечения blockhash.
+pub fn landing_status(
+    status: Option<&TransactionStatus>,
+    commitment: CommitmentConfig,
+    block_height: u64,
+    last_valid_block_height: u64,
+) -> LandingStatus {
+    match status {
+        Some(status) if status.satisfies_commitment(commitment) => match &status.err {
+            Some(error) => LandingStatus::Failed(error.clone()),
+            None => LandingStatus::Landed,
+        },
+        Some(_) => LandingStatus::Pending,
+        None if block_height > last_valid_block_height => LandingStatus::Expired,
+        None => LandingStatus::Pending,
+    }
+}
+
+/// Итог транзакции для решений, которые нельзя отменить: сделку признают неудачной или
+/// подписывают заново. Статус ищется по всей истории узла, а истечение blockhash
+/// засчитывается, только если узел статусов видел слот, на котором измерена высота:
+/// отстающий узел не находит транзакцию, уже попавшую в блок.
+/// Возвращает и слот, в котором транзакция попала в блок.
+pub async fn confirm_landing(
+    rpc: &dyn SolanaRpc,
+    signature: &Signature,
+    last_valid_block_height: u64,
+) -> anyhow::Result<(LandingStatus, Option<u64>)> {
+    let (block_height, height_slot) = rpc.get_block_height_with_slot().await?;
+    let response = rpc.get_signature_statuses_with_history(&[*signature]).await?;
+    let status = response.statuses.into_iter().next().flatten();
+
+    let landing = match landing_status(status.as_ref(), rpc.commitment(), block_height, last_valid_block_height) {
+        LandingStatus::Expired if response.context_slot < height_slot => LandingStatus::Pending,
+        landing => landing,
+    };
+    Ok((landing, status.map(|status| status.slot)))
+}
+
+/// Отправка подписанной транзакции с повторами до подтверждения или истечения blockhash.
+/// Повторно отправляются те же байты с той же подписью, поэтому сеть исполнит
+/// транзакцию не больше одного раза. Новая подпись — только после `LandingError::Expired`.
+/// С подписками подтверждение приходит push-уведомлением, а статусы опрашиваются
+/// только после переподключения и перед тем, как признать blockhash истекшим.
+pub struct TransactionSender {
+    rpc: Arc<dyn SolanaRpc>,
+    rebroadcast_interval: Duration,
+    pubsub: Option<Arc<PubsubManager>>,
+}
+
+impl TransactionSender {
+    pub fn new(rpc: Arc<dyn SolanaRpc>, rebroadcast_interval: Duration) -> Self {
+        Self { rpc, rebroadcast_interval, pubsub: None }
+    }
+
+    pub fn with_pubsub(mut self, pubsub: Option<Arc<PubsubManager>>) -> Self {
+        self.pubsub = pubsub;
+        self
+    }
+
+    pub async fn send(
+        &self,
+        transaction: &VersionedTransaction,
+        last_valid_block_height: u64,
+    ) -> Result<Signature, LandingError> {
+        let signature = transaction.signatures[0];
+        let started_at = Instant::now();
+        let mut seen = false;
+        let mut broadcasts = 0u32;
+        let mut cycles = 0u32;
+
+        // Подписка оформляется до отправки, чтобы не пропустить быстрое подтверждение
+        let (mut events, _subscription) = match self.subscribe(signature) {
+            Some((events, subscription)) => (Some(events), Some(subscription)),
+            None => (None, None),
+        };
+
+        loop {
+            // Пока транзакции нет в сети, лидер мог ее потерять — отправляем снова.
+            // Симуляция уже пройдена, поэтому preflight пропускается, а повторы узла заменяет цикл.
+            if !seen {
+                broadcasts += 1;
+                if let Err(e) = self.rpc.send_transaction(transaction).await {
+                    tracing::warn!("Failed to broadcast {} (attempt {}): {}", signature, broadcasts, e);
+                }
+            }
+
+            cycles += 1;
+            let mut must_poll = events.is_none() || cycles.is_multiple_of(PUSH_FALLBACK_POLL_EVERY);
+            if let Some(receiver) = events.as_mut() {
+                tokio::select! {
+                    _ = tokio::time::sleep(self.rebroadcast_interval) => {}
+                    event = receiver.recv() => match event {
+                        Some(PubsubEvent::Signature { error: None, .. }) => {
+                            tracing::debug!("Transaction {} landed after {} broadcasts", signature, broadcasts);
+                            return Ok(signature);
+                        }
+                        Some(PubsubEvent::Signature { error: Some(error), .. }) => {
+                            return Err(LandingError::Failed(error));
+                        }
+                        // Уведомление могло потеряться во время разрыва
+                        Some(_) => must_poll = true,
+                        None => {
+                            events = None;
+                            must_poll = true;
+                        }
+                    },
+                }
+            } else {
+                tokio::time::sleep(self.rebroadcast_interval).await;
+            }
+
+            // Без опроса нужно только следить за истечением blockhash
+            if !must_poll {
+                match self.rpc.get_block_height().await {
+                    Ok(block_height) => must_poll = block_height > last_valid_block_height,
+                    Err(e) => tracing::warn!("Failed to get block height for {}: {}", signature, e),
+                }
+            }
+
+            if must_poll {
+                match self.poll(&signature, last_valid_block_height).await {
+                    Ok((status, landed)) => {
+                        seen = landed;
+                        match status {
+                            LandingStatus::Pending => {}
+                            LandingStatus::Landed => {
+                                tracing::debug!("Transaction {} landed after {} broadcasts", signature, broadcasts);
+                                return Ok(signature);
+                            }
+                            LandingStatus::Failed(error) => return Err(LandingError::Failed(error)),
+                            LandingStatus::Expired => {
+                                tracing::info!("Transaction {} expired after {} broadcasts", signature, broadcasts);
+                                return Err(LandingError::Expired);
+                            }
+                        }
+                    }
+                    Err(e) => tracing::warn!("Failed to check status of {}: {}", signature, e),
+                }
+            }
+
+            if started_at.elapsed() > MAX_LANDING_TIME {
+                return Err(LandingError::Unknown(signature));
+            }
+        }
+    }
+
+    fn subscribe(
+        &self,
+        signature: Signature,
+    ) -> Option<(mpsc::Receiver<PubsubEvent>, Subscription)> {
+        let pubsub = self.pubsub.as_ref()?;
+        let (sender, receiver) = pubsub.channel();
+        match pubsub.subscribe(PubsubTopic::Signature(signature), sender) {
+            Ok(subscription) => Some((receiver, subscription)),
+            Err(e) => {
+                tracing::warn!("Falling back to status polling for {}: {}", signature, e);
+                None
+            }
+        }
+    }
+
+    /// Статус подписи и признак того, что транзакция уже есть в сети
+    async fn poll(
+        &self,
+        signature: &Signature,
+        last_valid_block_height: u64,
+    ) -> anyhow::Result<(LandingStatus, bool)> {
+        let commitment = self.rpc.commitment();
+        // Высота запрашивается до статуса: иначе транзакция из последнего блока сочтется истекшей
+        let block_height = self.rpc.get_block_height().await?;
+        let statuses = self.rpc.get_signature_statuses(&[*signature]).await?;
+        let status = statuses.into_iter().next().flatten();
+
+        match landing_status(status.as_ref(), commitment, block_height, last_valid_block_height) {
+            // Высота и статус могли прийти с разных узлов, а недавний статус — выпасть из кэша:
+            // истечение признается только по истории подписи с узла, не отставшего от высоты
+            LandingStatus::Expired => {
+                let (landing, slot) = confirm_landing(self.rpc.as_ref(), signature, last_valid_block_height).await?;
+                Ok((landing, slot.is_some()))
+            }
+            landing => Ok((landing, status.is_some())),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use solana_client::rpc_response::RpcSimulateTransactionResult;
+use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+use thiserror::Error;
+
+// Ошибка `SlippageToleranceExceeded` программы Jupiter v6
+const JUPITER_SLIPPAGE_EXCEEDED: u32 = 6001;
+
+/// Понятная причина, по которой транзакция не прошла симуляцию
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SimulationFailure {
+    #[error("цена изменилась сильнее допустимого проскальзывания, повторите сделку")]
+    SlippageExceeded,
+    #[error("недостаточно средств на кошельке")]
+    InsufficientFunds,
+    #[error("недостаточно SOL для оплаты комиссии сети")]
+    InsufficientSolForFees,
+    #[error("транзакция устарела, повторите сделку")]
+    BlockhashExpired,
+    #[error("транзакции не хватило вычислительного бюджета")]
+    ComputeBudgetExceeded,
+    #[error("симуляция транзакции завершилась ошибкой: {0}")]
+    Other(String),
+}
+
+/// Результат `simulateTransaction`; сохраняется в сделке для отладки
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwapSimulation {
+    pub error: Option<TransactionError>,
+    pub units_consumed: Option<u64>,
+    pub logs: Vec<String>,
+}
+
+impl From<RpcSimulateTransactionResult> for SwapSimulation {
+    fn from(result: RpcSimulateTransactionResult) -> Self {
+        Self {
+            error: result.err.map(Into::into),
+            units_consumed: result.units_consumed,
+            logs: result.logs.unwrap_or_default(),
+        }
+    }
+}
+
+impl SwapSimulation {
+    /// Причина ошибки симуляции; `None`, если транзакция прошла
+    pub fn failure(&self) -> Option<SimulationFailure> {
+        self.error.as_ref().map(|error| classify(error, &self.logs))
+    }
+}
+
+fn logs_contain(logs: &[String], needles: &[&str]) -> bool {
+    logs.iter().any(|line| needles.iter().any(|needle| line.contains(needle)))
+}
+
+/// Сопоставляет ошибку транзакции и логи программ с понятной причиной.
+/// Логи точнее кода ошибки: `Custom(1)` у разных программ означает разное.
+pub fn classify(error: &TransactionError, logs: &[String]) -> SimulationFailure {
+    if logs_contain(logs, &["SlippageToleranceExceeded", "custom program error: 0x1771"]) {
+        return SimulationFailure::SlippageExceeded;
+    }
+    if logs_contain(logs, &["insufficient funds", "insufficient lamports"]) {
+        return SimulationFailure::InsufficientFunds;
+    }
+    if logs_contain(logs, &["exceeded CUs meter"]) {
+        return SimulationFailure::ComputeBudgetExceeded;
+    }
+
+    match error {
+        TransactionError::InstructionError(_, InstructionError::Custom(JUPITER_SLIPPAGE_EXCEEDED)) => {
+            SimulationFailure::SlippageExceeded
+        }
+        TransactionError::InstructionError(_, InstructionError::InsufficientFunds) => SimulationFailure::InsufficientFunds,
+        TransactionError::InstructionError(_, InstructionError::ComputationalBudgetExceeded) => {
+            SimulationFailure::ComputeBudgetExceeded
+        }
+        // Кошелек без SOL еще не существует как аккаунт
+        TransactionError::InsufficientFundsForFee | TransactionError::AccountNotFound => {
+            SimulationFailure::InsufficientSolForFees
+        }
+        TransactionError::BlockhashNotFound => SimulationFailure::BlockhashExpired,
+        other => SimulationFailure::Other(other.to_string()),
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, UiInstruction, UiLoadedAddresses,
+    UiMessage, UiTransactionStatusMeta,
+};
+
+use super::constants::SOL_MINT;
+
+/// Программы агрегатора и AMM, свапы через которые повторяются
+pub const SWAP_PROGRAMS: &[(&str, &str)] = &[
+    ("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4", "Jupiter"),
+    ("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8", "Raydium AMM"),
+    ("CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK", "Raydium CLMM"),
+    ("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C", "Raydium CPMM"),
+    ("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc", "Orca Whirlpool"),
+    ("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo", "Meteora DLMM"),
+    ("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P", "Pump.fun"),
+];
+
+/// Свап кошелька, восстановленный по изменениям его балансов
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedSwap {
+    pub program: &'static str,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub input_amount: u64,
+    pub output_amount: u64,
+    // Баланс входного токена до свапа: по нему считается доля проданной позиции
+    pub input_balance_before: u64,
+}
+
+/// Разбирает транзакцию `owner` как свап: ровно один токен убыл и ровно один прибавился.
+/// Нативный SOL и wSOL считаются одним токеном, комиссия сети в сумму не входит.
+/// Для свапа токен→токен изменение SOL (рента аккаунтов) не учитывается.
+pub fn decode_swap(owner: &Pubkey, tx: &EncodedConfirmedTransactionWithStatusMeta) -> Option<DecodedSwap> {
+    let meta = tx.transaction.meta.as_ref()?;
+    if meta.err.is_some() {
+        return None;
+    }
+
+    let EncodedTransaction::Json(transaction) = &tx.transaction.transaction else {
+        return None;
+    };
+    let UiMessage::Raw(message) = &transaction.message else {
+        return None;
+    };
+
+    // В версионных транзакциях к ключам сообщения добавляются адреса из lookup-таблиц
+    let mut account_keys = message.account_keys.clone();
+    if let Some(loaded) = Option::<&UiLoadedAddresses>::from(meta.loaded_addresses.as_ref()) {
+        account_keys.extend(loaded.writable.iter().cloned());
+        account_keys.extend(loaded.readonly.iter().cloned());
+    }
+
+    let owner_key = owner.to_string();
+    let owner_index = account_keys.iter().position(|key| *key == owner_key)?;
+    // Повторяем только транзакции, подписанные самим кошельком
+    if owner_index >= message.header.num_required_signatures as usize {
+        return None;
+    }
+
+    let program = swap_program(&account_keys, message.instructions.iter().map(|ix| ix.program_id_index), meta)?;
+
+    let mut changes = token_changes(&owner_key, meta);
+    let pre = *meta.pre_balances.get(owner_index)? as i128;
+    let post = *meta.post_balances.get(owner_index)? as i128;
+    let fee = if owner_index == 0 { meta.fee as i128 } else { 0 };
+    let sol = changes.entry(SOL_MINT.to_string()).or_default();
+    sol.delta += post - pre + fee;
+    sol.before += pre;
+
+    let sol_mint = SOL_MINT.to_string();
+    let tokens_only = changes
+        .iter()
+        .filter(|(mint, change)| **mint != sol_mint && change.delta != 0)
+        .count()
+        == 2;
+    let moved: Vec<(&String, &BalanceChange)> = changes
+        .iter()
+        .filter(|(mint, change)| change.delta != 0 && !(tokens_only && **mint == sol_mint))
+        .collect();
+
+    let [(a_mint, a), (b_mint, b)] = moved.as_slice() else {
+        return None;
+    };
+    let ((input_mint, input), (output_mint, output)) = match (a.delta < 0, b.delta < 0) {
+        (true, false) => ((a_mint, a), (b_mint, b)),
+        (false, true) => ((b_mint, b), (a_mint, a)),
+        _ => return None,
+    };
+
+    Some(DecodedSwap {
+        program,
+        input_mint: Pubkey::from_str(input_mint).ok()?,
+        output_mint: Pubkey::from_str(output_mint).ok()?,
+        input_amount: u64::try_from(-input.delta).ok()?,
+        output_amount: u64::try_from(output.delta).ok()?,
+        input_balance_before: u64::try_from(input.before).ok()?,
+    })
+}
+
+#[derive(Debug, Default)]
+struct BalanceChange {
+    before: i128,
+    delta: i128,
+}
+
+/// Изменения токен-балансов владельца по mint
+fn token_changes(owner: &str, meta: &UiTransactionStatusMeta) -> HashMap<String, BalanceChange> {
+    let mut changes: HashMap<String, BalanceChange> = HashMap::new();
+
+    let sides = [(&meta.pre_token_balances, -1i128), (&meta.post_token_balances, 1i128)];
+    for (balances, sign) in sides {
+        let Some(balances) = Option::<&Vec<_>>::from(balances.as_ref()) else {
+            continue;
+        };
+        for balance in balances {
+            if Option::<&String>::from(balance.owner.as_ref()).is_none_or(|o| o != owner) {
+                continue;
+            }
+            let Ok(amount) = balance.ui_token_amount.amount.parse::<i128>() else {
+                continue;
+            };
+
+            let change = changes.entry(balance.mint.clone()).or_default();
+            change.delta += sign * amount;
+            if sign < 0 {
+                change.before += amount;
+            }
+        }
+    }
+
+    changes
+}
+
+/// Первая известная программа свапа среди вызовов транзакции, включая вложенные
+fn swap_program(
+    account_keys: &[String],
+    top_level: impl Iterator<Item = u8>,
+    meta: &UiTransactionStatusMeta,
+) -> Option<&'static str> {
+    let mut program_indexes: Vec<u8> = top_level.collect();
+    if let Some(inner) = Option::<&Vec<_>>::from(meta.inner_instructions.as_ref()) {
+        for group in inner {
+            for instruction in &group.instructions {
+                if let UiInstruction::Compiled(instruction) = instruction {
+                    program_indexes.push(instruction.program_id_index);
+                }
+            }
+        }
+    }
+
+    program_indexes.into_iter().find_map(|index| {
+        let key = account_keys.get(index as usize)?;
+        SWAP_PROGRAMS.iter().find(|(program, _)| program == key).map(|(_, name)| *name)
+    })
+}
@@ -0,0 +1,226 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::Context;
+use dashmap::DashMap;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+
+use crate::{
+    database::{DatabaseConnectionPool, tokens},
+    entities::tokens as token_entity,
+    solana::{
+        constants::{SOL_DECIMALS, SOL_MINT, TokenMeta, token_symbol},
+        launch::{MintState, TOKEN_2022_PROGRAM},
+        rpc::SolanaRpc,
+        token_safety::mint_extension_entries,
+    },
+};
+
+// Программа Metaplex Token Metadata
+pub const METAPLEX_METADATA_PROGRAM: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+
+// `Key::MetadataV1` — первый байт аккаунта метаданных Metaplex
+const METAPLEX_KEY_METADATA_V1: u8 = 4;
+
+// Номер расширения `TokenMetadata` в Token-2022
+const EXT_TOKEN_METADATA: u16 = 19;
+
+/// Аккаунт метаданных Metaplex для mint.
+/// PDA: ["metadata", program_id, mint] в программе Token Metadata.
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    let program_id = Pubkey::from_str(METAPLEX_METADATA_PROGRAM).expect("valid metadata program id");
+
+    Pubkey::find_program_address(
+        &[b"metadata", program_id.as_ref(), mint.as_ref()],
+        &program_id,
+    ).0
+}
+
+/// Название и символ из метаданных токена
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenLabel {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+}
+
+// Borsh-строка: длина u32 и байты. Metaplex дополняет строки нулями до фиксированной длины.
+fn string_at(data: &[u8], offset: usize) -> Option<(Option<String>, usize)> {
+    let length = u32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?) as usize;
+    let start = offset + 4;
+    let bytes = data.get(start..start + length)?;
+    let value = String::from_utf8_lossy(bytes).trim_matches(char::from(0)).trim().to_string();
+
+    Some(((!value.is_empty()).then_some(value), start + length))
+}
+
+// name, symbol, uri подряд начиная со смещения
+fn label_at(data: &[u8], offset: usize) -> Option<TokenLabel> {
+    let (name, offset) = string_at(data, offset)?;
+    let (symbol, _) = string_at(data, offset)?;
+    Some(TokenLabel { name, symbol })
+}
+
+/// Метаданные Metaplex: key, update_authority, mint, затем name, symbol, uri
+pub fn decode_metaplex_metadata(data: &[u8]) -> Option<TokenLabel> {
+    if data.first() != Some(&METAPLEX_KEY_METADATA_V1) {
+        return None;
+    }
+    label_at(data, 65)
+}
+
+/// Расширение `TokenMetadata` Token-2022: update_authority, mint, затем name, symbol, uri
+pub fn decode_token_2022_metadata(data: &[u8]) -> Option<TokenLabel> {
+    let (_, value) = mint_extension_entries(data)?
+        .into_iter()
+        .find(|(kind, _)| *kind == EXT_TOKEN_METADATA)?;
+    label_at(value, 64)
+}
+
+/// Метаданные mint: знаки из mint-аккаунта, название и символ — если токен их публикует
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenInfo {
+    pub mint: Pubkey,
+    pub symbol: Option<String>,
+    pub name: Option<String>,
+    pub decimals: u8,
+    pub token_2022: bool,
+}
+
+impl TokenInfo {
+    /// Разбирает mint-аккаунт и аккаунт метаданных Metaplex. Расширение метаданных
+    /// Token-2022 важнее Metaplex: его обновляет сам выпускающий токен.
+    pub fn from_accounts(mint: Pubkey, mint_account: &Account, metadata: Option<&Account>) -> Option<Self> {
+        let state = MintState::decode(&mint_account.owner, &mint_account.data)?;
+        let token_2022 = mint_account.owner.to_string() == TOKEN_2022_PROGRAM;
+
+        let metaplex = || {
+            metadata
+                .filter(|account| account.owner.to_string() == METAPLEX_METADATA_PROGRAM)
+                .and_then(|account| decode_metaplex_metadata(&account.data))
+        };
+        let label = token_2022
+            .then(|| decode_token_2022_metadata(&mint_account.data))
+            .flatten()
+            .or_else(metaplex)
+            .unwrap_or(TokenLabel { name: None, symbol: None });
+
+        Some(Self {
+            mint,
+            symbol: label.symbol,
+            name: label.name,
+            decimals: state.decimals,
+            token_2022,
+        })
+    }
+
+    fn from_model(model: token_entity::Model) -> Option<Self> {
+        Some(Self {
+            mint: Pubkey::from_str(&model.mint).ok()?,
+            symbol: model.symbol,
+            name: model.name,
+            decimals: u8::try_from(model.decimals).ok()?,
+            token_2022: model.token_2022,
+        })
+    }
+
+    /// Символ для отображения сумм: из метаданных, иначе известный символ или сокращенный адрес
+    pub fn meta(&self) -> TokenMeta {
+        TokenMeta {
+            symbol: self.symbol.clone().unwrap_or_else(|| token_symbol(&self.mint)),
+            decimals: self.decimals,
+        }
+    }
+}
+
+/// Метаданные токенов из сети с кешем в памяти и в таблице `tokens`
+pub struct TokenResolver {
+    rpc: Arc<dyn SolanaRpc>,
+    database: Option<Arc<DatabaseConnectionPool>>,
+    cache: DashMap<Pubkey, TokenInfo>,
+}
+
+impl TokenResolver {
+    pub fn new(rpc: Arc<dyn SolanaRpc>) -> Self {
+        Self {
+            rpc,
+            database: None,
+            cache: DashMap::new(),
+        }
+    }
+
+    /// Хранить прочитанные метаданные в БД, чтобы не запрашивать их после перезапуска
+    pub fn with_database(mut self, database: Arc<DatabaseConnectionPool>) -> Self {
+        self.database = Some(database);
+        self
+    }
+
+    /// Метаданные mint: из памяти, из БД или из сети
+    pub async fn resolve(&self, mint: &Pubkey) -> anyhow::Result<TokenInfo> {
+        if let Some(info) = self.cache.get(mint) {
+            return Ok(info.clone());
+        }
+
+        let info = match self.load(mint).await {
+            Some(info) => info,
+            None => {
+                let info = self.fetch(mint).await?;
+                self.save(&info).await;
+                info
+            }
+        };
+
+        self.cache.insert(*mint, info.clone());
+        Ok(info)
+    }
+
+    async fn load(&self, mint: &Pubkey) -> Option<TokenInfo> {
+        // Знаки нативного SOL заданы протоколом, аккаунт wrapped SOL читать незачем
+        if mint.to_string() == SOL_MINT {
+            return Some(TokenInfo {
+                mint: *mint,
+                symbol: Some("SOL".to_string()),
+                name: Some("Wrapped SOL".to_string()),
+                decimals: SOL_DECIMALS,
+                token_2022: false,
+            });
+        }
+
+        let database = self.database.as_ref()?;
+        match tokens::find(database.get_connection(), &mint.to_string()).await {
+            Ok(model) => model.and_then(TokenInfo::from_model),
+            Err(e) => {
+                tracing::warn!("Failed to load token {} from database: {}", mint, e);
+                None
+            }
+        }
+    }
+
+    async fn fetch(&self, mint: &Pubkey) -> anyhow::Result<TokenInfo> {
+        let accounts = self.rpc
+            .get_multiple_accounts(&[*mint, metadata_address(mint)])
+            .await
+            .with_context(|| format!("Failed to fetch mint account {}", mint))?;
+        let mint_account = accounts.first()
+            .and_then(Option::as_ref)
+            .with_context(|| format!("Mint account {} not found", mint))?;
+
+        TokenInfo::from_accounts(*mint, mint_account, accounts.get(1).and_then(Option::as_ref))
+            .with_context(|| format!("Account {} is not an SPL token mint", mint))
+    }
+
+    async fn save(&self, info: &TokenInfo) {
+        let Some(database) = &self.database else {
+            return;
+        };
+        let token = tokens::NewToken {
+            mint: info.mint.to_string(),
+            symbol: info.symbol.clone(),
+            name: info.name.clone(),
+            decimals: info.decimals.into(),
+            token_2022: info.token_2022,
+        };
+        if let Err(e) = tokens::upsert(database.get_connection(), token).await {
+            tracing::warn!("Failed to save token {}: {}", info.mint, e);
+        }
+    }
+}
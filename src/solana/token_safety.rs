@@ -0,0 +1,389 @@
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use dashmap::DashMap;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    config::settings::TokenSafetySettings,
+    jupiter::{QuoteResponseV6, SwapMode},
+    solana::{
+        constants::{SOL_MINT, find_known_token},
+        launch::{MintState, TOKEN_2022_PROGRAM, top_holders_pct},
+        trader::{Trader, price_impact_pct},
+    },
+};
+
+// Размер аккаунта токена SPL: у Token-2022 за ним идут тип аккаунта и TLV-расширения
+const ACCOUNT_TYPE_OFFSET: usize = 165;
+const ACCOUNT_TYPE_MINT: u8 = 1;
+
+// Номера расширений `ExtensionType` в Token-2022
+const EXT_TRANSFER_FEE_CONFIG: u16 = 1;
+const EXT_DEFAULT_ACCOUNT_STATE: u16 = 6;
+const EXT_NON_TRANSFERABLE: u16 = 9;
+const EXT_PERMANENT_DELEGATE: u16 = 12;
+const EXT_TRANSFER_HOOK: u16 = 14;
+const EXT_PAUSABLE: u16 = 26;
+
+// `AccountState::Frozen`
+const ACCOUNT_STATE_FROZEN: u8 = 2;
+
+/// Комиссия за перевод Token-2022
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferFee {
+    pub basis_points: u16,
+    pub maximum_fee: u64,
+}
+
+/// Расширения mint Token-2022, которые влияют на возможность продать токен
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MintExtensions {
+    pub transfer_fee: Option<TransferFee>,
+    pub permanent_delegate: Option<Pubkey>,
+    // Программа, которую вызывает каждый перевод
+    pub transfer_hook: Option<Pubkey>,
+    pub non_transferable: bool,
+    pub default_frozen: bool,
+    pub pausable: bool,
+}
+
+// `OptionalNonZeroPubkey`: нулевой ключ означает отсутствие
+fn optional_pubkey_at(data: &[u8], offset: usize) -> Option<Pubkey> {
+    let bytes: [u8; 32] = data.get(offset..offset + 32)?.try_into().ok()?;
+    Some(Pubkey::new_from_array(bytes)).filter(|key| *key != Pubkey::default())
+}
+
+fn u64_at(data: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(data.get(offset..offset + 8)?.try_into().ok()?))
+}
+
+fn u16_at(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(offset..offset + 2)?.try_into().ok()?))
+}
+
+impl TransferFee {
+    // `TransferFeeConfig`: два ключа, withheld_amount, затем старая и новая комиссии
+    // (epoch, maximum_fee, basis_points). Берется большая из двух: новая вступает в силу с эпохи.
+    fn decode(value: &[u8]) -> Option<Self> {
+        let older = Self { maximum_fee: u64_at(value, 80)?, basis_points: u16_at(value, 88)? };
+        let newer = Self { maximum_fee: u64_at(value, 98)?, basis_points: u16_at(value, 106)? };
+        Some(if newer.basis_points >= older.basis_points { newer } else { older })
+    }
+}
+
+/// TLV-записи расширений из данных mint: пустой список у mint без расширений,
+/// `None` — если данные не похожи на mint Token-2022
+pub(crate) fn mint_extension_entries(data: &[u8]) -> Option<Vec<(u16, &[u8])>> {
+    let mut entries = Vec::new();
+    if data.len() <= ACCOUNT_TYPE_OFFSET {
+        return Some(entries);
+    }
+    if data[ACCOUNT_TYPE_OFFSET] != ACCOUNT_TYPE_MINT {
+        return None;
+    }
+
+    let mut offset = ACCOUNT_TYPE_OFFSET + 1;
+    while let (Some(kind), Some(length)) = (u16_at(data, offset), u16_at(data, offset + 2)) {
+        // Нулевой тип — неиспользуемый хвост аккаунта
+        if kind == 0 {
+            break;
+        }
+        let start = offset + 4;
+        entries.push((kind, data.get(start..start + length as usize)?));
+        offset = start + length as usize;
+    }
+
+    Some(entries)
+}
+
+impl MintExtensions {
+    /// Разбирает TLV-расширения из данных mint. У mint без расширений возвращает пустой набор,
+    /// `None` — если данные не похожи на mint Token-2022.
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        let mut extensions = Self::default();
+        for (kind, value) in mint_extension_entries(data)? {
+            match kind {
+                EXT_TRANSFER_FEE_CONFIG => extensions.transfer_fee = TransferFee::decode(value).filter(|fee| fee.basis_points > 0),
+                EXT_DEFAULT_ACCOUNT_STATE => extensions.default_frozen = value.first() == Some(&ACCOUNT_STATE_FROZEN),
+                EXT_NON_TRANSFERABLE => extensions.non_transferable = true,
+                EXT_PERMANENT_DELEGATE => extensions.permanent_delegate = optional_pubkey_at(value, 0),
+                // authority, затем program_id
+                EXT_TRANSFER_HOOK => extensions.transfer_hook = optional_pubkey_at(value, 32),
+                EXT_PAUSABLE => extensions.pausable = true,
+                _ => {}
+            }
+        }
+
+        Some(extensions)
+    }
+}
+
+/// Результат обратной продажи купленного количества в SOL
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SellCheck {
+    // Потери сверх влияния на цену обеих сделок и комиссии сервиса, включая комиссии пулов
+    Sellable { sell_tax_pct: f64 },
+    // Jupiter не нашел маршрута обратно в SOL
+    NoRoute,
+    // Котировка не получена по другой причине
+    Unknown,
+}
+
+/// Потери на круге SOL → токен → SOL сверх влияния на цену и комиссии сервиса, в процентах
+pub fn round_trip_tax_pct(buy: &QuoteResponseV6, sell: &QuoteResponseV6) -> f64 {
+    if buy.in_amount == 0 {
+        return 0.0;
+    }
+
+    let loss_pct = (1.0 - sell.out_amount as f64 / buy.in_amount as f64) * 100.0;
+    let platform_fee_pct = [buy, sell]
+        .iter()
+        .filter_map(|quote| quote.platform_fee.as_ref())
+        .map(|fee| fee.fee_bps as f64 / 100.0)
+        .sum::<f64>();
+
+    (loss_pct - price_impact_pct(buy) - price_impact_pct(sell) - platform_fee_pct).max(0.0)
+}
+
+/// Ошибка Jupiter об отсутствии маршрута
+pub fn is_no_route_error(error: &anyhow::Error) -> bool {
+    let text = format!("{:#}", error);
+    text.contains("COULD_NOT_FIND_ANY_ROUTE") || text.contains("NO_ROUTES_FOUND")
+}
+
+/// Результат проверки токена перед покупкой
+#[derive(Debug, Clone, PartialEq)]
+pub struct SafetyReport {
+    pub mint: Pubkey,
+    pub token_2022: bool,
+    pub mint_authority: Option<Pubkey>,
+    pub freeze_authority: Option<Pubkey>,
+    pub extensions: MintExtensions,
+    pub supply: u64,
+    // Доля крупнейших держателей без аккаунтов программ (хранилищ пулов)
+    pub top_holders_pct: f64,
+    pub sell: SellCheck,
+}
+
+impl SafetyReport {
+    /// Найденные риски и их вес; сумма весов — оценка риска
+    fn risks(&self) -> Vec<(u8, String)> {
+        let mut risks = Vec::new();
+        let ext = &self.extensions;
+
+        if ext.non_transferable {
+            risks.push((100, "Токен непереводимый, продать его нельзя".to_string()));
+        }
+        if self.sell == SellCheck::NoRoute {
+            risks.push((100, "Нет маршрута для продажи обратно в SOL".to_string()));
+        }
+        if ext.permanent_delegate.is_some() {
+            risks.push((35, "Постоянный делегат может забрать или сжечь токены с любого кошелька".to_string()));
+        }
+        if ext.transfer_hook.is_some() {
+            risks.push((25, "Переводы проходят через стороннюю программу и могут быть запрещены".to_string()));
+        }
+        if ext.default_frozen {
+            risks.push((25, "Новые счета токена создаются замороженными".to_string()));
+        }
+        if self.freeze_authority.is_some() {
+            risks.push((20, "Владелец может заморозить токены на вашем кошельке".to_string()));
+        }
+        if ext.pausable {
+            risks.push((20, "Владелец может приостановить переводы".to_string()));
+        }
+        if self.mint_authority.is_some() {
+            risks.push((15, "Владелец может выпускать новые токены".to_string()));
+        }
+        if let Some(fee) = ext.transfer_fee {
+            let pct = fee.basis_points as f64 / 100.0;
+            risks.push(((10.0 + pct * 2.0).min(30.0) as u8, format!("Комиссия за перевод {:.2}%", pct)));
+        }
+        match self.sell {
+            SellCheck::Sellable { sell_tax_pct } if sell_tax_pct >= 2.0 => risks.push((
+                (sell_tax_pct * 2.0).min(40.0) as u8,
+                format!("Потери при продаже ~{:.1}% сверх влияния на цену", sell_tax_pct),
+            )),
+            SellCheck::Unknown => risks.push((10, "Не удалось оценить продажу токена".to_string())),
+            _ => {}
+        }
+        if self.top_holders_pct >= 50.0 {
+            risks.push((20, format!("Крупнейшие держатели владеют {:.1}% supply", self.top_holders_pct)));
+        } else if self.top_holders_pct >= 25.0 {
+            risks.push((10, format!("Крупнейшие держатели владеют {:.1}% supply", self.top_holders_pct)));
+        }
+
+        risks
+    }
+
+    /// Оценка риска от 0 до 100
+    pub fn risk_score(&self) -> u8 {
+        self.risks().iter().map(|(weight, _)| *weight as u32).sum::<u32>().min(100) as u8
+    }
+
+    pub fn warnings(&self) -> Vec<String> {
+        self.risks().into_iter().map(|(_, warning)| warning).collect()
+    }
+
+    /// Причины блокировки покупки по правилам администратора; пусто — покупка разрешена
+    pub fn block_reasons(&self, settings: &TokenSafetySettings) -> Vec<String> {
+        let mut reasons = Vec::new();
+        let ext = &self.extensions;
+
+        let score = self.risk_score();
+        if score > settings.max_risk_score {
+            reasons.push(format!("риск {}/100 выше допустимого {}", score, settings.max_risk_score));
+        }
+        if settings.block_freeze_authority && self.freeze_authority.is_some() {
+            reasons.push("у токена есть freeze authority".to_string());
+        }
+        if settings.block_permanent_delegate && ext.permanent_delegate.is_some() {
+            reasons.push("у токена есть постоянный делегат".to_string());
+        }
+        if settings.block_transfer_hook && ext.transfer_hook.is_some() {
+            reasons.push("у токена есть transfer hook".to_string());
+        }
+        if let Some(fee) = ext.transfer_fee.filter(|fee| fee.basis_points > settings.max_transfer_fee_bps) {
+            reasons.push(format!(
+                "комиссия за перевод {} bps выше допустимой {} bps",
+                fee.basis_points, settings.max_transfer_fee_bps,
+            ));
+        }
+        match self.sell {
+            SellCheck::NoRoute if settings.block_unsellable => {
+                reasons.push("токен нельзя продать обратно в SOL".to_string());
+            }
+            SellCheck::Sellable { sell_tax_pct } if sell_tax_pct > settings.max_sell_tax_pct => {
+                reasons.push(format!(
+                    "потери при продаже {:.1}% выше допустимых {}%",
+                    sell_tax_pct, settings.max_sell_tax_pct,
+                ));
+            }
+            _ => {}
+        }
+
+        reasons
+    }
+}
+
+/// Данные mint, не зависящие от суммы сделки
+struct MintScan {
+    token_2022: bool,
+    mint: MintState,
+    extensions: MintExtensions,
+    top_holders_pct: f64,
+}
+
+/// Проверка токена перед покупкой: mint по RPC и обратная котировка продажи.
+/// Отчеты кешируются по mint на `cache_ttl_secs`.
+pub struct TokenSafety {
+    trader: Arc<Trader>,
+    settings: TokenSafetySettings,
+    cache: DashMap<Pubkey, (SafetyReport, Instant)>,
+}
+
+impl TokenSafety {
+    pub fn new(trader: Arc<Trader>, settings: TokenSafetySettings) -> Self {
+        Self {
+            trader,
+            settings,
+            cache: DashMap::new(),
+        }
+    }
+
+    pub fn settings(&self) -> &TokenSafetySettings {
+        &self.settings
+    }
+
+    /// Отчет о покупаемом токене котировки. `None`, если проверка выключена,
+    /// котировка не покупает токен за SOL или токен из списка известных.
+    pub async fn check(&self, quote: &QuoteResponseV6) -> anyhow::Result<Option<SafetyReport>> {
+        let sol_mint = Pubkey::from_str(SOL_MINT)?;
+        let mint = quote.output_mint;
+
+        if !self.settings.enabled || quote.input_mint != sol_mint || find_known_token(&mint.to_string()).is_some() {
+            return Ok(None);
+        }
+
+        let ttl = Duration::from_secs(self.settings.cache_ttl_secs);
+        if let Some(entry) = self.cache.get(&mint).filter(|entry| entry.1.elapsed() < ttl) {
+            return Ok(Some(entry.0.clone()));
+        }
+
+        let scan = self.scan_mint(&mint).await?;
+        let sell = self.round_trip(quote, sol_mint).await;
+
+        let report = SafetyReport {
+            mint,
+            token_2022: scan.token_2022,
+            mint_authority: scan.mint.mint_authority,
+            freeze_authority: scan.mint.freeze_authority,
+            extensions: scan.extensions,
+            supply: scan.mint.supply,
+            top_holders_pct: scan.top_holders_pct,
+            sell,
+        };
+        self.cache.insert(mint, (report.clone(), Instant::now()));
+
+        Ok(Some(report))
+    }
+
+    async fn scan_mint(&self, mint: &Pubkey) -> anyhow::Result<MintScan> {
+        let rpc = self.trader.rpc();
+
+        let account = rpc
+            .get_account(mint)
+            .await
+            .context("failed to fetch mint account")?
+            .context("mint account not found")?;
+        let state = MintState::decode(&account.owner, &account.data).context("account is not a token mint")?;
+        let token_2022 = account.owner.to_string() == TOKEN_2022_PROGRAM;
+        let extensions = if token_2022 {
+            MintExtensions::decode(&account.data).context("malformed Token-2022 mint extensions")?
+        } else {
+            MintExtensions::default()
+        };
+
+        let largest = rpc.get_token_largest_accounts(mint).await?;
+        let keys: Vec<Pubkey> = largest.iter().map(|(account, _)| *account).collect();
+        let accounts = rpc.get_multiple_accounts(&keys).await?;
+
+        // Аккаунты, которыми владеет программа (хранилища пулов, локеры), держателями не считаются
+        let excluded: Vec<Pubkey> = keys
+            .iter()
+            .zip(&accounts)
+            .filter(|(_, account)| {
+                account
+                    .as_ref()
+                    .and_then(|account| optional_pubkey_at(&account.data, 32))
+                    .is_some_and(|owner| !owner.is_on_curve())
+            })
+            .map(|(key, _)| *key)
+            .collect();
+
+        Ok(MintScan {
+            token_2022,
+            top_holders_pct: top_holders_pct(state.supply, &largest, &excluded, self.settings.top_holders),
+            mint: state,
+            extensions,
+        })
+    }
+
+    async fn round_trip(&self, buy: &QuoteResponseV6, sol_mint: Pubkey) -> SellCheck {
+        let sell = self.trader
+            .quote(buy.output_mint, sol_mint, buy.out_amount, SwapMode::ExactIn, buy.slippage_bps)
+            .await;
+
+        match sell {
+            Ok(sell) => SellCheck::Sellable { sell_tax_pct: round_trip_tax_pct(buy, &sell) },
+            Err(e) if is_no_route_error(&e) => SellCheck::NoRoute,
+            Err(e) => {
+                tracing::warn!("Failed to quote round-trip sell of {}: {:#}", buy.output_mint, e);
+                SellCheck::Unknown
+            }
+        }
+    }
+}
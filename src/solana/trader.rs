@@ -0,0 +1,453 @@
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use dashmap::DashSet;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::VersionedTransaction,
+};
+
+use crate::{
+    config::settings::{TradingLimits, default_rebroadcast_interval_ms},
+    database::DatabaseConnectionPool,
+    jupiter::{
+        JupiterClient, PrioritizationFee, QuoteParamsV6, QuoteResponseV6, SwapMode, SwapParamsV6,
+        fees::{fee_mint, referral_fee_account},
+    },
+    solana::{
+        amount::TokenAmount,
+        constants::{SOL_DECIMALS, SOL_MINT, TokenMeta},
+        priority_fee::paid_priority_fee,
+        pubsub::PubsubManager,
+        rpc::SolanaRpc,
+        sender::{LandingError, TransactionSender},
+        simulation::{SimulationFailure, SwapSimulation},
+        token_metadata::TokenResolver,
+    },
+};
+
+const DEFAULT_MAX_ACCOUNTS: u32 = 64;
+
+#[derive(Debug, thiserror::Error)]
+pub enum TradeError {
+    #[error("сумма сделки {amount_sol} SOL превышает лимит {limit_sol} SOL")]
+    AmountAboveLimit { amount_sol: TokenAmount, limit_sol: TokenAmount },
+    #[error("сумма сделки {amount_sol} SOL меньше минимальной {limit_sol} SOL")]
+    AmountBelowLimit { amount_sol: TokenAmount, limit_sol: TokenAmount },
+    #[error("проскальзывание {slippage_bps} bps превышает лимит {limit_bps} bps")]
+    SlippageAboveLimit { slippage_bps: u64, limit_bps: u64 },
+    #[error("влияние на цену {impact_pct:.2}% превышает допустимые {limit_pct}%")]
+    PriceImpactTooHigh { impact_pct: f64, limit_pct: f64 },
+    #[error("достигнут лимит {limit} сделок за {period}")]
+    TooManyTrades { limit: u32, period: &'static str },
+    #[error("объем за сутки {amount_sol} SOL превысит лимит {limit_sol} SOL")]
+    DailyVolumeExceeded { amount_sol: TokenAmount, limit_sol: TokenAmount },
+    #[error(transparent)]
+    Simulation(#[from] SimulationFailure),
+    #[error(transparent)]
+    Landing(#[from] LandingError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+/// Сумма покупки: сколько SOL потратить (ExactIn) или сколько токенов получить (ExactOut)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuyAmount {
+    SpendLamports(u64),
+    ReceiveExact(u64),
+}
+
+impl BuyAmount {
+    pub fn swap_mode(&self) -> SwapMode {
+        match self {
+            BuyAmount::SpendLamports(_) => SwapMode::ExactIn,
+            BuyAmount::ReceiveExact(_) => SwapMode::ExactOut,
+        }
+    }
+
+    pub fn amount(&self) -> u64 {
+        match self {
+            BuyAmount::SpendLamports(amount) | BuyAmount::ReceiveExact(amount) => *amount,
+        }
+    }
+}
+
+/// Максимальная сумма, которая может быть списана по котировке.
+/// Для ExactOut это `other_amount_threshold` — вход с учетом проскальзывания.
+pub fn max_input_amount(quote: &QuoteResponseV6) -> u64 {
+    match quote.swap_mode {
+        SwapMode::ExactIn => quote.in_amount,
+        SwapMode::ExactOut => quote.other_amount_threshold,
+    }
+}
+
+/// Влияние на цену в процентах (Jupiter возвращает долю)
+pub fn price_impact_pct(quote: &QuoteResponseV6) -> f64 {
+    quote.price_impact_pct * 100.0
+}
+
+/// Худший по объему SOL сценарий сделки: что может быть потрачено при покупке
+/// или что будет получено при продаже. Для сделок без SOL возвращает `None`.
+pub fn worst_case_sol_lamports(quote: &QuoteResponseV6) -> Option<u64> {
+    let sol_mint = Pubkey::from_str(SOL_MINT).ok()?;
+
+    if quote.input_mint == sol_mint {
+        Some(max_input_amount(quote))
+    } else if quote.output_mint == sol_mint {
+        Some(quote.out_amount)
+    } else {
+        None
+    }
+}
+
+/// Недавняя торговая активность пользователя для проверки частотных лимитов
+#[derive(Debug, Clone, Copy)]
+pub struct TradeActivity {
+    pub trades_last_hour: u64,
+    pub trades_last_day: u64,
+    pub sol_spent_last_day: TokenAmount,
+}
+
+impl Default for TradeActivity {
+    fn default() -> Self {
+        Self {
+            trades_last_hour: 0,
+            trades_last_day: 0,
+            sol_spent_last_day: TokenAmount::zero(SOL_DECIMALS),
+        }
+    }
+}
+
+/// Комиссия платформы, удерживаемая Jupiter в пользу нашего реферального аккаунта
+#[derive(Debug, Clone, Copy)]
+pub struct PlatformFeeConfig {
+    pub fee_bps: u16,
+    pub referral_account: Pubkey,
+}
+
+/// Подписанная, но еще не отправленная транзакция свапа.
+/// Подпись известна до отправки, поэтому сделку можно записать в БД заранее.
+pub struct PreparedSwap {
+    pub transaction: VersionedTransaction,
+    pub signature: Signature,
+    pub last_valid_block_height: u64,
+    pub fee_account: Option<Pubkey>,
+    // Приоритетная комиссия по инструкциям ComputeBudget подписанной транзакции
+    pub priority_fee_lamports: u64,
+}
+
+pub struct Trader {
+    jupiter: JupiterClient,
+    rpc: Arc<dyn SolanaRpc>,
+    limits: TradingLimits,
+    platform_fee: Option<PlatformFeeConfig>,
+    // Токен-аккаунты для комиссий, существование которых уже проверено
+    fee_accounts: DashSet<Pubkey>,
+    rebroadcast_interval: Duration,
+    pubsub: Option<Arc<PubsubManager>>,
+    tokens: TokenResolver,
+}
+
+impl Trader {
+    pub fn new(jupiter: JupiterClient, rpc: Arc<dyn SolanaRpc>, limits: TradingLimits) -> Self {
+        Self {
+            jupiter,
+            tokens: TokenResolver::new(rpc.clone()),
+            rpc,
+            limits,
+            platform_fee: None,
+            fee_accounts: DashSet::new(),
+            rebroadcast_interval: Duration::from_millis(default_rebroadcast_interval_ms()),
+            pubsub: None,
+        }
+    }
+
+    /// Подтверждения свапов по подписке вместо опроса статусов
+    pub fn with_pubsub(mut self, pubsub: Option<Arc<PubsubManager>>) -> Self {
+        self.pubsub = pubsub;
+        self
+    }
+
+    /// Кеш метаданных токенов в таблице `tokens`
+    pub fn with_token_cache(mut self, database: Arc<DatabaseConnectionPool>) -> Self {
+        self.tokens = self.tokens.with_database(database);
+        self
+    }
+
+    /// Как часто повторно отправлять транзакцию, пока она не подтверждена
+    pub fn with_rebroadcast_interval(mut self, rebroadcast_interval: Duration) -> Self {
+        self.rebroadcast_interval = rebroadcast_interval;
+        self
+    }
+
+    pub fn with_platform_fee(mut self, platform_fee: PlatformFeeConfig) -> Self {
+        self.platform_fee = Some(platform_fee).filter(|fee| fee.fee_bps > 0);
+        self
+    }
+
+    pub fn platform_fee(&self) -> Option<&PlatformFeeConfig> {
+        self.platform_fee.as_ref()
+    }
+
+    pub fn limits(&self) -> &TradingLimits {
+        &self.limits
+    }
+
+    pub fn jupiter(&self) -> &JupiterClient {
+        &self.jupiter
+    }
+
+    pub fn rpc(&self) -> &Arc<dyn SolanaRpc> {
+        &self.rpc
+    }
+
+    pub async fn quote(
+        &self,
+        input_mint: Pubkey,
+        output_mint: Pubkey,
+        amount: u64,
+        swap_mode: SwapMode,
+        slippage_bps: u64,
+    ) -> anyhow::Result<QuoteResponseV6> {
+        let platform_fee_bps = match self.platform_fee {
+            Some(fee) if self.fee_account_ready(&input_mint, &output_mint, swap_mode).await => {
+                Some(fee.fee_bps)
+            }
+            _ => None,
+        };
+
+        let params = QuoteParamsV6 {
+            input_mint,
+            output_mint,
+            amount,
+            slippage_bps,
+            only_direct_routes: false,
+            as_legacy_transaction: false,
+            swap_mode,
+            max_accounts: DEFAULT_MAX_ACCOUNTS,
+            platform_fee_bps,
+        };
+
+        self.jupiter.get_quote_v6(&params).await
+    }
+
+    /// Новая котировка с теми же параметрами, что и у устаревшей
+    pub async fn refresh_quote(&self, quote: &QuoteResponseV6) -> anyhow::Result<QuoteResponseV6> {
+        let amount = match quote.swap_mode {
+            SwapMode::ExactIn => quote.in_amount,
+            SwapMode::ExactOut => quote.out_amount,
+        };
+        self.quote(quote.input_mint, quote.output_mint, amount, quote.swap_mode, quote.slippage_bps).await
+    }
+
+    /// Котировка покупки токена за SOL
+    pub async fn quote_buy(
+        &self,
+        output_mint: Pubkey,
+        amount: BuyAmount,
+        slippage_bps: u64,
+    ) -> anyhow::Result<QuoteResponseV6> {
+        let sol_mint = Pubkey::from_str(SOL_MINT)?;
+        self.quote(sol_mint, output_mint, amount.amount(), amount.swap_mode(), slippage_bps).await
+    }
+
+    /// Проверка `TradingLimits` по худшему сценарию котировки
+    pub fn check_limits(&self, quote: &QuoteResponseV6) -> Result<(), TradeError> {
+        self.check_exit_limits(quote)?;
+
+        let impact_pct = price_impact_pct(quote);
+        if impact_pct > self.limits.price_impact_block_pct {
+            return Err(TradeError::PriceImpactTooHigh {
+                impact_pct,
+                limit_pct: self.limits.price_impact_block_pct,
+            });
+        }
+
+        let Some(lamports) = worst_case_sol_lamports(quote) else {
+            return Ok(());
+        };
+        let amount_sol = TokenAmount::sol(lamports);
+
+        let limit_sol = TokenAmount::sol_setting(self.limits.max_trade_amount_sol);
+        if amount_sol > limit_sol {
+            return Err(TradeError::AmountAboveLimit { amount_sol, limit_sol });
+        }
+        let limit_sol = TokenAmount::sol_setting(self.limits.min_trade_amount_sol);
+        if amount_sol < limit_sol {
+            return Err(TradeError::AmountBelowLimit { amount_sol, limit_sol });
+        }
+
+        Ok(())
+    }
+
+    /// Проверка защитного выхода из позиции: только потолок проскальзывания.
+    /// Размер сделки, влияние на цену и активность не мешают закрыть позицию.
+    pub fn check_exit_limits(&self, quote: &QuoteResponseV6) -> Result<(), TradeError> {
+        if quote.slippage_bps > self.limits.max_slippage_bps {
+            return Err(TradeError::SlippageAboveLimit {
+                slippage_bps: quote.slippage_bps,
+                limit_bps: self.limits.max_slippage_bps,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Проверка лимитов на количество сделок и суточный объем с учетом новой сделки
+    pub fn check_activity_limits(
+        &self,
+        activity: &TradeActivity,
+        quote: &QuoteResponseV6,
+    ) -> Result<(), TradeError> {
+        if activity.trades_last_hour >= self.limits.max_trades_per_hour as u64 {
+            return Err(TradeError::TooManyTrades { limit: self.limits.max_trades_per_hour, period: "час" });
+        }
+        if activity.trades_last_day >= self.limits.max_trades_per_day as u64 {
+            return Err(TradeError::TooManyTrades { limit: self.limits.max_trades_per_day, period: "сутки" });
+        }
+
+        let sol_mint = Pubkey::from_str(SOL_MINT).context("Invalid SOL mint")?;
+        if quote.input_mint == sol_mint {
+            let amount_sol = activity.sol_spent_last_day
+                .checked_add(TokenAmount::sol(max_input_amount(quote)))
+                .unwrap_or(TokenAmount::sol(u64::MAX));
+            let limit_sol = TokenAmount::sol_setting(self.limits.daily_trade_limit_sol);
+            if amount_sol > limit_sol {
+                return Err(TradeError::DailyVolumeExceeded { amount_sol, limit_sol });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Токен-аккаунт для комиссии по котировке, если комиссия была запрошена
+    pub fn fee_account_for(&self, quote: &QuoteResponseV6) -> Option<Pubkey> {
+        let config = self.platform_fee?;
+        quote.platform_fee.as_ref()?;
+
+        let mint = fee_mint(&quote.input_mint, &quote.output_mint, quote.swap_mode);
+        Some(referral_fee_account(&config.referral_account, &mint))
+    }
+
+    /// Jupiter отклоняет свап с неинициализированным `feeAccount`, поэтому комиссию
+    /// запрашиваем только если токен-аккаунт реферала для нужного mint уже создан
+    async fn fee_account_ready(&self, input_mint: &Pubkey, output_mint: &Pubkey, swap_mode: SwapMode) -> bool {
+        let Some(config) = self.platform_fee else {
+            return false;
+        };
+
+        let mint = fee_mint(input_mint, output_mint, swap_mode);
+        let account = referral_fee_account(&config.referral_account, &mint);
+
+        if self.fee_accounts.contains(&account) {
+            return true;
+        }
+
+        match self.rpc.get_account(&account).await {
+            Ok(Some(_)) => {
+                self.fee_accounts.insert(account);
+                true
+            }
+            Ok(None) => {
+                tracing::warn!("Referral fee account {} for mint {} does not exist", account, mint);
+                false
+            }
+            Err(e) => {
+                tracing::warn!("Referral fee account {} for mint {} is not available: {}", account, mint, e);
+                false
+            }
+        }
+    }
+
+    /// Получает транзакцию у Jupiter и подписывает ее; лимиты проверяет вызывающий
+    pub async fn prepare_swap(
+        &self,
+        quote: &QuoteResponseV6,
+        keypair: &Keypair,
+        prioritization_fee: PrioritizationFee,
+    ) -> Result<PreparedSwap, TradeError> {
+        let fee_account = self.fee_account_for(quote);
+        let swap = self.jupiter
+            .get_swap_transaction_v6(&SwapParamsV6 {
+                quote_response: quote.clone(),
+                user_public_key: keypair.pubkey(),
+                wrap_and_unwrap_sol: true,
+                fee_account,
+                prioritization_fee,
+            })
+            .await?;
+
+        let raw = BASE64
+            .decode(&swap.swap_transaction)
+            .context("Invalid base64 in swap transaction")?;
+        let unsigned: VersionedTransaction = bincode::deserialize(&raw)
+            .context("Failed to deserialize swap transaction")?;
+        let transaction = VersionedTransaction::try_new(unsigned.message, &[keypair])
+            .context("Failed to sign swap transaction")?;
+
+        Ok(PreparedSwap {
+            signature: transaction.signatures[0],
+            priority_fee_lamports: paid_priority_fee(&transaction),
+            transaction,
+            last_valid_block_height: swap.last_valid_block_height,
+            fee_account,
+        })
+    }
+
+    /// Прогоняет подписанную транзакцию через `simulateTransaction` без отправки
+    pub async fn simulate_swap(&self, prepared: &PreparedSwap) -> Result<SwapSimulation, TradeError> {
+        let simulation = self.rpc
+            .simulate_transaction(&prepared.transaction)
+            .await
+            .context("Failed to simulate swap transaction")?;
+
+        Ok(simulation)
+    }
+
+    /// Отправляет подписанную транзакцию с повторами до подтверждения или истечения blockhash
+    pub async fn send_swap(&self, prepared: &PreparedSwap) -> Result<Signature, TradeError> {
+        let sender = TransactionSender::new(self.rpc.clone(), self.rebroadcast_interval)
+            .with_pubsub(self.pubsub.clone());
+        Ok(sender.send(&prepared.transaction, prepared.last_valid_block_height).await?)
+    }
+
+    /// Проверяет лимиты, подписывает, симулирует и отправляет свап
+    pub async fn execute_swap(
+        &self,
+        quote: &QuoteResponseV6,
+        keypair: &Keypair,
+        prioritization_fee: PrioritizationFee,
+    ) -> Result<Signature, TradeError> {
+        self.check_limits(quote)?;
+        let prepared = self.prepare_swap(quote, keypair, prioritization_fee).await?;
+        if let Some(failure) = self.simulate_swap(&prepared).await?.failure() {
+            return Err(failure.into());
+        }
+        self.send_swap(&prepared).await
+    }
+
+    pub fn tokens(&self) -> &TokenResolver {
+        &self.tokens
+    }
+
+    /// Количество знаков после запятой у токена из его mint-аккаунта
+    pub async fn mint_decimals(&self, mint: &Pubkey) -> anyhow::Result<u8> {
+        Ok(self.tokens.resolve(mint).await?.decimals)
+    }
+
+    /// Символ и знаки токена для записи сделки и отображения сумм
+    pub async fn token_meta(&self, mint: &Pubkey) -> anyhow::Result<TokenMeta> {
+        Ok(self.tokens.resolve(mint).await?.meta())
+    }
+
+    /// Баланс токена на всех токен-аккаунтах владельца, в минимальных единицах
+    pub async fn token_balance(&self, owner: &Pubkey, mint: &Pubkey) -> anyhow::Result<u64> {
+        let accounts = self.rpc.get_token_accounts(owner, mint).await?;
+
+        Ok(accounts.iter().fold(0u64, |total, account| total.saturating_add(account.amount)))
+    }
+}
@@ -6,10 +6,14 @@ use teloxide::{
 use std::sync::Arc;
 
 use crate::{
-    config::settings::TelegramSettings,
-    database::connection::DatabaseConnectionPool,
+    config::settings::{Settings, TelegramSettings},
+    database::{connection::DatabaseConnectionPool, repositories::Repositories},
+    entities::exit_orders::ExitKind,
+    jupiter::{PriceService, SwapMode},
     security::secrets_manager::SecretsManager,
     monitoring::metrics::MetricsRegistry,
+    solana::{executor::SwapExecutor, priority_fee::PriorityFeeEstimator, token_safety::TokenSafety, trader::Trader},
+    telegram::{handlers::{alert, copy, dca, exits, fee, limit, notify, snipe, start, trade}, notifier::Notifier, quote_store::QuoteStore},
 };
 
 #[derive(BotCommands, Clone)]
@@ -21,10 +25,34 @@ pub enum Command {
     Help,
     #[command(description = "Баланс кошелька")]
     Balance,
-    #[command(description = "Купить токен")]
-    Buy,
+    #[command(description = "Купить токен за SOL")]
+    Buy(String),
+    #[command(description = "Купить точное количество токенов")]
+    BuyExact(String),
     #[command(description = "Продать токен")]
     Sell,
+    #[command(description = "Лимитные ордера")]
+    Limit(String),
+    #[command(description = "Стоп-лосс для позиции")]
+    Sl(String),
+    #[command(description = "Тейк-профит для позиции")]
+    Tp(String),
+    #[command(description = "Трейлинг-стоп для позиции")]
+    Trail(String),
+    #[command(description = "Стоп-лоссы и тейк-профиты")]
+    Exits(String),
+    #[command(description = "Регулярные покупки по расписанию")]
+    Dca(String),
+    #[command(description = "Копирование сделок кошелька")]
+    Copy(String),
+    #[command(description = "Уведомления о цене")]
+    Alert(String),
+    #[command(description = "Автопокупка новых токенов")]
+    Snipe(String),
+    #[command(description = "Приоритетная комиссия")]
+    Fee(String),
+    #[command(description = "Уведомления о балансе кошелька")]
+    Notify(String),
     #[command(description = "Поиск токена", parse_with = "split")]
     Search(String),
     #[command(description = "История сделок")]
@@ -48,8 +76,20 @@ impl Command {
         descriptions.push_str("/start - Начать работу с ботом\n");
         descriptions.push_str("/help - Помощь и список команд\n");
         descriptions.push_str("/balance - Баланс кошелька\n");
-        descriptions.push_str("/buy - Купить токен\n");
+        descriptions.push_str("/buy <токен> <сумма SOL | max | доля%> - Купить токен за SOL\n");
+        descriptions.push_str("/buyexact <токен> <количество> - Купить точное количество токенов\n");
         descriptions.push_str("/sell - Продать токен\n");
+        descriptions.push_str("/limit <buy|sell> <токен> <сумма> <цена $> - Лимитный ордер\n");
+        descriptions.push_str("/sl <id сделки> <цена | 10%> - Стоп-лосс для позиции\n");
+        descriptions.push_str("/tp <id сделки> <цена | 25%> - Тейк-профит для позиции\n");
+        descriptions.push_str("/trail <id сделки> <процент> - Трейлинг-стоп для позиции\n");
+        descriptions.push_str("/exits - Стоп-лоссы и тейк-профиты\n");
+        descriptions.push_str("/dca <токен> <сумма SOL> <интервал> <циклов> - Регулярные покупки\n");
+        descriptions.push_str("/copy <кошелек> <сумма SOL | доля%> - Копирование сделок кошелька\n");
+        descriptions.push_str("/alert <токен> <above|below цена | ±10% 1h> - Уведомления о цене\n");
+        descriptions.push_str("/snipe <on сумма SOL | off> - Автопокупка новых токенов\n");
+        descriptions.push_str("/fee <low|medium|high|turbo> [потолок SOL] - Приоритетная комиссия\n");
+        descriptions.push_str("/notify <on|off> - Уведомления о балансе кошелька\n");
         descriptions.push_str("/search <запрос> - Поиск токена\n");
         descriptions.push_str("/history - История сделок\n");
         descriptions.push_str("/settings - Настройки\n");
@@ -64,18 +104,31 @@ impl Command {
 
 pub struct TelegramBot {
     bot: Bot,
-    settings: TelegramSettings,
+    settings: Arc<Settings>,
     database: DatabaseConnectionPool,
     secrets: SecretsManager,
     metrics: MetricsRegistry,
+    trader: Arc<Trader>,
+    executor: Arc<SwapExecutor>,
+    price_service: Arc<PriceService>,
+    quotes: Arc<QuoteStore>,
+    token_safety: Arc<TokenSafety>,
+    priority_fees: Arc<PriorityFeeEstimator>,
 }
 
 impl TelegramBot {
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
-        settings: TelegramSettings,
+        settings: Arc<Settings>,
         database: DatabaseConnectionPool,
         secrets: SecretsManager,
         metrics: MetricsRegistry,
+        trader: Arc<Trader>,
+        executor: Arc<SwapExecutor>,
+        price_service: Arc<PriceService>,
+        quotes: Arc<QuoteStore>,
+        token_safety: Arc<TokenSafety>,
+        priority_fees: Arc<PriorityFeeEstimator>,
     ) -> Result<Self, anyhow::Error> {
         let bot_token = secrets.get_telegram_token().await;
         let bot = Bot::new(bot_token);
@@ -86,13 +139,24 @@ impl TelegramBot {
             database,
             secrets,
             metrics,
+            trader,
+            executor,
+            price_service,
+            quotes,
+            token_safety,
+            priority_fees,
         })
     }
 
+    /// Уведомления пользователям для фоновых задач
+    pub fn notifier(&self) -> Notifier {
+        Notifier::new(self.bot.clone())
+    }
+
     pub async fn start(self) -> Result<(), anyhow::Error> {
         tracing::info!("Starting Telegram bot...");
 
-        let handler = Update::filter_message()
+        let message_handler = Update::filter_message()
             .branch(
                 dptree::entry()
                     .filter_command::<Command>()
@@ -103,18 +167,41 @@ impl TelegramBot {
                     .endpoint(Self::handle_text)
             );
 
+        let callback_handler = Update::filter_callback_query()
+            .endpoint(trade::handle_swap_callback);
+
+        let handler = dptree::entry()
+            .branch(message_handler)
+            .branch(callback_handler);
+
         let bot = self.bot.clone();
-        let settings = self.settings.clone();
+        let settings = self.settings.telegram.clone();
+        let config = self.settings.clone();
         let database = Arc::new(self.database.clone());
+        let repositories = Arc::new(Repositories::postgres(database.clone()));
         let secrets = Arc::new(self.secrets.clone());
         let metrics = Arc::new(self.metrics.clone());
+        let trader = self.trader.clone();
+        let executor = self.executor.clone();
+        let price_service = self.price_service.clone();
+        let quotes = self.quotes.clone();
+        let token_safety = self.token_safety.clone();
+        let priority_fees = self.priority_fees.clone();
 
         Dispatcher::builder(bot, handler)
             .dependencies(dptree::deps![
                 settings,
+                config,
                 database,
+                repositories,
                 secrets,
-                metrics
+                metrics,
+                trader,
+                executor,
+                price_service,
+                quotes,
+                token_safety,
+                priority_fees
             ])
             .default_handler(|upd| async move {
                 tracing::warn!("Unhandled update: {:?}", upd);
@@ -130,20 +217,25 @@ impl TelegramBot {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn handle_command(
         bot: Bot,
         msg: Message,
         cmd: Command,
-        settings: TelegramSettings,
+        config: Arc<Settings>,
         database: Arc<DatabaseConnectionPool>,
-        secrets: Arc<SecretsManager>,
-        metrics: Arc<MetricsRegistry>,
+        repositories: Arc<Repositories>,
+        trader: Arc<Trader>,
+        price_service: Arc<PriceService>,
+        quotes: Arc<QuoteStore>,
+        token_safety: Arc<TokenSafety>,
+        priority_fees: Arc<PriorityFeeEstimator>,
     ) -> Result<(), teloxide::RequestError> {
         let chat_id = msg.chat.id;
 
         match cmd {
             Command::Start => {
-                bot.send_message(chat_id, "Добро пожаловать в Solana Trading Bot! Используйте /help для списка команд.").await?;
+                start::handle_start(bot, msg, repositories).await?;
             }
             Command::Help => {
                 bot.send_message(chat_id, Command::descriptions()).await?;
@@ -151,12 +243,48 @@ impl TelegramBot {
             Command::Balance => {
                 bot.send_message(chat_id, "Функция баланса пока не реализована").await?;
             }
-            Command::Buy => {
-                bot.send_message(chat_id, "Функция покупки пока не реализована").await?;
+            Command::Buy(args) => {
+                trade::handle_buy(bot, msg, args, SwapMode::ExactIn, repositories.wallets.clone(), trader, price_service, quotes, token_safety).await?;
+            }
+            Command::BuyExact(args) => {
+                trade::handle_buy(bot, msg, args, SwapMode::ExactOut, repositories.wallets.clone(), trader, price_service, quotes, token_safety).await?;
             }
             Command::Sell => {
                 bot.send_message(chat_id, "Функция продажи пока не реализована").await?;
             }
+            Command::Limit(args) => {
+                limit::handle_limit(bot, msg, args, database, trader, config.limit_orders.clone()).await?;
+            }
+            Command::Sl(args) => {
+                exits::handle_exit(bot, msg, args, ExitKind::StopLoss, database, trader, config.exit_orders.clone()).await?;
+            }
+            Command::Tp(args) => {
+                exits::handle_exit(bot, msg, args, ExitKind::TakeProfit, database, trader, config.exit_orders.clone()).await?;
+            }
+            Command::Trail(args) => {
+                exits::handle_exit(bot, msg, args, ExitKind::TrailingStop, database, trader, config.exit_orders.clone()).await?;
+            }
+            Command::Exits(args) => {
+                exits::handle_exits(bot, msg, args, database).await?;
+            }
+            Command::Dca(args) => {
+                dca::handle_dca(bot, msg, args, database, trader, config.dca.clone()).await?;
+            }
+            Command::Copy(args) => {
+                copy::handle_copy(bot, msg, args, database, trader, config.copy_trading.clone()).await?;
+            }
+            Command::Alert(args) => {
+                alert::handle_alert(bot, msg, args, database, config.alerts.clone()).await?;
+            }
+            Command::Snipe(args) => {
+                snipe::handle_snipe(bot, msg, args, database, trader, config.sniper.clone()).await?;
+            }
+            Command::Fee(args) => {
+                fee::handle_fee(bot, msg, args, database, priority_fees).await?;
+            }
+            Command::Notify(args) => {
+                notify::handle_notify(bot, msg, args, database).await?;
+            }
             Command::Search(query) => {
                 bot.send_message(chat_id, format!("Поиск токена: {}", query)).await?;
             }
@@ -186,10 +314,10 @@ impl TelegramBot {
     pub async fn handle_text(
         bot: Bot,
         msg: Message,
-        settings: TelegramSettings,
-        database: Arc<DatabaseConnectionPool>,
-        secrets: Arc<SecretsManager>,
-        metrics: Arc<MetricsRegistry>,
+        _settings: TelegramSettings,
+        _database: Arc<DatabaseConnectionPool>,
+        _secrets: Arc<SecretsManager>,
+        _metrics: Arc<MetricsRegistry>,
     ) -> Result<(), teloxide::RequestError> {
         let chat_id = msg.chat.id;
         let text = msg.text().unwrap_or("");
@@ -0,0 +1,188 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use bigdecimal::{BigDecimal, Zero};
+use teloxide::prelude::*;
+
+use crate::{
+    automation::alerts::describe_condition,
+    config::settings::AlertSettings,
+    database::{DatabaseConnectionPool, alerts::{self, NewPriceAlert}},
+    entities::price_alerts::{AlertKind, Model as PriceAlert},
+    telegram::handlers::{dca::parse_interval, trade::{ResolvedToken, resolve_token}},
+    utils::short_id,
+};
+
+const USAGE: &str = "Использование:\n\
+    /alert <токен> above|below <цена $> — цена выше или ниже уровня\n\
+    /alert <токен> +10%|-10%|±10% [1h|24h] — рост, падение или любое изменение за период\n\
+    /alert <токен> volume <объем $> — объем торгов за 24 часа\n\
+    /alert — ваши уведомления\n\
+    /alert delete <id> — удалить уведомление\n\n\
+    Например: /alert SOL below 120 или /alert BONK ±15% 1h";
+
+pub enum AlertCommand {
+    List,
+    Delete(String),
+    Create {
+        token: ResolvedToken,
+        kind: AlertKind,
+        threshold: BigDecimal,
+        // `None` — окно по умолчанию из настроек
+        window_secs: Option<u64>,
+    },
+}
+
+fn parse_positive(value: &str) -> Option<BigDecimal> {
+    let value = BigDecimal::from_str(&value.trim_start_matches('$').replace(',', ".")).ok()?;
+    (value > BigDecimal::zero()).then_some(value)
+}
+
+/// `+10%` — рост, `-10%` — падение, `±10%` или `10%` — изменение в любую сторону
+pub fn parse_change(value: &str) -> Option<(AlertKind, BigDecimal)> {
+    let value = value.strip_suffix('%')?;
+    let (kind, percent) = if let Some(percent) = value.strip_prefix('+') {
+        (AlertKind::Rise, percent)
+    } else if let Some(percent) = value.strip_prefix('-') {
+        (AlertKind::Drop, percent)
+    } else if let Some(percent) = value.strip_prefix('±') {
+        (AlertKind::Move, percent)
+    } else {
+        (AlertKind::Move, value)
+    };
+
+    Some((kind, parse_positive(percent)?))
+}
+
+pub fn parse_alert_args(args: &str) -> Option<AlertCommand> {
+    let parts: Vec<&str> = args.split_whitespace().collect();
+
+    match parts.as_slice() {
+        [] => Some(AlertCommand::List),
+        [action, id] if action.eq_ignore_ascii_case("delete") => Some(AlertCommand::Delete(id.to_lowercase())),
+        [token, condition, value] if !condition.ends_with('%') => {
+            let kind = match condition.to_lowercase().as_str() {
+                "above" => AlertKind::Above,
+                "below" => AlertKind::Below,
+                "volume" => AlertKind::Volume,
+                _ => return None,
+            };
+
+            Some(AlertCommand::Create {
+                token: resolve_token(token)?,
+                kind,
+                threshold: parse_positive(value)?,
+                window_secs: None,
+            })
+        }
+        [token, change, window @ ..] if window.len() <= 1 => {
+            let (kind, threshold) = parse_change(change)?;
+            let window_secs = match window.first() {
+                Some(window) => Some(parse_interval(window)?),
+                None => None,
+            };
+
+            Some(AlertCommand::Create {
+                token: resolve_token(token)?,
+                kind,
+                threshold,
+                window_secs,
+            })
+        }
+        _ => None,
+    }
+}
+
+pub fn describe_alert(alert: &PriceAlert) -> String {
+    let mut text = format!("{} • {}: {}", short_id(&alert.id), alert.token_symbol, describe_condition(alert));
+    if !alert.armed {
+        text.push_str(" (сработало, ждет возврата цены)");
+    }
+    if alert.trigger_count > 0 {
+        text.push_str(&format!(", срабатываний: {}", alert.trigger_count));
+    }
+    text
+}
+
+/// `/alert` — уведомления о цене и объеме токена
+pub async fn handle_alert(
+    bot: Bot,
+    msg: Message,
+    args: String,
+    database: Arc<DatabaseConnectionPool>,
+    settings: AlertSettings,
+) -> Result<(), teloxide::RequestError> {
+    let chat_id = msg.chat.id;
+    let Some(user) = msg.from.as_ref() else {
+        return Ok(());
+    };
+    let user_id = user.id.0 as i64;
+    let db = database.get_connection();
+
+    let Some(command) = parse_alert_args(&args) else {
+        bot.send_message(chat_id, USAGE).await?;
+        return Ok(());
+    };
+
+    let text = match command {
+        AlertCommand::List => match alerts::list_for_user(db, user_id).await {
+            Ok(alerts) if alerts.is_empty() => "Уведомлений нет".to_string(),
+            Ok(alerts) => {
+                let lines: Vec<String> = alerts.iter().map(describe_alert).collect();
+                format!("Уведомления:\n\n{}", lines.join("\n"))
+            }
+            Err(e) => {
+                tracing::error!("Failed to list price alerts for user {}: {}", user_id, e);
+                "Не удалось загрузить уведомления".to_string()
+            }
+        },
+        AlertCommand::Delete(prefix) => {
+            let alerts = alerts::list_for_user(db, user_id).await.unwrap_or_default();
+            let Some(alert) = alerts.iter().find(|alert| short_id(&alert.id).starts_with(&prefix)) else {
+                bot.send_message(chat_id, "Уведомление с таким id не найдено").await?;
+                return Ok(());
+            };
+
+            match alerts::delete(db, alert.id, user_id).await {
+                Ok(true) => format!("Уведомление {} удалено", short_id(&alert.id)),
+                Ok(false) => "Уведомление уже удалено".to_string(),
+                Err(e) => {
+                    tracing::error!("Failed to delete price alert {}: {}", alert.id, e);
+                    "Не удалось удалить уведомление".to_string()
+                }
+            }
+        }
+        AlertCommand::Create { token, kind, threshold, window_secs } => {
+            let count = alerts::count_for_user(db, user_id).await.unwrap_or_default();
+            if count >= settings.max_alerts_per_user {
+                format!("Не больше {} уведомлений. Удалите лишние: /alert delete <id>", settings.max_alerts_per_user)
+            } else {
+                let window_secs = match kind {
+                    AlertKind::Rise | AlertKind::Drop | AlertKind::Move => {
+                        Some(window_secs.map_or(settings.default_window_secs, |secs| secs as i64))
+                    }
+                    _ => None,
+                };
+                let result = alerts::create(db, NewPriceAlert {
+                    user_id,
+                    token_mint: token.mint.to_string(),
+                    token_symbol: token.symbol,
+                    kind,
+                    threshold,
+                    window_secs,
+                }).await;
+
+                match result {
+                    Ok(alert) => format!("🔔 Уведомление создано\n{}", describe_alert(&alert)),
+                    Err(e) => {
+                        tracing::error!("Failed to create price alert for user {}: {}", user_id, e);
+                        "Не удалось создать уведомление".to_string()
+                    }
+                }
+            }
+        }
+    };
+    bot.send_message(chat_id, text).await?;
+
+    Ok(())
+}
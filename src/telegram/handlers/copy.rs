@@ -0,0 +1,255 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use bigdecimal::{BigDecimal, Zero};
+use solana_sdk::pubkey::Pubkey;
+use teloxide::prelude::*;
+
+use crate::{
+    config::settings::CopyTradingSettings,
+    database::{DatabaseConnectionPool, copy_trading::{self, NewCopyFollow}},
+    entities::copy_follows::{CopyFollowStatus, CopyMode, Model as CopyFollow},
+    solana::{amount::TokenAmount, constants::short_address, trader::Trader},
+    telegram::handlers::trade::resolve_token,
+    utils::short_id,
+};
+
+const USAGE: &str = "Использование:\n\
+    /copy <кошелек> <сумма SOL | доля%> [max <SOL>] [delay <сек>] — повторять свапы кошелька\n\
+    /copy — подписки\n\
+    /copy allow <id> <токены через запятую | off> — повторять только эти токены\n\
+    /copy deny <id> <токены через запятую | off> — никогда не повторять эти токены\n\
+    /copy pause|resume|stop <id> — пауза, возобновление, отписка\n\n\
+    Например: /copy 7xKX...AsU 10% max 0.5 delay 30";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FollowAction {
+    Pause,
+    Resume,
+    Stop,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MintFilter {
+    Allow,
+    Deny,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CopyCommand {
+    List,
+    Change(FollowAction, String),
+    // `None` очищает список
+    Filter(MintFilter, String, Option<Vec<String>>),
+    Follow {
+        wallet: Pubkey,
+        mode: CopyMode,
+        amount: BigDecimal,
+        max_amount_sol: Option<BigDecimal>,
+        delay_secs: u64,
+    },
+}
+
+fn parse_positive(value: &str) -> Option<BigDecimal> {
+    let value = BigDecimal::from_str(&value.replace(',', ".")).ok()?;
+    (value > BigDecimal::zero()).then_some(value)
+}
+
+/// `0.1` — фиксированная сумма в SOL, `10%` — доля от суммы лидера
+pub fn parse_copy_size(value: &str) -> Option<(CopyMode, BigDecimal)> {
+    match value.strip_suffix('%') {
+        Some(percent) => Some((CopyMode::Proportional, parse_positive(percent)? / BigDecimal::from(100))),
+        None => Some((CopyMode::Fixed, parse_positive(value)?)),
+    }
+}
+
+fn parse_mints(value: &str) -> Option<Option<Vec<String>>> {
+    if value.eq_ignore_ascii_case("off") {
+        return Some(None);
+    }
+
+    let mints = value
+        .split(',')
+        .filter(|token| !token.trim().is_empty())
+        .map(|token| resolve_token(token.trim()).map(|token| token.mint.to_string()))
+        .collect::<Option<Vec<_>>>()?;
+    (!mints.is_empty()).then_some(Some(mints))
+}
+
+pub fn parse_copy_args(args: &str) -> Option<CopyCommand> {
+    let parts: Vec<&str> = args.split_whitespace().collect();
+
+    match parts.as_slice() {
+        [] => Some(CopyCommand::List),
+        [action, id] => {
+            let action = match action.to_lowercase().as_str() {
+                "pause" => FollowAction::Pause,
+                "resume" => FollowAction::Resume,
+                "stop" => FollowAction::Stop,
+                _ => return None,
+            };
+            Some(CopyCommand::Change(action, id.to_lowercase()))
+        }
+        [filter @ ("allow" | "deny"), id, mints @ ..] if !mints.is_empty() => {
+            let filter = if *filter == "allow" { MintFilter::Allow } else { MintFilter::Deny };
+            Some(CopyCommand::Filter(filter, id.to_lowercase(), parse_mints(&mints.join(""))?))
+        }
+        [wallet, size, options @ ..] => {
+            let wallet = Pubkey::from_str(wallet).ok()?;
+            let (mode, amount) = parse_copy_size(size)?;
+
+            let mut max_amount_sol = None;
+            let mut delay_secs = 0;
+            for option in options.chunks(2) {
+                match option {
+                    ["max", value] => max_amount_sol = Some(parse_positive(value)?),
+                    ["delay", value] => delay_secs = value.parse().ok()?,
+                    _ => return None,
+                }
+            }
+
+            Some(CopyCommand::Follow { wallet, mode, amount, max_amount_sol, delay_secs })
+        }
+        _ => None,
+    }
+}
+
+pub fn describe_follow(follow: &CopyFollow) -> String {
+    let size = match follow.mode {
+        CopyMode::Fixed => format!("{} SOL", follow.amount.normalized()),
+        CopyMode::Proportional => format!("{}% от суммы", (&follow.amount * BigDecimal::from(100)).normalized()),
+    };
+    let wallet = Pubkey::from_str(&follow.leader_wallet)
+        .map(|wallet| short_address(&wallet))
+        .unwrap_or_else(|_| follow.leader_wallet.clone());
+
+    let mut text = format!(
+        "{} • {}: {}, не больше {} SOL",
+        short_id(&follow.id), wallet, size, follow.max_amount_sol.normalized(),
+    );
+    if follow.delay_secs > 0 {
+        text.push_str(&format!(", задержка {} с", follow.delay_secs));
+    }
+    if !follow.allowed_mints.0.is_empty() {
+        text.push_str(&format!(", только {} ток.", follow.allowed_mints.0.len()));
+    }
+    if !follow.denied_mints.0.is_empty() {
+        text.push_str(&format!(", исключено {} ток.", follow.denied_mints.0.len()));
+    }
+    if follow.status == CopyFollowStatus::Paused {
+        text.push_str(" (на паузе)");
+    }
+    text
+}
+
+/// `/copy` — подписки на свапы других кошельков
+pub async fn handle_copy(
+    bot: Bot,
+    msg: Message,
+    args: String,
+    database: Arc<DatabaseConnectionPool>,
+    trader: Arc<Trader>,
+    settings: CopyTradingSettings,
+) -> Result<(), teloxide::RequestError> {
+    let chat_id = msg.chat.id;
+    let Some(user) = msg.from.as_ref() else {
+        return Ok(());
+    };
+    let user_id = user.id.0 as i64;
+    let db = database.get_connection();
+
+    let Some(command) = parse_copy_args(&args) else {
+        bot.send_message(chat_id, USAGE).await?;
+        return Ok(());
+    };
+
+    let text = match command {
+        CopyCommand::List => match copy_trading::follows_for_user(db, user_id).await {
+            Ok(follows) if follows.is_empty() => "Подписок на кошельки нет".to_string(),
+            Ok(follows) => {
+                let lines: Vec<String> = follows.iter().map(describe_follow).collect();
+                format!("Подписки на кошельки:\n\n{}", lines.join("\n"))
+            }
+            Err(e) => {
+                tracing::error!("Failed to list copy follows for user {}: {}", user_id, e);
+                "Не удалось загрузить подписки".to_string()
+            }
+        },
+        CopyCommand::Change(action, prefix) => {
+            let follows = copy_trading::follows_for_user(db, user_id).await.unwrap_or_default();
+            let Some(follow) = follows.iter().find(|follow| short_id(&follow.id).starts_with(&prefix)) else {
+                bot.send_message(chat_id, "Подписка с таким id не найдена").await?;
+                return Ok(());
+            };
+
+            let (from, to, done): (&[CopyFollowStatus], _, _) = match action {
+                FollowAction::Pause => (&[CopyFollowStatus::Active], CopyFollowStatus::Paused, "приостановлена"),
+                FollowAction::Resume => (&[CopyFollowStatus::Paused], CopyFollowStatus::Active, "возобновлена"),
+                FollowAction::Stop => (&[CopyFollowStatus::Active, CopyFollowStatus::Paused], CopyFollowStatus::Stopped, "удалена"),
+            };
+
+            match copy_trading::transition_follow(db, follow.id, user_id, from, to).await {
+                Ok(true) => format!("Подписка {} {}", short_id(&follow.id), done),
+                Ok(false) => "Действие недоступно в текущем статусе подписки".to_string(),
+                Err(e) => {
+                    tracing::error!("Failed to change copy follow {}: {}", follow.id, e);
+                    "Не удалось изменить подписку".to_string()
+                }
+            }
+        }
+        CopyCommand::Filter(filter, prefix, mints) => {
+            let follows = copy_trading::follows_for_user(db, user_id).await.unwrap_or_default();
+            let Some(follow) = follows.iter().find(|follow| short_id(&follow.id).starts_with(&prefix)) else {
+                bot.send_message(chat_id, "Подписка с таким id не найдена").await?;
+                return Ok(());
+            };
+
+            let mints = mints.unwrap_or_default();
+            let result = match filter {
+                MintFilter::Allow => copy_trading::set_mint_filters(db, follow.id, Some(mints), None).await,
+                MintFilter::Deny => copy_trading::set_mint_filters(db, follow.id, None, Some(mints)).await,
+            };
+
+            match result {
+                Ok(()) => format!("Списки токенов подписки {} обновлены", short_id(&follow.id)),
+                Err(e) => {
+                    tracing::error!("Failed to update mint filters of copy follow {}: {}", follow.id, e);
+                    "Не удалось обновить подписку".to_string()
+                }
+            }
+        }
+        CopyCommand::Follow { wallet, mode, amount, max_amount_sol, delay_secs } => {
+            let max_amount_sol = max_amount_sol
+                .unwrap_or_else(|| TokenAmount::sol_setting(settings.default_max_amount_sol).to_big_decimal());
+
+            if delay_secs > settings.max_delay_secs {
+                format!("Задержка не может быть больше {} с", settings.max_delay_secs)
+            } else {
+                let slippage_bps = settings.default_slippage_bps.min(trader.limits().max_slippage_bps);
+                let result = copy_trading::create_follow(db, NewCopyFollow {
+                    user_id,
+                    leader_wallet: wallet.to_string(),
+                    mode,
+                    amount,
+                    max_amount_sol: max_amount_sol.with_scale(9),
+                    delay_secs: delay_secs as i32,
+                    max_slippage_bps: slippage_bps as i32,
+                }).await;
+
+                match result {
+                    Ok(follow) => format!(
+                        "✅ Подписка создана, повторяются свапы после этого момента\n{}",
+                        describe_follow(&follow),
+                    ),
+                    Err(e) => {
+                        tracing::warn!("Failed to create copy follow for user {}: {}", user_id, e);
+                        "Не удалось создать подписку. Сначала добавьте кошелек: /addwallet".to_string()
+                    }
+                }
+            }
+        }
+    };
+    bot.send_message(chat_id, text).await?;
+
+    Ok(())
+}
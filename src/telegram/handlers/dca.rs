@@ -0,0 +1,202 @@
+use std::sync::Arc;
+
+use bigdecimal::BigDecimal;
+use teloxide::prelude::*;
+
+use crate::{
+    config::settings::DcaSettings,
+    database::{DatabaseConnectionPool, dca::{self, NewDcaSchedule}},
+    entities::dca_schedules::{DcaStatus, Model as DcaSchedule},
+    solana::{amount::TokenAmount, constants::SOL_DECIMALS, trader::Trader},
+    telegram::handlers::trade::{ResolvedToken, resolve_token, token_display_symbol},
+    utils::short_id,
+};
+
+const USAGE: &str = "Использование:\n\
+    /dca <токен> <сумма SOL> <интервал> <циклов> — покупать токен по расписанию\n\
+    /dca — активные расписания\n\
+    /dca pause|resume|cancel <id> — пауза, возобновление, отмена\n\n\
+    Интервал: 30m, 6h, 1d, 1w. Например: /dca BONK 0.1 1d 30";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DcaAction {
+    Pause,
+    Resume,
+    Cancel,
+}
+
+pub enum DcaCommand {
+    List,
+    Change(DcaAction, String),
+    Create {
+        token: ResolvedToken,
+        amount_sol: BigDecimal,
+        interval_secs: u64,
+        cycles: u32,
+    },
+}
+
+/// Интервал вида `30m`, `6h`, `1d`, `1w` в секундах
+pub fn parse_interval(value: &str) -> Option<u64> {
+    let value = value.to_lowercase();
+    let unit_at = value.find(|c: char| !c.is_ascii_digit())?;
+    let (count, unit) = value.split_at(unit_at);
+
+    let unit_secs = match unit {
+        "m" | "min" => 60,
+        "h" => 3_600,
+        "d" => 86_400,
+        "w" => 604_800,
+        _ => return None,
+    };
+    let count: u64 = count.parse().ok().filter(|count| *count > 0)?;
+
+    count.checked_mul(unit_secs)
+}
+
+pub fn format_interval(secs: i64) -> String {
+    match secs {
+        s if s % 604_800 == 0 => format!("{}w", s / 604_800),
+        s if s % 86_400 == 0 => format!("{}d", s / 86_400),
+        s if s % 3_600 == 0 => format!("{}h", s / 3_600),
+        s => format!("{}m", s / 60),
+    }
+}
+
+pub fn parse_dca_args(args: &str) -> Option<DcaCommand> {
+    let parts: Vec<&str> = args.split_whitespace().collect();
+
+    match parts.as_slice() {
+        [] => Some(DcaCommand::List),
+        [action, id] => {
+            let action = match action.to_lowercase().as_str() {
+                "pause" => DcaAction::Pause,
+                "resume" => DcaAction::Resume,
+                "cancel" => DcaAction::Cancel,
+                _ => return None,
+            };
+            Some(DcaCommand::Change(action, id.to_lowercase()))
+        }
+        [token, amount, interval, cycles] => {
+            let amount_sol = TokenAmount::parse(amount, SOL_DECIMALS).ok()?.to_big_decimal();
+
+            Some(DcaCommand::Create {
+                token: resolve_token(token)?,
+                amount_sol,
+                interval_secs: parse_interval(interval)?,
+                cycles: cycles.parse().ok().filter(|cycles| *cycles > 0)?,
+            })
+        }
+        _ => None,
+    }
+}
+
+pub fn describe_schedule(schedule: &DcaSchedule) -> String {
+    let mut text = format!(
+        "{} • {} на {} SOL каждые {}, выполнено {}/{}",
+        short_id(&schedule.id),
+        schedule.token_symbol,
+        schedule.amount_sol.normalized(),
+        format_interval(schedule.interval_secs),
+        schedule.cycles_done,
+        schedule.total_cycles,
+    );
+    if schedule.cycles_skipped > 0 {
+        text.push_str(&format!(", пропущено {}", schedule.cycles_skipped));
+    }
+    match schedule.status {
+        DcaStatus::Paused => text.push_str(" (на паузе)"),
+        _ => text.push_str(&format!(", следующий {}", schedule.next_run_at.format("%d.%m.%Y %H:%M UTC"))),
+    }
+    text
+}
+
+/// `/dca` — регулярные покупки по расписанию
+pub async fn handle_dca(
+    bot: Bot,
+    msg: Message,
+    args: String,
+    database: Arc<DatabaseConnectionPool>,
+    trader: Arc<Trader>,
+    settings: DcaSettings,
+) -> Result<(), teloxide::RequestError> {
+    let chat_id = msg.chat.id;
+    let Some(user) = msg.from.as_ref() else {
+        return Ok(());
+    };
+    let user_id = user.id.0 as i64;
+    let db = database.get_connection();
+
+    let Some(command) = parse_dca_args(&args) else {
+        bot.send_message(chat_id, USAGE).await?;
+        return Ok(());
+    };
+
+    let text = match command {
+        DcaCommand::List => match dca::list_for_user(db, user_id).await {
+            Ok(schedules) if schedules.is_empty() => "Активных расписаний DCA нет".to_string(),
+            Ok(schedules) => {
+                let lines: Vec<String> = schedules.iter().map(describe_schedule).collect();
+                format!("Расписания DCA:\n\n{}", lines.join("\n"))
+            }
+            Err(e) => {
+                tracing::error!("Failed to list DCA schedules for user {}: {}", user_id, e);
+                "Не удалось загрузить расписания".to_string()
+            }
+        },
+        DcaCommand::Change(action, prefix) => {
+            let schedules = dca::list_for_user(db, user_id).await.unwrap_or_default();
+            let Some(schedule) = schedules.iter().find(|schedule| short_id(&schedule.id).starts_with(&prefix)) else {
+                bot.send_message(chat_id, "Расписание с таким id не найдено").await?;
+                return Ok(());
+            };
+
+            let (from, to, done): (&[DcaStatus], _, _) = match action {
+                DcaAction::Pause => (&[DcaStatus::Active], DcaStatus::Paused, "приостановлено"),
+                DcaAction::Resume => (&[DcaStatus::Paused], DcaStatus::Active, "возобновлено"),
+                DcaAction::Cancel => (&[DcaStatus::Active, DcaStatus::Paused], DcaStatus::Cancelled, "отменено"),
+            };
+
+            match dca::transition(db, schedule.id, user_id, from, to).await {
+                Ok(true) => format!("Расписание {} {}", short_id(&schedule.id), done),
+                Ok(false) => "Действие недоступно в текущем статусе расписания".to_string(),
+                Err(e) => {
+                    tracing::error!("Failed to change DCA schedule {}: {}", schedule.id, e);
+                    "Не удалось изменить расписание".to_string()
+                }
+            }
+        }
+        DcaCommand::Create { token, amount_sol, interval_secs, cycles } => {
+            if interval_secs < settings.min_interval_secs {
+                format!("Интервал должен быть не меньше {}", format_interval(settings.min_interval_secs as i64))
+            } else if cycles > settings.max_cycles {
+                format!("Не больше {} циклов в одном расписании", settings.max_cycles)
+            } else {
+                let slippage_bps = settings.default_slippage_bps.min(trader.limits().max_slippage_bps);
+                let result = dca::create(db, NewDcaSchedule {
+                    user_id,
+                    token_mint: token.mint.to_string(),
+                    token_symbol: token_display_symbol(&trader, &token).await,
+                    amount_sol,
+                    interval_secs: interval_secs as i64,
+                    total_cycles: cycles as i32,
+                    max_slippage_bps: slippage_bps as i32,
+                }).await;
+
+                match result {
+                    Ok(schedule) => format!(
+                        "✅ Расписание DCA создано, первая покупка в ближайшие минуты\n{}",
+                        describe_schedule(&schedule),
+                    ),
+                    Err(e) => {
+                        tracing::warn!("Failed to create DCA schedule for user {}: {}", user_id, e);
+                        "Не удалось создать расписание. Сначала добавьте кошелек: /addwallet".to_string()
+                    }
+                }
+            }
+        }
+    };
+    bot.send_message(chat_id, text).await?;
+
+    Ok(())
+}
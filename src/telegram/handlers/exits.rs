@@ -0,0 +1,221 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use bigdecimal::{BigDecimal, Zero};
+use solana_sdk::pubkey::Pubkey;
+use teloxide::prelude::*;
+
+use crate::{
+    automation::exit_orders::kind_label,
+    config::settings::ExitOrderSettings,
+    database::{DatabaseConnectionPool, exit_orders::{self, NewExitOrder}, trades},
+    entities::{
+        exit_orders::{ExitKind, ExitOrderStatus, Model as ExitOrder},
+        trades::{TradeStatus, TradeType},
+    },
+    solana::{constants::token_symbol, trader::Trader},
+    utils::short_id,
+};
+
+// Сколько последних сделок просматривать при поиске позиции по id
+const RECENT_TRADES: u64 = 100;
+
+/// Уровень выхода: абсолютная цена или процент от цены входа
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExitTarget {
+    Price(BigDecimal),
+    Percent(BigDecimal),
+}
+
+pub fn parse_exit_target(value: &str) -> Option<ExitTarget> {
+    let value = value.replace(',', ".");
+
+    let target = match value.strip_suffix('%') {
+        Some(percent) => {
+            let percent = percent.trim_start_matches(['+', '-']);
+            ExitTarget::Percent(BigDecimal::from_str(percent).ok()?)
+        }
+        None => ExitTarget::Price(BigDecimal::from_str(&value).ok()?),
+    };
+
+    match &target {
+        ExitTarget::Price(value) | ExitTarget::Percent(value) if *value > BigDecimal::zero() => Some(target),
+        _ => None,
+    }
+}
+
+/// Уровень срабатывания в единицах цены входа.
+/// Для стоп-лосса и трейлинга процент откладывается вниз, для тейк-профита — вверх.
+pub fn trigger_price(kind: ExitKind, entry_price: &BigDecimal, target: &ExitTarget) -> Option<BigDecimal> {
+    let hundred = BigDecimal::from(100);
+
+    match (kind, target) {
+        (ExitKind::TrailingStop, ExitTarget::Price(_)) => None,
+        (_, ExitTarget::Price(price)) => Some(price.clone()),
+        (ExitKind::TakeProfit, ExitTarget::Percent(pct)) => Some(entry_price * (&hundred + pct) / &hundred),
+        (ExitKind::StopLoss | ExitKind::TrailingStop, ExitTarget::Percent(pct)) => {
+            (*pct < hundred).then(|| entry_price * (&hundred - pct) / &hundred)
+        }
+    }
+}
+
+fn usage(kind: ExitKind) -> &'static str {
+    match kind {
+        ExitKind::StopLoss => "Использование: /sl <id сделки> <цена | 10%>\nНапример: /sl 1a2b3c4d 10%",
+        ExitKind::TakeProfit => "Использование: /tp <id сделки> <цена | 25%>\nНапример: /tp 1a2b3c4d 25%",
+        ExitKind::TrailingStop => "Использование: /trail <id сделки> <процент>\nНапример: /trail 1a2b3c4d 15%",
+    }
+}
+
+pub fn describe_exit(order: &ExitOrder) -> String {
+    let quote_symbol = Pubkey::from_str(&order.quote_mint)
+        .map(|mint| token_symbol(&mint))
+        .unwrap_or_default();
+    let direction = match order.kind {
+        ExitKind::TakeProfit => "≥",
+        ExitKind::StopLoss | ExitKind::TrailingStop => "≤",
+    };
+
+    let mut text = format!(
+        "{} • {} {}: продать {} при цене {} {} {} (вход {})",
+        short_id(&order.id),
+        kind_label(order.kind),
+        order.token_symbol,
+        order.amount.normalized(),
+        direction,
+        order.trigger_price.normalized(),
+        quote_symbol,
+        order.entry_price.normalized(),
+    );
+    if let Some(pct) = &order.trail_pct {
+        text.push_str(&format!(", {}% от максимума", pct.normalized()));
+    }
+    text
+}
+
+/// `/sl`, `/tp` и `/trail` — выход из позиции, открытой покупкой
+pub async fn handle_exit(
+    bot: Bot,
+    msg: Message,
+    args: String,
+    kind: ExitKind,
+    database: Arc<DatabaseConnectionPool>,
+    trader: Arc<Trader>,
+    settings: ExitOrderSettings,
+) -> Result<(), teloxide::RequestError> {
+    let chat_id = msg.chat.id;
+    let Some(user) = msg.from.as_ref() else {
+        return Ok(());
+    };
+    let user_id = user.id.0 as i64;
+    let db = database.get_connection();
+
+    let parts: Vec<&str> = args.split_whitespace().collect();
+    let [trade_prefix, target] = parts.as_slice() else {
+        bot.send_message(chat_id, usage(kind)).await?;
+        return Ok(());
+    };
+    let Some(target) = parse_exit_target(target) else {
+        bot.send_message(chat_id, usage(kind)).await?;
+        return Ok(());
+    };
+
+    let trade_prefix = trade_prefix.to_lowercase();
+    let trade = trades::recent_for_user(db, user_id, RECENT_TRADES)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .find(|trade| short_id(&trade.id).starts_with(&trade_prefix));
+    let Some(trade) = trade.filter(|trade| {
+        trade.trade_type == TradeType::Buy && trade.status == TradeStatus::Completed
+    }) else {
+        bot.send_message(chat_id, "Завершенная покупка с таким id не найдена").await?;
+        return Ok(());
+    };
+
+    let Some(trigger) = trigger_price(kind, &trade.price, &target) else {
+        bot.send_message(chat_id, usage(kind)).await?;
+        return Ok(());
+    };
+    let trail_pct = match (kind, &target) {
+        (ExitKind::TrailingStop, ExitTarget::Percent(pct)) => Some(pct.clone()),
+        _ => None,
+    };
+    let slippage_bps = settings.default_slippage_bps.min(trader.limits().max_slippage_bps);
+
+    let result = exit_orders::create(db, NewExitOrder {
+        user_id,
+        trade_id: trade.id,
+        kind,
+        token_mint: trade.output_mint.clone(),
+        token_symbol: trade.output_symbol.clone(),
+        quote_mint: trade.input_mint.clone(),
+        amount: trade.output_amount.clone(),
+        entry_price: trade.price.clone(),
+        trigger_price: trigger.with_scale(12),
+        trail_pct,
+        max_slippage_bps: slippage_bps as i32,
+    }).await;
+
+    let text = match result {
+        Ok(order) => format!("✅ {} создан\n{}", kind_label(kind), describe_exit(&order)),
+        Err(e) => {
+            tracing::error!("Failed to create exit order for user {}: {}", user_id, e);
+            "Не удалось создать ордер".to_string()
+        }
+    };
+    bot.send_message(chat_id, text).await?;
+
+    Ok(())
+}
+
+/// `/exits` — открытые стоп-лоссы и тейк-профиты, `/exits cancel <id>` — отмена
+pub async fn handle_exits(
+    bot: Bot,
+    msg: Message,
+    args: String,
+    database: Arc<DatabaseConnectionPool>,
+) -> Result<(), teloxide::RequestError> {
+    let chat_id = msg.chat.id;
+    let Some(user) = msg.from.as_ref() else {
+        return Ok(());
+    };
+    let user_id = user.id.0 as i64;
+    let db = database.get_connection();
+
+    let orders = match exit_orders::list(db, Some(user_id), Some(ExitOrderStatus::Open)).await {
+        Ok(orders) => orders,
+        Err(e) => {
+            tracing::error!("Failed to list exit orders for user {}: {}", user_id, e);
+            bot.send_message(chat_id, "Не удалось загрузить ордера").await?;
+            return Ok(());
+        }
+    };
+
+    let parts: Vec<&str> = args.split_whitespace().collect();
+    let text = match parts.as_slice() {
+        [] if orders.is_empty() => "Открытых стоп-лоссов и тейк-профитов нет".to_string(),
+        [] => {
+            let lines: Vec<String> = orders.iter().map(describe_exit).collect();
+            format!("Открытые ордера на выход:\n\n{}", lines.join("\n"))
+        }
+        ["cancel", prefix] => {
+            let prefix = prefix.to_lowercase();
+            match orders.iter().find(|order| short_id(&order.id).starts_with(&prefix)) {
+                None => "Открытый ордер с таким id не найден".to_string(),
+                Some(order) => match exit_orders::cancel(db, order.id, user_id).await {
+                    Ok(true) => format!("{} {} отменен", kind_label(order.kind), short_id(&order.id)),
+                    Ok(false) => "Ордер уже исполняется или закрыт".to_string(),
+                    Err(e) => {
+                        tracing::error!("Failed to cancel exit order {}: {}", order.id, e);
+                        "Не удалось отменить ордер".to_string()
+                    }
+                },
+            }
+        }
+        _ => "Использование: /exits — список, /exits cancel <id> — отмена".to_string(),
+    };
+    bot.send_message(chat_id, text).await?;
+
+    Ok(())
+}
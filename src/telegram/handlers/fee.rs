@@ -0,0 +1,117 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use teloxide::prelude::*;
+
+use crate::{
+    database::{DatabaseConnectionPool, fee_preferences},
+    entities::fee_preferences::PriorityTier,
+    solana::{
+        amount::TokenAmount,
+        constants::SOL_DECIMALS,
+        priority_fee::{PriorityFeeEstimator, fee_cap, fee_lamports},
+    },
+};
+
+const USAGE: &str = "Использование:\n\
+    /fee — текущий уровень и оценка комиссий\n\
+    /fee <low|medium|high|turbo> [потолок SOL] — уровень приоритетной комиссии\n\n\
+    Уровни соответствуют 25, 50, 75 и 95 перцентилю недавних комиссий сети. \
+    Чем выше уровень, тем быстрее сделка попадает в блок.";
+
+#[derive(Debug, PartialEq)]
+pub enum FeeCommand {
+    Show,
+    // `None` — потолок по умолчанию из настроек
+    Set { tier: PriorityTier, max_lamports: Option<u64> },
+}
+
+pub fn parse_fee_args(args: &str) -> Option<FeeCommand> {
+    let parts: Vec<&str> = args.split_whitespace().collect();
+
+    match parts.as_slice() {
+        [] => Some(FeeCommand::Show),
+        [tier] => Some(FeeCommand::Set { tier: PriorityTier::from_str(tier).ok()?, max_lamports: None }),
+        [tier, max_sol] => Some(FeeCommand::Set {
+            tier: PriorityTier::from_str(tier).ok()?,
+            max_lamports: Some(TokenAmount::parse(max_sol, SOL_DECIMALS).ok()?.raw()),
+        }),
+        _ => None,
+    }
+}
+
+fn format_sol(lamports: u64) -> String {
+    format!("{:.6} SOL", TokenAmount::sol(lamports))
+}
+
+/// `/fee` — уровень приоритетной комиссии и ее потолок
+pub async fn handle_fee(
+    bot: Bot,
+    msg: Message,
+    args: String,
+    database: Arc<DatabaseConnectionPool>,
+    priority_fees: Arc<PriorityFeeEstimator>,
+) -> Result<(), teloxide::RequestError> {
+    let chat_id = msg.chat.id;
+    let Some(user) = msg.from.as_ref() else {
+        return Ok(());
+    };
+    let user_id = user.id.0 as i64;
+    let db = database.get_connection();
+    let settings = priority_fees.settings();
+
+    let Some(command) = parse_fee_args(&args) else {
+        bot.send_message(chat_id, USAGE).await?;
+        return Ok(());
+    };
+
+    let text = match command {
+        FeeCommand::Show => {
+            let preference = fee_preferences::find(db, user_id).await.ok().flatten();
+            let tier = preference.as_ref().map(|preference| preference.tier).unwrap_or(settings.default_tier);
+            let cap = fee_cap(preference.and_then(|preference| preference.max_lamports).map(|max| max as u64), settings);
+
+            let mut lines = vec![
+                format!("⚡ Приоритетная комиссия: {}, не больше {}", tier.name(), format_sol(cap)),
+                String::new(),
+            ];
+            match priority_fees.levels(&[]).await {
+                Ok(levels) => {
+                    lines.push("Сейчас в сети:".to_string());
+                    for level in PriorityTier::ALL {
+                        let lamports = fee_lamports(levels.price(level), settings.compute_unit_estimate).min(cap);
+                        let marker = if level == tier { "▸" } else { " " };
+                        lines.push(format!("{} {} — ~{}", marker, level.name(), format_sol(lamports)));
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to fetch recent prioritization fees: {:#}", e);
+                    lines.push("Не удалось получить текущие комиссии сети".to_string());
+                }
+            }
+            lines.push(String::new());
+            lines.push("Изменить: /fee <low|medium|high|turbo> [потолок SOL]".to_string());
+            lines.join("\n")
+        }
+        FeeCommand::Set { tier, max_lamports } => {
+            if max_lamports.is_some_and(|max| max > settings.max_lamports) {
+                format!("Потолок комиссии не больше {}", format_sol(settings.max_lamports))
+            } else {
+                match fee_preferences::upsert(db, user_id, tier, max_lamports.map(|max| max as i64)).await {
+                    Ok(preference) => format!(
+                        "⚡ Уровень комиссии: {}, не больше {}",
+                        preference.tier.name(),
+                        format_sol(fee_cap(preference.max_lamports.map(|max| max as u64), settings)),
+                    ),
+                    Err(e) => {
+                        tracing::warn!("Failed to save fee preference for user {}: {}", user_id, e);
+                        "Не удалось сохранить настройки комиссии. Сначала добавьте кошелек: /addwallet".to_string()
+                    }
+                }
+            }
+        }
+    };
+    bot.send_message(chat_id, text).await?;
+
+    Ok(())
+}
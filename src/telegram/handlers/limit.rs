@@ -0,0 +1,173 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{Duration, Utc};
+use teloxide::prelude::*;
+
+use crate::{
+    config::settings::LimitOrderSettings,
+    database::{DatabaseConnectionPool, limit_orders::{self, NewLimitOrder}},
+    entities::limit_orders::{LimitOrderStatus, Model as LimitOrder, OrderSide},
+    solana::trader::Trader,
+    telegram::handlers::trade::{ResolvedToken, resolve_token, token_display_symbol},
+    utils::short_id,
+};
+
+const USAGE: &str = "Использование:\n\
+    /limit buy <токен> <сумма SOL> <цена $> [срок, ч] — купить, когда цена опустится до цели\n\
+    /limit sell <токен> <количество> <цена $> [срок, ч] — продать, когда цена поднимется до цели\n\
+    /limit — открытые ордера\n\
+    /limit cancel <id> — отменить ордер\n\n\
+    Например: /limit buy BONK 0.5 0.00002";
+
+pub enum LimitCommand {
+    List,
+    Cancel(String),
+    Create {
+        side: OrderSide,
+        token: ResolvedToken,
+        amount: BigDecimal,
+        target_price: BigDecimal,
+        expiry_hours: Option<u64>,
+    },
+}
+
+fn parse_positive(value: &str) -> Option<BigDecimal> {
+    let value = BigDecimal::from_str(&value.replace(',', ".")).ok()?;
+    (value > BigDecimal::zero()).then_some(value)
+}
+
+pub fn parse_limit_args(args: &str) -> Option<LimitCommand> {
+    let parts: Vec<&str> = args.split_whitespace().collect();
+
+    match parts.as_slice() {
+        [] => Some(LimitCommand::List),
+        ["cancel", id] => Some(LimitCommand::Cancel(id.to_lowercase())),
+        [side, token, amount, price, rest @ ..] if rest.len() <= 1 => {
+            let side = match side.to_lowercase().as_str() {
+                "buy" => OrderSide::Buy,
+                "sell" => OrderSide::Sell,
+                _ => return None,
+            };
+            let expiry_hours = match rest {
+                [hours] => Some(hours.parse().ok().filter(|hours| *hours > 0)?),
+                _ => None,
+            };
+
+            Some(LimitCommand::Create {
+                side,
+                token: resolve_token(token)?,
+                amount: parse_positive(amount)?,
+                target_price: parse_positive(price)?,
+                expiry_hours,
+            })
+        }
+        _ => None,
+    }
+}
+
+pub fn describe_order(order: &LimitOrder) -> String {
+    let mut text = match order.side {
+        OrderSide::Buy => format!(
+            "{} • купить {} на {} SOL при цене ≤ ${}",
+            short_id(&order.id), order.token_symbol, order.amount.normalized(), order.target_price.normalized(),
+        ),
+        OrderSide::Sell => format!(
+            "{} • продать {} {} при цене ≥ ${}",
+            short_id(&order.id), order.amount.normalized(), order.token_symbol, order.target_price.normalized(),
+        ),
+    };
+    if let Some(expires_at) = order.expires_at {
+        text.push_str(&format!(" до {}", expires_at.format("%d.%m.%Y %H:%M UTC")));
+    }
+    text
+}
+
+/// `/limit` — создание, просмотр и отмена лимитных ордеров
+pub async fn handle_limit(
+    bot: Bot,
+    msg: Message,
+    args: String,
+    database: Arc<DatabaseConnectionPool>,
+    trader: Arc<Trader>,
+    settings: LimitOrderSettings,
+) -> Result<(), teloxide::RequestError> {
+    let chat_id = msg.chat.id;
+    let Some(user) = msg.from.as_ref() else {
+        return Ok(());
+    };
+    let user_id = user.id.0 as i64;
+    let db = database.get_connection();
+
+    let Some(command) = parse_limit_args(&args) else {
+        bot.send_message(chat_id, USAGE).await?;
+        return Ok(());
+    };
+
+    match command {
+        LimitCommand::List => {
+            let orders = match limit_orders::list(db, Some(user_id), Some(LimitOrderStatus::Open)).await {
+                Ok(orders) => orders,
+                Err(e) => {
+                    tracing::error!("Failed to list limit orders for user {}: {}", user_id, e);
+                    bot.send_message(chat_id, "Не удалось загрузить ордера").await?;
+                    return Ok(());
+                }
+            };
+
+            let text = if orders.is_empty() {
+                "Открытых лимитных ордеров нет".to_string()
+            } else {
+                let lines: Vec<String> = orders.iter().map(describe_order).collect();
+                format!("Открытые лимитные ордера:\n\n{}", lines.join("\n"))
+            };
+            bot.send_message(chat_id, text).await?;
+        }
+        LimitCommand::Cancel(prefix) => {
+            let orders = limit_orders::list(db, Some(user_id), Some(LimitOrderStatus::Open))
+                .await
+                .unwrap_or_default();
+            let Some(order) = orders.iter().find(|order| short_id(&order.id).starts_with(&prefix)) else {
+                bot.send_message(chat_id, "Открытый ордер с таким id не найден").await?;
+                return Ok(());
+            };
+
+            let text = match limit_orders::cancel(db, order.id, Some(user_id)).await {
+                Ok(true) => format!("Ордер {} отменен", short_id(&order.id)),
+                Ok(false) => "Ордер уже исполняется или закрыт".to_string(),
+                Err(e) => {
+                    tracing::error!("Failed to cancel limit order {}: {}", order.id, e);
+                    "Не удалось отменить ордер".to_string()
+                }
+            };
+            bot.send_message(chat_id, text).await?;
+        }
+        LimitCommand::Create { side, token, amount, target_price, expiry_hours } => {
+            let hours = expiry_hours.unwrap_or(settings.default_expiry_hours);
+            let slippage_bps = settings.default_slippage_bps.min(trader.limits().max_slippage_bps);
+
+            let result = limit_orders::create(db, NewLimitOrder {
+                user_id,
+                side,
+                token_mint: token.mint.to_string(),
+                token_symbol: token_display_symbol(&trader, &token).await,
+                amount,
+                target_price,
+                max_slippage_bps: slippage_bps as i32,
+                expires_at: Some(Utc::now() + Duration::hours(hours as i64)),
+            }).await;
+
+            let text = match result {
+                Ok(order) => format!("✅ Лимитный ордер создан\n{}", describe_order(&order)),
+                Err(e) => {
+                    tracing::warn!("Failed to create limit order for user {}: {}", user_id, e);
+                    "Не удалось создать ордер. Сначала добавьте кошелек: /addwallet".to_string()
+                }
+            };
+            bot.send_message(chat_id, text).await?;
+        }
+    }
+
+    Ok(())
+}
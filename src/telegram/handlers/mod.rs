@@ -0,0 +1,10 @@
+pub mod alert;
+pub mod copy;
+pub mod dca;
+pub mod exits;
+pub mod fee;
+pub mod limit;
+pub mod notify;
+pub mod snipe;
+pub mod start;
+pub mod trade;
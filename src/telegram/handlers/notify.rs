@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use teloxide::prelude::*;
+
+use crate::database::{DatabaseConnectionPool, wallets};
+
+const USAGE: &str = "Использование:\n\
+    /notify on — сообщать об изменениях баланса SOL кошелька по умолчанию\n\
+    /notify off — отключить уведомления о балансе";
+
+/// `Some(true)` для включения, `Some(false)` для выключения
+pub fn parse_notify_args(args: &str) -> Option<bool> {
+    match args.trim().to_lowercase().as_str() {
+        "on" | "вкл" => Some(true),
+        "off" | "выкл" => Some(false),
+        _ => None,
+    }
+}
+
+pub async fn handle_notify(
+    bot: Bot,
+    msg: Message,
+    args: String,
+    database: Arc<DatabaseConnectionPool>,
+) -> Result<(), teloxide::RequestError> {
+    let chat_id = msg.chat.id;
+    let Some(user) = msg.from.as_ref() else {
+        return Ok(());
+    };
+    let user_id = user.id.0 as i64;
+
+    let Some(enabled) = parse_notify_args(&args) else {
+        bot.send_message(chat_id, USAGE).await?;
+        return Ok(());
+    };
+
+    let text = match wallets::set_balance_notifications(database.get_connection(), user_id, enabled).await {
+        Ok(true) if enabled => "🔔 Уведомления о балансе включены".to_string(),
+        Ok(true) => "🔕 Уведомления о балансе отключены".to_string(),
+        Ok(false) => "❌ Кошелек по умолчанию не найден".to_string(),
+        Err(e) => {
+            tracing::error!("Failed to update balance notifications for user {}: {}", user_id, e);
+            "❌ Не удалось сохранить настройку".to_string()
+        }
+    };
+
+    bot.send_message(chat_id, text).await?;
+    Ok(())
+}
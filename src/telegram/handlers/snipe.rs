@@ -0,0 +1,139 @@
+use std::sync::Arc;
+
+use bigdecimal::BigDecimal;
+use teloxide::prelude::*;
+
+use crate::{
+    config::settings::SniperSettings,
+    database::{DatabaseConnectionPool, sniper},
+    entities::sniper_decisions::{Model as SniperDecision, SniperDecisionStatus},
+    solana::{amount::TokenAmount, constants::SOL_DECIMALS, trader::Trader},
+};
+
+const USAGE: &str = "Использование:\n\
+    /snipe on [сумма SOL] — покупать новые токены, прошедшие проверки\n\
+    /snipe off — выключить автопокупки\n\
+    /snipe — состояние и последние покупки\n\n\
+    Проверяются отзыв mint и freeze authority, сожженные LP и доля крупнейших держателей.";
+
+#[derive(Debug, PartialEq)]
+pub enum SnipeCommand {
+    Status,
+    // `None` — сумма по умолчанию из настроек
+    On(Option<BigDecimal>),
+    Off,
+}
+
+pub fn parse_snipe_args(args: &str) -> Option<SnipeCommand> {
+    let parts: Vec<&str> = args.split_whitespace().collect();
+
+    match parts.as_slice() {
+        [] => Some(SnipeCommand::Status),
+        [action] if action.eq_ignore_ascii_case("off") => Some(SnipeCommand::Off),
+        [action] if action.eq_ignore_ascii_case("on") => Some(SnipeCommand::On(None)),
+        [action, amount] if action.eq_ignore_ascii_case("on") => {
+            let amount = TokenAmount::parse(amount, SOL_DECIMALS).ok()?;
+            Some(SnipeCommand::On(Some(amount.to_big_decimal())))
+        }
+        _ => None,
+    }
+}
+
+pub fn describe_decision(decision: &SniperDecision) -> String {
+    let status = match decision.status {
+        SniperDecisionStatus::Passed => "проверки пройдены",
+        SniperDecisionStatus::Rejected => "отклонен",
+        SniperDecisionStatus::Executing => "покупается",
+        SniperDecisionStatus::Bought => "куплен",
+        SniperDecisionStatus::Skipped => "пропущен",
+        SniperDecisionStatus::Failed => "ошибка",
+    };
+
+    let mut text = format!(
+        "{} {} — {}",
+        decision.created_at.format("%d.%m %H:%M"),
+        decision.token_symbol,
+        status,
+    );
+    if !decision.reasons.0.is_empty() {
+        text.push_str(&format!(": {}", decision.reasons.0.join("; ")));
+    }
+    text
+}
+
+/// `/snipe` — автоматические покупки новых токенов
+pub async fn handle_snipe(
+    bot: Bot,
+    msg: Message,
+    args: String,
+    database: Arc<DatabaseConnectionPool>,
+    trader: Arc<Trader>,
+    settings: SniperSettings,
+) -> Result<(), teloxide::RequestError> {
+    let chat_id = msg.chat.id;
+    let Some(user) = msg.from.as_ref() else {
+        return Ok(());
+    };
+    let user_id = user.id.0 as i64;
+    let db = database.get_connection();
+
+    let Some(command) = parse_snipe_args(&args) else {
+        bot.send_message(chat_id, USAGE).await?;
+        return Ok(());
+    };
+
+    let mut text = match command {
+        SnipeCommand::Status => {
+            let subscription = sniper::find_subscription(db, user_id).await.ok().flatten();
+            let mut text = match subscription.filter(|subscription| subscription.enabled) {
+                Some(subscription) => format!(
+                    "🎯 Автопокупки включены: {} SOL на токен, проскальзывание до {}%",
+                    subscription.amount_sol.normalized(),
+                    subscription.max_slippage_bps as f64 / 100.0,
+                ),
+                None => "Автопокупки выключены. Включить: /snipe on <сумма SOL>".to_string(),
+            };
+
+            let decisions = sniper::recent_decisions(db, Some(user_id), None, 5).await.unwrap_or_default();
+            if !decisions.is_empty() {
+                let lines: Vec<String> = decisions.iter().map(describe_decision).collect();
+                text.push_str(&format!("\n\nПоследние покупки:\n{}", lines.join("\n")));
+            }
+            text
+        }
+        SnipeCommand::Off => match sniper::disable_subscription(db, user_id).await {
+            Ok(true) => "Автопокупки выключены".to_string(),
+            Ok(false) => "Автопокупки и так выключены".to_string(),
+            Err(e) => {
+                tracing::error!("Failed to disable sniper for user {}: {}", user_id, e);
+                "Не удалось выключить автопокупки".to_string()
+            }
+        },
+        SnipeCommand::On(amount) => {
+            let max_amount = TokenAmount::sol_setting(settings.max_amount_sol).to_big_decimal();
+            let amount = amount.unwrap_or_else(|| TokenAmount::sol_setting(settings.default_amount_sol).to_big_decimal());
+
+            if amount > max_amount {
+                format!("Сумма одной автопокупки не больше {} SOL", settings.max_amount_sol)
+            } else {
+                let slippage_bps = settings.slippage_bps.min(trader.limits().max_slippage_bps);
+                match sniper::upsert_subscription(db, user_id, amount, slippage_bps as i32).await {
+                    Ok(subscription) => format!(
+                        "🎯 Автопокупки включены: {} SOL на каждый новый токен, прошедший проверки",
+                        subscription.amount_sol.normalized(),
+                    ),
+                    Err(e) => {
+                        tracing::warn!("Failed to enable sniper for user {}: {}", user_id, e);
+                        "Не удалось включить автопокупки. Сначала добавьте кошелек: /addwallet".to_string()
+                    }
+                }
+            }
+        }
+    };
+    if !settings.enabled {
+        text.push_str("\n\n⚠️ Снайпер сейчас отключен администратором");
+    }
+    bot.send_message(chat_id, text).await?;
+
+    Ok(())
+}
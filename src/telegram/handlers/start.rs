@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use sea_orm::DbErr;
+use teloxide::{prelude::*, types::User};
+
+use crate::database::{
+    repositories::{Repositories, UserRepository},
+    users::TelegramProfile,
+};
+
+pub fn telegram_profile(user: &User) -> TelegramProfile {
+    TelegramProfile {
+        id: user.id.0 as i64,
+        username: user.username.clone(),
+        first_name: user.first_name.clone(),
+        last_name: user.last_name.clone(),
+        language_code: user.language_code.clone(),
+        is_premium: user.is_premium,
+    }
+}
+
+/// Сохраняет профиль пользователя. Возвращает `true`, если пользователь новый.
+pub async fn register_user(users: &dyn UserRepository, profile: TelegramProfile) -> Result<bool, DbErr> {
+    let is_new = users.find(profile.id).await?.is_none();
+    users.upsert(profile).await?;
+    Ok(is_new)
+}
+
+pub async fn handle_start(
+    bot: Bot,
+    msg: Message,
+    repositories: Arc<Repositories>,
+) -> Result<(), teloxide::RequestError> {
+    let chat_id = msg.chat.id;
+    let Some(user) = msg.from.as_ref() else {
+        return Ok(());
+    };
+
+    let text = match register_user(repositories.users.as_ref(), telegram_profile(user)).await {
+        Ok(true) => "Добро пожаловать в Solana Trading Bot! Используйте /help для списка команд.",
+        Ok(false) => "С возвращением! Используйте /help для списка команд.",
+        Err(e) => {
+            tracing::error!("Failed to register user {}: {}", user.id, e);
+            "❌ Не удалось зарегистрировать пользователя, попробуйте позже"
+        }
+    };
+
+    bot.send_message(chat_id, text).await?;
+    Ok(())
+}
@@ -0,0 +1,461 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::Context;
+use solana_sdk::pubkey::Pubkey;
+use teloxide::{
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup},
+};
+use uuid::Uuid;
+
+use crate::{
+    automation::FEE_RESERVE_LAMPORTS,
+    database::repositories::WalletRepository,
+    entities::trades::TradeType,
+    jupiter::{PriceService, QuoteResponseV6, SwapMode},
+    solana::{
+        amount::{AmountInput, TokenAmount},
+        constants::{SOL_DECIMALS, find_known_token},
+        executor::{ExecutionError, SwapExecutor, SwapOrder},
+        sender::LandingError,
+        token_safety::TokenSafety,
+        trader::{BuyAmount, TradeError, Trader, price_impact_pct},
+    },
+    telegram::{
+        preview::{
+            PriceImpactLevel, TokenMeta, price_impact_level, quote_mints, render_quote_preview,
+            render_safety_report, short_address,
+        },
+        quote_store::{QuoteStore, StoredQuote, adverse_deviation_bps},
+    },
+    utils::short_id,
+};
+
+pub const DEFAULT_SLIPPAGE_BPS: u64 = 50;
+
+pub const CALLBACK_CONFIRM: &str = "swap:confirm";
+pub const CALLBACK_ACK_IMPACT: &str = "swap:ack_impact";
+pub const CALLBACK_CANCEL: &str = "swap:cancel";
+
+/// Действие кнопки под котировкой
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapAction {
+    Confirm,
+    AckImpact,
+    Cancel,
+}
+
+impl SwapAction {
+    fn prefix(self) -> &'static str {
+        match self {
+            SwapAction::Confirm => CALLBACK_CONFIRM,
+            SwapAction::AckImpact => CALLBACK_ACK_IMPACT,
+            SwapAction::Cancel => CALLBACK_CANCEL,
+        }
+    }
+}
+
+/// Данные кнопки: действие и id котировки в хранилище, например `swap:confirm:<uuid>`
+pub fn callback_data(action: SwapAction, quote_id: &Uuid) -> String {
+    format!("{}:{}", action.prefix(), quote_id)
+}
+
+pub fn parse_callback(data: &str) -> Option<(SwapAction, Uuid)> {
+    let (prefix, id) = data.rsplit_once(':')?;
+    let action = [SwapAction::Confirm, SwapAction::AckImpact, SwapAction::Cancel]
+        .into_iter()
+        .find(|action| action.prefix() == prefix)?;
+
+    Some((action, Uuid::parse_str(id).ok()?))
+}
+
+/// Токен, указанный пользователем: символ известного токена или адрес mint
+pub struct ResolvedToken {
+    pub mint: Pubkey,
+    pub symbol: String,
+}
+
+pub fn resolve_token(query: &str) -> Option<ResolvedToken> {
+    if let Some(token) = find_known_token(query) {
+        return Some(ResolvedToken {
+            mint: Pubkey::from_str(token.mint).ok()?,
+            symbol: token.symbol.to_string(),
+        });
+    }
+
+    let mint = Pubkey::from_str(query).ok()?;
+    Some(ResolvedToken {
+        mint,
+        symbol: short_address(&mint),
+    })
+}
+
+fn parse_args(args: &str) -> Option<(ResolvedToken, AmountInput)> {
+    let mut parts = args.split_whitespace();
+    let token = resolve_token(parts.next()?)?;
+    let amount: AmountInput = parts.next()?.parse().ok()?;
+
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some((token, amount))
+}
+
+/// Символ токена из его метаданных; если их не удалось прочитать — введенный пользователем
+pub async fn token_display_symbol(trader: &Trader, token: &ResolvedToken) -> String {
+    match trader.tokens().resolve(&token.mint).await {
+        Ok(info) => info.meta().symbol,
+        Err(e) => {
+            tracing::warn!("Failed to resolve token {}: {:#}", token.mint, e);
+            token.symbol.clone()
+        }
+    }
+}
+
+/// SOL, доступный для покупки: баланс кошелька за вычетом запаса на комиссии
+pub async fn spendable_sol(wallets: &dyn WalletRepository, trader: &Trader, user_id: i64) -> anyhow::Result<TokenAmount> {
+    let wallet = wallets.default_wallet(user_id).await?.context("Default wallet not found")?;
+    let owner: Pubkey = wallet.public_key.parse().context("Invalid wallet public key")?;
+    let balance = trader.rpc().get_balance(&owner).await?;
+    Ok(TokenAmount::sol(balance).saturating_sub(TokenAmount::sol(FEE_RESERVE_LAMPORTS)))
+}
+
+/// Символы и знаки всех токенов котировки
+pub async fn token_metas(trader: &Trader, mints: &[Pubkey]) -> anyhow::Result<HashMap<Pubkey, TokenMeta>> {
+    let mut tokens = HashMap::new();
+    for mint in mints {
+        tokens.insert(*mint, trader.token_meta(mint).await?);
+    }
+    Ok(tokens)
+}
+
+fn confirm_keyboard(quote_id: &Uuid) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![vec![
+        InlineKeyboardButton::callback("✅ Подтвердить", callback_data(SwapAction::Confirm, quote_id)),
+        InlineKeyboardButton::callback("✖️ Отмена", callback_data(SwapAction::Cancel, quote_id)),
+    ]])
+}
+
+fn impact_keyboard(quote_id: &Uuid) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![vec![
+        InlineKeyboardButton::callback("⚠️ Продолжить", callback_data(SwapAction::AckImpact, quote_id)),
+        InlineKeyboardButton::callback("✖️ Отмена", callback_data(SwapAction::Cancel, quote_id)),
+    ]])
+}
+
+/// Показывает предпросмотр котировки и сохраняет ее в хранилище до подтверждения
+#[allow(clippy::too_many_arguments)]
+async fn present_quote(
+    bot: &Bot,
+    chat_id: ChatId,
+    user_id: i64,
+    quote: QuoteResponseV6,
+    notice: Option<String>,
+    trader: &Trader,
+    price_service: &PriceService,
+    quotes: &QuoteStore,
+    token_safety: &TokenSafety,
+) -> Result<(), teloxide::RequestError> {
+    let mints = quote_mints(&quote);
+    let tokens = match token_metas(trader, &mints).await {
+        Ok(tokens) => tokens,
+        Err(e) => {
+            tracing::warn!("Failed to resolve token metadata for quote: {:#}", e);
+            bot.send_message(chat_id, "Не удалось получить информацию о токене").await?;
+            return Ok(());
+        }
+    };
+    let prices = price_service.get_prices(&mints).await.unwrap_or_else(|e| {
+        tracing::warn!("Failed to fetch prices for quote preview: {:#}", e);
+        HashMap::new()
+    });
+
+    let mut text = render_quote_preview(&quote, &tokens, &prices, trader.limits());
+    if let Some(notice) = notice {
+        text = format!("{}\n\n{}", notice, text);
+    }
+
+    // Лимиты проверяются по худшему сценарию еще до показа кнопки подтверждения
+    if let Err(e) = trader.check_limits(&quote) {
+        text.push_str(&format!("\n\n❌ Сделка недоступна: {}", e));
+        bot.send_message(chat_id, text).await?;
+        return Ok(());
+    }
+
+    // Та же проверка повторяется при исполнении; здесь пользователь видит риски заранее
+    match token_safety.check(&quote).await {
+        Ok(Some(report)) => {
+            text.push_str(&format!("\n\n{}", render_safety_report(&report, token_safety.settings())));
+            if !report.block_reasons(token_safety.settings()).is_empty() {
+                bot.send_message(chat_id, text).await?;
+                return Ok(());
+            }
+        }
+        Ok(None) => {}
+        Err(e) => {
+            tracing::warn!("Token safety check failed for {}: {:#}", quote.output_mint, e);
+            text.push_str("\n\n❌ Не удалось проверить безопасность токена, попробуйте позже");
+            bot.send_message(chat_id, text).await?;
+            return Ok(());
+        }
+    }
+
+    let needs_impact_ack = price_impact_level(&quote, trader.limits()) == PriceImpactLevel::Warn;
+    let quote_id = quotes.insert(user_id, quote, tokens, needs_impact_ack);
+    let keyboard = if needs_impact_ack {
+        impact_keyboard(&quote_id)
+    } else {
+        confirm_keyboard(&quote_id)
+    };
+
+    bot.send_message(chat_id, text).reply_markup(keyboard).await?;
+
+    Ok(())
+}
+
+/// `/buy <токен> <сумма SOL>` и `/buyexact <токен> <количество токенов>`
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_buy(
+    bot: Bot,
+    msg: Message,
+    args: String,
+    swap_mode: SwapMode,
+    wallets: Arc<dyn WalletRepository>,
+    trader: Arc<Trader>,
+    price_service: Arc<PriceService>,
+    quotes: Arc<QuoteStore>,
+    token_safety: Arc<TokenSafety>,
+) -> Result<(), teloxide::RequestError> {
+    let chat_id = msg.chat.id;
+    let Some(user) = msg.from.as_ref() else {
+        return Ok(());
+    };
+
+    let Some((token, amount)) = parse_args(&args).filter(|(_, amount)| {
+        swap_mode == SwapMode::ExactIn || !amount.needs_balance()
+    }) else {
+        let usage = match swap_mode {
+            SwapMode::ExactIn => "Использование: /buy <токен> <сумма SOL | max | доля%>\nНапример: /buy BONK 0.5 или /buy BONK 25%",
+            SwapMode::ExactOut => "Использование: /buyexact <токен> <количество>\nНапример: /buyexact USDC 100",
+        };
+        bot.send_message(chat_id, usage).await?;
+        return Ok(());
+    };
+
+    let resolved = match swap_mode {
+        SwapMode::ExactIn => {
+            let available = if amount.needs_balance() {
+                match spendable_sol(wallets.as_ref(), &trader, user.id.0 as i64).await {
+                    Ok(available) => Some(available),
+                    Err(e) => {
+                        tracing::warn!("Failed to get SOL balance of user {}: {:#}", user.id, e);
+                        bot.send_message(chat_id, "Не удалось получить баланс кошелька").await?;
+                        return Ok(());
+                    }
+                }
+            } else {
+                None
+            };
+            amount.resolve(SOL_DECIMALS, available).map(|amount| BuyAmount::SpendLamports(amount.raw()))
+        }
+        SwapMode::ExactOut => match trader.mint_decimals(&token.mint).await {
+            Ok(decimals) => amount.resolve(decimals, None).map(|amount| BuyAmount::ReceiveExact(amount.raw())),
+            Err(e) => {
+                tracing::warn!("Failed to resolve decimals for {}: {:#}", token.mint, e);
+                bot.send_message(chat_id, "Не удалось получить информацию о токене").await?;
+                return Ok(());
+            }
+        },
+    };
+    let buy_amount = match resolved {
+        Ok(buy_amount) => buy_amount,
+        Err(e) => {
+            bot.send_message(chat_id, format!("❌ Некорректная сумма: {}", e)).await?;
+            return Ok(());
+        }
+    };
+
+    let quote = match trader.quote_buy(token.mint, buy_amount, DEFAULT_SLIPPAGE_BPS).await {
+        Ok(quote) => quote,
+        Err(e) => {
+            tracing::warn!("Quote for {} failed: {:#}", token.mint, e);
+            bot.send_message(chat_id, "Не удалось получить котировку. Попробуйте позже.").await?;
+            return Ok(());
+        }
+    };
+
+    present_quote(
+        &bot,
+        chat_id,
+        user.id.0 as i64,
+        quote,
+        None,
+        &trader,
+        &price_service,
+        &quotes,
+        &token_safety,
+    ).await
+}
+
+pub async fn handle_swap_callback(
+    bot: Bot,
+    q: CallbackQuery,
+    executor: Arc<SwapExecutor>,
+    trader: Arc<Trader>,
+    price_service: Arc<PriceService>,
+    quotes: Arc<QuoteStore>,
+    token_safety: Arc<TokenSafety>,
+) -> Result<(), teloxide::RequestError> {
+    bot.answer_callback_query(q.id.clone()).await?;
+
+    let Some(chat_id) = q.message.as_ref().map(|m| m.chat().id) else {
+        return Ok(());
+    };
+    let Some((action, quote_id)) = q.data.as_deref().and_then(parse_callback) else {
+        return Ok(());
+    };
+    let user_id = q.from.id.0 as i64;
+
+    if action == SwapAction::AckImpact {
+        let Some(stored) = quotes.acknowledge_impact(&quote_id, user_id) else {
+            bot.send_message(chat_id, "Котировка устарела или уже использована").await?;
+            return Ok(());
+        };
+
+        bot.send_message(
+            chat_id,
+            format!(
+                "⚠️ Влияние на цену составит {:.2}%. Вы уверены, что хотите продолжить?",
+                price_impact_pct(&stored.quote),
+            ),
+        ).reply_markup(confirm_keyboard(&quote_id)).await?;
+        return Ok(());
+    }
+
+    if action == SwapAction::Cancel {
+        let text = match quotes.take(&quote_id, user_id) {
+            Some(_) => "Сделка отменена",
+            None => "Котировка устарела или уже использована",
+        };
+        bot.send_message(chat_id, text).await?;
+        return Ok(());
+    }
+
+    // Котировка извлекается из хранилища, поэтому повторное нажатие ничего не сделает.
+    // Без принятого предупреждения о влиянии на цену она остается для подтверждения.
+    let Some(stored) = quotes.take_confirmed(&quote_id, user_id) else {
+        let text = if quotes.awaits_impact_ack(&quote_id, user_id) {
+            "Сначала подтвердите предупреждение о влиянии на цену"
+        } else {
+            "Котировка устарела или уже использована"
+        };
+        bot.send_message(chat_id, text).await?;
+        return Ok(());
+    };
+
+    let expired = stored.is_expired(quotes.ttl());
+    let StoredQuote { id, quote, tokens, impact_acknowledged, .. } = stored;
+
+    // Устаревшая котировка перезапрашивается; если условия заметно хуже, сделка подтверждается заново
+    let quote = if expired {
+        let fresh = match trader.refresh_quote(&quote).await {
+            Ok(fresh) => fresh,
+            Err(e) => {
+                tracing::warn!("Re-quote of {} failed: {:#}", id, e);
+                bot.send_message(chat_id, "Котировка устарела, а получить новую не удалось. Попробуйте позже.").await?;
+                return Ok(());
+            }
+        };
+
+        let deviation_bps = adverse_deviation_bps(&quote, &fresh);
+        let impact_unconfirmed = !impact_acknowledged
+            && price_impact_level(&fresh, trader.limits()) == PriceImpactLevel::Warn;
+
+        if deviation_bps > quotes.requote_tolerance_bps() || impact_unconfirmed {
+            tracing::info!("Quote {} re-prompted after re-quote ({} bps worse)", id, deviation_bps);
+            let notice = format!(
+                "🔄 Котировка устарела, новые условия хуже на {:.2}%. Подтвердите сделку заново.",
+                deviation_bps as f64 / 100.0,
+            );
+            return present_quote(
+                &bot,
+                chat_id,
+                user_id,
+                fresh,
+                Some(notice),
+                &trader,
+                &price_service,
+                &quotes,
+                &token_safety,
+            ).await;
+        }
+
+        tracing::info!("Quote {} refreshed on confirm ({} bps worse)", id, deviation_bps);
+        fresh
+    } else {
+        quote
+    };
+
+    let (Some(input), Some(output)) = (
+        tokens.get(&quote.input_mint).cloned(),
+        tokens.get(&quote.output_mint).cloned(),
+    ) else {
+        bot.send_message(chat_id, "Не удалось получить информацию о токене").await?;
+        return Ok(());
+    };
+
+    let result = executor.execute(SwapOrder {
+        user_id,
+        trade_type: TradeType::Buy,
+        quote: &quote,
+        input,
+        output,
+        jupiter_quote_id: Some(id.to_string()),
+        exit: false,
+    }).await;
+
+    match result {
+        Ok(executed) => {
+            bot.send_message(
+                chat_id,
+                format!(
+                    "✅ Сделка выполнена\nhttps://solscan.io/tx/{}\n\n\
+                     ID сделки: {id}\nАвтовыход: /sl {id} 10% · /tp {id} 25% · /trail {id} 15%",
+                    executed.signature,
+                    id = short_id(&executed.trade_id),
+                ),
+            ).await?;
+        }
+        Err(ExecutionError::Wallet(e)) => {
+            tracing::warn!("Failed to load wallet for user {}: {:#}", user_id, e);
+            bot.send_message(chat_id, "Не найден кошелек по умолчанию. Добавьте кошелек: /addwallet").await?;
+        }
+        Err(ExecutionError::Database(e)) => {
+            tracing::error!("Failed to record trade for user {}: {}", user_id, e);
+            bot.send_message(chat_id, "❌ Не удалось сохранить сделку, она не была отправлена").await?;
+        }
+        // Транзакция могла попасть в блок: не называем это ошибкой, чтобы не спровоцировать повторную покупку
+        Err(ExecutionError::Send { trade_id, error: TradeError::Landing(LandingError::Unknown(signature)) }) => {
+            tracing::warn!("Swap {} for user {} is unconfirmed: {}", trade_id, user_id, signature);
+            bot.send_message(
+                chat_id,
+                format!(
+                    "⏳ Сделка отправлена, статус неизвестен\nhttps://solscan.io/tx/{}\n\n\
+                     Она еще может пройти — проверьте транзакцию, прежде чем повторять покупку.\n\
+                     ID сделки: {}",
+                    signature,
+                    short_id(&trade_id),
+                ),
+            ).await?;
+        }
+        Err(e) => {
+            tracing::warn!("Swap for user {} failed: {:#}", user_id, e);
+            bot.send_message(chat_id, format!("❌ Сделка не выполнена: {}", e)).await?;
+        }
+    }
+
+    Ok(())
+}
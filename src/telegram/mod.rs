@@ -1,2 +1,5 @@
 pub mod bot;
-
+pub mod handlers;
+pub mod notifier;
+pub mod preview;
+pub mod quote_store;
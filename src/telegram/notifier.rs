@@ -0,0 +1,20 @@
+use teloxide::prelude::*;
+
+/// Отправка уведомлений пользователям из фоновых задач.
+/// В личном чате ID чата совпадает с Telegram ID пользователя.
+#[derive(Clone)]
+pub struct Notifier {
+    bot: Bot,
+}
+
+impl Notifier {
+    pub fn new(bot: Bot) -> Self {
+        Self { bot }
+    }
+
+    pub async fn notify(&self, user_id: i64, text: impl Into<String>) {
+        if let Err(e) = self.bot.send_message(ChatId(user_id), text).await {
+            tracing::warn!("Failed to notify user {}: {}", user_id, e);
+        }
+    }
+}
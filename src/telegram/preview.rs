@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    config::settings::{TokenSafetySettings, TradingLimits},
+    jupiter::{QuoteResponseV6, SwapMode},
+    solana::{
+        amount::TokenAmount,
+        constants::from_lamports,
+        token_safety::SafetyReport,
+        trader::{max_input_amount, price_impact_pct},
+    },
+};
+
+pub use crate::solana::constants::{TokenMeta, short_address, token_symbol};
+
+/// Все mint, участвующие в котировке, включая промежуточные и mint комиссий
+pub fn quote_mints(quote: &QuoteResponseV6) -> Vec<Pubkey> {
+    let mut mints = vec![quote.input_mint, quote.output_mint];
+    for step in &quote.route_plan {
+        for mint in [step.swap_info.input_mint, step.swap_info.output_mint, step.swap_info.fee_mint] {
+            if !mints.contains(&mint) {
+                mints.push(mint);
+            }
+        }
+    }
+    mints
+}
+
+/// Один шаг маршрута: пара токенов и пулы, между которыми разделен объем
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteHop {
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub legs: Vec<(String, u8)>,
+}
+
+/// Jupiter отдает плоский `route_plan`; соседние шаги с одной парой — это сплит
+pub fn route_hops(quote: &QuoteResponseV6) -> Vec<RouteHop> {
+    let mut hops: Vec<RouteHop> = Vec::new();
+
+    for step in &quote.route_plan {
+        let info = &step.swap_info;
+        let label = info.label.clone().unwrap_or_else(|| short_address(&info.amm_key));
+
+        match hops.last_mut() {
+            Some(hop) if hop.input_mint == info.input_mint && hop.output_mint == info.output_mint => {
+                hop.legs.push((label, step.percent));
+            }
+            _ => hops.push(RouteHop {
+                input_mint: info.input_mint,
+                output_mint: info.output_mint,
+                legs: vec![(label, step.percent)],
+            }),
+        }
+    }
+
+    hops
+}
+
+fn symbol_of(tokens: &HashMap<Pubkey, TokenMeta>, mint: &Pubkey) -> String {
+    tokens.get(mint).map(|meta| meta.symbol.clone()).unwrap_or_else(|| token_symbol(mint))
+}
+
+/// Например: "SOL → USDC via Orca 60% / Raydium 40%", по строке на каждый шаг
+pub fn render_route(quote: &QuoteResponseV6, tokens: &HashMap<Pubkey, TokenMeta>) -> String {
+    route_hops(quote)
+        .iter()
+        .map(|hop| {
+            let legs = hop.legs
+                .iter()
+                .map(|(label, percent)| format!("{} {}%", label, percent))
+                .collect::<Vec<_>>()
+                .join(" / ");
+            format!(
+                "{} → {} via {}",
+                symbol_of(tokens, &hop.input_mint),
+                symbol_of(tokens, &hop.output_mint),
+                legs,
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Сумма комиссий пулов и комиссии платформы в USD.
+/// `None`, если для какой-то комиссии нет цены или знаков токена.
+pub fn total_fees_usd(
+    quote: &QuoteResponseV6,
+    tokens: &HashMap<Pubkey, TokenMeta>,
+    prices: &HashMap<Pubkey, f64>,
+) -> Option<f64> {
+    let to_usd = |amount: u64, mint: &Pubkey| -> Option<f64> {
+        if amount == 0 {
+            return Some(0.0);
+        }
+        Some(from_lamports(amount, tokens.get(mint)?.decimals) * prices.get(mint)?)
+    };
+
+    let mut total = 0.0;
+    for step in &quote.route_plan {
+        total += to_usd(step.swap_info.fee_amount, &step.swap_info.fee_mint)?;
+    }
+
+    if let Some(fee) = &quote.platform_fee {
+        let fee_mint = match quote.swap_mode {
+            SwapMode::ExactIn => &quote.output_mint,
+            SwapMode::ExactOut => &quote.input_mint,
+        };
+        total += to_usd(fee.amount, fee_mint)?;
+    }
+
+    Some(total)
+}
+
+/// Фактическая цена исполнения: сколько входного токена за единицу выходного
+pub fn effective_price(quote: &QuoteResponseV6, tokens: &HashMap<Pubkey, TokenMeta>) -> Option<f64> {
+    let input = from_lamports(quote.in_amount, tokens.get(&quote.input_mint)?.decimals);
+    let output = from_lamports(quote.out_amount, tokens.get(&quote.output_mint)?.decimals);
+
+    (output > 0.0).then(|| input / output)
+}
+
+/// Рыночная (mid) цена в тех же единицах, что и `effective_price`
+pub fn mid_price(quote: &QuoteResponseV6, prices: &HashMap<Pubkey, f64>) -> Option<f64> {
+    let input_usd = prices.get(&quote.input_mint)?;
+    let output_usd = prices.get(&quote.output_mint)?;
+
+    (*input_usd > 0.0).then(|| output_usd / input_usd)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceImpactLevel {
+    Normal,
+    Warn,
+    Block,
+}
+
+pub fn price_impact_level(quote: &QuoteResponseV6, limits: &TradingLimits) -> PriceImpactLevel {
+    let impact = price_impact_pct(quote);
+
+    if impact > limits.price_impact_block_pct {
+        PriceImpactLevel::Block
+    } else if impact >= limits.price_impact_warn_pct {
+        PriceImpactLevel::Warn
+    } else {
+        PriceImpactLevel::Normal
+    }
+}
+
+/// Суммы сделки: сколько будет потрачено и получено с учетом проскальзывания
+pub fn render_amounts(quote: &QuoteResponseV6, tokens: &HashMap<Pubkey, TokenMeta>) -> Option<String> {
+    let input = tokens.get(&quote.input_mint)?;
+    let output = tokens.get(&quote.output_mint)?;
+    let slippage_pct = quote.slippage_bps as f64 / 100.0;
+
+    let text = match quote.swap_mode {
+        SwapMode::ExactIn => format!(
+            "Потратите: {} {}\n\
+             Получите: ~{} {}\n\
+             Минимум с учетом проскальзывания {}%: {} {}",
+            TokenAmount::new(quote.in_amount, input.decimals), input.symbol,
+            TokenAmount::new(quote.out_amount, output.decimals), output.symbol,
+            slippage_pct,
+            TokenAmount::new(quote.other_amount_threshold, output.decimals), output.symbol,
+        ),
+        SwapMode::ExactOut => format!(
+            "Получите ровно: {} {}\n\
+             Ориентировочная стоимость: {} {}\n\
+             Максимум к списанию с учетом проскальзывания {}%: {} {}",
+            TokenAmount::new(quote.out_amount, output.decimals), output.symbol,
+            TokenAmount::new(quote.in_amount, input.decimals), input.symbol,
+            slippage_pct,
+            TokenAmount::new(max_input_amount(quote), input.decimals), input.symbol,
+        ),
+    };
+
+    Some(text)
+}
+
+/// Полный предпросмотр котировки: суммы, маршрут, комиссии, цена и влияние на цену
+pub fn render_quote_preview(
+    quote: &QuoteResponseV6,
+    tokens: &HashMap<Pubkey, TokenMeta>,
+    prices: &HashMap<Pubkey, f64>,
+    limits: &TradingLimits,
+) -> String {
+    let input_symbol = symbol_of(tokens, &quote.input_mint);
+    let output_symbol = symbol_of(tokens, &quote.output_mint);
+
+    let mut lines = vec![format!("Обмен {} → {}", input_symbol, output_symbol), String::new()];
+
+    if let Some(amounts) = render_amounts(quote, tokens) {
+        lines.push(amounts);
+    }
+
+    lines.push(String::new());
+    lines.push("Маршрут:".to_string());
+    lines.push(render_route(quote, tokens));
+    lines.push(String::new());
+
+    match total_fees_usd(quote, tokens, prices) {
+        Some(fees) => lines.push(format!("Комиссии: ~${:.4}", fees)),
+        None => lines.push("Комиссии: нет данных о цене".to_string()),
+    }
+    if let Some(fee) = &quote.platform_fee {
+        lines.push(format!("Комиссия сервиса: {}%", fee.fee_bps as f64 / 100.0));
+    }
+
+    if let Some(effective) = effective_price(quote, tokens) {
+        let mut line = format!("Цена: {:.9} {} за 1 {}", effective, input_symbol, output_symbol);
+        if let Some(mid) = mid_price(quote, prices) {
+            let deviation = (effective / mid - 1.0) * 100.0;
+            line.push_str(&format!(" (рынок {:.9}, {:+.2}%)", mid, deviation));
+        }
+        lines.push(line);
+    }
+
+    let impact = price_impact_pct(quote);
+    lines.push(format!("Влияние на цену: {:.2}%", impact));
+
+    match price_impact_level(quote, limits) {
+        PriceImpactLevel::Normal => {}
+        PriceImpactLevel::Warn => lines.push(format!(
+            "\n⚠️ Влияние на цену выше {}%. Потребуется дополнительное подтверждение.",
+            limits.price_impact_warn_pct,
+        )),
+        PriceImpactLevel::Block => lines.push(format!(
+            "\n⛔ Влияние на цену выше {}%. Сделка заблокирована.",
+            limits.price_impact_block_pct,
+        )),
+    }
+
+    lines.join("\n")
+}
+
+/// Оценка риска токена и предупреждения для предпросмотра покупки
+pub fn render_safety_report(report: &SafetyReport, settings: &TokenSafetySettings) -> String {
+    let mut lines = vec![format!("Безопасность токена: риск {}/100", report.risk_score())];
+    lines.extend(report.warnings().into_iter().map(|warning| format!("⚠️ {}", warning)));
+
+    let reasons = report.block_reasons(settings);
+    if !reasons.is_empty() {
+        lines.push(format!("\n⛔ Покупка заблокирована: {}", reasons.join("; ")));
+    }
+
+    lines.join("\n")
+}
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use solana_sdk::pubkey::Pubkey;
+use uuid::Uuid;
+
+use crate::{
+    jupiter::{QuoteResponseV6, SwapMode},
+    telegram::preview::TokenMeta,
+};
+
+// Сколько хранить котировку после истечения TTL, чтобы по ней можно было перезапросить цену
+const RETENTION: Duration = Duration::from_secs(600);
+
+/// Котировка, показанная пользователю и ожидающая подтверждения
+#[derive(Debug, Clone)]
+pub struct StoredQuote {
+    pub id: Uuid,
+    pub user_id: i64,
+    pub quote: QuoteResponseV6,
+    pub tokens: HashMap<Pubkey, TokenMeta>,
+    pub created_at: Instant,
+    // Высокое влияние на цену требует отдельного подтверждения перед основным
+    pub needs_impact_ack: bool,
+    pub impact_acknowledged: bool,
+}
+
+impl StoredQuote {
+    pub fn is_expired(&self, ttl: Duration) -> bool {
+        self.created_at.elapsed() > ttl
+    }
+}
+
+/// Серверное хранилище котировок. Кнопки в Telegram ссылаются на котировку по id,
+/// а подтверждение извлекает ее из хранилища, поэтому повторное нажатие ничего не делает.
+pub struct QuoteStore {
+    quotes: DashMap<Uuid, StoredQuote>,
+    ttl: Duration,
+    requote_tolerance_bps: u64,
+}
+
+impl QuoteStore {
+    pub fn new(ttl: Duration, requote_tolerance_bps: u64) -> Self {
+        Self {
+            quotes: DashMap::new(),
+            ttl,
+            requote_tolerance_bps,
+        }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Допустимое ухудшение перезапрошенной котировки без повторного подтверждения
+    pub fn requote_tolerance_bps(&self) -> u64 {
+        self.requote_tolerance_bps
+    }
+
+    pub fn insert(
+        &self,
+        user_id: i64,
+        quote: QuoteResponseV6,
+        tokens: HashMap<Pubkey, TokenMeta>,
+        needs_impact_ack: bool,
+    ) -> Uuid {
+        self.quotes.retain(|_, stored| stored.created_at.elapsed() < RETENTION);
+
+        let id = Uuid::new_v4();
+        tracing::debug!(
+            "Stored quote {} for user {} (context slot {:?}, took {:?}s)",
+            id, user_id, quote.context_slot, quote.time_taken,
+        );
+
+        self.quotes.insert(id, StoredQuote {
+            id,
+            user_id,
+            quote,
+            tokens,
+            created_at: Instant::now(),
+            needs_impact_ack,
+            impact_acknowledged: false,
+        });
+
+        id
+    }
+
+    /// Отмечает предупреждение о влиянии на цену как принятое
+    pub fn acknowledge_impact(&self, id: &Uuid, user_id: i64) -> Option<StoredQuote> {
+        let mut stored = self.quotes.get_mut(id).filter(|stored| stored.user_id == user_id)?;
+        stored.impact_acknowledged = true;
+        Some(stored.clone())
+    }
+
+    /// Атомарно извлекает котировку пользователя
+    pub fn take(&self, id: &Uuid, user_id: i64) -> Option<StoredQuote> {
+        self.quotes
+            .remove_if(id, |_, stored| stored.user_id == user_id)
+            .map(|(_, stored)| stored)
+    }
+
+    /// Атомарно извлекает котировку для исполнения. Пока предупреждение о влиянии
+    /// на цену не принято, котировка остается в хранилище.
+    pub fn take_confirmed(&self, id: &Uuid, user_id: i64) -> Option<StoredQuote> {
+        self.quotes
+            .remove_if(id, |_, stored| {
+                stored.user_id == user_id && (!stored.needs_impact_ack || stored.impact_acknowledged)
+            })
+            .map(|(_, stored)| stored)
+    }
+
+    /// Ждет ли котировка пользователя принятия предупреждения о влиянии на цену
+    pub fn awaits_impact_ack(&self, id: &Uuid, user_id: i64) -> bool {
+        self.quotes
+            .get(id)
+            .is_some_and(|stored| stored.user_id == user_id && stored.needs_impact_ack && !stored.impact_acknowledged)
+    }
+}
+
+/// Ухудшение новой котировки относительно показанной, в bps.
+/// Для ExactIn сравнивается выход, для ExactOut — требуемый вход.
+pub fn adverse_deviation_bps(shown: &QuoteResponseV6, fresh: &QuoteResponseV6) -> u64 {
+    let (before, after, worse) = match shown.swap_mode {
+        SwapMode::ExactIn => (shown.out_amount, fresh.out_amount, fresh.out_amount < shown.out_amount),
+        SwapMode::ExactOut => (shown.in_amount, fresh.in_amount, fresh.in_amount > shown.in_amount),
+    };
+
+    if !worse || before == 0 {
+        return 0;
+    }
+
+    (before.abs_diff(after) as u128 * 10_000 / before as u128) as u64
+}
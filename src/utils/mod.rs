@@ -0,0 +1,6 @@
+use uuid::Uuid;
+
+/// Короткий id для показа пользователю и поиска по префиксу
+pub fn short_id(id: &Uuid) -> String {
+    id.simple().to_string()[..8].to_string()
+}
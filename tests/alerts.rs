@@ -0,0 +1,109 @@
+use bigdecimal::BigDecimal;
+use chrono::{Duration, TimeZone, Utc};
+use std::str::FromStr;
+
+use solana_sdk::pubkey::Pubkey;
+use solana_trading_bot::{
+    automation::alerts::{AlertAction, Observation, PriceHistory, condition_met, decide},
+    entities::price_alerts::AlertKind,
+    solana::constants::BONK_MINT,
+    telegram::handlers::alert::{AlertCommand, parse_alert_args, parse_change},
+};
+
+#[test]
+fn price_level_alerts_are_parsed() {
+    let Some(AlertCommand::Create { token, kind, threshold, window_secs }) = parse_alert_args("bonk below $0,00002") else {
+        panic!("expected create command");
+    };
+
+    assert_eq!(token.mint.to_string(), BONK_MINT);
+    assert_eq!(kind, AlertKind::Below);
+    assert_eq!(threshold, BigDecimal::from_str("0.00002").unwrap());
+    assert_eq!(window_secs, None);
+
+    assert!(matches!(
+        parse_alert_args("bonk volume 1000000"),
+        Some(AlertCommand::Create { kind: AlertKind::Volume, .. })
+    ));
+    assert!(parse_alert_args("bonk above 0").is_none());
+    assert!(parse_alert_args("bonk near 1").is_none());
+}
+
+#[test]
+fn change_alerts_are_parsed() {
+    assert_eq!(parse_change("+10%"), Some((AlertKind::Rise, BigDecimal::from(10))));
+    assert_eq!(parse_change("-7.5%"), Some((AlertKind::Drop, BigDecimal::from_str("7.5").unwrap())));
+    assert_eq!(parse_change("±10%"), Some((AlertKind::Move, BigDecimal::from(10))));
+    assert_eq!(parse_change("10%"), Some((AlertKind::Move, BigDecimal::from(10))));
+    assert_eq!(parse_change("10"), None);
+
+    assert!(matches!(
+        parse_alert_args("bonk -10% 1h"),
+        Some(AlertCommand::Create { kind: AlertKind::Drop, window_secs: Some(3_600), .. })
+    ));
+    assert!(matches!(
+        parse_alert_args("bonk +25%"),
+        Some(AlertCommand::Create { kind: AlertKind::Rise, window_secs: None, .. })
+    ));
+    assert!(parse_alert_args("bonk +10% sometime").is_none());
+}
+
+#[test]
+fn list_and_delete_are_parsed() {
+    assert!(matches!(parse_alert_args(""), Some(AlertCommand::List)));
+    assert!(matches!(parse_alert_args("delete 1A2B"), Some(AlertCommand::Delete(id)) if id == "1a2b"));
+}
+
+#[test]
+fn conditions_are_checked_against_observation() {
+    let observation = Observation {
+        price: 100.0,
+        change_pct: Some(-12.0),
+        volume_24h: None,
+    };
+
+    assert_eq!(condition_met(AlertKind::Above, 100.0, &observation), Some(true));
+    assert_eq!(condition_met(AlertKind::Below, 90.0, &observation), Some(false));
+    assert_eq!(condition_met(AlertKind::Drop, 10.0, &observation), Some(true));
+    assert_eq!(condition_met(AlertKind::Rise, 10.0, &observation), Some(false));
+    assert_eq!(condition_met(AlertKind::Move, 15.0, &observation), Some(false));
+    // Без данных об объеме уведомление не проверяется
+    assert_eq!(condition_met(AlertKind::Volume, 1.0, &observation), None);
+}
+
+#[test]
+fn alerts_fire_once_and_respect_cooldown() {
+    let now = Utc.with_ymd_and_hms(2025, 12, 29, 12, 0, 0).unwrap();
+    let cooldown = Duration::minutes(15);
+
+    assert_eq!(decide(true, true, None, now, cooldown), AlertAction::Fire);
+    // Сработавшее уведомление молчит, пока условие выполняется
+    assert_eq!(decide(false, true, Some(now), now + Duration::hours(1), cooldown), AlertAction::Wait);
+    // и включается снова, когда условие перестало выполняться
+    assert_eq!(decide(false, false, Some(now), now + Duration::minutes(1), cooldown), AlertAction::Rearm);
+    // Повторное пересечение уровня раньше cooldown не уведомляет
+    assert_eq!(decide(true, true, Some(now), now + Duration::minutes(5), cooldown), AlertAction::Wait);
+    assert_eq!(decide(true, true, Some(now), now + Duration::minutes(15), cooldown), AlertAction::Fire);
+    assert_eq!(decide(true, false, Some(now), now, cooldown), AlertAction::Wait);
+}
+
+#[test]
+fn price_history_measures_change_over_window() {
+    let mint = Pubkey::from_str(BONK_MINT).unwrap();
+    let start = Utc.with_ymd_and_hms(2025, 12, 29, 12, 0, 0).unwrap();
+    let mut history = PriceHistory::default();
+
+    history.record(mint, start, 100.0);
+    history.record(mint, start + Duration::minutes(30), 105.0);
+    // История короче окна
+    assert_eq!(history.change_pct(&mint, start + Duration::minutes(30), Duration::hours(1)), None);
+
+    history.record(mint, start + Duration::minutes(70), 90.0);
+    let change = history.change_pct(&mint, start + Duration::minutes(70), Duration::hours(1)).unwrap();
+    assert!((change + 10.0).abs() < 1e-9);
+
+    // Старые записи удаляются, но база для суточного окна остается
+    history.record(mint, start + Duration::hours(25), 84.0);
+    let change = history.change_pct(&mint, start + Duration::hours(25), Duration::hours(24)).unwrap();
+    assert!((change + 20.0).abs() < 1e-9);
+}
@@ -0,0 +1,14 @@
+use solana_trading_bot::api::middleware::auth::is_authorized;
+
+#[test]
+fn admin_token_must_match_exactly() {
+    assert!(is_authorized(Some("Bearer s3cret"), "s3cret"));
+
+    assert!(!is_authorized(None, "s3cret"));
+    assert!(!is_authorized(Some("s3cret"), "s3cret"));
+    assert!(!is_authorized(Some("Bearer s3cre"), "s3cret"));
+    assert!(!is_authorized(Some("Bearer s3cret2"), "s3cret"));
+    assert!(!is_authorized(Some("Bearer S3CRET"), "s3cret"));
+    // Пустой токен в настройках не открывает доступ
+    assert!(!is_authorized(Some("Bearer "), ""));
+}
@@ -0,0 +1,148 @@
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use chrono::Utc;
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use uuid::Uuid;
+
+use solana_trading_bot::{
+    automation::copy_trading::{buy_amount_lamports, filter_reason, sell_amount},
+    entities::copy_follows::{CopyFollowStatus, CopyMode, MintList, Model as CopyFollow},
+    solana::{
+        constants::{BONK_MINT, SOL_MINT},
+        swap_decoder::decode_swap,
+    },
+    telegram::handlers::copy::{CopyCommand, FollowAction, MintFilter, parse_copy_args},
+};
+
+const LEADER: &str = "8bHgn3T6oSTzBDhFwxxUK5JXkMorVNf5UzQCRtAVPk4o";
+
+fn fixture(name: &str) -> EncodedConfirmedTransactionWithStatusMeta {
+    let path = format!("{}/tests/fixtures/solana/{}", env!("CARGO_MANIFEST_DIR"), name);
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn leader() -> Pubkey {
+    Pubkey::from_str(LEADER).unwrap()
+}
+
+fn follow(allowed: &[&str], denied: &[&str]) -> CopyFollow {
+    CopyFollow {
+        id: Uuid::new_v4(),
+        user_id: 1,
+        leader_wallet: LEADER.to_string(),
+        mode: CopyMode::Fixed,
+        amount: BigDecimal::from_str("0.1").unwrap(),
+        max_amount_sol: BigDecimal::from_str("0.5").unwrap(),
+        allowed_mints: MintList(allowed.iter().map(|mint| mint.to_string()).collect()),
+        denied_mints: MintList(denied.iter().map(|mint| mint.to_string()).collect()),
+        delay_secs: 0,
+        max_slippage_bps: 150,
+        status: CopyFollowStatus::Active,
+        last_signature: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[test]
+fn jupiter_buy_is_decoded_from_balance_changes() {
+    let swap = decode_swap(&leader(), &fixture("jupiter_buy_tx.json")).expect("swap");
+
+    assert_eq!(swap.program, "Jupiter");
+    assert_eq!(swap.input_mint.to_string(), SOL_MINT);
+    assert_eq!(swap.output_mint.to_string(), BONK_MINT);
+    // Комиссия сети не входит в сумму свапа, балансы пула не учитываются
+    assert_eq!(swap.input_amount, 100_000_000);
+    assert_eq!(swap.output_amount, 3_500_000_000);
+    assert_eq!(swap.input_balance_before, 5_000_000_000);
+}
+
+#[test]
+fn raydium_sell_is_decoded_with_position_share() {
+    let swap = decode_swap(&leader(), &fixture("raydium_sell_tx.json")).expect("swap");
+
+    assert_eq!(swap.program, "Raydium AMM");
+    assert_eq!(swap.input_mint.to_string(), BONK_MINT);
+    assert_eq!(swap.output_mint.to_string(), SOL_MINT);
+    assert_eq!(swap.input_amount, 1_000_000_000);
+    assert_eq!(swap.input_balance_before, 2_000_000_000);
+    assert_eq!(swap.output_amount, 28_000_000);
+}
+
+#[test]
+fn failed_or_foreign_transactions_are_ignored() {
+    let mut failed = fixture("jupiter_buy_tx.json");
+    if let Some(meta) = failed.transaction.meta.as_mut() {
+        meta.err = Some(serde_json::from_str("\"AccountInUse\"").unwrap());
+    }
+    assert!(decode_swap(&leader(), &failed).is_none());
+
+    // Кошелек пула участвует в транзакции, но не подписывает ее
+    let pool = Pubkey::from_str("D5VNsNCrhGGWvFdG2733N4r18cLEwjnHffDsRpukpJVt").unwrap();
+    assert!(decode_swap(&pool, &fixture("jupiter_buy_tx.json")).is_none());
+
+    let mut no_balances = fixture("raydium_sell_tx.json");
+    if let Some(meta) = no_balances.transaction.meta.as_mut() {
+        meta.pre_token_balances = OptionSerializer::None;
+        meta.post_token_balances = OptionSerializer::None;
+    }
+    // Остается только приход SOL: это не свап
+    assert!(decode_swap(&leader(), &no_balances).is_none());
+}
+
+#[test]
+fn mint_lists_filter_swaps() {
+    let swap = decode_swap(&leader(), &fixture("jupiter_buy_tx.json")).unwrap();
+
+    assert_eq!(filter_reason(&follow(&[], &[]), &swap), None);
+    // SOL не требуется добавлять в список разрешенных
+    assert_eq!(filter_reason(&follow(&[BONK_MINT], &[]), &swap), None);
+    assert!(filter_reason(&follow(&[], &[BONK_MINT]), &swap).is_some());
+    assert!(filter_reason(&follow(&["EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"], &[]), &swap).is_some());
+}
+
+#[test]
+fn buy_size_is_fixed_or_proportional_and_capped() {
+    let max = BigDecimal::from_str("0.5").unwrap();
+
+    let fixed = BigDecimal::from_str("0.1").unwrap();
+    assert_eq!(buy_amount_lamports(CopyMode::Fixed, &fixed, &max, 2_000_000_000), Some(100_000_000));
+
+    let ratio = BigDecimal::from_str("0.1").unwrap();
+    assert_eq!(buy_amount_lamports(CopyMode::Proportional, &ratio, &max, 2_000_000_000), Some(200_000_000));
+    assert_eq!(buy_amount_lamports(CopyMode::Proportional, &ratio, &max, 9_000_000_000), Some(500_000_000));
+}
+
+#[test]
+fn sell_repeats_leader_position_share() {
+    assert_eq!(sell_amount(600, 1_000, 2_000), 300);
+    assert_eq!(sell_amount(600, 2_000, 2_000), 600);
+    assert_eq!(sell_amount(0, 1_000, 2_000), 0);
+}
+
+#[test]
+fn copy_commands_are_parsed() {
+    let Some(CopyCommand::Follow { wallet, mode, amount, max_amount_sol, delay_secs }) =
+        parse_copy_args(&format!("{} 10% max 0,5 delay 30", LEADER))
+    else {
+        panic!("expected follow command");
+    };
+    assert_eq!(wallet, leader());
+    assert_eq!(mode, CopyMode::Proportional);
+    assert_eq!(amount, BigDecimal::from_str("0.1").unwrap());
+    assert_eq!(max_amount_sol, Some(BigDecimal::from_str("0.5").unwrap()));
+    assert_eq!(delay_secs, 30);
+
+    assert_eq!(parse_copy_args("pause 1A2B"), Some(CopyCommand::Change(FollowAction::Pause, "1a2b".to_string())));
+    assert_eq!(
+        parse_copy_args("deny 1a2b bonk, SOL"),
+        Some(CopyCommand::Filter(MintFilter::Deny, "1a2b".to_string(), Some(vec![BONK_MINT.to_string(), SOL_MINT.to_string()]))),
+    );
+    assert_eq!(parse_copy_args("allow 1a2b off"), Some(CopyCommand::Filter(MintFilter::Allow, "1a2b".to_string(), None)));
+
+    assert!(parse_copy_args("not-a-wallet 0.1").is_none());
+    assert!(parse_copy_args(&format!("{} 0.1 delay", LEADER)).is_none());
+}
@@ -0,0 +1,59 @@
+use bigdecimal::BigDecimal;
+use std::str::FromStr;
+
+use solana_trading_bot::{
+    solana::constants::BONK_MINT,
+    telegram::handlers::dca::{DcaAction, DcaCommand, format_interval, parse_dca_args, parse_interval},
+};
+
+#[test]
+fn intervals_are_parsed_in_seconds() {
+    assert_eq!(parse_interval("30m"), Some(1_800));
+    assert_eq!(parse_interval("6h"), Some(21_600));
+    assert_eq!(parse_interval("1D"), Some(86_400));
+    assert_eq!(parse_interval("2w"), Some(1_209_600));
+
+    assert_eq!(parse_interval("0h"), None);
+    assert_eq!(parse_interval("12"), None);
+    assert_eq!(parse_interval("h"), None);
+    assert_eq!(parse_interval("5y"), None);
+}
+
+#[test]
+fn intervals_are_formatted_in_largest_unit() {
+    assert_eq!(format_interval(1_800), "30m");
+    assert_eq!(format_interval(21_600), "6h");
+    assert_eq!(format_interval(172_800), "2d");
+    assert_eq!(format_interval(604_800), "1w");
+}
+
+#[test]
+fn create_command_is_parsed() {
+    let Some(DcaCommand::Create { token, amount_sol, interval_secs, cycles }) = parse_dca_args("bonk 0,1 1d 30") else {
+        panic!("expected create command");
+    };
+
+    assert_eq!(token.mint.to_string(), BONK_MINT);
+    assert_eq!(amount_sol, BigDecimal::from_str("0.1").unwrap());
+    assert_eq!(interval_secs, 86_400);
+    assert_eq!(cycles, 30);
+}
+
+#[test]
+fn schedule_actions_are_parsed() {
+    assert!(matches!(parse_dca_args(""), Some(DcaCommand::List)));
+    assert!(matches!(
+        parse_dca_args("pause 1A2B"),
+        Some(DcaCommand::Change(DcaAction::Pause, id)) if id == "1a2b"
+    ));
+    assert!(matches!(parse_dca_args("resume 1a2b"), Some(DcaCommand::Change(DcaAction::Resume, _))));
+    assert!(matches!(parse_dca_args("cancel 1a2b"), Some(DcaCommand::Change(DcaAction::Cancel, _))));
+}
+
+#[test]
+fn invalid_arguments_are_rejected() {
+    assert!(parse_dca_args("stop 1a2b").is_none());
+    assert!(parse_dca_args("bonk 0 1d 30").is_none());
+    assert!(parse_dca_args("bonk 0.1 1d 0").is_none());
+    assert!(parse_dca_args("bonk 0.1 daily 30").is_none());
+}
@@ -0,0 +1,208 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use bigdecimal::BigDecimal;
+use chrono::Utc;
+use secrecy::SecretString;
+use serde_json::json;
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    message::{Message, VersionedMessage},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::VersionedTransaction,
+};
+use uuid::Uuid;
+use wiremock::{Mock, MockServer, ResponseTemplate, matchers::{method, path}};
+
+use solana_trading_bot::{
+    automation::{TradeOutcome, settle_trade},
+    config::settings::{default_priority_fee, default_trading_limits},
+    database::{
+        repositories::{FakeTradeRepository, FakeUserRepository, FakeWalletRepository, Repositories, TradeRepository},
+        trade_events::NewTradeEvent,
+    },
+    entities::{
+        trade_events::TradeEventKind,
+        trades::{TradeStatus, TradeType},
+        wallets::{Model as Wallet, WalletType},
+    },
+    jupiter::{JupiterClient, QuoteResponseV6},
+    monitoring::metrics::MetricsRegistry,
+    security::{
+        encryption::AesGcmEncryption,
+        secrets_manager::{BotSecrets, SecretsManager},
+    },
+    solana::{
+        constants::TokenMeta,
+        executor::{SwapExecutor, SwapOrder},
+        priority_fee::PriorityFeeEstimator,
+        rpc::{FakeLanding, FakeRpc},
+        trader::Trader,
+    },
+};
+
+const USER_ID: i64 = 42;
+const MASTER_KEY: &str = "executor-test-master-key";
+const LAST_VALID_BLOCK_HEIGHT: u64 = 279_632_475;
+
+fn quote() -> QuoteResponseV6 {
+    let path = format!("{}/tests/fixtures/jupiter/quote_exact_in.json", env!("CARGO_MANIFEST_DIR"));
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn secret(value: &str) -> SecretString {
+    SecretString::new(value.to_string().into_boxed_str())
+}
+
+fn secrets() -> SecretsManager {
+    SecretsManager::from_secrets(BotSecrets {
+        telegram_token: secret("token"),
+        jupiter_api_key: None,
+        master_encryption_key: secret(MASTER_KEY),
+        session_secret_key: secret("session"),
+    })
+}
+
+/// Кошелек по умолчанию с ключом, зашифрованным мастер-ключом теста
+fn wallet(keypair: &Keypair) -> Wallet {
+    let encryption = AesGcmEncryption::new(&secret(MASTER_KEY)).unwrap();
+    let now = Utc::now();
+    Wallet {
+        id: Uuid::new_v4(),
+        user_id: USER_ID,
+        public_key: keypair.pubkey().to_string(),
+        encrypted_private_key: BASE64.encode(encryption.encrypt(&keypair.to_bytes()).unwrap()),
+        wallet_type: WalletType::Hot,
+        name: "Main".to_string(),
+        is_default: true,
+        is_active: true,
+        balance_sol: BigDecimal::from(0),
+        notify_balance: false,
+        last_synced_at: now,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+/// Jupiter отдает неподписанную транзакцию, где плательщик — кошелек пользователя
+async fn jupiter(payer: &Pubkey) -> MockServer {
+    let instruction = Instruction::new_with_bytes(Pubkey::new_unique(), &[1], vec![]);
+    let message = Message::new_with_blockhash(&[instruction], Some(payer), &Hash::new_unique());
+    let unsigned = VersionedTransaction {
+        signatures: vec![Signature::default()],
+        message: VersionedMessage::Legacy(message),
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/swap/v6/swap"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "swapTransaction": BASE64.encode(bincode::serialize(&unsigned).unwrap()),
+            "lastValidBlockHeight": LAST_VALID_BLOCK_HEIGHT,
+            "prioritizationFeeLamports": 0,
+        })))
+        .mount(&server)
+        .await;
+    server
+}
+
+struct Harness {
+    executor: SwapExecutor,
+    trades: Arc<FakeTradeRepository>,
+    rpc: Arc<FakeRpc>,
+    _jupiter: MockServer,
+}
+
+async fn harness() -> Harness {
+    let keypair = Keypair::new();
+    let jupiter = jupiter(&keypair.pubkey()).await;
+
+    let wallets = Arc::new(FakeWalletRepository::new());
+    wallets.insert(wallet(&keypair));
+    let trades = Arc::new(FakeTradeRepository::new());
+    let repositories = Repositories {
+        users: Arc::new(FakeUserRepository::new()),
+        wallets,
+        trades: trades.clone(),
+    };
+
+    let rpc = Arc::new(FakeRpc::new());
+    rpc.set_block_height(LAST_VALID_BLOCK_HEIGHT - 100, 1);
+    let trader = Trader::new(JupiterClient::new(&jupiter.uri(), None), rpc.clone(), default_trading_limits())
+        .with_rebroadcast_interval(Duration::from_millis(1));
+    let executor = SwapExecutor::new(
+        repositories,
+        Arc::new(secrets()),
+        Arc::new(MetricsRegistry::new()),
+        Arc::new(trader),
+        Arc::new(PriorityFeeEstimator::new(rpc.clone(), default_priority_fee())),
+    );
+
+    Harness { executor, trades, rpc, _jupiter: jupiter }
+}
+
+fn order(quote: &QuoteResponseV6) -> SwapOrder<'_> {
+    SwapOrder {
+        user_id: USER_ID,
+        trade_type: TradeType::Buy,
+        quote,
+        input: TokenMeta { symbol: "SOL".to_string(), decimals: 9 },
+        output: TokenMeta { symbol: "USDC".to_string(), decimals: 6 },
+        jupiter_quote_id: None,
+        exit: false,
+    }
+}
+
+#[tokio::test]
+async fn recorded_swap_is_sent_and_completed() {
+    let harness = harness().await;
+    harness.rpc.set_landing(FakeLanding { after_sends: 1, error: None });
+    let quote = quote();
+
+    let recorded = harness.executor.record(order(&quote)).await.unwrap();
+    let trade_id = recorded.trade.id;
+    assert_eq!(recorded.trade.status, TradeStatus::Pending);
+    assert_eq!(harness.trades.find(trade_id).await.unwrap().unwrap().status, TradeStatus::Pending);
+
+    let executed = harness.executor.send(recorded).await.unwrap();
+    assert_eq!(executed.trade_id, trade_id);
+    assert_eq!(harness.rpc.sent().len(), 1);
+
+    let trade = harness.trades.find(trade_id).await.unwrap().unwrap();
+    assert_eq!(trade.status, TradeStatus::Completed);
+    assert!(trade.simulation.is_some());
+    let kinds: Vec<_> = harness.trades.timeline(trade_id).await.unwrap().into_iter().map(|event| event.kind).collect();
+    assert_eq!(kinds, vec![
+        TradeEventKind::QuoteReceived,
+        TradeEventKind::Signed,
+        TradeEventKind::Sent,
+        TradeEventKind::Confirmed,
+    ]);
+    assert_eq!(harness.trades.last_valid_block_height(trade_id).await.unwrap(), Some(LAST_VALID_BLOCK_HEIGHT));
+
+    // Завершенную сделку сверка не трогает
+    assert!(matches!(settle_trade(&harness.executor, trade_id).await.unwrap(), TradeOutcome::Landed));
+}
+
+#[tokio::test]
+async fn settle_completes_sent_trade_found_in_history() {
+    let harness = harness().await;
+    let quote = quote();
+
+    // Сбой после перехода в исполнение: транзакция ушла, итог не записан
+    let recorded = harness.executor.record(order(&quote)).await.unwrap();
+    let trade_id = recorded.trade.id;
+    let signature: Signature = recorded.trade.transaction_signature.parse().unwrap();
+    let event = NewTradeEvent::new(TradeEventKind::Sent)
+        .with_details(json!({"last_valid_block_height": LAST_VALID_BLOCK_HEIGHT}));
+    harness.trades.transition(&recorded.trade, TradeStatus::Executing, event).await.unwrap().unwrap();
+
+    assert!(matches!(settle_trade(&harness.executor, trade_id).await.unwrap(), TradeOutcome::Pending));
+
+    harness.rpc.set_historical_status(signature, FakeRpc::confirmed_status(LAST_VALID_BLOCK_HEIGHT - 90, None));
+    assert!(matches!(settle_trade(&harness.executor, trade_id).await.unwrap(), TradeOutcome::Landed));
+    assert_eq!(harness.trades.find(trade_id).await.unwrap().unwrap().status, TradeStatus::Completed);
+}
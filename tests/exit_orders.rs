@@ -0,0 +1,66 @@
+use bigdecimal::BigDecimal;
+use std::str::FromStr;
+
+use solana_trading_bot::{
+    automation::exit_orders::evaluate,
+    entities::exit_orders::ExitKind,
+    solana::amount::{AmountError, TokenAmount},
+    telegram::handlers::exits::{ExitTarget, parse_exit_target, trigger_price},
+};
+
+fn dec(value: &str) -> BigDecimal {
+    BigDecimal::from_str(value).unwrap()
+}
+
+#[test]
+fn stop_loss_and_take_profit_trigger_on_their_side() {
+    assert!(evaluate(ExitKind::StopLoss, 0.9, None, None, 0.89).triggered);
+    assert!(!evaluate(ExitKind::StopLoss, 0.9, None, None, 0.95).triggered);
+
+    assert!(evaluate(ExitKind::TakeProfit, 1.25, None, None, 1.3).triggered);
+    assert!(!evaluate(ExitKind::TakeProfit, 1.25, None, None, 1.2).triggered);
+}
+
+#[test]
+fn trailing_stop_follows_new_highs() {
+    // Цена выросла с 1.0 до 2.0: стоп подтягивается до 1.8
+    let check = evaluate(ExitKind::TrailingStop, 0.9, Some(10.0), Some(1.0), 2.0);
+    assert!(!check.triggered);
+    assert_eq!(check.new_high, Some(2.0));
+    assert!((check.trigger_price - 1.8).abs() < 1e-12);
+
+    // Откат ниже уровня от прежнего максимума
+    let check = evaluate(ExitKind::TrailingStop, 1.8, Some(10.0), Some(2.0), 1.79);
+    assert!(check.triggered);
+    assert_eq!(check.new_high, None);
+}
+
+#[test]
+fn exit_targets_are_parsed() {
+    assert_eq!(parse_exit_target("10%"), Some(ExitTarget::Percent(dec("10"))));
+    assert_eq!(parse_exit_target("-7,5%"), Some(ExitTarget::Percent(dec("7.5"))));
+    assert_eq!(parse_exit_target("0.0042"), Some(ExitTarget::Price(dec("0.0042"))));
+    assert!(parse_exit_target("0%").is_none());
+    assert!(parse_exit_target("abc").is_none());
+}
+
+#[test]
+fn trigger_price_is_derived_from_entry() {
+    let entry = dec("0.002");
+    let pct = ExitTarget::Percent(dec("25"));
+
+    assert_eq!(trigger_price(ExitKind::StopLoss, &entry, &pct), Some(dec("0.0015")));
+    assert_eq!(trigger_price(ExitKind::TakeProfit, &entry, &pct), Some(dec("0.0025")));
+    assert_eq!(trigger_price(ExitKind::TrailingStop, &entry, &pct), Some(dec("0.0015")));
+
+    // Трейлинг задается только процентом, стоп не может быть ниже нуля
+    assert!(trigger_price(ExitKind::TrailingStop, &entry, &ExitTarget::Price(dec("0.001"))).is_none());
+    assert!(trigger_price(ExitKind::StopLoss, &entry, &ExitTarget::Percent(dec("100"))).is_none());
+}
+
+#[test]
+fn decimal_amounts_convert_to_base_units() {
+    assert_eq!(TokenAmount::from_big_decimal(&dec("1.5"), 6), Ok(TokenAmount::new(1_500_000, 6)));
+    assert_eq!(TokenAmount::from_big_decimal(&dec("0.0000000019"), 9), Ok(TokenAmount::sol(1)));
+    assert_eq!(TokenAmount::from_big_decimal(&dec("-1"), 9), Err(AmountError::NotPositive));
+}
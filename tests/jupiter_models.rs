@@ -0,0 +1,142 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_trading_bot::jupiter::{
+    PrioritizationFee, PriceResponse, QuoteResponseV6, SwapMode, SwapRequestV6, SwapResponseV6,
+    fees::{fee_mint, referral_fee_account},
+};
+use std::str::FromStr;
+
+const SOL: &str = "So11111111111111111111111111111111111111112";
+const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const JUP: &str = "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN";
+
+fn fixture(name: &str) -> String {
+    let path = format!("{}/tests/fixtures/jupiter/{}", env!("CARGO_MANIFEST_DIR"), name);
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("failed to read {}: {}", path, e))
+}
+
+fn pubkey(s: &str) -> Pubkey {
+    Pubkey::from_str(s).unwrap()
+}
+
+#[test]
+fn quote_exact_in_deserializes() {
+    let quote: QuoteResponseV6 = serde_json::from_str(&fixture("quote_exact_in.json")).unwrap();
+
+    assert_eq!(quote.input_mint, pubkey(SOL));
+    assert_eq!(quote.output_mint, pubkey(USDC));
+    assert_eq!(quote.in_amount, 100_000_000);
+    assert_eq!(quote.out_amount, 23_658_512);
+    assert_eq!(quote.other_amount_threshold, 23_540_220);
+    assert_eq!(quote.swap_mode, SwapMode::ExactIn);
+    assert_eq!(quote.slippage_bps, 50);
+    assert!(quote.platform_fee.is_none());
+    assert!((quote.price_impact_pct - 0.000_127_945_264_312_731_5).abs() < f64::EPSILON);
+    assert_eq!(quote.context_slot, Some(299_283_763));
+
+    assert_eq!(quote.route_plan.len(), 2);
+    let first = &quote.route_plan[0];
+    assert_eq!(first.percent, 60);
+    assert_eq!(first.swap_info.label.as_deref(), Some("Orca"));
+    assert_eq!(first.swap_info.fee_amount, 2_400);
+    assert_eq!(first.swap_info.fee_mint, pubkey(SOL));
+}
+
+#[test]
+fn quote_exact_out_deserializes_with_platform_fee() {
+    let quote: QuoteResponseV6 = serde_json::from_str(&fixture("quote_exact_out.json")).unwrap();
+
+    assert_eq!(quote.swap_mode, SwapMode::ExactOut);
+    assert_eq!(quote.output_mint, pubkey(JUP));
+    assert_eq!(quote.out_amount, 1_000_000);
+    // Для ExactOut порог — это максимальный вход
+    assert!(quote.other_amount_threshold > quote.in_amount);
+
+    let fee = quote.platform_fee.as_ref().unwrap();
+    assert_eq!(fee.amount, 2_000);
+    assert_eq!(fee.fee_bps, 20);
+}
+
+#[test]
+fn quote_round_trips_in_wire_format() {
+    let raw = fixture("quote_exact_in.json");
+    let quote: QuoteResponseV6 = serde_json::from_str(&raw).unwrap();
+
+    let value = serde_json::to_value(&quote).unwrap();
+    assert_eq!(value["inAmount"], "100000000");
+    assert_eq!(value["inputMint"], SOL);
+    assert_eq!(value["swapMode"], "ExactIn");
+    assert_eq!(value["routePlan"][1]["swapInfo"]["ammKey"], "58oQChx4yWmvKdwLLZzBi4ChoCc2fqCUWBkwMihLYQo2");
+
+    let again: QuoteResponseV6 = serde_json::from_value(value).unwrap();
+    assert_eq!(again.out_amount, quote.out_amount);
+    assert_eq!(again.route_plan.len(), quote.route_plan.len());
+}
+
+#[test]
+fn swap_response_deserializes() {
+    let swap: SwapResponseV6 = serde_json::from_str(&fixture("swap.json")).unwrap();
+
+    assert_eq!(swap.last_valid_block_height, 279_632_475);
+    assert_eq!(swap.prioritization_fee_lamports, Some(9_999));
+    assert!(!swap.swap_transaction.is_empty());
+}
+
+#[test]
+fn price_response_deserializes() {
+    let prices: PriceResponse = serde_json::from_str(&fixture("price.json")).unwrap();
+
+    let sol = prices.data[SOL].as_ref().unwrap();
+    assert_eq!(sol.token_type, "derivedPrice");
+    assert!((sol.price - 236.58512).abs() < 1e-9);
+    assert!(sol.price_change_24h.is_none());
+
+    // Токен без цены приходит как null
+    assert!(prices.data["11111111111111111111111111111111"].is_none());
+}
+
+#[test]
+fn invalid_amount_is_rejected() {
+    let raw = fixture("quote_exact_in.json").replace("\"100000000\"", "\"-1\"");
+    assert!(serde_json::from_str::<QuoteResponseV6>(&raw).is_err());
+}
+
+#[test]
+fn swap_request_includes_fee_account_only_when_set() {
+    let quote: QuoteResponseV6 = serde_json::from_str(&fixture("quote_exact_out.json")).unwrap();
+    let mut request = SwapRequestV6 {
+        quote_response: quote,
+        user_public_key: pubkey(USDC),
+        wrap_and_unwrap_sol: true,
+        dynamic_compute_unit_limit: true,
+        prioritization_fee_lamports: PrioritizationFee::Lamports(150_000),
+        fee_account: None,
+    };
+
+    let value = serde_json::to_value(&request).unwrap();
+    assert!(value.get("feeAccount").is_none());
+    assert_eq!(value["userPublicKey"], USDC);
+    assert_eq!(value["prioritizationFeeLamports"], 150_000);
+
+    // Без своей оценки комиссию считает Jupiter, но не выше потолка
+    request.prioritization_fee_lamports = PrioritizationFee::capped("veryHigh", 2_000_000);
+    let value = serde_json::to_value(&request).unwrap();
+    assert_eq!(
+        value["prioritizationFeeLamports"],
+        serde_json::json!({"priorityLevelWithMaxLamports": {"priorityLevel": "veryHigh", "maxLamports": 2_000_000}})
+    );
+
+    let referral = pubkey(JUP);
+    let fee_account = referral_fee_account(&referral, &pubkey(SOL));
+    request.fee_account = Some(fee_account);
+
+    let value = serde_json::to_value(&request).unwrap();
+    assert_eq!(value["feeAccount"], fee_account.to_string());
+}
+
+#[test]
+fn platform_fee_is_taken_from_output_for_exact_in_and_input_for_exact_out() {
+    let (sol, usdc) = (pubkey(SOL), pubkey(USDC));
+
+    assert_eq!(fee_mint(&sol, &usdc, SwapMode::ExactIn), usdc);
+    assert_eq!(fee_mint(&sol, &usdc, SwapMode::ExactOut), sol);
+}
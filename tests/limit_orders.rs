@@ -0,0 +1,51 @@
+use bigdecimal::BigDecimal;
+use std::str::FromStr;
+
+use solana_trading_bot::{
+    automation::limit_orders::is_triggered,
+    entities::limit_orders::OrderSide,
+    solana::constants::BONK_MINT,
+    telegram::handlers::limit::{LimitCommand, parse_limit_args},
+};
+
+#[test]
+fn buy_triggers_at_or_below_target_and_sell_at_or_above() {
+    assert!(is_triggered(OrderSide::Buy, 0.9, 1.0));
+    assert!(is_triggered(OrderSide::Buy, 1.0, 1.0));
+    assert!(!is_triggered(OrderSide::Buy, 1.1, 1.0));
+
+    assert!(is_triggered(OrderSide::Sell, 1.1, 1.0));
+    assert!(is_triggered(OrderSide::Sell, 1.0, 1.0));
+    assert!(!is_triggered(OrderSide::Sell, 0.9, 1.0));
+}
+
+#[test]
+fn create_command_is_parsed() {
+    let Some(LimitCommand::Create { side, token, amount, target_price, expiry_hours }) =
+        parse_limit_args("buy bonk 0,5 0.00002 24")
+    else {
+        panic!("expected create command");
+    };
+
+    assert_eq!(side, OrderSide::Buy);
+    assert_eq!(token.mint.to_string(), BONK_MINT);
+    assert_eq!(amount, BigDecimal::from_str("0.5").unwrap());
+    assert_eq!(target_price, BigDecimal::from_str("0.00002").unwrap());
+    assert_eq!(expiry_hours, Some(24));
+}
+
+#[test]
+fn list_and_cancel_commands_are_parsed() {
+    assert!(matches!(parse_limit_args(""), Some(LimitCommand::List)));
+    assert!(matches!(parse_limit_args("cancel 1A2B3C4D"), Some(LimitCommand::Cancel(id)) if id == "1a2b3c4d"));
+}
+
+#[test]
+fn invalid_commands_are_rejected() {
+    assert!(parse_limit_args("hold BONK 1 1").is_none());
+    assert!(parse_limit_args("buy BONK 0 1").is_none());
+    assert!(parse_limit_args("sell BONK 1 -2").is_none());
+    assert!(parse_limit_args("buy UNKNOWN 1 1").is_none());
+    assert!(parse_limit_args("buy BONK 1 1 0").is_none());
+    assert!(parse_limit_args("buy BONK 1 1 24 extra").is_none());
+}
@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+
+use bigdecimal::BigDecimal;
+use chrono::Utc;
+use migration::{Migrator, MigratorTrait};
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, IdenStatic,
+    Iterable, Set, Statement,
+};
+use secrecy::SecretString;
+use testcontainers::{ContainerAsync, runners::AsyncRunner};
+use testcontainers_modules::postgres::Postgres;
+use uuid::Uuid;
+
+use solana_trading_bot::{
+    config::settings::DatabaseSettings,
+    database::{DatabaseConnectionPool, trades::{self as trade_queries, NewTrade}},
+    entities::{
+        CopyFollows, CopyTrades, DcaSchedules, ExitOrders, FeePreferences, LimitOrders, PriceAlerts, SniperDecisions,
+        SniperSubscriptions, Tokens, TradeEvents, Trades, Users, Wallets,
+        fee_preferences::PriorityTier,
+        trades::{self, TradeStatus, TradeType},
+        users,
+        wallets::{self, WalletType},
+    },
+    jupiter::QuoteResponseV6,
+};
+
+fn settings(url: String) -> DatabaseSettings {
+    DatabaseSettings {
+        url: SecretString::new(url.into_boxed_str()),
+        pool_max_connections: 5,
+        pool_min_connections: 1,
+        connect_timeout_secs: 30,
+        acquire_timeout_secs: 30,
+        idle_timeout_secs: 600,
+        max_lifetime_secs: 1800,
+    }
+}
+
+async fn start_postgres() -> (ContainerAsync<Postgres>, DatabaseConnectionPool) {
+    let container = Postgres::default().start().await.unwrap();
+    let url = format!(
+        "postgres://postgres:postgres@{}:{}/postgres",
+        container.get_host().await.unwrap(),
+        container.get_host_port_ipv4(5432).await.unwrap(),
+    );
+    let database = DatabaseConnectionPool::connect(&settings(url)).await.unwrap();
+    (container, database)
+}
+
+/// Колонки таблицы в БД: имя и допускает ли NULL
+async fn table_columns(db: &DatabaseConnection, table: &str) -> BTreeMap<String, bool> {
+    db.query_all(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT column_name, is_nullable FROM information_schema.columns WHERE table_schema = 'public' AND table_name = $1",
+        [table.into()],
+    ))
+    .await
+    .unwrap()
+    .into_iter()
+    .map(|row| {
+        let name: String = row.try_get("", "column_name").unwrap();
+        let nullable: String = row.try_get("", "is_nullable").unwrap();
+        (name, nullable == "YES")
+    })
+    .collect()
+}
+
+async fn assert_matches_entity<E: EntityTrait>(db: &DatabaseConnection, entity: E) {
+    let expected: BTreeMap<String, bool> = E::Column::iter()
+        .map(|column| (column.as_str().to_string(), column.def().is_null()))
+        .collect();
+
+    assert_eq!(table_columns(db, entity.table_name()).await, expected, "table {}", entity.table_name());
+}
+
+#[tokio::test]
+#[ignore = "нужен Docker"]
+async fn migrated_schema_matches_entities() {
+    let (_container, database) = start_postgres().await;
+
+    // Реплики, стартующие одновременно, не мешают друг другу
+    let (first, second) = tokio::join!(database.run_migrations(), database.run_migrations());
+    first.unwrap();
+    second.unwrap();
+    assert!(database.migration_status().await.unwrap().iter().all(|migration| migration.applied));
+
+    let db = database.get_connection();
+    assert_matches_entity(db, Users).await;
+    assert_matches_entity(db, Trades).await;
+    assert_matches_entity(db, Wallets).await;
+    assert_matches_entity(db, LimitOrders).await;
+    assert_matches_entity(db, ExitOrders).await;
+    assert_matches_entity(db, DcaSchedules).await;
+    assert_matches_entity(db, CopyFollows).await;
+    assert_matches_entity(db, CopyTrades).await;
+    assert_matches_entity(db, PriceAlerts).await;
+    assert_matches_entity(db, SniperSubscriptions).await;
+    assert_matches_entity(db, SniperDecisions).await;
+    assert_matches_entity(db, FeePreferences).await;
+    assert_matches_entity(db, Tokens).await;
+    assert_matches_entity(db, TradeEvents).await;
+
+    // Все миграции откатываются и применяются заново
+    let total = database.migration_status().await.unwrap().len() as u32;
+    database.rollback_migrations(total).await.unwrap();
+    assert!(database.migration_status().await.unwrap().iter().all(|migration| !migration.applied));
+    database.run_migrations().await.unwrap();
+    assert_matches_entity(db, Trades).await;
+}
+
+async fn insert_user(db: &DatabaseConnection, id: i64) {
+    let now = Utc::now();
+    users::ActiveModel {
+        id: Set(id),
+        telegram_username: Set(None),
+        first_name: Set("Test".to_string()),
+        last_name: Set(None),
+        language_code: Set(None),
+        is_premium: Set(None),
+        is_admin: Set(false),
+        is_active: Set(true),
+        daily_trade_limit: Set(Decimal::ONE_HUNDRED),
+        total_trades: Set(0),
+        total_volume_sol: Set(Decimal::ZERO),
+        created_at: Set(now),
+        updated_at: Set(now),
+        last_active_at: Set(now),
+    }
+    .insert(db)
+    .await
+    .unwrap();
+}
+
+fn quote() -> QuoteResponseV6 {
+    let path = format!("{}/tests/fixtures/jupiter/quote_exact_in.json", env!("CARGO_MANIFEST_DIR"));
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[tokio::test]
+#[ignore = "нужен Docker"]
+async fn trade_and_wallet_enums_round_trip() {
+    let (_container, database) = start_postgres().await;
+    database.run_migrations().await.unwrap();
+    let db = database.get_connection();
+    insert_user(db, 1).await;
+
+    let quote = quote();
+    for trade_type in TradeType::iter() {
+        let trade = trade_queries::insert_pending(db, NewTrade {
+            user_id: 1,
+            trade_type: trade_type.clone(),
+            quote: &quote,
+            input_symbol: "SOL".to_string(),
+            output_symbol: "USDC".to_string(),
+            input_decimals: 9,
+            output_decimals: 6,
+            transaction_signature: Uuid::new_v4().to_string(),
+            jupiter_quote_id: None,
+            priority_fee_lamports: 5_000,
+            priority_tier: PriorityTier::Medium,
+        }).await.unwrap();
+
+        for status in TradeStatus::iter() {
+            let mut model: trades::ActiveModel = trade.clone().into();
+            model.status = Set(status.clone());
+            model.update(db).await.unwrap();
+
+            let stored = trades::Entity::find_by_id(trade.id).one(db).await.unwrap().unwrap();
+            assert_eq!((stored.trade_type, stored.status), (trade_type.clone(), status));
+        }
+    }
+
+    for wallet_type in WalletType::iter() {
+        let now = Utc::now();
+        let wallet = wallets::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            user_id: Set(1),
+            public_key: Set(Uuid::new_v4().to_string()),
+            encrypted_private_key: Set(String::new()),
+            wallet_type: Set(wallet_type.clone()),
+            name: Set("Main".to_string()),
+            is_default: Set(false),
+            is_active: Set(true),
+            balance_sol: Set(BigDecimal::from(0)),
+            notify_balance: Set(false),
+            last_synced_at: Set(now),
+            created_at: Set(now),
+            updated_at: Set(now),
+        }
+        .insert(db)
+        .await
+        .unwrap();
+
+        let stored = wallets::Entity::find_by_id(wallet.id).one(db).await.unwrap().unwrap();
+        assert_eq!(stored.wallet_type, wallet_type);
+    }
+
+    // Значения вне перечисления отклоняет CHECK
+    assert!(db.execute_unprepared("UPDATE trades SET status = 'DONE'").await.is_err());
+}
+
+#[tokio::test]
+#[ignore = "нужен Docker"]
+async fn single_letter_codes_are_converted() {
+    let (_container, database) = start_postgres().await;
+    let db = database.get_connection();
+    // Схема до перевода перечислений в varchar(16)
+    let widen = Migrator::migrations()
+        .iter()
+        .position(|migration| migration.name() == "m20260126_000001_widen_trade_and_wallet_enums")
+        .unwrap() as u32;
+    Migrator::up(db, Some(widen)).await.unwrap();
+    insert_user(db, 1).await;
+
+    db.execute_unprepared(
+        "INSERT INTO wallets (id, user_id, public_key, encrypted_private_key, wallet_type, name) \
+         VALUES (gen_random_uuid(), 1, 'key', '', 'L', 'Ledger')",
+    ).await.unwrap();
+    db.execute_unprepared(
+        "INSERT INTO trades (id, user_id, trade_type, input_mint, output_mint, input_amount, output_amount, \
+         input_symbol, output_symbol, price, slippage_bps, transaction_signature, status) \
+         VALUES (gen_random_uuid(), 1, 'b', 'a', 'b', 1, 1, 'SOL', 'USDC', 1, 50, 'sig', 'F')",
+    ).await.unwrap();
+
+    database.run_migrations().await.unwrap();
+
+    let trade = trades::Entity::find().one(db).await.unwrap().unwrap();
+    assert_eq!((trade.trade_type, trade.status), (TradeType::Buy, TradeStatus::Failed));
+    let wallet = wallets::Entity::find().one(db).await.unwrap().unwrap();
+    assert_eq!(wallet.wallet_type, WalletType::Ledger);
+}
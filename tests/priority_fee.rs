@@ -0,0 +1,112 @@
+use std::str::FromStr;
+
+use solana_client::rpc_response::RpcPrioritizationFee;
+use solana_sdk::{
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::{Message, VersionedMessage},
+    pubkey::Pubkey,
+    transaction::VersionedTransaction,
+};
+
+use solana_trading_bot::{
+    config::settings::default_priority_fee,
+    entities::fee_preferences::PriorityTier,
+    solana::priority_fee::{
+        COMPUTE_BUDGET_PROGRAM, FeeLevels, fee_cap, fee_lamports, paid_priority_fee, percentile,
+    },
+    telegram::handlers::fee::{FeeCommand, parse_fee_args},
+};
+
+fn read_fixture(name: &str) -> String {
+    let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+    std::fs::read_to_string(path).unwrap()
+}
+
+/// Неподписанная транзакция с заданными инструкциями; подписи для разбора не нужны
+fn transaction(instructions: &[Instruction]) -> VersionedTransaction {
+    let payer = Pubkey::new_unique();
+    let message = Message::new_with_blockhash(instructions, Some(&payer), &Hash::default());
+    VersionedTransaction {
+        signatures: vec![Default::default()],
+        message: VersionedMessage::Legacy(message),
+    }
+}
+
+fn compute_budget(data: Vec<u8>) -> Instruction {
+    Instruction::new_with_bytes(Pubkey::from_str(COMPUTE_BUDGET_PROGRAM).unwrap(), &data, vec![])
+}
+
+fn swap_instruction() -> Instruction {
+    Instruction::new_with_bytes(Pubkey::new_unique(), &[1, 2, 3], vec![AccountMeta::new(Pubkey::new_unique(), false)])
+}
+
+#[test]
+fn percentile_uses_nearest_rank() {
+    let samples = [10, 20, 30, 40];
+    assert_eq!(percentile(&samples, 25.0), 10);
+    assert_eq!(percentile(&samples, 50.0), 20);
+    assert_eq!(percentile(&samples, 75.0), 30);
+    assert_eq!(percentile(&samples, 95.0), 40);
+    assert_eq!(percentile(&samples, 0.0), 10);
+    assert_eq!(percentile(&[], 50.0), 0);
+}
+
+#[test]
+fn fee_levels_are_built_from_recent_fees() {
+    let fees: Vec<RpcPrioritizationFee> =
+        serde_json::from_str(&read_fixture("solana/recent_prioritization_fees.json")).unwrap();
+    let levels = FeeLevels::from_samples(&fees);
+
+    // Слоты без комиссий тянут нижние уровни вниз, выброс влияет только на turbo
+    assert_eq!(levels, FeeLevels { low: 1_000, medium: 10_000, high: 50_000, turbo: 1_500_000 });
+    assert_eq!(levels.price(PriorityTier::High), 50_000);
+    assert!(PriorityTier::ALL.windows(2).all(|pair| levels.price(pair[0]) <= levels.price(pair[1])));
+}
+
+#[test]
+fn fee_is_converted_to_lamports_and_capped() {
+    // 10_000 микролампортов × 300_000 единиц = 3_000 лампортов
+    assert_eq!(fee_lamports(10_000, 300_000), 3_000);
+    // Доли лампорта округляются вверх
+    assert_eq!(fee_lamports(1, 1), 1);
+    assert_eq!(fee_lamports(0, 1_400_000), 0);
+
+    let settings = default_priority_fee();
+    assert_eq!(fee_cap(None, &settings), settings.default_max_lamports);
+    assert_eq!(fee_cap(Some(50_000), &settings), 50_000);
+    assert_eq!(fee_cap(Some(u64::MAX), &settings), settings.max_lamports);
+}
+
+#[test]
+fn paid_fee_is_read_from_compute_budget_instructions() {
+    let mut limit = vec![2];
+    limit.extend_from_slice(&400_000u32.to_le_bytes());
+    let mut price = vec![3];
+    price.extend_from_slice(&25_000u64.to_le_bytes());
+
+    let tx = transaction(&[compute_budget(limit), compute_budget(price.clone()), swap_instruction()]);
+    assert_eq!(paid_priority_fee(&tx), 10_000);
+
+    // Без лимита берется 200_000 единиц на каждую инструкцию, кроме ComputeBudget
+    let tx = transaction(&[compute_budget(price), swap_instruction(), swap_instruction()]);
+    assert_eq!(paid_priority_fee(&tx), 10_000);
+
+    // Без цены приоритетной комиссии нет
+    assert_eq!(paid_priority_fee(&transaction(&[swap_instruction()])), 0);
+}
+
+#[test]
+fn fee_command_is_parsed() {
+    assert_eq!(parse_fee_args(""), Some(FeeCommand::Show));
+    assert_eq!(parse_fee_args("turbo"), Some(FeeCommand::Set { tier: PriorityTier::Turbo, max_lamports: None }));
+    assert_eq!(
+        parse_fee_args("HIGH 0,005"),
+        Some(FeeCommand::Set { tier: PriorityTier::High, max_lamports: Some(5_000_000) })
+    );
+
+    assert_eq!(parse_fee_args("fast"), None);
+    assert_eq!(parse_fee_args("low 0"), None);
+    assert_eq!(parse_fee_args("low -1"), None);
+    assert_eq!(parse_fee_args("low 0.1 extra"), None);
+}
@@ -0,0 +1,149 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use solana_client::rpc_response::{
+    ProcessedSignatureResult, ReceivedSignatureResult, Response, RpcLogsResponse, RpcResponseContext,
+    RpcSignatureResult,
+};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{
+    hash::Hash,
+    instruction::{Instruction, InstructionError},
+    message::{Message, VersionedMessage},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::{TransactionError, VersionedTransaction},
+};
+
+use solana_trading_bot::{
+    automation::balance::{balance_change_text, should_notify},
+    config::settings::default_solana,
+    solana::{
+        pubsub::{PubsubError, PubsubEvent, PubsubManager, PubsubTopic, logs_event, reconnect_delay, signature_event},
+        rpc::{FakeLanding, FakeRpc},
+        sender::TransactionSender,
+    },
+    telegram::handlers::notify::parse_notify_args,
+};
+
+fn response<T>(slot: u64, value: T) -> Response<T> {
+    Response { context: RpcResponseContext { slot, api_version: None }, value }
+}
+
+#[test]
+fn processed_signature_becomes_event() {
+    let signature = Signature::new_unique();
+    let error = TransactionError::InstructionError(1, InstructionError::Custom(6001));
+
+    let landed = response(300, RpcSignatureResult::ProcessedSignature(ProcessedSignatureResult { err: None }));
+    assert_eq!(
+        signature_event(signature, landed),
+        Some(PubsubEvent::Signature { signature, slot: 300, error: None }),
+    );
+
+    let failed = response(301, RpcSignatureResult::ProcessedSignature(ProcessedSignatureResult {
+        err: Some(error.clone().into()),
+    }));
+    assert_eq!(
+        signature_event(signature, failed),
+        Some(PubsubEvent::Signature { signature, slot: 301, error: Some(error) }),
+    );
+}
+
+#[test]
+fn received_signature_notification_is_ignored() {
+    let received = response(300, RpcSignatureResult::ReceivedSignature(ReceivedSignatureResult::ReceivedSignature));
+    assert_eq!(signature_event(Signature::new_unique(), received), None);
+}
+
+#[test]
+fn logs_notification_becomes_event() {
+    let address = Pubkey::new_unique();
+    let logs = response(42, RpcLogsResponse {
+        signature: "5h6xBEauJ3PK6SWCZ1PGjBvj8vDdWG3KpwATGy1ARAXFSDwt8GFXM7W5Ncn16wmqokgpiKRLuS83KUxyZyv2sUYv".to_string(),
+        err: Some(TransactionError::AccountNotFound.into()),
+        logs: vec!["Program log: Instruction: Swap".to_string()],
+    });
+
+    let PubsubEvent::Logs { address: event_address, failed, logs, .. } = logs_event(address, logs) else {
+        panic!("expected logs event");
+    };
+    assert_eq!(event_address, address);
+    assert!(failed);
+    assert_eq!(logs, vec!["Program log: Instruction: Swap".to_string()]);
+}
+
+#[test]
+fn reconnect_delay_grows_up_to_limit() {
+    let max = Duration::from_secs(30);
+
+    assert_eq!(reconnect_delay(0, max), Duration::from_secs(1));
+    assert_eq!(reconnect_delay(1, max), Duration::from_secs(2));
+    assert_eq!(reconnect_delay(4, max), Duration::from_secs(16));
+    assert_eq!(reconnect_delay(5, max), max);
+    assert_eq!(reconnect_delay(100, max), max);
+}
+
+fn unreachable_pubsub(max_subscriptions: usize) -> Arc<PubsubManager> {
+    let mut settings = default_solana();
+    settings.ws_url = "ws://127.0.0.1:1".to_string();
+    settings.pubsub_max_subscriptions = max_subscriptions;
+    PubsubManager::start(&settings, CommitmentConfig::confirmed())
+}
+
+#[tokio::test]
+async fn subscriptions_are_limited_and_released_on_drop() {
+    let pubsub = unreachable_pubsub(1);
+    let (sender, _events) = pubsub.channel();
+
+    let first = pubsub.subscribe(PubsubTopic::Account(Pubkey::new_unique()), sender.clone()).unwrap();
+    let second = pubsub.subscribe(PubsubTopic::Account(Pubkey::new_unique()), sender.clone());
+    assert!(matches!(second, Err(PubsubError::TooManySubscriptions(1))));
+
+    drop(first);
+    assert!(pubsub.subscribe(PubsubTopic::Account(Pubkey::new_unique()), sender).is_ok());
+}
+
+#[tokio::test]
+async fn sender_falls_back_to_polling_without_push_notifications() {
+    let rpc = Arc::new(FakeRpc::new());
+    rpc.set_block_height(1_000, 1);
+    rpc.set_landing(FakeLanding { after_sends: 2, error: None });
+
+    let payer = Keypair::new();
+    let instruction = Instruction::new_with_bytes(Pubkey::new_unique(), &[1], vec![]);
+    let message = Message::new_with_blockhash(&[instruction], Some(&payer.pubkey()), &Hash::new_unique());
+    let transaction = VersionedTransaction::try_new(VersionedMessage::Legacy(message), &[&payer]).unwrap();
+
+    let sender = TransactionSender::new(rpc.clone(), Duration::from_millis(1))
+        .with_pubsub(Some(unreachable_pubsub(10)));
+    let signature = sender.send(&transaction, 1_150).await.unwrap();
+
+    assert_eq!(signature, transaction.signatures[0]);
+}
+
+#[test]
+fn small_balance_changes_are_not_notified() {
+    assert!(!should_notify(1_000_000_000, 1_000_000_000, 1_000_000));
+    assert!(!should_notify(1_000_000_000, 999_995_000, 1_000_000));
+    assert!(should_notify(1_000_000_000, 1_500_000_000, 1_000_000));
+    assert!(should_notify(1_000_000_000, 0, 1_000_000));
+}
+
+#[test]
+fn balance_change_text_shows_direction_and_new_balance() {
+    let text = balance_change_text("Main", 1_000_000_000, 1_250_000_000);
+    assert!(text.contains("+0.2500 SOL"));
+    assert!(text.contains("Теперь: 1.2500 SOL"));
+
+    let text = balance_change_text("Main", 1_000_000_000, 400_000_000);
+    assert!(text.contains("-0.6000 SOL"));
+}
+
+#[test]
+fn notify_args() {
+    assert_eq!(parse_notify_args("on"), Some(true));
+    assert_eq!(parse_notify_args(" OFF "), Some(false));
+    assert_eq!(parse_notify_args(""), None);
+    assert_eq!(parse_notify_args("maybe"), None);
+}
@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use solana_sdk::pubkey::Pubkey;
+use solana_trading_bot::{
+    config::settings::{TradingLimits, default_trading_limits},
+    jupiter::QuoteResponseV6,
+    telegram::preview::{
+        PriceImpactLevel, TokenMeta, effective_price, mid_price, price_impact_level, render_quote_preview,
+        render_route, route_hops, total_fees_usd,
+    },
+};
+
+const SOL: &str = "So11111111111111111111111111111111111111112";
+const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const JUP: &str = "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN";
+
+fn fixture(name: &str) -> QuoteResponseV6 {
+    let path = format!("{}/tests/fixtures/jupiter/{}", env!("CARGO_MANIFEST_DIR"), name);
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn pubkey(s: &str) -> Pubkey {
+    Pubkey::from_str(s).unwrap()
+}
+
+fn tokens() -> HashMap<Pubkey, TokenMeta> {
+    HashMap::from([
+        (pubkey(SOL), TokenMeta { symbol: "SOL".to_string(), decimals: 9 }),
+        (pubkey(USDC), TokenMeta { symbol: "USDC".to_string(), decimals: 6 }),
+        (pubkey(JUP), TokenMeta { symbol: "JUP".to_string(), decimals: 6 }),
+    ])
+}
+
+fn prices() -> HashMap<Pubkey, f64> {
+    HashMap::from([(pubkey(SOL), 236.0), (pubkey(USDC), 1.0), (pubkey(JUP), 1.0)])
+}
+
+#[test]
+fn split_route_is_rendered_on_one_line() {
+    let quote = fixture("quote_exact_in.json");
+
+    assert_eq!(route_hops(&quote).len(), 1);
+    assert_eq!(render_route(&quote, &tokens()), "SOL → USDC via Orca 60% / Raydium 40%");
+}
+
+#[test]
+fn multi_hop_route_is_rendered_per_hop() {
+    let quote = fixture("quote_exact_out.json");
+
+    assert_eq!(
+        render_route(&quote, &tokens()),
+        "SOL → USDC via Meteora DLMM 100%\nUSDC → JUP via Whirlpool 100%"
+    );
+}
+
+#[test]
+fn fees_are_summed_in_usd() {
+    let quote = fixture("quote_exact_in.json");
+
+    // 2400 + 100000 лампортов по $236
+    let fees = total_fees_usd(&quote, &tokens(), &prices()).unwrap();
+    assert!((fees - 0.102_400 * 0.236).abs() < 1e-9);
+
+    // Без цены комиссию посчитать нельзя
+    assert!(total_fees_usd(&quote, &tokens(), &HashMap::new()).is_none());
+}
+
+#[test]
+fn effective_price_is_compared_with_mid_price() {
+    let quote = fixture("quote_exact_in.json");
+
+    let effective = effective_price(&quote, &tokens()).unwrap();
+    let mid = mid_price(&quote, &prices()).unwrap();
+
+    assert!((effective - 0.1 / 23.658_512).abs() < 1e-12);
+    assert!((mid - 1.0 / 236.0).abs() < 1e-12);
+}
+
+#[test]
+fn price_impact_levels_follow_thresholds() {
+    let mut quote = fixture("quote_exact_in.json");
+    let limits = TradingLimits {
+        price_impact_warn_pct: 1.0,
+        price_impact_block_pct: 5.0,
+        ..default_trading_limits()
+    };
+
+    assert_eq!(price_impact_level(&quote, &limits), PriceImpactLevel::Normal);
+
+    quote.price_impact_pct = 0.02;
+    assert_eq!(price_impact_level(&quote, &limits), PriceImpactLevel::Warn);
+    assert!(render_quote_preview(&quote, &tokens(), &prices(), &limits).contains("дополнительное подтверждение"));
+
+    quote.price_impact_pct = 0.07;
+    assert_eq!(price_impact_level(&quote, &limits), PriceImpactLevel::Block);
+    assert!(render_quote_preview(&quote, &tokens(), &prices(), &limits).contains("заблокирована"));
+}
@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use solana_trading_bot::{
+    jupiter::QuoteResponseV6,
+    telegram::{
+        handlers::trade::{SwapAction, callback_data, parse_callback},
+        quote_store::{QuoteStore, adverse_deviation_bps},
+    },
+};
+
+fn fixture(name: &str) -> QuoteResponseV6 {
+    let path = format!("{}/tests/fixtures/jupiter/{}", env!("CARGO_MANIFEST_DIR"), name);
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn confirmed_quote_cannot_be_replayed() {
+    let store = QuoteStore::new(Duration::from_secs(20), 50);
+    let id = store.insert(1, fixture("quote_exact_in.json"), HashMap::new(), false);
+
+    // Чужой пользователь не может забрать котировку
+    assert!(store.take(&id, 2).is_none());
+
+    let stored = store.take(&id, 1).unwrap();
+    assert_eq!(stored.id, id);
+    assert!(!stored.is_expired(store.ttl()));
+    assert!(store.take(&id, 1).is_none());
+}
+
+#[test]
+fn impact_acknowledgement_is_kept_until_confirm() {
+    let store = QuoteStore::new(Duration::from_secs(20), 50);
+    let id = store.insert(1, fixture("quote_exact_in.json"), HashMap::new(), true);
+
+    assert!(store.acknowledge_impact(&id, 2).is_none());
+    assert!(store.acknowledge_impact(&id, 1).unwrap().impact_acknowledged);
+    assert!(store.take(&id, 1).unwrap().impact_acknowledged);
+}
+
+#[test]
+fn unacknowledged_impact_keeps_quote_for_confirm() {
+    let store = QuoteStore::new(Duration::from_secs(20), 50);
+    let id = store.insert(1, fixture("quote_exact_in.json"), HashMap::new(), true);
+
+    // Подтверждение до принятия предупреждения не расходует котировку
+    assert!(store.take_confirmed(&id, 1).is_none());
+    assert!(store.awaits_impact_ack(&id, 1));
+    assert!(!store.awaits_impact_ack(&id, 2));
+
+    store.acknowledge_impact(&id, 1).unwrap();
+    assert!(!store.awaits_impact_ack(&id, 1));
+    assert!(store.take_confirmed(&id, 2).is_none());
+    assert!(store.take_confirmed(&id, 1).unwrap().impact_acknowledged);
+    assert!(store.take_confirmed(&id, 1).is_none());
+}
+
+#[test]
+fn quote_expires_after_ttl() {
+    let store = QuoteStore::new(Duration::ZERO, 50);
+    let id = store.insert(1, fixture("quote_exact_in.json"), HashMap::new(), false);
+
+    std::thread::sleep(Duration::from_millis(5));
+    assert!(store.take(&id, 1).unwrap().is_expired(store.ttl()));
+}
+
+#[test]
+fn only_adverse_deviation_is_counted() {
+    let shown = fixture("quote_exact_in.json");
+
+    let mut fresh = shown.clone();
+    fresh.out_amount = shown.out_amount + 1_000;
+    assert_eq!(adverse_deviation_bps(&shown, &fresh), 0);
+
+    fresh.out_amount = shown.out_amount - shown.out_amount / 100;
+    assert_eq!(adverse_deviation_bps(&shown, &fresh), 99);
+
+    // Для ExactOut хуже — когда нужно отдать больше
+    let shown = fixture("quote_exact_out.json");
+    let mut fresh = shown.clone();
+    fresh.in_amount = shown.in_amount * 2;
+    assert_eq!(adverse_deviation_bps(&shown, &fresh), 10_000);
+}
+
+#[test]
+fn callback_data_round_trips() {
+    let id = uuid::Uuid::new_v4();
+
+    for action in [SwapAction::Confirm, SwapAction::AckImpact, SwapAction::Cancel] {
+        assert_eq!(parse_callback(&callback_data(action, &id)), Some((action, id)));
+    }
+    assert!(parse_callback("swap:confirm").is_none());
+    assert!(parse_callback(&format!("swap:unknown:{}", id)).is_none());
+}
@@ -0,0 +1,263 @@
+use std::sync::Arc;
+
+use bigdecimal::BigDecimal;
+use chrono::{Duration, Utc};
+use sea_orm::{ActiveModelTrait, Iterable};
+use secrecy::SecretString;
+use solana_sdk::pubkey::Pubkey;
+use testcontainers::runners::AsyncRunner;
+use testcontainers_modules::postgres::Postgres;
+use uuid::Uuid;
+
+use solana_trading_bot::{
+    config::settings::{DatabaseSettings, default_trading_limits},
+    database::{
+        DatabaseConnectionPool,
+        repositories::{
+            FakeTradeRepository, FakeUserRepository, FakeWalletRepository, PostgresTradeRepository,
+            PostgresUserRepository, TradeRepository, UserRepository,
+        },
+        trade_events::NewTradeEvent,
+        trades::{NewTrade, TransitionError},
+        users::TelegramProfile,
+    },
+    entities::{
+        fee_preferences::PriorityTier,
+        trade_events::TradeEventKind,
+        trades::{Model as Trade, TradeStatus, TradeType},
+        wallets::{Model as Wallet, WalletType},
+    },
+    jupiter::{JupiterClient, QuoteResponseV6},
+    solana::{amount::TokenAmount, rpc::FakeRpc, trader::Trader},
+    telegram::handlers::{start::register_user, trade::spendable_sol},
+};
+
+fn quote() -> QuoteResponseV6 {
+    let path = format!("{}/tests/fixtures/jupiter/quote_exact_in.json", env!("CARGO_MANIFEST_DIR"));
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn new_trade(quote: &QuoteResponseV6) -> NewTrade<'_> {
+    NewTrade {
+        user_id: 1,
+        trade_type: TradeType::Buy,
+        quote,
+        input_symbol: "SOL".to_string(),
+        output_symbol: "USDC".to_string(),
+        input_decimals: 9,
+        output_decimals: 6,
+        transaction_signature: Uuid::new_v4().to_string(),
+        jupiter_quote_id: None,
+        priority_fee_lamports: 5_000,
+        priority_tier: PriorityTier::Medium,
+    }
+}
+
+fn sent() -> NewTradeEvent {
+    NewTradeEvent::new(TradeEventKind::Sent)
+}
+
+fn confirmed() -> NewTradeEvent {
+    NewTradeEvent::new(TradeEventKind::Confirmed).with_slot(42)
+}
+
+fn profile(first_name: &str) -> TelegramProfile {
+    TelegramProfile {
+        id: 1,
+        username: Some("trader".to_string()),
+        first_name: first_name.to_string(),
+        last_name: None,
+        language_code: Some("ru".to_string()),
+        is_premium: false,
+    }
+}
+
+/// Сделка на `sol` SOL, созданная `age` назад
+fn past_trade(template: &Trade, sol: u64, status: TradeStatus, age: Duration) -> Trade {
+    Trade {
+        id: Uuid::new_v4(),
+        input_amount: TokenAmount::sol(sol * 1_000_000_000).to_big_decimal(),
+        status,
+        created_at: Utc::now() - age,
+        ..template.clone()
+    }
+}
+
+fn wallet(user_id: i64, public_key: Pubkey, is_default: bool) -> Wallet {
+    let now = Utc::now();
+    Wallet {
+        id: Uuid::new_v4(),
+        user_id,
+        public_key: public_key.to_string(),
+        encrypted_private_key: String::new(),
+        wallet_type: WalletType::Hot,
+        name: "Main".to_string(),
+        is_default,
+        is_active: true,
+        balance_sol: BigDecimal::from(0),
+        notify_balance: false,
+        last_synced_at: now,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+#[tokio::test]
+async fn start_registers_new_user_once() {
+    let users = FakeUserRepository::new();
+
+    assert!(register_user(&users, profile("Ivan")).await.unwrap());
+    assert!(!register_user(&users, profile("Ivan P.")).await.unwrap());
+    assert_eq!(users.find(1).await.unwrap().unwrap().first_name, "Ivan P.");
+}
+
+#[tokio::test]
+async fn stale_trade_cannot_transition() {
+    let trades = FakeTradeRepository::new();
+    let quote = quote();
+    let pending = trades.create_pending(new_trade(&quote)).await.unwrap();
+    assert_eq!(pending.status, TradeStatus::Pending);
+
+    let executing = trades.transition(&pending, TradeStatus::Executing, sent()).await.unwrap().unwrap();
+    // Вторая реплика прочитала сделку до первого перехода
+    assert!(trades.transition(&pending, TradeStatus::Cancelled, NewTradeEvent::cancelled("timeout")).await.unwrap().is_none());
+
+    let completed = trades.transition(&executing, TradeStatus::Completed, confirmed()).await.unwrap().unwrap();
+    assert!(completed.completed_at.is_some());
+    assert_eq!(trades.find(pending.id).await.unwrap().unwrap().status, TradeStatus::Completed);
+}
+
+#[test]
+fn trade_status_follows_state_machine() {
+    use TradeStatus::*;
+    let allowed = [(Pending, Executing), (Pending, Cancelled), (Executing, Completed), (Executing, Failed)];
+
+    for from in TradeStatus::iter() {
+        for to in TradeStatus::iter() {
+            let expected = allowed.iter().any(|(a, b)| (a, b) == (&from, &to));
+            assert_eq!(from.can_transition_to(&to), expected, "{:?} -> {:?}", from, to);
+        }
+        assert_eq!(from.is_final(), !allowed.iter().any(|(a, _)| a == &from));
+    }
+}
+
+#[tokio::test]
+async fn transitions_are_validated_and_recorded_in_timeline() {
+    let trades = FakeTradeRepository::new();
+    let quote = quote();
+    let pending = trades.create_pending(new_trade(&quote)).await.unwrap();
+
+    let skipped = trades.transition(&pending, TradeStatus::Completed, confirmed()).await;
+    assert!(matches!(skipped, Err(TransitionError::Illegal { from: TradeStatus::Pending, to: TradeStatus::Completed })));
+
+    let executing = trades.transition(&pending, TradeStatus::Executing, sent()).await.unwrap().unwrap();
+    let failed = trades
+        .transition(&executing, TradeStatus::Failed, NewTradeEvent::error("slippage exceeded").with_slot(41))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(failed.error_message.as_deref(), Some("slippage exceeded"));
+    assert!(failed.completed_at.is_none());
+    assert!(trades.transition(&failed, TradeStatus::Completed, confirmed()).await.is_err());
+
+    let timeline: Vec<_> = trades
+        .timeline(pending.id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|event| (event.kind, event.status, event.slot))
+        .collect();
+    assert_eq!(timeline, vec![
+        (TradeEventKind::QuoteReceived, TradeStatus::Pending, None),
+        (TradeEventKind::Signed, TradeStatus::Pending, None),
+        (TradeEventKind::Sent, TradeStatus::Executing, None),
+        (TradeEventKind::Error, TradeStatus::Failed, Some(41)),
+    ]);
+}
+
+#[tokio::test]
+async fn activity_counts_trades_in_range() {
+    let trades = FakeTradeRepository::new();
+    let quote = quote();
+    let template = trades.create_pending(new_trade(&quote)).await.unwrap();
+    let template = Trade { input_amount: BigDecimal::from(0), ..template };
+    trades.insert(template.clone());
+
+    trades.insert(past_trade(&template, 1, TradeStatus::Completed, Duration::minutes(30)));
+    trades.insert(past_trade(&template, 2, TradeStatus::Completed, Duration::hours(5)));
+    trades.insert(past_trade(&template, 4, TradeStatus::Failed, Duration::hours(5)));
+    trades.insert(past_trade(&template, 8, TradeStatus::Completed, Duration::days(2)));
+
+    let activity = trades.activity(1, Utc::now() + Duration::seconds(1)).await.unwrap();
+    assert_eq!(activity.trades_last_hour, 2);
+    assert_eq!(activity.trades_last_day, 3);
+    assert_eq!(activity.sol_spent_last_day, TokenAmount::sol(3_000_000_000));
+    assert_eq!(trades.activity(2, Utc::now()).await.unwrap().trades_last_day, 0);
+}
+
+#[tokio::test]
+async fn spendable_sol_uses_default_wallet() {
+    let rpc = Arc::new(FakeRpc::new());
+    let owner = Pubkey::new_unique();
+    rpc.set_balance(owner, 1_010_000_000);
+    let trader = Trader::new(JupiterClient::new("http://127.0.0.1:1", None), rpc, default_trading_limits());
+
+    let wallets = FakeWalletRepository::new();
+    wallets.insert(wallet(1, Pubkey::new_unique(), false));
+    wallets.insert(wallet(1, owner, true));
+
+    assert_eq!(spendable_sol(&wallets, &trader, 1).await.unwrap(), TokenAmount::sol(1_000_000_000));
+    assert!(spendable_sol(&wallets, &trader, 2).await.is_err());
+}
+
+#[tokio::test]
+#[ignore = "нужен Docker"]
+async fn postgres_repositories_follow_the_same_rules() {
+    let container = Postgres::default().start().await.unwrap();
+    let url = format!(
+        "postgres://postgres:postgres@{}:{}/postgres",
+        container.get_host().await.unwrap(),
+        container.get_host_port_ipv4(5432).await.unwrap(),
+    );
+    let database = Arc::new(DatabaseConnectionPool::connect(&DatabaseSettings {
+        url: SecretString::new(url.into_boxed_str()),
+        pool_max_connections: 5,
+        pool_min_connections: 1,
+        connect_timeout_secs: 30,
+        acquire_timeout_secs: 30,
+        idle_timeout_secs: 600,
+        max_lifetime_secs: 1800,
+    }).await.unwrap());
+    database.run_migrations().await.unwrap();
+
+    let users = PostgresUserRepository::new(database.clone());
+    assert!(register_user(&users, profile("Ivan")).await.unwrap());
+    assert!(!register_user(&users, profile("Ivan P.")).await.unwrap());
+    assert_eq!(users.find(1).await.unwrap().unwrap().first_name, "Ivan P.");
+
+    let trades = PostgresTradeRepository::new(database.clone());
+    let quote = quote();
+    let pending = trades.create_pending(new_trade(&quote)).await.unwrap();
+    let executing = trades.transition(&pending, TradeStatus::Executing, sent()).await.unwrap().unwrap();
+    assert!(trades.transition(&pending, TradeStatus::Cancelled, NewTradeEvent::cancelled("timeout")).await.unwrap().is_none());
+    assert!(trades.transition(&executing, TradeStatus::Cancelled, NewTradeEvent::cancelled("late")).await.is_err());
+    let completed = trades.transition(&executing, TradeStatus::Completed, confirmed()).await.unwrap().unwrap();
+    assert!(completed.completed_at.is_some());
+
+    let timeline = trades.timeline(pending.id).await.unwrap();
+    let kinds: Vec<_> = timeline.iter().map(|event| event.kind).collect();
+    assert_eq!(kinds, vec![
+        TradeEventKind::QuoteReceived,
+        TradeEventKind::Signed,
+        TradeEventKind::Sent,
+        TradeEventKind::Confirmed,
+    ]);
+    assert_eq!(timeline.last().unwrap().slot, Some(42));
+
+    let old = past_trade(&pending, 8, TradeStatus::Completed, Duration::days(2));
+    solana_trading_bot::entities::trades::ActiveModel::from(old).reset_all().insert(database.get_connection()).await.unwrap();
+
+    let activity = trades.activity(1, Utc::now() + Duration::seconds(1)).await.unwrap();
+    assert_eq!((activity.trades_last_hour, activity.trades_last_day), (1, 1));
+    assert_eq!(activity.sol_spent_last_day, TokenAmount::from_big_decimal(&pending.input_amount, 9).unwrap());
+}
@@ -0,0 +1,69 @@
+use std::io;
+
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_custom_error::JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY,
+    rpc_request::{RpcError, RpcResponseErrorData},
+};
+
+use solana_trading_bot::solana::rpc_pool::{endpoint_name, is_lagging, is_retryable, parse_endpoint, pick_weighted};
+
+fn response_error(code: i64) -> ClientError {
+    ClientErrorKind::RpcError(RpcError::RpcResponseError {
+        code,
+        message: "error".to_string(),
+        data: RpcResponseErrorData::Empty,
+    }).into()
+}
+
+#[test]
+fn endpoints_are_parsed_with_weights() {
+    assert_eq!(
+        parse_endpoint("https://api.mainnet-beta.solana.com"),
+        Some(("https://api.mainnet-beta.solana.com".to_string(), 1)),
+    );
+    assert_eq!(
+        parse_endpoint(" https://mainnet.helius-rpc.com/?api-key=secret|3 "),
+        Some(("https://mainnet.helius-rpc.com/?api-key=secret".to_string(), 3)),
+    );
+
+    assert_eq!(parse_endpoint("https://rpc.example.com|0"), None);
+    assert_eq!(parse_endpoint("https://rpc.example.com|heavy"), None);
+    assert_eq!(parse_endpoint("wss://rpc.example.com"), None);
+    assert_eq!(parse_endpoint("not a url"), None);
+}
+
+#[test]
+fn endpoint_name_hides_api_keys() {
+    assert_eq!(endpoint_name("https://mainnet.helius-rpc.com/?api-key=secret"), "mainnet.helius-rpc.com");
+    assert_eq!(endpoint_name("https://solana-mainnet.g.alchemy.com/v2/secret"), "solana-mainnet.g.alchemy.com");
+    assert_eq!(endpoint_name("garbage"), "unknown");
+}
+
+#[test]
+fn lagging_endpoints_are_detected() {
+    assert!(!is_lagging(1_000, 1_000, 50));
+    assert!(!is_lagging(950, 1_000, 50));
+    assert!(is_lagging(949, 1_000, 50));
+    // Узел впереди пула не отстает
+    assert!(!is_lagging(1_010, 1_000, 50));
+}
+
+#[test]
+fn endpoint_is_picked_by_weight() {
+    let weights = [3, 1, 2];
+    let picks: Vec<Option<usize>> = (0..7).map(|roll| pick_weighted(&weights, roll)).collect();
+    assert_eq!(picks, vec![Some(0), Some(0), Some(0), Some(1), Some(2), Some(2), None]);
+    assert_eq!(pick_weighted(&[], 0), None);
+}
+
+#[test]
+fn only_node_failures_are_retried_elsewhere() {
+    assert!(is_retryable(&ClientErrorKind::Io(io::Error::from(io::ErrorKind::TimedOut)).into()));
+    assert!(is_retryable(&response_error(JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY)));
+    assert!(is_retryable(&ClientErrorKind::RpcError(RpcError::RpcRequestError("bad gateway".to_string())).into()));
+
+    // Неверные параметры другой узел отклонит так же
+    assert!(!is_retryable(&response_error(-32602)));
+    assert!(!is_retryable(&ClientErrorKind::Custom("invalid".to_string()).into()));
+}
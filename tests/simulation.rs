@@ -0,0 +1,67 @@
+use solana_client::rpc_response::RpcSimulateTransactionResult;
+use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+
+use solana_trading_bot::solana::{
+    simulation::{SimulationFailure, SwapSimulation, classify},
+    trader::TradeError,
+};
+
+fn simulation(name: &str) -> SwapSimulation {
+    let path = format!("{}/tests/fixtures/solana/simulation/{}", env!("CARGO_MANIFEST_DIR"), name);
+    let result: RpcSimulateTransactionResult = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    result.into()
+}
+
+#[test]
+fn successful_simulation_has_no_failure() {
+    let simulation = simulation("success.json");
+
+    assert_eq!(simulation.failure(), None);
+    assert_eq!(simulation.units_consumed, Some(98_491));
+    assert_eq!(simulation.logs.len(), 7);
+}
+
+#[test]
+fn jupiter_slippage_error_is_recognized() {
+    let simulation = simulation("slippage_exceeded.json");
+
+    assert_eq!(
+        simulation.error,
+        Some(TransactionError::InstructionError(3, InstructionError::Custom(6001))),
+    );
+    assert_eq!(simulation.failure(), Some(SimulationFailure::SlippageExceeded));
+
+    // Понятное сообщение доходит до пользователя через ошибку сделки
+    let error = TradeError::from(SimulationFailure::SlippageExceeded);
+    assert!(error.to_string().contains("проскальзывания"));
+}
+
+#[test]
+fn insufficient_lamports_are_recognized_from_logs() {
+    // `Custom(1)` сам по себе неоднозначен, причину дают логи системной программы
+    assert_eq!(simulation("insufficient_funds.json").failure(), Some(SimulationFailure::InsufficientFunds));
+}
+
+#[test]
+fn transaction_errors_without_logs_are_classified() {
+    assert_eq!(classify(&TransactionError::InsufficientFundsForFee, &[]), SimulationFailure::InsufficientSolForFees);
+    assert_eq!(classify(&TransactionError::AccountNotFound, &[]), SimulationFailure::InsufficientSolForFees);
+    assert_eq!(classify(&TransactionError::BlockhashNotFound, &[]), SimulationFailure::BlockhashExpired);
+    assert_eq!(
+        classify(&TransactionError::InstructionError(0, InstructionError::ComputationalBudgetExceeded), &[]),
+        SimulationFailure::ComputeBudgetExceeded,
+    );
+
+    let other = classify(&TransactionError::InstructionError(1, InstructionError::Custom(42)), &[]);
+    assert!(matches!(other, SimulationFailure::Other(_)));
+}
+
+#[test]
+fn simulation_round_trips_through_json() {
+    // Так результат хранится в колонке `trades.simulation`
+    let simulation = simulation("slippage_exceeded.json");
+    let stored = serde_json::to_value(&simulation).unwrap();
+
+    assert_eq!(stored["units_consumed"], 76_100);
+    assert_eq!(serde_json::from_value::<SwapSimulation>(stored).unwrap(), simulation);
+}
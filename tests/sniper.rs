@@ -0,0 +1,170 @@
+use std::str::FromStr;
+
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use solana_client::rpc_response::RpcTokenAccountBalance;
+use solana_sdk::pubkey::Pubkey;
+
+use solana_trading_bot::{
+    automation::sniper::{Launch, rejection_reasons},
+    config::settings::default_sniper,
+    jupiter::RecentToken,
+    solana::{
+        constants::SOL_MINT,
+        launch::{MintState, PoolKind, PoolState, build_report, holder_balances, lp_burned_pct},
+    },
+    telegram::handlers::snipe::{SnipeCommand, parse_snipe_args},
+};
+
+const LAUNCH_MINT: &str = "FqUwnBMN1shpeqKVm7W5fN73tvrjVr19TQFFgkoFFzhq";
+const POOL_VAULT: &str = "AYX2TATe2GGqU2FmTBVgbj4qm2Bx5rJj9UCXpoFVwKj6";
+const LP_MINT: &str = "EpBLJivcREeg7qUpu3xNB3nFExBgYQAjSFiGJDgD1qdZ";
+const DEV: &str = "H6Y6fMwwW1adjL4uG7zXQdgVUB5hDP5xf57q4T8zisTc";
+
+fn read_fixture(name: &str) -> String {
+    let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+    std::fs::read_to_string(path).unwrap()
+}
+
+/// Аккаунт из записанного ответа `getAccountInfo`: владелец и данные
+fn account(name: &str) -> (Pubkey, Vec<u8>) {
+    let value: serde_json::Value = serde_json::from_str(&read_fixture(&format!("solana/accounts/{}", name))).unwrap();
+    let owner = Pubkey::from_str(value["owner"].as_str().unwrap()).unwrap();
+    let data = base64::engine::general_purpose::STANDARD.decode(value["data"][0].as_str().unwrap()).unwrap();
+    (owner, data)
+}
+
+fn largest_accounts() -> Vec<(Pubkey, u64)> {
+    let largest: Vec<RpcTokenAccountBalance> = serde_json::from_str(&read_fixture("solana/accounts/launch_largest_accounts.json")).unwrap();
+    holder_balances(&largest)
+}
+
+fn key(value: &str) -> Pubkey {
+    Pubkey::from_str(value).unwrap()
+}
+
+#[test]
+fn raydium_amm_pool_is_decoded() {
+    let (owner, data) = account("raydium_amm_pool.json");
+    let pool = PoolState::decode(&owner, &data).unwrap();
+
+    assert_eq!(pool.kind, PoolKind::RaydiumAmm);
+    assert_eq!(pool.mint_a, key(LAUNCH_MINT));
+    assert_eq!(pool.mint_b, key(SOL_MINT));
+    assert_eq!(pool.vault_a, key(POOL_VAULT));
+    assert_eq!(pool.lp_mint, Some(key(LP_MINT)));
+    assert_eq!(pool.lp_issued, Some(1_000_000_000));
+
+    // Обрезанные данные и чужая программа не разбираются
+    assert!(PoolState::decode(&owner, &data[..400]).is_none());
+    assert!(PoolState::decode(&key(SOL_MINT), &data).is_none());
+}
+
+#[test]
+fn whirlpool_has_no_lp_token() {
+    let (owner, data) = account("orca_whirlpool_pool.json");
+    let pool = PoolState::decode(&owner, &data).unwrap();
+
+    assert_eq!(pool.kind, PoolKind::OrcaWhirlpool);
+    assert_eq!(pool.mint_a, key(SOL_MINT));
+    assert_eq!(pool.mint_b, key(LAUNCH_MINT));
+    assert_eq!(pool.lp_mint, None);
+}
+
+#[test]
+fn mint_authorities_are_read() {
+    let (owner, data) = account("launch_mint_revoked.json");
+    let revoked = MintState::decode(&owner, &data).unwrap();
+    assert_eq!(revoked.mint_authority, None);
+    assert_eq!(revoked.freeze_authority, None);
+    assert_eq!(revoked.supply, 1_000_000_000_000_000);
+    assert_eq!(revoked.decimals, 6);
+
+    let (owner, data) = account("launch_mint_unsafe.json");
+    let unsafe_mint = MintState::decode(&owner, &data).unwrap();
+    assert_eq!(unsafe_mint.mint_authority, Some(key(DEV)));
+    assert_eq!(unsafe_mint.freeze_authority, Some(key(DEV)));
+
+    // Аккаунт пула — не mint
+    let (owner, data) = account("raydium_amm_pool.json");
+    assert!(MintState::decode(&owner, &data).is_none());
+}
+
+#[test]
+fn safe_launch_passes_checks() {
+    let (owner, data) = account("launch_mint_revoked.json");
+    let mint = MintState::decode(&owner, &data).unwrap();
+    let (owner, data) = account("raydium_amm_pool.json");
+    let pool = PoolState::decode(&owner, &data).unwrap();
+    let lp_supply: serde_json::Value = serde_json::from_str(&read_fixture("solana/accounts/lp_token_supply.json")).unwrap();
+    let lp_supply = lp_supply["amount"].as_str().unwrap().parse().unwrap();
+
+    let report = build_report(&mint, Some(&pool), Some(lp_supply), &largest_accounts(), 10);
+
+    assert_eq!(report.pool, Some(PoolKind::RaydiumAmm));
+    assert!((report.lp_burned_pct.unwrap() - 95.0).abs() < 1e-9);
+    // Хранилище пула с 80% supply держателем не считается
+    assert!((report.top_holders_pct - 13.5).abs() < 1e-9);
+    assert!(rejection_reasons(&report, &default_sniper()).is_empty());
+}
+
+#[test]
+fn unsafe_launch_is_rejected_with_reasons() {
+    let (owner, data) = account("launch_mint_unsafe.json");
+    let mint = MintState::decode(&owner, &data).unwrap();
+    let (owner, data) = account("raydium_amm_pool.json");
+    let pool = PoolState::decode(&owner, &data).unwrap();
+
+    // LP не сожжены, а без исключения хранилища пула концентрация огромная
+    let report = build_report(&mint, Some(&pool), Some(1_000_000_000), &largest_accounts(), 10);
+    let mut rules = default_sniper();
+    rules.max_top_holders_pct = 10.0;
+    let reasons = rejection_reasons(&report, &rules);
+
+    assert_eq!(reasons.len(), 4, "{:?}", reasons);
+    assert!(reasons[0].contains("mint authority"));
+    assert!(reasons[1].contains("freeze authority"));
+    assert!(reasons[2].contains("LP"));
+    assert!(reasons[3].contains("13.5%"));
+
+    // Без пула с LP-токеном проверка сжигания не применяется
+    let report = build_report(&mint, None, None, &largest_accounts(), 10);
+    assert_eq!(report.lp_burned_pct, None);
+    assert!(report.top_holders_pct > 90.0);
+}
+
+#[test]
+fn lp_burn_share_is_measured_against_issued_lp() {
+    assert_eq!(lp_burned_pct(1_000, 0), Some(100.0));
+    assert_eq!(lp_burned_pct(1_000, 1_000), Some(0.0));
+    assert_eq!(lp_burned_pct(0, 0), None);
+}
+
+#[test]
+fn only_fresh_pools_become_launches() {
+    let tokens: Vec<RecentToken> = serde_json::from_str(&read_fixture("jupiter/recent_tokens.json")).unwrap();
+    let now: DateTime<Utc> = "2026-01-05T12:01:00Z".parse().unwrap();
+
+    let launches: Vec<Launch> = tokens
+        .iter()
+        .filter_map(|token| Launch::from_recent(token, now, Duration::seconds(120)))
+        .collect();
+
+    assert_eq!(launches.len(), 1);
+    assert_eq!(launches[0].mint, key(LAUNCH_MINT));
+    assert_eq!(launches[0].symbol, "FRESH");
+    assert_eq!(launches[0].pool, Some(key("4LioTcTWqUxLwzuU2r5Y3HYbbjGe8Y37kKLccQRNrJSh")));
+}
+
+#[test]
+fn snipe_commands_are_parsed() {
+    assert_eq!(parse_snipe_args(""), Some(SnipeCommand::Status));
+    assert_eq!(parse_snipe_args("OFF"), Some(SnipeCommand::Off));
+    assert_eq!(parse_snipe_args("on"), Some(SnipeCommand::On(None)));
+    assert_eq!(
+        parse_snipe_args("on 0,25"),
+        Some(SnipeCommand::On(Some(bigdecimal::BigDecimal::from_str("0.25").unwrap())))
+    );
+    assert_eq!(parse_snipe_args("on 0"), None);
+    assert_eq!(parse_snipe_args("maybe"), None);
+}
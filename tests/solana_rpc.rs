@@ -0,0 +1,241 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use solana_client::rpc_response::RpcPrioritizationFee;
+use solana_sdk::{
+    account::Account,
+    hash::Hash,
+    instruction::{Instruction, InstructionError},
+    message::{Message, VersionedMessage},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{TransactionError, VersionedTransaction},
+};
+use spl_token::{solana_program::program_pack::Pack, state::Mint};
+
+use solana_trading_bot::{
+    config::settings::{default_priority_fee, default_trading_limits},
+    entities::fee_preferences::PriorityTier,
+    jupiter::{JupiterClient, PrioritizationFee, QuoteResponseV6},
+    solana::{
+        priority_fee::PriorityFeeEstimator,
+        rpc::{FakeLanding, FakeRpc, SolanaRpc, TokenAccountBalance},
+        sender::{LandingError, LandingStatus, TransactionSender, confirm_landing},
+        simulation::{SimulationFailure, SwapSimulation},
+        trader::{PreparedSwap, Trader},
+    },
+};
+
+const LAST_VALID_BLOCK_HEIGHT: u64 = 277_504_150;
+
+fn signed_transaction() -> VersionedTransaction {
+    let payer = Keypair::new();
+    let instruction = Instruction::new_with_bytes(Pubkey::new_unique(), &[1], vec![]);
+    let message = Message::new_with_blockhash(&[instruction], Some(&payer.pubkey()), &Hash::new_unique());
+    VersionedTransaction::try_new(VersionedMessage::Legacy(message), &[&payer]).unwrap()
+}
+
+fn sender(rpc: &Arc<FakeRpc>) -> TransactionSender {
+    TransactionSender::new(rpc.clone(), Duration::from_millis(1))
+}
+
+fn trader(rpc: &Arc<FakeRpc>) -> Trader {
+    Trader::new(JupiterClient::new("http://localhost", None), rpc.clone(), default_trading_limits())
+}
+
+fn quote() -> QuoteResponseV6 {
+    let path = format!("{}/tests/fixtures/jupiter/quote_exact_in.json", env!("CARGO_MANIFEST_DIR"));
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn sender_rebroadcasts_the_same_transaction_until_it_lands() {
+    let rpc = Arc::new(FakeRpc::new());
+    rpc.set_block_height(LAST_VALID_BLOCK_HEIGHT - 100, 1);
+    rpc.set_landing(FakeLanding { after_sends: 3, error: None });
+
+    let transaction = signed_transaction();
+    let signature = sender(&rpc).send(&transaction, LAST_VALID_BLOCK_HEIGHT).await.unwrap();
+
+    assert_eq!(signature, transaction.signatures[0]);
+    // Повторы отправляют те же байты: новой подписи не появляется
+    let sent = rpc.sent();
+    assert_eq!(sent.len(), 3);
+    assert!(sent.iter().all(|tx| *tx == transaction));
+}
+
+#[tokio::test]
+async fn sender_stops_after_blockhash_expiry() {
+    let rpc = Arc::new(FakeRpc::new());
+    rpc.set_block_height(LAST_VALID_BLOCK_HEIGHT - 2, 1);
+
+    let transaction = signed_transaction();
+    let error = sender(&rpc).send(&transaction, LAST_VALID_BLOCK_HEIGHT).await.unwrap_err();
+
+    assert_eq!(error, LandingError::Expired);
+    assert!(error.is_retryable());
+    // Отправка на каждой проверке, пока высота не превысит последнюю допустимую
+    let sent = rpc.sent();
+    assert_eq!(sent.len(), 4);
+    assert!(sent.iter().all(|tx| tx.signatures[0] == transaction.signatures[0]));
+}
+
+#[tokio::test]
+async fn sender_checks_status_history_before_expiry() {
+    let rpc = Arc::new(FakeRpc::new());
+    rpc.set_block_height(LAST_VALID_BLOCK_HEIGHT + 1, 0);
+    let transaction = signed_transaction();
+    // Кэш недавних статусов транзакцию не помнит, а история подписи — да
+    rpc.set_historical_status(transaction.signatures[0], FakeRpc::confirmed_status(LAST_VALID_BLOCK_HEIGHT - 1, None));
+
+    let signature = sender(&rpc).send(&transaction, LAST_VALID_BLOCK_HEIGHT).await.unwrap();
+
+    assert_eq!(signature, transaction.signatures[0]);
+}
+
+#[tokio::test]
+async fn sender_reports_landed_but_failed_transaction() {
+    let rpc = Arc::new(FakeRpc::new());
+    rpc.set_block_height(LAST_VALID_BLOCK_HEIGHT - 100, 1);
+    let error = TransactionError::InstructionError(2, InstructionError::Custom(6001));
+    rpc.set_landing(FakeLanding { after_sends: 1, error: Some(error.clone()) });
+
+    let result = sender(&rpc).send(&signed_transaction(), LAST_VALID_BLOCK_HEIGHT).await;
+
+    assert_eq!(result, Err(LandingError::Failed(error)));
+    assert_eq!(rpc.sent().len(), 1);
+}
+
+#[tokio::test]
+async fn landing_is_confirmed_from_status_history() {
+    let rpc = Arc::new(FakeRpc::new());
+    rpc.set_block_height(LAST_VALID_BLOCK_HEIGHT + 500, 0);
+    let signature = signed_transaction().signatures[0];
+    rpc.set_historical_status(signature, FakeRpc::confirmed_status(LAST_VALID_BLOCK_HEIGHT - 5, None));
+
+    // Кэш недавних статусов транзакцию уже не помнит
+    assert_eq!(rpc.get_signature_statuses(&[signature]).await.unwrap(), vec![None]);
+    assert_eq!(
+        confirm_landing(rpc.as_ref(), &signature, LAST_VALID_BLOCK_HEIGHT).await.unwrap(),
+        (LandingStatus::Landed, Some(LAST_VALID_BLOCK_HEIGHT - 5)),
+    );
+
+    let missing = signed_transaction().signatures[0];
+    assert_eq!(
+        confirm_landing(rpc.as_ref(), &missing, LAST_VALID_BLOCK_HEIGHT).await.unwrap(),
+        (LandingStatus::Expired, None),
+    );
+}
+
+#[tokio::test]
+async fn lagging_status_node_does_not_expire_landed_transaction() {
+    let rpc = Arc::new(FakeRpc::new());
+    rpc.set_block_height(LAST_VALID_BLOCK_HEIGHT + 10, 0);
+    let signature = signed_transaction().signatures[0];
+    rpc.set_signature_status(signature, FakeRpc::confirmed_status(LAST_VALID_BLOCK_HEIGHT, None));
+
+    rpc.set_status_lag(30);
+    assert_eq!(
+        confirm_landing(rpc.as_ref(), &signature, LAST_VALID_BLOCK_HEIGHT).await.unwrap(),
+        (LandingStatus::Pending, None),
+    );
+
+    rpc.set_status_lag(0);
+    assert_eq!(
+        confirm_landing(rpc.as_ref(), &signature, LAST_VALID_BLOCK_HEIGHT).await.unwrap(),
+        (LandingStatus::Landed, Some(LAST_VALID_BLOCK_HEIGHT)),
+    );
+}
+
+#[tokio::test]
+async fn token_balance_sums_all_token_accounts() {
+    let rpc = Arc::new(FakeRpc::new());
+    let (owner, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    rpc.set_token_accounts(owner, mint, vec![
+        TokenAccountBalance { pubkey: Pubkey::new_unique(), amount: 1_500 },
+        TokenAccountBalance { pubkey: Pubkey::new_unique(), amount: 2_500 },
+    ]);
+
+    let trader = trader(&rpc);
+    assert_eq!(trader.token_balance(&owner, &mint).await.unwrap(), 4_000);
+    assert_eq!(trader.token_balance(&owner, &Pubkey::new_unique()).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn mint_decimals_are_read_from_mint_account() {
+    let rpc = Arc::new(FakeRpc::new());
+    let mint = Pubkey::new_unique();
+    let mut data = vec![0; Mint::LEN];
+    Mint::pack(Mint { decimals: 6, is_initialized: true, supply: 1_000_000, ..Default::default() }, &mut data).unwrap();
+    rpc.set_account(mint, Account { lamports: 1_461_600, data, owner: spl_token::id(), executable: false, rent_epoch: 0 });
+
+    let trader = trader(&rpc);
+    assert_eq!(trader.mint_decimals(&mint).await.unwrap(), 6);
+    // Повторно mint не запрашивается, знаки SOL заданы протоколом
+    assert_eq!(trader.mint_decimals(&mint).await.unwrap(), 6);
+    assert_eq!(trader.mint_decimals(&quote().input_mint).await.unwrap(), 9);
+    assert_eq!(rpc.calls(), vec!["getMultipleAccounts"]);
+
+    assert!(trader.mint_decimals(&Pubkey::new_unique()).await.is_err());
+}
+
+#[tokio::test]
+async fn failed_simulation_is_classified() {
+    let rpc = Arc::new(FakeRpc::new());
+    rpc.push_simulation(SwapSimulation {
+        error: Some(TransactionError::InstructionError(3, InstructionError::Custom(6001))),
+        units_consumed: Some(98_000),
+        logs: vec![],
+    });
+
+    let transaction = signed_transaction();
+    let prepared = PreparedSwap {
+        signature: transaction.signatures[0],
+        transaction,
+        last_valid_block_height: LAST_VALID_BLOCK_HEIGHT,
+        fee_account: None,
+        priority_fee_lamports: 0,
+    };
+
+    let trader = trader(&rpc);
+    let simulation = trader.simulate_swap(&prepared).await.unwrap();
+    assert_eq!(simulation.failure(), Some(SimulationFailure::SlippageExceeded));
+    // Без сценария симуляция проходит
+    assert_eq!(trader.simulate_swap(&prepared).await.unwrap().failure(), None);
+    assert!(rpc.sent().is_empty());
+}
+
+#[tokio::test]
+async fn priority_fee_falls_back_to_jupiter_when_fees_are_unavailable() {
+    let rpc = Arc::new(FakeRpc::new());
+    let settings = default_priority_fee();
+    let estimator = PriorityFeeEstimator::new(rpc.clone(), settings.clone());
+
+    let fee = estimator.fee_for(&quote(), PriorityTier::Turbo, Some(2_000_000)).await;
+    assert_eq!(fee, PrioritizationFee::capped("veryHigh", 2_000_000));
+
+    rpc.set_prioritization_fees(vec![
+        RpcPrioritizationFee { slot: 1, prioritization_fee: 10_000 },
+        RpcPrioritizationFee { slot: 2, prioritization_fee: 1_000_000_000 },
+    ]);
+    let estimator = PriorityFeeEstimator::new(rpc.clone(), settings.clone());
+    // 10_000 микролампортов за 300_000 единиц
+    let fee = estimator.fee_for(&quote(), PriorityTier::Low, None).await;
+    assert_eq!(fee, PrioritizationFee::Lamports(3_000));
+    // Выброс упирается в потолок
+    let fee = estimator.fee_for(&quote(), PriorityTier::Turbo, None).await;
+    assert_eq!(fee, PrioritizationFee::Lamports(settings.default_max_lamports));
+}
+
+#[tokio::test]
+async fn fake_rpc_reports_missing_data_as_errors() {
+    let rpc = FakeRpc::new();
+    let mint = Pubkey::new_unique();
+
+    assert_eq!(rpc.get_balance(&mint).await.unwrap(), 0);
+    assert!(rpc.get_account(&mint).await.unwrap().is_none());
+    assert!(rpc.get_token_supply(&mint).await.is_err());
+
+    rpc.set_token_supply(mint, 42);
+    assert_eq!(rpc.get_token_supply(&mint).await.unwrap(), 42);
+}
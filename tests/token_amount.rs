@@ -0,0 +1,117 @@
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use proptest::prelude::*;
+use rust_decimal::Decimal;
+
+use solana_trading_bot::solana::amount::{AmountError, AmountInput, TokenAmount};
+
+#[test]
+fn parses_user_input_exactly() {
+    // В f64 0.3 * 10^9 дает 299999999
+    assert_eq!(TokenAmount::parse("0.3", 9), Ok(TokenAmount::sol(300_000_000)));
+    assert_eq!(TokenAmount::parse("1,5", 9), Ok(TokenAmount::sol(1_500_000_000)));
+    assert_eq!(TokenAmount::parse(" 100 ", 6), Ok(TokenAmount::new(100_000_000, 6)));
+    assert_eq!(TokenAmount::parse(".5", 2), Ok(TokenAmount::new(50, 2)));
+}
+
+#[test]
+fn rejects_invalid_input() {
+    assert_eq!(TokenAmount::parse("", 9), Err(AmountError::Empty));
+    assert_eq!(TokenAmount::parse("0", 9), Err(AmountError::NotPositive));
+    assert_eq!(TokenAmount::parse("0.0000000001", 9), Err(AmountError::TooPrecise(9)));
+    assert_eq!(TokenAmount::parse("18446744073.709551616", 9), Err(AmountError::Overflow));
+    assert_eq!(TokenAmount::parse("max", 9), Err(AmountError::BalanceRequired));
+    for input in ["-1", "1e9", "1.2.3", "abc", "1 000", "NaN", "inf", "."] {
+        assert!(matches!(TokenAmount::parse(input, 9), Err(AmountError::Invalid(_))), "{}", input);
+    }
+}
+
+#[test]
+fn max_and_percent_resolve_against_balance() {
+    let balance = TokenAmount::sol(1_000_000_001);
+
+    assert_eq!("max".parse::<AmountInput>(), Ok(AmountInput::Max));
+    assert_eq!("MAX".parse::<AmountInput>().unwrap().resolve(9, Some(balance)), Ok(balance));
+    assert_eq!("50%".parse::<AmountInput>().unwrap().resolve(9, Some(balance)), Ok(TokenAmount::sol(500_000_000)));
+    assert_eq!("12,5%".parse::<AmountInput>().unwrap().resolve(9, Some(balance)), Ok(TokenAmount::sol(125_000_000)));
+    assert_eq!("max".parse::<AmountInput>().unwrap().resolve(9, None), Err(AmountError::BalanceRequired));
+    assert_eq!("max".parse::<AmountInput>().unwrap().resolve(9, Some(TokenAmount::sol(0))), Err(AmountError::NotPositive));
+
+    assert_eq!("0%".parse::<AmountInput>(), Err(AmountError::InvalidPercent));
+    assert_eq!("101%".parse::<AmountInput>(), Err(AmountError::InvalidPercent));
+}
+
+#[test]
+fn displays_all_mint_decimals() {
+    assert_eq!(TokenAmount::sol(1_500_000_000).to_string(), "1.5");
+    assert_eq!(TokenAmount::sol(1).to_string(), "0.000000001");
+    assert_eq!(TokenAmount::new(100, 0).to_string(), "100");
+    assert_eq!(TokenAmount::new(12_345_678, 6).to_string(), "12.345678");
+    assert_eq!(format!("{:.4}", TokenAmount::sol(1_234_567_890)), "1.2345");
+    assert_eq!(format!("{:.2}", TokenAmount::new(5, 0)), "5.00");
+}
+
+#[test]
+fn amounts_with_different_decimals_are_not_comparable() {
+    assert_eq!(TokenAmount::new(1, 6).partial_cmp(&TokenAmount::new(1, 9)), None);
+    assert!(TokenAmount::sol(2) > TokenAmount::sol(1));
+    assert_eq!(TokenAmount::new(1, 6).checked_add(TokenAmount::new(1, 9)), None);
+}
+
+#[test]
+fn converts_to_and_from_database_decimal() {
+    let amount = TokenAmount::new(1_234_500, 6);
+    assert_eq!(amount.to_big_decimal(), BigDecimal::from_str("1.2345").unwrap());
+    assert_eq!(TokenAmount::from_big_decimal(&BigDecimal::from_str("1.2345").unwrap(), 6), Ok(amount));
+    assert_eq!(
+        TokenAmount::from_big_decimal(&BigDecimal::from_str("18446744073709.551616").unwrap(), 6),
+        Err(AmountError::Overflow),
+    );
+}
+
+proptest! {
+    #[test]
+    fn display_round_trips_through_parse(raw in 1u64.., decimals in 0u8..=18) {
+        let amount = TokenAmount::new(raw, decimals);
+        prop_assert_eq!(TokenAmount::parse(&amount.to_string(), decimals), Ok(amount));
+    }
+
+    #[test]
+    fn big_decimal_round_trips(raw in any::<u64>(), decimals in 0u8..=18) {
+        let amount = TokenAmount::new(raw, decimals);
+        prop_assert_eq!(TokenAmount::from_big_decimal(&amount.to_big_decimal(), decimals), Ok(amount));
+    }
+
+    #[test]
+    fn decimal_round_trips(raw in any::<u64>(), decimals in 0u8..=18) {
+        let amount = TokenAmount::new(raw, decimals);
+        prop_assert_eq!(TokenAmount::from_decimal(amount.to_decimal(), decimals), Ok(amount));
+    }
+
+    #[test]
+    fn parse_never_rounds(integer in 0u64..1_000_000, fraction in "[0-9]{1,12}", decimals in 0u8..=9) {
+        let input = format!("{}.{}", integer, fraction);
+        let significant = fraction.trim_end_matches('0').len();
+
+        match TokenAmount::parse(&input, decimals) {
+            Ok(amount) => {
+                prop_assert!(significant <= decimals as usize);
+                prop_assert_eq!(amount.to_decimal(), Decimal::from_str(&input).unwrap());
+            }
+            Err(AmountError::TooPrecise(_)) => prop_assert!(significant > decimals as usize),
+            Err(AmountError::NotPositive) => prop_assert_eq!(Decimal::from_str(&input).unwrap(), Decimal::ZERO),
+            Err(e) => prop_assert!(false, "unexpected error {:?}", e),
+        }
+    }
+
+    #[test]
+    fn percent_never_exceeds_balance(raw in any::<u64>(), percent in 1u32..=10_000) {
+        let balance = TokenAmount::sol(raw);
+        let part = balance.percent(Decimal::new(percent as i64, 2));
+        prop_assert!(part.raw() <= raw);
+        if percent == 10_000 {
+            prop_assert_eq!(part, balance);
+        }
+    }
+}
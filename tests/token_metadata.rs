@@ -0,0 +1,109 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use base64::Engine;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use spl_token::{solana_program::program_pack::Pack, state::Mint};
+
+use solana_trading_bot::solana::{
+    constants::{SOL_MINT, short_address},
+    rpc::FakeRpc,
+    token_metadata::{
+        TokenLabel, TokenResolver, decode_metaplex_metadata, decode_token_2022_metadata, metadata_address,
+    },
+};
+
+fn read_fixture(name: &str) -> String {
+    let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+    std::fs::read_to_string(path).unwrap()
+}
+
+/// Аккаунт из записанного ответа `getAccountInfo`
+fn account(name: &str) -> Account {
+    let value: serde_json::Value = serde_json::from_str(&read_fixture(&format!("solana/accounts/{}", name))).unwrap();
+    Account {
+        lamports: value["lamports"].as_u64().unwrap(),
+        data: base64::engine::general_purpose::STANDARD.decode(value["data"][0].as_str().unwrap()).unwrap(),
+        owner: Pubkey::from_str(value["owner"].as_str().unwrap()).unwrap(),
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn spl_mint(decimals: u8) -> Account {
+    let mut data = vec![0; Mint::LEN];
+    Mint::pack(Mint { decimals, is_initialized: true, supply: 1_000_000, ..Default::default() }, &mut data).unwrap();
+    Account { lamports: 1_461_600, data, owner: spl_token::id(), executable: false, rent_epoch: 0 }
+}
+
+fn label(name: &str, symbol: &str) -> TokenLabel {
+    TokenLabel { name: Some(name.to_string()), symbol: Some(symbol.to_string()) }
+}
+
+#[test]
+fn metaplex_metadata_is_decoded_without_padding() {
+    assert_eq!(decode_metaplex_metadata(&account("metaplex_metadata.json").data), Some(label("Bonk Inu", "BINU")));
+    assert_eq!(decode_metaplex_metadata(&[0; 100]), None);
+}
+
+#[test]
+fn token_2022_metadata_extension_is_decoded() {
+    let mint = account("token2022_mint_metadata.json");
+    assert_eq!(decode_token_2022_metadata(&mint.data), Some(label("Pump Cat", "PCAT")));
+    // Расширения без метаданных
+    assert_eq!(decode_token_2022_metadata(&account("token2022_mint_extensions.json").data), None);
+}
+
+#[tokio::test]
+async fn spl_mint_uses_metaplex_metadata_and_is_cached() {
+    let rpc = Arc::new(FakeRpc::new());
+    let mint = Pubkey::new_unique();
+    rpc.set_account(mint, spl_mint(5));
+    rpc.set_account(metadata_address(&mint), account("metaplex_metadata.json"));
+
+    let resolver = TokenResolver::new(rpc.clone());
+    let info = resolver.resolve(&mint).await.unwrap();
+    assert_eq!((info.decimals, info.token_2022), (5, false));
+    assert_eq!(info.meta().symbol, "BINU");
+    assert_eq!(info.name.as_deref(), Some("Bonk Inu"));
+
+    resolver.resolve(&mint).await.unwrap();
+    assert_eq!(rpc.calls(), vec!["getMultipleAccounts"]);
+}
+
+#[tokio::test]
+async fn token_2022_mint_reads_its_own_metadata() {
+    let rpc = Arc::new(FakeRpc::new());
+    let mint = Pubkey::new_unique();
+    rpc.set_account(mint, account("token2022_mint_metadata.json"));
+
+    let info = TokenResolver::new(rpc).resolve(&mint).await.unwrap();
+    assert_eq!((info.decimals, info.token_2022), (6, true));
+    assert_eq!(info.meta().symbol, "PCAT");
+}
+
+#[tokio::test]
+async fn token_without_metadata_falls_back_to_short_address() {
+    let rpc = Arc::new(FakeRpc::new());
+    let mint = Pubkey::new_unique();
+    rpc.set_account(mint, spl_mint(9));
+    // Аккаунт метаданных чужой программы не учитывается
+    let mut foreign = account("metaplex_metadata.json");
+    foreign.owner = Pubkey::new_unique();
+    rpc.set_account(metadata_address(&mint), foreign);
+
+    let resolver = TokenResolver::new(rpc);
+    let meta = resolver.resolve(&mint).await.unwrap().meta();
+    assert_eq!((meta.symbol, meta.decimals), (short_address(&mint), 9));
+
+    assert!(resolver.resolve(&Pubkey::new_unique()).await.is_err());
+}
+
+#[tokio::test]
+async fn sol_is_resolved_without_rpc() {
+    let rpc = Arc::new(FakeRpc::new());
+    let meta = TokenResolver::new(rpc.clone()).resolve(&Pubkey::from_str(SOL_MINT).unwrap()).await.unwrap().meta();
+
+    assert_eq!((meta.symbol.as_str(), meta.decimals), ("SOL", 9));
+    assert!(rpc.calls().is_empty());
+}
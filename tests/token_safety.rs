@@ -0,0 +1,201 @@
+use std::str::FromStr;
+
+use base64::Engine;
+use solana_sdk::pubkey::Pubkey;
+
+use solana_trading_bot::{
+    config::settings::default_token_safety,
+    jupiter::{PlatformFee, QuoteResponseV6},
+    solana::{
+        launch::MintState,
+        token_safety::{
+            MintExtensions, SafetyReport, SellCheck, TransferFee, is_no_route_error, round_trip_tax_pct,
+        },
+    },
+    telegram::preview::render_safety_report,
+};
+
+const DEV: &str = "H6Y6fMwwW1adjL4uG7zXQdgVUB5hDP5xf57q4T8zisTc";
+const HOOK_PROGRAM: &str = "HooKD5NC9QNxk25QuzCssB8ecrEzGt6eewrJ6S4DfvS5";
+
+fn read_fixture(name: &str) -> String {
+    let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+    std::fs::read_to_string(path).unwrap()
+}
+
+/// Аккаунт из записанного ответа `getAccountInfo`: владелец и данные
+fn account(name: &str) -> (Pubkey, Vec<u8>) {
+    let value: serde_json::Value = serde_json::from_str(&read_fixture(&format!("solana/accounts/{}", name))).unwrap();
+    let owner = Pubkey::from_str(value["owner"].as_str().unwrap()).unwrap();
+    let data = base64::engine::general_purpose::STANDARD.decode(value["data"][0].as_str().unwrap()).unwrap();
+    (owner, data)
+}
+
+fn key(value: &str) -> Pubkey {
+    Pubkey::from_str(value).unwrap()
+}
+
+fn buy_quote() -> QuoteResponseV6 {
+    serde_json::from_str(&read_fixture("jupiter/quote_exact_in.json")).unwrap()
+}
+
+/// Обратная котировка: продажа купленного количества с заданным выходом в SOL
+fn sell_quote(buy: &QuoteResponseV6, out_lamports: u64, price_impact_pct: f64) -> QuoteResponseV6 {
+    let mut sell = buy.clone();
+    sell.input_mint = buy.output_mint;
+    sell.output_mint = buy.input_mint;
+    sell.in_amount = buy.out_amount;
+    sell.out_amount = out_lamports;
+    sell.price_impact_pct = price_impact_pct;
+    sell
+}
+
+fn clean_report() -> SafetyReport {
+    SafetyReport {
+        mint: key(DEV),
+        token_2022: false,
+        mint_authority: None,
+        freeze_authority: None,
+        extensions: MintExtensions::default(),
+        supply: 1_000_000_000,
+        top_holders_pct: 12.0,
+        sell: SellCheck::Sellable { sell_tax_pct: 0.5 },
+    }
+}
+
+#[test]
+fn token_2022_extensions_are_decoded() {
+    let (owner, data) = account("token2022_mint_extensions.json");
+
+    // Базовая часть mint разбирается так же, как у SPL Token
+    let mint = MintState::decode(&owner, &data).unwrap();
+    assert_eq!(mint.mint_authority, None);
+    assert_eq!(mint.supply, 1_000_000_000_000_000);
+
+    let extensions = MintExtensions::decode(&data).unwrap();
+    assert_eq!(extensions.transfer_fee, Some(TransferFee { basis_points: 300, maximum_fee: 5_000_000_000 }));
+    assert_eq!(extensions.permanent_delegate, Some(key(DEV)));
+    assert_eq!(extensions.transfer_hook, Some(key(HOOK_PROGRAM)));
+    assert!(!extensions.non_transferable);
+    assert!(!extensions.pausable);
+
+    // Обрезанное расширение — битые данные
+    assert!(MintExtensions::decode(&data[..300]).is_none());
+}
+
+#[test]
+fn spl_token_mint_has_no_extensions() {
+    let (_, data) = account("launch_mint_revoked.json");
+    assert_eq!(MintExtensions::decode(&data), Some(MintExtensions::default()));
+}
+
+#[test]
+fn round_trip_tax_excludes_price_impact_and_platform_fee() {
+    let buy = buy_quote();
+
+    // 100_000_000 → 99_000_000: 1% потерь при ~0.01% влияния покупки и 0.5% влияния продажи
+    let sell = sell_quote(&buy, 99_000_000, 0.005);
+    let tax = round_trip_tax_pct(&buy, &sell);
+    assert!((tax - 0.487).abs() < 0.01, "tax = {}", tax);
+
+    // Комиссия сервиса на обеих сделках не считается налогом
+    let mut buy_with_fee = buy.clone();
+    buy_with_fee.platform_fee = Some(PlatformFee { amount: 0, fee_bps: 50 });
+    let mut sell_with_fee = sell_quote(&buy, 98_000_000, 0.005);
+    sell_with_fee.platform_fee = Some(PlatformFee { amount: 0, fee_bps: 50 });
+    let tax = round_trip_tax_pct(&buy_with_fee, &sell_with_fee);
+    assert!((tax - 0.487).abs() < 0.01, "tax = {}", tax);
+
+    // Продажа с 20% налогом
+    let sell = sell_quote(&buy, 80_000_000, 0.0);
+    assert!(round_trip_tax_pct(&buy, &sell) > 19.9);
+
+    // Выход больше входа — налога нет
+    let sell = sell_quote(&buy, 101_000_000, 0.0);
+    assert_eq!(round_trip_tax_pct(&buy, &sell), 0.0);
+}
+
+#[test]
+fn no_route_errors_are_recognized() {
+    let error = anyhow::anyhow!(r#"Jupiter API error: {{"error":"Could not find any route","errorCode":"COULD_NOT_FIND_ANY_ROUTE"}}"#);
+    assert!(is_no_route_error(&error));
+    assert!(!is_no_route_error(&anyhow::anyhow!("Jupiter API error: rate limited")));
+}
+
+#[test]
+fn clean_token_has_low_score_and_is_allowed() {
+    let report = clean_report();
+    let settings = default_token_safety();
+
+    assert_eq!(report.risk_score(), 0);
+    assert!(report.warnings().is_empty());
+    assert!(report.block_reasons(&settings).is_empty());
+}
+
+#[test]
+fn risky_token_is_scored_and_blocked_by_rules() {
+    let mut settings = default_token_safety();
+    let mut report = clean_report();
+    report.freeze_authority = Some(key(DEV));
+    report.mint_authority = Some(key(DEV));
+
+    // Полномочия сами по себе не блокируют покупку по умолчанию
+    assert_eq!(report.risk_score(), 35);
+    assert_eq!(report.warnings().len(), 2);
+    assert!(report.block_reasons(&settings).is_empty());
+
+    // ...но администратор может запретить freeze authority
+    settings.block_freeze_authority = true;
+    assert_eq!(report.block_reasons(&settings).len(), 1);
+    settings.block_freeze_authority = false;
+
+    // Постоянный делегат блокируется отдельным правилом, а сумма весов — порогом риска
+    report.extensions.permanent_delegate = Some(key(DEV));
+    assert_eq!(report.risk_score(), 70);
+    let reasons = report.block_reasons(&settings);
+    assert_eq!(reasons.len(), 1);
+    assert!(reasons[0].contains("делегат"));
+
+    report.top_holders_pct = 60.0;
+    assert_eq!(report.risk_score(), 90);
+    assert_eq!(report.block_reasons(&settings).len(), 2);
+}
+
+#[test]
+fn unsellable_and_taxed_tokens_are_blocked() {
+    let settings = default_token_safety();
+
+    let mut report = clean_report();
+    report.sell = SellCheck::NoRoute;
+    assert_eq!(report.risk_score(), 100);
+    assert!(report.block_reasons(&settings).iter().any(|reason| reason.contains("нельзя продать")));
+
+    report.sell = SellCheck::Sellable { sell_tax_pct: 20.0 };
+    assert_eq!(report.risk_score(), 40);
+    assert!(report.block_reasons(&settings).iter().any(|reason| reason.contains("потери при продаже")));
+
+    // Не удалось получить обратную котировку — предупреждение, но не блокировка
+    report.sell = SellCheck::Unknown;
+    assert_eq!(report.risk_score(), 10);
+    assert!(report.block_reasons(&settings).is_empty());
+
+    report.sell = SellCheck::Sellable { sell_tax_pct: 0.5 };
+    report.extensions.transfer_fee = Some(TransferFee { basis_points: 1000, maximum_fee: u64::MAX });
+    assert!(report.block_reasons(&settings).iter().any(|reason| reason.contains("комиссия за перевод")));
+}
+
+#[test]
+fn safety_report_is_rendered_for_preview() {
+    let settings = default_token_safety();
+    let mut report = clean_report();
+    report.freeze_authority = Some(key(DEV));
+
+    let text = render_safety_report(&report, &settings);
+    assert!(text.starts_with("Безопасность токена: риск 20/100"));
+    assert!(text.contains("⚠️ Владелец может заморозить"));
+    assert!(!text.contains("⛔"));
+
+    report.sell = SellCheck::NoRoute;
+    let text = render_safety_report(&report, &settings);
+    assert!(text.contains("⛔ Покупка заблокирована"));
+}
@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use rust_decimal::Decimal;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_trading_bot::{
+    config::settings::{TradingLimits, default_trading_limits},
+    jupiter::{JupiterClient, QuoteResponseV6},
+    solana::{
+        amount::TokenAmount,
+        trader::{TradeActivity, TradeError, Trader, max_input_amount, worst_case_sol_lamports},
+    },
+};
+
+fn fixture(name: &str) -> QuoteResponseV6 {
+    let path = format!("{}/tests/fixtures/jupiter/{}", env!("CARGO_MANIFEST_DIR"), name);
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn trader(limits: TradingLimits) -> Trader {
+    let jupiter = JupiterClient::new("http://localhost", None);
+    let rpc = Arc::new(RpcClient::new("http://localhost".to_string()));
+    Trader::new(jupiter, rpc, limits)
+}
+
+#[test]
+fn exact_out_uses_threshold_as_worst_case_input() {
+    let quote = fixture("quote_exact_out.json");
+
+    assert_eq!(max_input_amount(&quote), 4_326_939);
+    assert_eq!(worst_case_sol_lamports(&quote), Some(4_326_939));
+}
+
+#[test]
+fn exact_in_uses_input_amount() {
+    let quote = fixture("quote_exact_in.json");
+
+    assert_eq!(max_input_amount(&quote), 100_000_000);
+}
+
+#[test]
+fn exact_out_rejected_when_worst_case_exceeds_max() {
+    let quote = fixture("quote_exact_out.json");
+    let trader = trader(TradingLimits {
+        // Ориентировочный вход 0.0043 SOL проходит, а максимальный 0.004327 — нет
+        max_trade_amount_sol: Decimal::new(43, 4),
+        min_trade_amount_sol: Decimal::new(1, 3),
+        ..default_trading_limits()
+    });
+
+    match trader.check_limits(&quote) {
+        Err(TradeError::AmountAboveLimit { limit_sol, .. }) => assert_eq!(limit_sol, TokenAmount::sol(4_300_000)),
+        other => panic!("expected AmountAboveLimit, got {:?}", other),
+    }
+}
+
+#[test]
+fn slippage_above_limit_is_rejected() {
+    let quote = fixture("quote_exact_in.json");
+    let trader = trader(TradingLimits {
+        max_slippage_bps: 10,
+        ..default_trading_limits()
+    });
+
+    assert!(matches!(
+        trader.check_limits(&quote),
+        Err(TradeError::SlippageAboveLimit { slippage_bps: 50, limit_bps: 10 })
+    ));
+}
+
+#[test]
+fn quote_within_limits_passes() {
+    let quote = fixture("quote_exact_in.json");
+
+    assert!(trader(default_trading_limits()).check_limits(&quote).is_ok());
+}
+
+#[test]
+fn exit_is_checked_only_against_slippage_cap() {
+    let quote = fixture("quote_exact_in.json");
+    // Вход 0.1 SOL выше максимума сделки, а влияние на цену выше блокирующего порога
+    let limited = trader(TradingLimits {
+        max_trade_amount_sol: Decimal::new(1, 2),
+        min_trade_amount_sol: Decimal::new(1, 3),
+        price_impact_block_pct: 0.0,
+        ..default_trading_limits()
+    });
+
+    assert!(limited.check_limits(&quote).is_err());
+    assert!(limited.check_exit_limits(&quote).is_ok());
+
+    let strict = trader(TradingLimits { max_slippage_bps: 10, ..default_trading_limits() });
+    assert!(matches!(
+        strict.check_exit_limits(&quote),
+        Err(TradeError::SlippageAboveLimit { slippage_bps: 50, limit_bps: 10 })
+    ));
+}
+
+#[test]
+fn hourly_trade_count_is_enforced() {
+    let quote = fixture("quote_exact_in.json");
+    let trader = trader(TradingLimits {
+        max_trades_per_hour: 3,
+        ..default_trading_limits()
+    });
+    let activity = TradeActivity { trades_last_hour: 3, trades_last_day: 3, sol_spent_last_day: TokenAmount::sol(0) };
+
+    assert!(matches!(
+        trader.check_activity_limits(&activity, &quote),
+        Err(TradeError::TooManyTrades { limit: 3, .. })
+    ));
+}
+
+#[test]
+fn daily_volume_includes_new_trade() {
+    // Котировка тратит 0.1 SOL: вместе с 0.95 за сутки лимит 1 SOL превышен
+    let quote = fixture("quote_exact_in.json");
+    let trader = trader(TradingLimits {
+        daily_trade_limit_sol: Decimal::ONE,
+        ..default_trading_limits()
+    });
+
+    let within = TradeActivity { sol_spent_last_day: TokenAmount::sol(850_000_000), ..Default::default() };
+    assert!(trader.check_activity_limits(&within, &quote).is_ok());
+
+    let above = TradeActivity { sol_spent_last_day: TokenAmount::sol(950_000_000), ..Default::default() };
+    assert!(matches!(
+        trader.check_activity_limits(&above, &quote),
+        Err(TradeError::DailyVolumeExceeded { .. })
+    ));
+}
@@ -0,0 +1,103 @@
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::Signature,
+    transaction::TransactionError,
+};
+use solana_transaction_status::TransactionStatus;
+
+use uuid::Uuid;
+
+use solana_trading_bot::solana::{
+    executor::ExecutionError,
+    sender::{LandingError, LandingStatus, landing_status},
+    trader::TradeError,
+};
+
+const LAST_VALID_BLOCK_HEIGHT: u64 = 277_504_150;
+
+fn read_fixture(name: &str) -> String {
+    let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+    std::fs::read_to_string(path).unwrap()
+}
+
+/// Записанный ответ `getSignatureStatuses`: подтвержденная, обработанная,
+/// подтвержденная с ошибкой и неизвестная сети подписи
+fn statuses() -> Vec<Option<TransactionStatus>> {
+    let value: serde_json::Value = serde_json::from_str(&read_fixture("solana/signature_statuses.json")).unwrap();
+    serde_json::from_value(value["value"].clone()).unwrap()
+}
+
+fn slippage_error() -> TransactionError {
+    TransactionError::InstructionError(3, InstructionError::Custom(6001))
+}
+
+#[test]
+fn confirmed_transaction_has_landed_or_failed() {
+    let statuses = statuses();
+    let confirmed = CommitmentConfig::confirmed();
+
+    assert_eq!(
+        landing_status(statuses[0].as_ref(), confirmed, LAST_VALID_BLOCK_HEIGHT - 10, LAST_VALID_BLOCK_HEIGHT),
+        LandingStatus::Landed,
+    );
+    assert_eq!(
+        landing_status(statuses[2].as_ref(), confirmed, LAST_VALID_BLOCK_HEIGHT - 10, LAST_VALID_BLOCK_HEIGHT),
+        LandingStatus::Failed(slippage_error()),
+    );
+}
+
+#[test]
+fn processed_transaction_waits_for_confirmation_even_after_expiry() {
+    let statuses = statuses();
+    let processed = statuses[1].as_ref();
+
+    assert_eq!(
+        landing_status(processed, CommitmentConfig::confirmed(), LAST_VALID_BLOCK_HEIGHT + 5, LAST_VALID_BLOCK_HEIGHT),
+        LandingStatus::Pending,
+    );
+    // При commitment processed транзакция уже считается попавшей в блок
+    assert_eq!(
+        landing_status(processed, CommitmentConfig::processed(), LAST_VALID_BLOCK_HEIGHT, LAST_VALID_BLOCK_HEIGHT),
+        LandingStatus::Landed,
+    );
+    // Финализации подтвержденная транзакция еще не достигла
+    assert_eq!(
+        landing_status(statuses[0].as_ref(), CommitmentConfig::finalized(), LAST_VALID_BLOCK_HEIGHT, LAST_VALID_BLOCK_HEIGHT),
+        LandingStatus::Pending,
+    );
+}
+
+#[test]
+fn unknown_transaction_expires_only_after_last_valid_block_height() {
+    let unknown = statuses()[3].clone();
+    assert!(unknown.is_none());
+    let confirmed = CommitmentConfig::confirmed();
+
+    assert_eq!(landing_status(None, confirmed, LAST_VALID_BLOCK_HEIGHT - 1, LAST_VALID_BLOCK_HEIGHT), LandingStatus::Pending);
+    // На последней допустимой высоте транзакция еще может попасть в блок
+    assert_eq!(landing_status(None, confirmed, LAST_VALID_BLOCK_HEIGHT, LAST_VALID_BLOCK_HEIGHT), LandingStatus::Pending);
+    assert_eq!(landing_status(None, confirmed, LAST_VALID_BLOCK_HEIGHT + 1, LAST_VALID_BLOCK_HEIGHT), LandingStatus::Expired);
+}
+
+#[test]
+fn only_expired_transactions_are_safe_to_retry() {
+    assert!(LandingError::Expired.is_retryable());
+    assert!(!LandingError::Failed(slippage_error()).is_retryable());
+    assert!(!LandingError::Unknown(Signature::default()).is_retryable());
+
+    // Ошибка попавшей в блок транзакции объясняется так же, как при симуляции
+    let message = LandingError::Failed(slippage_error()).to_string();
+    assert!(message.contains("проскальзывания"), "{}", message);
+}
+
+#[test]
+fn only_unknown_landing_leaves_trade_for_reconcile() {
+    let send = |error: LandingError| ExecutionError::Send { trade_id: Uuid::new_v4(), error: TradeError::Landing(error) };
+
+    // Автоматизации получают ошибку через anyhow
+    let unknown = anyhow::Error::from(send(LandingError::Unknown(Signature::default())));
+    assert!(unknown.downcast_ref::<ExecutionError>().is_some_and(ExecutionError::is_unconfirmed));
+    assert!(!send(LandingError::Expired).is_unconfirmed());
+    assert!(!send(LandingError::Failed(slippage_error())).is_unconfirmed());
+}
@@ -0,0 +1,179 @@
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use sea_orm::{ConnectionTrait, DbBackend, DbErr, RuntimeErr, Statement};
+use secrecy::SecretString;
+use sqlx::error::{DatabaseError, ErrorKind};
+
+use solana_trading_bot::{
+    config::settings::DatabaseSettings,
+    database::connection::{DatabaseConnectionPool, RetryableError, in_transaction},
+};
+
+/// SQLite в памяти на одном соединении: у каждого соединения была бы своя база
+async fn database() -> DatabaseConnectionPool {
+    let database = DatabaseConnectionPool::connect(&DatabaseSettings {
+        url: SecretString::new("sqlite::memory:".into()),
+        pool_max_connections: 1,
+        pool_min_connections: 1,
+        connect_timeout_secs: 5,
+        acquire_timeout_secs: 5,
+        idle_timeout_secs: 600,
+        max_lifetime_secs: 1800,
+    })
+    .await
+    .unwrap();
+    database
+        .get_connection()
+        .execute_unprepared("CREATE TABLE notes (body TEXT NOT NULL)")
+        .await
+        .unwrap();
+    database
+}
+
+async fn insert<C: ConnectionTrait>(db: &C, body: &str) -> Result<(), DbErr> {
+    db.execute(Statement::from_sql_and_values(DbBackend::Sqlite, "INSERT INTO notes (body) VALUES (?)", [body.into()]))
+        .await
+        .map(|_| ())
+}
+
+async fn notes(database: &DatabaseConnectionPool) -> Vec<String> {
+    database
+        .get_connection()
+        .query_all(Statement::from_string(DbBackend::Sqlite, "SELECT body FROM notes ORDER BY body"))
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| row.try_get("", "body").unwrap())
+        .collect()
+}
+
+/// Ошибка Postgres с SQLSTATE 40001, как при конфликте транзакций SERIALIZABLE
+#[derive(Debug)]
+struct SerializationFailure;
+
+impl fmt::Display for SerializationFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "could not serialize access due to concurrent update")
+    }
+}
+
+impl Error for SerializationFailure {}
+
+impl DatabaseError for SerializationFailure {
+    fn message(&self) -> &str {
+        "could not serialize access due to concurrent update"
+    }
+
+    fn code(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Borrowed("40001"))
+    }
+
+    fn as_error(&self) -> &(dyn Error + Send + Sync + 'static) {
+        self
+    }
+
+    fn as_error_mut(&mut self) -> &mut (dyn Error + Send + Sync + 'static) {
+        self
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn Error + Send + Sync + 'static> {
+        self
+    }
+
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+fn serialization_failure() -> DbErr {
+    DbErr::Query(RuntimeErr::SqlxError(sqlx::Error::Database(Box::new(SerializationFailure))))
+}
+
+#[tokio::test]
+async fn callback_runs_inside_transaction() {
+    let database = database().await;
+
+    database
+        .transaction(|txn| Box::pin(async move { insert(txn, "kept").await }))
+        .await
+        .unwrap();
+
+    let result: Result<(), DbErr> = database
+        .transaction(|txn| Box::pin(async move {
+            insert(txn, "rolled back").await?;
+            Err(DbErr::Custom("cancelled".to_string()))
+        }))
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(notes(&database).await, vec!["kept"]);
+}
+
+#[tokio::test]
+async fn failed_savepoint_rolls_back_only_its_changes() {
+    let database = database().await;
+
+    database
+        .transaction(|txn| Box::pin(async move {
+            insert(txn, "outer").await?;
+            let nested: Result<(), DbErr> = in_transaction(txn, |savepoint| Box::pin(async move {
+                insert(savepoint, "inner").await?;
+                Err(DbErr::Custom("inner failed".to_string()))
+            }))
+            .await;
+            assert!(nested.is_err());
+            insert(txn, "after").await
+        }))
+        .await
+        .unwrap();
+
+    assert_eq!(notes(&database).await, vec!["after", "outer"]);
+}
+
+#[tokio::test]
+async fn serialization_failures_are_retried() {
+    let database = database().await;
+    let attempts = AtomicU32::new(0);
+
+    database
+        .serializable_transaction(3, |txn| {
+            let attempt = attempts.fetch_add(1, Ordering::SeqCst) + 1;
+            Box::pin(async move {
+                insert(txn, &format!("attempt {}", attempt)).await?;
+                if attempt < 3 { Err(serialization_failure()) } else { Ok(()) }
+            })
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    assert_eq!(notes(&database).await, vec!["attempt 3"]);
+}
+
+#[tokio::test]
+async fn retries_stop_after_max_attempts_and_on_other_errors() {
+    let database = database().await;
+    let attempts = AtomicU32::new(0);
+
+    let result: Result<(), anyhow::Error> = database
+        .serializable_transaction(2, |_| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move { Err(serialization_failure().into()) })
+        })
+        .await;
+    assert!(result.unwrap_err().is_serialization_failure());
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+
+    attempts.store(0, Ordering::SeqCst);
+    let result: Result<(), DbErr> = database
+        .serializable_transaction(5, |_| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move { Err(DbErr::Custom("constraint".to_string())) })
+        })
+        .await;
+    assert!(result.is_err());
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+}